@@ -9,9 +9,8 @@ use serde_json::json;
 
 #[tokio::main]
 async fn main() -> Result<(), refyne::Error> {
-    // Create a client with your API key
-    let api_key = std::env::var("REFYNE_API_KEY").expect("REFYNE_API_KEY must be set");
-    let client = Client::builder(api_key).build()?;
+    // Create a client using REFYNE_API_KEY and the other REFYNE_* variables
+    let client = Client::from_env()?;
 
     // Define the schema for the data you want to extract
     let schema = json!({
@@ -5,10 +5,11 @@
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 use refyne::{
-    AnalyzeRequest, Client, CrawlOptions, CrawlRequest, ExtractRequest, MAX_KNOWN_API_VERSION,
-    MIN_API_VERSION, SDK_VERSION,
+    AnalyzeRequest, Client, CrawlRequest, ExtractRequest, JobStatus, WaitOptions,
+    MAX_KNOWN_API_VERSION, MIN_API_VERSION, SDK_VERSION,
 };
 use serde_json::Value;
+use std::sync::Mutex;
 use std::time::Duration;
 
 // Configuration - Set via environment variables
@@ -112,7 +113,7 @@ async fn main() -> Result<(), refyne::Error> {
     info("Cache", "Enabled (in-memory)");
 
     // Create client
-    let client = Client::builder(&api_key).base_url(&base_url).build()?;
+    let client = Client::from_env()?;
 
     // ========== Subscription Info ==========
     header("Subscription Information");
@@ -243,24 +244,13 @@ async fn main() -> Result<(), refyne::Error> {
 
     let pb = spinner("Starting crawl job...");
     let crawl_result = match client
-        .crawl(CrawlRequest {
-            url: TEST_URL.into(),
-            schema: suggested_schema,
-            options: Some(CrawlOptions {
-                max_pages: Some(5),
-                max_depth: Some(1),
-                max_urls: Some(5),
-                concurrency: None,
-                delay: None,
-                extract_from_seeds: None,
-                follow_pattern: None,
-                follow_selector: None,
-                next_selector: None,
-                same_domain_only: None,
-                use_sitemap: None,
-            }),
-            ..Default::default()
-        })
+        .crawl(
+            CrawlRequest::builder(TEST_URL, suggested_schema)
+                .max_pages(5)
+                .max_depth(1)
+                .max_urls(5)
+                .build(),
+        )
         .await
     {
         Ok(result) => {
@@ -289,42 +279,36 @@ async fn main() -> Result<(), refyne::Error> {
 
     subheader("Monitoring job progress...");
 
-    let mut last_status = String::new();
-    let mut page_count = 0i64;
-    let poll_interval = Duration::from_secs(2);
-
-    loop {
-        let job = client.get_job(&job_id).await?;
-
-        if job.status != last_status {
-            println!("  {} Status: {}", "->".cyan(), job.status.bold());
-            last_status = job.status.clone();
-        }
+    let seen = Mutex::new((String::new(), 0i64));
+    let options = WaitOptions::new()
+        .interval(Duration::from_secs(2))
+        .on_progress(move |job| {
+            let mut seen = seen.lock().unwrap();
+            let (last_status, page_count) = &mut *seen;
 
-        if job.page_count > page_count {
-            let new_pages = job.page_count - page_count;
-            for i in 0..new_pages {
-                println!("  {} Page {} extracted", "[ok]".green(), page_count + i + 1);
+            if job.status != *last_status {
+                println!("  {} Status: {}", "->".cyan(), job.status.bold());
+                *last_status = job.status.clone();
             }
-            page_count = job.page_count;
-        }
 
-        match job.status.as_str() {
-            "completed" => {
-                success(&format!(
-                    "Crawl completed - {} pages processed",
-                    job.page_count
-                ));
-                break;
-            }
-            "failed" => {
-                let msg = job.error_message.as_deref().unwrap_or("Unknown error");
-                error(&format!("Crawl failed: {}", msg));
-                break;
-            }
-            _ => {
-                tokio::time::sleep(poll_interval).await;
+            if job.page_count > *page_count {
+                for page in *page_count..job.page_count {
+                    println!("  {} Page {} extracted", "[ok]".green(), page + 1);
+                }
+                *page_count = job.page_count;
             }
+        });
+    let job = client.jobs().wait(&job_id, options).await?;
+
+    match job.job_status() {
+        JobStatus::Completed => success(&format!(
+            "Crawl completed - {} pages processed",
+            job.page_count
+        )),
+        JobStatus::Cancelled => error("Crawl cancelled"),
+        _ => {
+            let msg = job.error_message.as_deref().unwrap_or("Unknown error");
+            error(&format!("Crawl failed: {}", msg));
         }
     }
 
@@ -0,0 +1,246 @@
+//! Derive macro for `refyne::RefyneSchema`.
+//!
+//! Use it through the `derive` feature of the `refyne` crate rather than
+//! depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, Fields, Lit, Meta};
+
+/// Derive `refyne::RefyneSchema` for a struct with named fields.
+///
+/// Each field maps to the schema of its type. Doc comments on fields become
+/// descriptions. The serde attributes that change which fields are
+/// deserialized are honoured: `rename` and `rename_all` rename fields,
+/// `skip` and `skip_deserializing` leave them out, and `flatten` merges the
+/// nested struct's fields into this one.
+#[proc_macro_derive(RefyneSchema, attributes(serde))]
+pub fn derive_refyne_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "RefyneSchema requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "RefyneSchema can only be derived for structs",
+            ))
+        }
+    };
+    let rename_all = container_attrs(&input.attrs)?;
+
+    let mut inserts = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let attrs = field_attrs(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let ty = &field.ty;
+        let schema = quote! { <#ty as ::refyne::RefyneSchema>::schema() };
+        if attrs.flatten {
+            inserts.push(quote! { ::refyne::__private::flatten(&mut map, #schema); });
+            continue;
+        }
+        let name = attrs.rename.unwrap_or_else(|| {
+            let name = ident.to_string().trim_start_matches("r#").to_string();
+            match rename_all {
+                Some(rule) => rule.apply(&name),
+                None => name,
+            }
+        });
+        let value = match doc_comment(&field.attrs) {
+            Some(doc) => quote! { ::refyne::__private::describe(#schema, #doc) },
+            None => schema,
+        };
+        inserts.push(quote! { map.insert(#name.to_string(), #value); });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::refyne::RefyneSchema for #ident #ty_generics #where_clause {
+            fn schema() -> ::refyne::__private::serde_json::Value {
+                let mut map = ::refyne::__private::serde_json::Map::new();
+                #(#inserts)*
+                ::refyne::__private::serde_json::Value::Object(map)
+            }
+        }
+    })
+}
+
+/// Join `///` lines into one description.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(s) => Some(s.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// A serde `rename_all` rule, applied to snake_case field names.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    format!("unknown rename rule `{other}`"),
+                ))
+            }
+        })
+    }
+
+    fn apply(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => field
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars.next().map_or_else(String::new, |first| {
+                        first.to_ascii_uppercase().to_string() + chars.as_str()
+                    })
+                })
+                .collect(),
+            Self::Camel => {
+                let pascal = Self::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Serde options on a field that change its place in the schema.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    flatten: bool,
+}
+
+/// The `rename_all` rule from the struct's `#[serde(...)]` attributes.
+fn container_attrs(attrs: &[Attribute]) -> syn::Result<Option<RenameRule>> {
+    let mut rename_all = None;
+    parse_serde(attrs, |meta| {
+        if meta.path.is_ident("rename_all") {
+            if let Some(lit) = deserialize_name(meta)? {
+                rename_all = Some(RenameRule::parse(&lit)?);
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    })?;
+    Ok(rename_all)
+}
+
+/// The options from a field's `#[serde(...)]` attributes.
+fn field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut field = FieldAttrs::default();
+    parse_serde(attrs, |meta| {
+        if meta.path.is_ident("rename") {
+            if let Some(lit) = deserialize_name(meta)? {
+                field.rename = Some(lit.value());
+            }
+        } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+            field.skip = true;
+        } else if meta.path.is_ident("flatten") {
+            field.flatten = true;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+    Ok(field)
+}
+
+/// Walk `#[serde(...)]` options, skipping any `handle` does not consume.
+fn parse_serde(
+    attrs: &[Attribute],
+    mut handle: impl FnMut(&syn::meta::ParseNestedMeta) -> syn::Result<bool>,
+) -> syn::Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if handle(&meta)? {
+                return Ok(());
+            }
+            if meta.input.peek(syn::Token![=]) {
+                // Skip other `key = value` options.
+                let _: Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// The name used when deserializing, from `key = "..."` or
+/// `key(serialize = "...", deserialize = "...")`.
+fn deserialize_name(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Option<syn::LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|inner| {
+        let value: syn::LitStr = inner.value()?.parse()?;
+        if inner.path.is_ident("deserialize") {
+            name = Some(value);
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
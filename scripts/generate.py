@@ -40,6 +40,104 @@ RUST_KEYWORDS = {
     "yield", "try", "union", "in", "as"
 }
 
+# String fields holding credentials, generated as SecretString so they are
+# redacted from Debug output
+SECRET_FIELDS = {"api_key", "key", "secret", "target_api_key"}
+
+# Response fields holding times, generated as Timestamp so they parse into
+# chrono types. Input bodies keep plain strings.
+TIMESTAMP_FIELDS = {"timestamp"}
+
+
+def is_timestamp_field(struct_name: str, prop_name: str, prop_schema: dict) -> bool:
+    if struct_name.endswith(("Input", "InputBody")):
+        return False
+    return (
+        prop_schema.get("format") == "date-time"
+        or prop_name.endswith("_at")
+        or prop_name in TIMESTAMP_FIELDS
+    )
+
+# Fields the SDK sends or reads that the specification does not describe,
+# keyed by struct. Each is (name, Rust type, doc lines, serde attributes) and
+# is placed among the generated fields in name order. Helpers on these types
+# live in src/types_ext.rs.
+EXTRA_FIELDS: dict[str, list[tuple[str, str, list[str], Optional[str]]]] = {
+    "AnalyzeResponseBody": [
+        ("page_types", "Vec<crate::PageTypeSuggestion>",
+         ["Suggestions per detected page type (depth > 0)"], "default"),
+    ],
+    "CrawlOptions": [
+        ("crawl_delay_from_robots", "Option<bool>",
+         ["Wait between requests for the Crawl-delay in robots.txt, when it is longer than delay"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("exclude_pattern", "Option<String>",
+         ["Regex pattern for URLs never to crawl, even if they match follow_pattern"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("respect_robots_txt", "Option<bool>",
+         ["Skip URLs disallowed for the crawler by the site's robots.txt"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("sitemap_url", "Option<String>",
+         ["Sitemap to read with use_sitemap, instead of the site's default /sitemap.xml"],
+         'skip_serializing_if = "Option::is_none"'),
+    ],
+    "CreateCrawlJobInputBody": [
+        ("fetch_options", "Option<crate::FetchOptions>",
+         ["Headers, cookies, and proxy country used to fetch each page"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("urls", "Vec<String>",
+         ["Further seed URLs, crawled as well as url"],
+         'default, skip_serializing_if = "Vec::is_empty"'),
+    ],
+    "ExtractInputBody": [
+        ("capture_screenshot", "Option<crate::ScreenshotOptions>",
+         ["Screenshot the page; the response carries the screenshot's artifact ID"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("fetch_options", "Option<crate::FetchOptions>",
+         ["Headers, cookies, and proxy country used to fetch the page"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("fields", "Option<Vec<String>>",
+         ["Top-level schema fields to extract (default: all)"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("include_content", "Option<crate::ContentFormat>",
+         ["Return the fetched page in this form alongside the data"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("schema_format", "Option<crate::SchemaFormat>",
+         ["How `schema` is written. A JSON Schema is converted to the Refyne form "
+          "before sending; see [`SchemaFormat`](crate::SchemaFormat)."],
+         "skip"),
+        ("schema_id", "Option<String>",
+         ["ID of a saved schema to use instead of an inline schema"],
+         'skip_serializing_if = "Option::is_none"'),
+    ],
+    "ExtractOutputBody": [
+        ("content", "Option<String>",
+         ["The fetched page, in the form requested with include_content"],
+         'skip_serializing_if = "Option::is_none"'),
+        ("screenshot_id", "Option<String>",
+         ["Artifact ID of the page screenshot, when one was requested"],
+         'skip_serializing_if = "Option::is_none"'),
+    ],
+    "JobResponse": [
+        ("resume_token", "Option<String>",
+         ["Checkpoint of a stopped crawl, if it can be resumed; see",
+          "[`Client::resume_job`](crate::Client::resume_job)."],
+         'default, skip_serializing_if = "Option::is_none"'),
+    ],
+    "MetadataResponse": [
+        ("final_url", "Option<String>",
+         ["URL of the page after following redirects"], None),
+        ("redirect_chain", "Option<Vec<String>>",
+         ["URLs redirected through, in order, starting with the submitted URL"], None),
+    ],
+}
+
+# Extra serde attributes for generated fields, keyed by (struct, property)
+FIELD_SERDE_ATTRS: dict[tuple[str, str], str] = {
+    # An extraction may name a saved schema instead of sending one inline
+    ("ExtractInputBody", "schema"): 'default, skip_serializing_if = "serde_json::Value::is_null"',
+}
+
 # Collected inline enums during processing
 inline_enums: dict[str, list[str]] = {}
 
@@ -338,23 +436,32 @@ def generate_struct(name: str, schema: dict, spec: dict) -> list[str]:
     lines.append(f"pub struct {name} {{")
 
     required_fields = set(schema.get("required", []))
+    fields: list[tuple[str, list[str]]] = []
 
     for prop_name, prop_schema in properties.items():
         # Skip JSON Schema metadata fields (like $schema)
         if prop_name.startswith("$"):
             continue
+        field_lines = []
         is_required = prop_name in required_fields
         rust_field_name = to_snake_case(prop_name)
         rust_field_name = escape_rust_keyword(rust_field_name)
         rust_type = openapi_type_to_rust(prop_schema, spec, is_required, name, prop_name)
+        if prop_name in SECRET_FIELDS and rust_type in ("String", "Option<String>"):
+            rust_type = rust_type.replace("String", "SecretString")
+        elif is_timestamp_field(name, prop_name, prop_schema) and rust_type in (
+            "String",
+            "Option<String>",
+        ):
+            rust_type = rust_type.replace("String", "Timestamp")
 
         # Doc comment for field
         prop_description = prop_schema.get("description", "")
         if prop_description:
-            lines.append(f"    /// {prop_description}")
+            field_lines.append(f"    /// {prop_description}")
         if prop_schema.get("deprecated"):
             msg = prop_schema.get("x-deprecated-message", "This field is deprecated.")
-            lines.append(f"    #[deprecated(note = \"{msg}\")]")
+            field_lines.append(f"    #[deprecated(note = \"{msg}\")]")
 
         # Add serde rename for fields that need it
         actual_field_name = rust_field_name.replace("r#", "")
@@ -368,14 +475,29 @@ def generate_struct(name: str, schema: dict, spec: dict) -> list[str]:
             needs_rename = True
 
         if needs_rename:
-            lines.append(f'    #[serde(rename = "{prop_name}")]')
+            field_lines.append(f'    #[serde(rename = "{prop_name}")]')
+        if (name, prop_name) in FIELD_SERDE_ATTRS:
+            field_lines.append(f"    #[serde({FIELD_SERDE_ATTRS[(name, prop_name)]})]")
 
         # Skip serializing None for optional fields in request types
         if not is_required and is_request_type(name):
-            lines.append("    #[serde(skip_serializing_if = \"Option::is_none\")]")
-
-        lines.append(f"    pub {rust_field_name}: {rust_type},")
-
+            field_lines.append("    #[serde(skip_serializing_if = \"Option::is_none\")]")
+
+        field_lines.append(f"    pub {rust_field_name}: {rust_type},")
+        fields.append((actual_field_name, field_lines))
+
+    for field_name, rust_type, docs, serde_attrs in EXTRA_FIELDS.get(name, []):
+        field_lines = [f"    /// {doc}" for doc in docs]
+        if serde_attrs:
+            field_lines.append(f"    #[serde({serde_attrs})]")
+        field_lines.append(f"    pub {field_name}: {rust_type},")
+        position = next(
+            (i for i, (other, _) in enumerate(fields) if other > field_name), len(fields)
+        )
+        fields.insert(position, (field_name, field_lines))
+
+    for _, field_lines in fields:
+        lines.extend(field_lines)
     lines.append("}")
     return lines
 
@@ -411,6 +533,8 @@ def generate_types(spec: dict) -> str:
         "",
         "#![allow(dead_code)]",
         "",
+        "use crate::secret::SecretString;",
+        "use crate::timestamp::Timestamp;",
         "use serde::{Deserialize, Serialize};",
         "",
     ]
@@ -527,12 +651,34 @@ def generate_types(spec: dict) -> str:
     if not any(name == "Model" for name, _ in other_schemas):
         lines.extend([
             "/// Available LLM model.",
-            "#[derive(Debug, Clone, Deserialize)]",
+            "#[derive(Debug, Clone, Default, Serialize, Deserialize)]",
+            "#[serde(default)]",
             "pub struct Model {",
             "    /// Model identifier.",
             "    pub id: String,",
             "    /// Display name.",
             "    pub name: String,",
+            "    /// Provider serving the model.",
+            '    #[serde(skip_serializing_if = "String::is_empty")]',
+            "    pub provider: String,",
+            "    /// Maximum input and output tokens per call.",
+            '    #[serde(alias = "context_size", skip_serializing_if = "Option::is_none")]',
+            "    pub context_window: Option<i64>,",
+            "    /// USD per input token.",
+            '    #[serde(skip_serializing_if = "Option::is_none")]',
+            "    pub input_price_per_token: Option<f64>,",
+            "    /// USD per output token.",
+            '    #[serde(skip_serializing_if = "Option::is_none")]',
+            "    pub output_price_per_token: Option<f64>,",
+            "    /// Whether the model accepts images.",
+            "    pub supports_vision: bool,",
+            "    /// Whether the model can be constrained to return JSON.",
+            "    pub supports_json_mode: bool,",
+            "    /// Whether the provider has deprecated the model.",
+            "    pub deprecated: bool,",
+            "    /// When a deprecated model stops being served.",
+            '    #[serde(skip_serializing_if = "Option::is_none")]',
+            "    pub sunset_at: Option<Timestamp>,",
             "}",
             "",
         ])
@@ -0,0 +1,259 @@
+//! The client's operations as a trait, for code that should not depend on
+//! the network.
+//!
+//! Write pipelines against [`RefyneApi`] instead of [`Client`] and pass a
+//! `Client` in production. With the `test-util` feature, tests can pass a
+//! [`MockRefyne`] that answers each operation with a closure:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, ExtractRequest, RefyneApi};
+//!
+//! async fn product_name(api: &dyn RefyneApi, url: &str) -> refyne::Result<String> {
+//!     let response = api
+//!         .extract(ExtractRequest {
+//!             url: url.into(),
+//!             schema: serde_json::json!({"name": "string"}),
+//!             ..Default::default()
+//!         })
+//!         .await?;
+//!     Ok(response.data["name"].as_str().unwrap_or_default().to_string())
+//! }
+//!
+//! # async fn run() -> refyne::Result<()> {
+//! let client = Client::from_env()?;
+//! println!("{}", product_name(&client, "https://example.com").await?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::runtime::BoxFuture;
+use crate::types::*;
+
+#[cfg(any(test, feature = "test-util"))]
+use std::sync::Mutex;
+
+/// Declares the trait, its implementation for [`Client`], and
+/// [`MockRefyne`] from one list of operations.
+macro_rules! refyne_api {
+    ($(
+        $(#[$doc:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),*) -> $output:ty => $on:ident;
+    )*) => {
+        /// The API operations of [`Client`].
+        ///
+        /// Object-safe, so pipelines can take `&dyn RefyneApi` or
+        /// `Arc<dyn RefyneApi>`. Generic helpers such as
+        /// [`Client::extract_as`] and job waiting are not part of the trait.
+        pub trait RefyneApi: Send + Sync {
+            $(
+                $(#[$doc])*
+                fn $name<'a>(&'a self, $($arg: $ty),*) -> BoxFuture<'a, Result<$output>>;
+            )*
+        }
+
+        impl RefyneApi for Client {
+            $(
+                fn $name<'a>(&'a self, $($arg: $ty),*) -> BoxFuture<'a, Result<$output>> {
+                    Box::pin(Client::$name(self, $($arg),*))
+                }
+            )*
+        }
+
+        /// A [`RefyneApi`] answering each operation with a closure.
+        ///
+        /// Operations without a closure panic when called.
+        ///
+        /// ```rust
+        /// use refyne::{MockRefyne, RefyneApi};
+        ///
+        /// # async fn run() -> refyne::Result<()> {
+        /// let mock = MockRefyne::new().on_get_job(|id| {
+        ///     assert_eq!(id, "job-1");
+        ///     Err(refyne::Error::Config("job is gone".into()))
+        /// });
+        /// assert!(mock.get_job("job-1").await.is_err());
+        /// assert_eq!(mock.calls(), ["get_job"]);
+        /// # Ok(())
+        /// # }
+        /// ```
+        #[cfg(any(test, feature = "test-util"))]
+        #[derive(Default)]
+        pub struct MockRefyne {
+            calls: Mutex<Vec<&'static str>>,
+            $(
+                $name: Option<Box<dyn for<'a> Fn($($ty),*) -> Result<$output> + Send + Sync>>,
+            )*
+        }
+
+        #[cfg(any(test, feature = "test-util"))]
+        #[cfg_attr(not(feature = "test-util"), allow(dead_code))]
+        impl MockRefyne {
+            $(
+                #[doc = concat!("Answer `", stringify!($name), "` calls with `handler`.")]
+                pub fn $on(
+                    mut self,
+                    handler: impl for<'a> Fn($($ty),*) -> Result<$output> + Send + Sync + 'static,
+                ) -> Self {
+                    self.$name = Some(Box::new(handler));
+                    self
+                }
+            )*
+        }
+
+        #[cfg(any(test, feature = "test-util"))]
+        impl RefyneApi for MockRefyne {
+            $(
+                fn $name<'a>(&'a self, $($arg: $ty),*) -> BoxFuture<'a, Result<$output>> {
+                    self.calls.lock().unwrap().push(stringify!($name));
+                    let handler = self.$name.as_ref().unwrap_or_else(|| {
+                        panic!("MockRefyne: unexpected call to {}", stringify!($name))
+                    });
+                    let result = handler($($arg),*);
+                    Box::pin(async move { result })
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockRefyne {
+    /// Create a mock that answers nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names of the operations called so far, in order.
+    pub fn calls(&self) -> Vec<&'static str> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+refyne_api! {
+    /// Extract structured data from a single page.
+    fn extract(request: ExtractRequest) -> ExtractResponse => on_extract;
+    /// Start a crawl job.
+    fn crawl(request: CrawlRequest) -> CrawlJobCreated => on_crawl;
+    /// Analyze a website to suggest a schema.
+    fn analyze(request: AnalyzeRequest) -> AnalyzeResponse => on_analyze;
+    /// Get usage statistics.
+    fn get_usage() -> GetUsageOutputBody => on_get_usage;
+    /// List jobs.
+    fn list_jobs(limit: Option<u32>, offset: Option<u32>) -> JobList => on_list_jobs;
+    /// Get a job by ID.
+    fn get_job(id: &'a str) -> Job => on_get_job;
+    /// Cancel a pending or running job.
+    fn cancel_job(id: &'a str) -> Job => on_cancel_job;
+    /// Delete a job.
+    fn delete_job(id: &'a str) -> () => on_delete_job;
+    /// Get the results of a job.
+    fn get_job_results(id: &'a str, merge: bool) -> JobResults => on_get_job_results;
+    /// Get a download URL for job results.
+    fn download_job(id: &'a str) -> GetJobResultsDownloadOutputBody => on_download_job;
+    /// List saved schemas.
+    fn list_schemas() -> SchemaList => on_list_schemas;
+    /// Get a schema by ID.
+    fn get_schema(id: &'a str) -> Schema => on_get_schema;
+    /// Create a schema.
+    fn create_schema(request: CreateSchemaRequest) -> Schema => on_create_schema;
+    /// Update a schema.
+    fn update_schema(id: &'a str, request: CreateSchemaRequest) -> Schema => on_update_schema;
+    /// Delete a schema.
+    fn delete_schema(id: &'a str) -> () => on_delete_schema;
+    /// List saved sites.
+    fn list_sites() -> SiteList => on_list_sites;
+    /// Get a site by ID.
+    fn get_site(id: &'a str) -> Site => on_get_site;
+    /// Create a site.
+    fn create_site(request: CreateSiteRequest) -> Site => on_create_site;
+    /// Update a site.
+    fn update_site(id: &'a str, request: CreateSiteRequest) -> Site => on_update_site;
+    /// Delete a site.
+    fn delete_site(id: &'a str) -> () => on_delete_site;
+    /// List API keys.
+    fn list_keys() -> ApiKeyList => on_list_keys;
+    /// Create an API key.
+    fn create_key(name: &'a str) -> ApiKeyCreated => on_create_key;
+    /// Revoke an API key.
+    fn revoke_key(id: &'a str) -> () => on_revoke_key;
+    /// List available LLM providers.
+    fn list_providers() -> ProvidersResponse => on_list_providers;
+    /// List configured LLM keys.
+    fn list_llm_keys() -> LlmKeyList => on_list_llm_keys;
+    /// Add or update an LLM key.
+    fn upsert_llm_key(request: UpsertLlmKeyRequest) -> LlmKey => on_upsert_llm_key;
+    /// Delete an LLM key.
+    fn delete_llm_key(id: &'a str) -> () => on_delete_llm_key;
+    /// Get the LLM fallback chain.
+    fn get_llm_chain() -> LlmChain => on_get_llm_chain;
+    /// Set the LLM fallback chain.
+    fn set_llm_chain(chain: Vec<LlmChainEntry>) -> () => on_set_llm_chain;
+    /// List available models for a provider.
+    fn list_models(provider: &'a str) -> ModelList => on_list_models;
+    /// List webhooks.
+    fn list_webhooks() -> ListWebhooksOutputBody => on_list_webhooks;
+    /// Get a webhook by ID.
+    fn get_webhook(id: &'a str) -> WebhookResponse => on_get_webhook;
+    /// Create a webhook.
+    fn create_webhook(input: WebhookInput) -> WebhookResponse => on_create_webhook;
+    /// Update a webhook.
+    fn update_webhook(id: &'a str, input: WebhookInput) -> WebhookResponse => on_update_webhook;
+    /// Delete a webhook.
+    fn delete_webhook(id: &'a str) -> () => on_delete_webhook;
+    /// Send a test event to a webhook.
+    fn test_webhook(id: &'a str) -> WebhookDeliveryResponse => on_test_webhook;
+    /// Check API health.
+    fn health() -> HealthCheckOutputBody => on_health;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::sync::Arc;
+
+    async fn job_status(api: &dyn RefyneApi, id: &str) -> Result<String> {
+        Ok(api.get_job(id).await?.status)
+    }
+
+    #[tokio::test]
+    async fn test_mock_answers_configured_operations() {
+        let job: Job =
+            serde_json::from_value(crate::golden::load("0.1.80", "job").unwrap()).unwrap();
+        let mock = MockRefyne::new()
+            .on_get_job(move |id| {
+                assert_eq!(id, "job-1");
+                Ok(job.clone())
+            })
+            .on_delete_job(|_| Err(Error::Config("not allowed".into())));
+
+        let api: Arc<dyn RefyneApi> = Arc::new(mock);
+        assert_eq!(job_status(&*api, "job-1").await.unwrap(), "completed");
+        assert!(api.delete_job("job-1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_records_calls() {
+        let mock = MockRefyne::new().on_list_jobs(|limit, offset| {
+            assert_eq!((limit, offset), (Some(10), None));
+            Ok(serde_json::from_value(serde_json::json!({"jobs": []})).unwrap())
+        });
+        mock.list_jobs(Some(10), None).await.unwrap();
+        mock.list_jobs(Some(10), None).await.unwrap();
+        assert_eq!(mock.calls(), ["list_jobs", "list_jobs"]);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "unexpected call to crawl")]
+    async fn test_mock_panics_on_unexpected_call() {
+        let _ = MockRefyne::new().crawl(CrawlRequest::default()).await;
+    }
+
+    #[test]
+    fn test_client_is_refyne_api() {
+        let client = Client::builder("test-key").build().unwrap();
+        let _: Arc<dyn RefyneApi> = Arc::new(client);
+    }
+}
@@ -0,0 +1,480 @@
+//! Batch extraction with a queue of classified failures.
+//!
+//! [`Client::extract_batch`](crate::Client::extract_batch) runs a list of
+//! extractions and collects the ones that failed into a [`FailureQueue`],
+//! classified so callers can tell transient failures from sites that refuse
+//! access or requests that will never succeed.
+//! [`Client::retry_failures`](crate::Client::retry_failures) re-runs only the
+//! retryable ones with a slower [`RetryPolicy`].
+//!
+//! [`Client::extract_many`](crate::Client::extract_many) runs extractions
+//! concurrently instead, yielding each result as it completes:
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use refyne::{Client, ExtractRequest};
+//!
+//! # async fn run(requests: Vec<ExtractRequest>) -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let mut results = client.extract_many(requests, 4);
+//! while let Some(item) = results.next().await {
+//!     match item.result {
+//!         Ok(response) => println!("{}: {}", item.url, response.data),
+//!         Err(e) => eprintln!("{}: {}", item.url, e),
+//!     }
+//! }
+//! println!("${:.4} spent", results.usage().cost_usd);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{ApiErrorCode, Error, Result};
+use crate::runtime::BoxFuture;
+use crate::types::{ExtractRequest, ExtractResponse};
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Why an extraction failed, and whether retrying can help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailureKind {
+    /// Transient failure: rate limiting, timeouts, network or server errors.
+    Retryable,
+    /// Access was refused for the target or account.
+    Blocked,
+    /// The request itself is invalid and will fail again as is.
+    Invalid,
+    /// The response timed out after the request was sent, so the API may
+    /// have run it. Retrying can run and bill it twice.
+    Uncertain,
+}
+
+impl FailureKind {
+    /// Classify an error.
+    pub fn of(error: &Error) -> Self {
+        if error.is_retryable() {
+            return FailureKind::Retryable;
+        }
+        match error {
+            Error::Forbidden { .. } => FailureKind::Blocked,
+            Error::Api { status: 451, .. } => FailureKind::Blocked,
+            Error::ReadTimeout { .. } => FailureKind::Uncertain,
+            _ if error.api_code() == Some(&ApiErrorCode::FetchBlocked) => FailureKind::Blocked,
+            _ => FailureKind::Invalid,
+        }
+    }
+}
+
+/// A failed extraction.
+#[derive(Debug)]
+pub struct Failure {
+    /// The request that failed.
+    pub request: ExtractRequest,
+    /// The error from the last attempt.
+    pub error: Error,
+    /// Classification of the error.
+    pub kind: FailureKind,
+    /// Number of attempts made so far.
+    pub attempts: u32,
+}
+
+impl Failure {
+    pub(crate) fn new(request: ExtractRequest, error: Error, attempts: u32) -> Self {
+        Self {
+            kind: FailureKind::of(&error),
+            request,
+            error,
+            attempts,
+        }
+    }
+}
+
+/// Failed extractions from a batch, in the order they failed.
+#[derive(Debug, Default)]
+pub struct FailureQueue {
+    failures: Vec<Failure>,
+}
+
+impl FailureQueue {
+    /// Add a failure to the queue.
+    pub fn push(&mut self, failure: Failure) {
+        self.failures.push(failure);
+    }
+
+    /// Number of failures in the queue.
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// True if nothing failed.
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Iterate over all failures.
+    pub fn iter(&self) -> impl Iterator<Item = &Failure> {
+        self.failures.iter()
+    }
+
+    /// Iterate over failures of one kind.
+    pub fn of_kind(&self, kind: FailureKind) -> impl Iterator<Item = &Failure> {
+        self.failures.iter().filter(move |f| f.kind == kind)
+    }
+
+    /// Number of failures of one kind.
+    pub fn count(&self, kind: FailureKind) -> usize {
+        self.of_kind(kind).count()
+    }
+
+    /// URLs of failures of one kind.
+    pub fn urls(&self, kind: FailureKind) -> Vec<&str> {
+        self.of_kind(kind).map(|f| f.request.url.as_str()).collect()
+    }
+
+    /// Split the queue into retryable failures and the rest.
+    pub(crate) fn split_retryable(self) -> (Vec<Failure>, FailureQueue) {
+        let (retryable, rest) = self
+            .failures
+            .into_iter()
+            .partition(|f| f.kind == FailureKind::Retryable);
+        (retryable, FailureQueue { failures: rest })
+    }
+}
+
+impl IntoIterator for FailureQueue {
+    type Item = Failure;
+    type IntoIter = std::vec::IntoIter<Failure>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.failures.into_iter()
+    }
+}
+
+/// Outcome of a batch of extractions.
+#[derive(Debug, Default)]
+pub struct BatchResult {
+    /// Successful extractions, in completion order.
+    pub succeeded: Vec<ExtractResponse>,
+    /// Extractions that failed.
+    pub failures: FailureQueue,
+}
+
+/// How [`Client::retry_failures`](crate::Client::retry_failures) re-runs
+/// retryable failures.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per failure in this retry pass.
+    pub max_attempts: u32,
+    /// Pause before each request.
+    pub delay: Duration,
+    /// Upper bound for the pause after it grows under rate limiting.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 2,
+            delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+/// One extraction from [`Client::extract_many`](crate::Client::extract_many).
+#[derive(Debug)]
+pub struct BatchItem {
+    /// Position of the request in the batch.
+    pub index: usize,
+    /// URL of the request.
+    pub url: String,
+    /// The extraction, or why it failed.
+    pub result: Result<ExtractResponse>,
+}
+
+/// Usage summed over the extractions of a batch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchUsage {
+    /// Extractions that succeeded.
+    pub succeeded: usize,
+    /// Extractions that failed.
+    pub failed: usize,
+    /// Input tokens used.
+    pub input_tokens: i64,
+    /// Output tokens used.
+    pub output_tokens: i64,
+    /// USD charged.
+    pub cost_usd: f64,
+    /// LLM cost reported by the providers, in USD.
+    pub llm_cost_usd: f64,
+}
+
+impl BatchUsage {
+    fn add(&mut self, result: &Result<ExtractResponse>) {
+        match result {
+            Ok(response) => {
+                let usage = &response.usage;
+                self.succeeded += 1;
+                self.input_tokens += usage.input_tokens;
+                self.output_tokens += usage.output_tokens;
+                self.cost_usd += usage.cost_usd;
+                self.llm_cost_usd += usage.llm_cost_usd;
+            }
+            Err(_) => self.failed += 1,
+        }
+    }
+}
+
+type StartExtraction =
+    Box<dyn FnMut(ExtractRequest) -> BoxFuture<'static, Result<ExtractResponse>> + Send>;
+
+/// A stream of extractions running concurrently.
+///
+/// At most `concurrency` extractions are in flight at once; the next request
+/// starts as soon as one finishes. Items are yielded in completion order, and
+/// a failed extraction does not stop the others.
+#[must_use = "streams do nothing unless polled"]
+pub struct ExtractMany {
+    start: StartExtraction,
+    queue: VecDeque<(usize, ExtractRequest)>,
+    running: Vec<BoxFuture<'static, BatchItem>>,
+    concurrency: usize,
+    usage: BatchUsage,
+}
+
+impl ExtractMany {
+    /// Run `requests` with `start`, `concurrency` at a time.
+    pub(crate) fn new<F>(requests: Vec<ExtractRequest>, concurrency: usize, start: F) -> Self
+    where
+        F: FnMut(ExtractRequest) -> BoxFuture<'static, Result<ExtractResponse>> + Send + 'static,
+    {
+        Self {
+            start: Box::new(start),
+            queue: requests.into_iter().enumerate().collect(),
+            running: Vec::new(),
+            concurrency: concurrency.max(1),
+            usage: BatchUsage::default(),
+        }
+    }
+
+    /// Usage of the extractions yielded so far. Once the stream has ended,
+    /// this covers the whole batch.
+    pub fn usage(&self) -> &BatchUsage {
+        &self.usage
+    }
+
+    /// Number of requests not yet started.
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Number of extractions in flight.
+    pub fn in_flight(&self) -> usize {
+        self.running.len()
+    }
+}
+
+// No field is structurally pinned; the running extractions are boxed.
+impl Unpin for ExtractMany {}
+
+impl Stream for ExtractMany {
+    type Item = BatchItem;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        while this.running.len() < this.concurrency {
+            let Some((index, request)) = this.queue.pop_front() else {
+                break;
+            };
+            let url = request.url.clone();
+            let extraction = (this.start)(request);
+            this.running.push(Box::pin(async move {
+                BatchItem {
+                    index,
+                    url,
+                    result: extraction.await,
+                }
+            }));
+        }
+        if this.running.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        for position in 0..this.running.len() {
+            if let Poll::Ready(item) = this.running[position].as_mut().poll(cx) {
+                drop(this.running.swap_remove(position));
+                this.usage.add(&item.result);
+                return Poll::Ready(Some(item));
+            }
+        }
+        Poll::Pending
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.queue.len() + self.running.len();
+        (remaining, Some(remaining))
+    }
+}
+
+/// Pause to apply after an error before the next request in a batch.
+///
+/// Rate limiting doubles the pace (bounded by `max`) and honours the server's
+/// Retry-After; other errors leave it unchanged.
+pub(crate) fn back_off(error: &Error, delay: Duration, max: Duration) -> Duration {
+    match error {
+        Error::RateLimit { retry_after, .. } | Error::CircuitOpen { retry_after } => (delay * 2)
+            .max(Duration::from_secs(*retry_after))
+            .min(max.max(delay)),
+        _ => delay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(url: &str, error: Error) -> Failure {
+        let request = ExtractRequest {
+            url: url.into(),
+            ..Default::default()
+        };
+        Failure::new(request, error, 1)
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(FailureKind::of(&Error::Timeout), FailureKind::Retryable);
+        assert_eq!(
+            FailureKind::of(&Error::ReadTimeout { idempotent: false }),
+            FailureKind::Uncertain
+        );
+        assert_eq!(
+            FailureKind::of(&Error::Api {
+                status: 502,
+                message: "Bad gateway".into(),
+                detail: None,
+                code: None,
+                request_id: None,
+            }),
+            FailureKind::Retryable
+        );
+        assert_eq!(
+            FailureKind::of(&Error::Forbidden {
+                message: "blocked".into(),
+                request_id: None,
+            }),
+            FailureKind::Blocked
+        );
+        assert_eq!(
+            FailureKind::of(&Error::NotFound {
+                message: "gone".into(),
+                request_id: None,
+            }),
+            FailureKind::Invalid
+        );
+        assert_eq!(
+            FailureKind::of(&Error::Api {
+                status: 422,
+                message: "Site refused access".into(),
+                detail: None,
+                code: Some(ApiErrorCode::FetchBlocked),
+                request_id: None,
+            }),
+            FailureKind::Blocked
+        );
+    }
+
+    #[test]
+    fn test_failure_queue() {
+        let mut queue = FailureQueue::default();
+        queue.push(failure("https://a.example", Error::Timeout));
+        queue.push(failure(
+            "https://b.example",
+            Error::Forbidden {
+                message: "no".into(),
+                request_id: None,
+            },
+        ));
+        queue.push(failure("https://c.example", Error::Timeout));
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.count(FailureKind::Retryable), 2);
+        assert_eq!(queue.urls(FailureKind::Blocked), vec!["https://b.example"]);
+
+        let (retryable, rest) = queue.split_retryable();
+        assert_eq!(retryable.len(), 2);
+        assert_eq!(rest.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_extract_many_bounds_concurrency() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let fixture = crate::golden::load("0.1.80", "extract").unwrap();
+        let response: ExtractResponse = serde_json::from_value(fixture).unwrap();
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let requests = (0..5)
+            .map(|i| ExtractRequest {
+                url: format!("https://{}.example", i),
+                ..Default::default()
+            })
+            .collect();
+
+        let (counter, high) = (running.clone(), peak.clone());
+        let mut results = ExtractMany::new(requests, 2, move |request| {
+            let (counter, high, response) = (counter.clone(), high.clone(), response.clone());
+            Box::pin(async move {
+                high.fetch_max(counter.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                counter.fetch_sub(1, Ordering::SeqCst);
+                if request.url.starts_with("https://3.") {
+                    Err(Error::Timeout)
+                } else {
+                    Ok(response)
+                }
+            })
+        });
+
+        let mut seen = Vec::new();
+        while let Some(item) = results.next().await {
+            assert_eq!(item.url, format!("https://{}.example", item.index));
+            assert_eq!(item.result.is_err(), item.index == 3);
+            seen.push(item.index);
+        }
+        seen.sort();
+        assert_eq!(seen, [0, 1, 2, 3, 4]);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        let usage = results.usage();
+        assert_eq!((usage.succeeded, usage.failed), (4, 1));
+        let fixture = crate::golden::load("0.1.80", "extract").unwrap();
+        let per_page = fixture["usage"]["input_tokens"].as_i64().unwrap();
+        assert_eq!(usage.input_tokens, 4 * per_page);
+    }
+
+    #[test]
+    fn test_back_off() {
+        let max = Duration::from_secs(60);
+        let rate_limited = Error::RateLimit {
+            retry_after: 10,
+            message: "slow down".into(),
+            retries: 0,
+            waited: Duration::ZERO,
+            request_id: None,
+        };
+        assert_eq!(
+            back_off(&rate_limited, Duration::from_secs(2), max),
+            Duration::from_secs(10)
+        );
+        assert_eq!(back_off(&rate_limited, Duration::from_secs(40), max), max);
+        assert_eq!(
+            back_off(&Error::Timeout, Duration::from_secs(2), max),
+            Duration::from_secs(2)
+        );
+    }
+}
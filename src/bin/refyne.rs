@@ -0,0 +1,372 @@
+//! Command-line interface to the Refyne API.
+//!
+//! Build with `cargo install refyne --features cli`. The client is
+//! configured from `REFYNE_API_KEY` and the other `REFYNE_*` variables, or
+//! from a named profile with `--profile`:
+//!
+//! ```sh
+//! refyne extract https://example.com/product --schema '{"name": "string"}'
+//! refyne crawl https://example.com --schema @schema.json --max-pages 20 --wait
+//! refyne jobs list --limit 10
+//! refyne --output json jobs get 01HXYZ...
+//! refyne jobs results 01HXYZ... --stream > results.jsonl
+//! ```
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use futures_core::Stream;
+use refyne::{
+    Client, ClientBuilder, CrawlOptions, CrawlRequest, Error, ExtractRequest, WaitOptions,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+use std::pin::pin;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    name = "refyne",
+    version,
+    about = "LLM-powered web extraction from the command line"
+)]
+struct Cli {
+    /// Named profile from the config file instead of `REFYNE_*` variables.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// API base URL, overriding the environment or profile.
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+
+    /// How to print results.
+    #[arg(long, global = true, value_enum, default_value_t = Output::Table)]
+    output: Output,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    /// Tables for lists and single records, JSON for extracted data.
+    Table,
+    /// Pretty-printed JSON.
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Extract data from a single page.
+    Extract(ExtractArgs),
+    /// Start a crawl job.
+    Crawl(CrawlArgs),
+    /// Inspect crawl jobs and their results.
+    #[command(subcommand)]
+    Jobs(JobsCommand),
+    /// Inspect saved schemas.
+    #[command(subcommand)]
+    Schemas(ResourceCommand),
+    /// Inspect saved sites.
+    #[command(subcommand)]
+    Sites(ResourceCommand),
+}
+
+#[derive(Args)]
+struct ExtractArgs {
+    /// Page to extract from.
+    url: String,
+    /// Schema as JSON, a freeform prompt, or `@path` to read it from a file.
+    #[arg(long)]
+    schema: String,
+    /// How to fetch the page: auto, static, or dynamic.
+    #[arg(long)]
+    fetch_mode: Option<String>,
+}
+
+#[derive(Args)]
+struct CrawlArgs {
+    /// Page to start crawling from.
+    url: String,
+    /// Schema as JSON, a freeform prompt, or `@path` to read it from a file.
+    #[arg(long)]
+    schema: String,
+    /// Stop after extracting this many pages.
+    #[arg(long)]
+    max_pages: Option<i64>,
+    /// Follow links at most this deep.
+    #[arg(long)]
+    max_depth: Option<i64>,
+    /// Wait for the job to finish and print its final state.
+    #[arg(long)]
+    wait: bool,
+}
+
+#[derive(Subcommand)]
+enum JobsCommand {
+    /// List recent jobs.
+    List {
+        /// Jobs to return.
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Jobs to skip.
+        #[arg(long)]
+        offset: Option<u32>,
+    },
+    /// Show one job.
+    Get {
+        /// Job ID.
+        id: String,
+    },
+    /// Print a job's results.
+    Results {
+        /// Job ID.
+        id: String,
+        /// Merge the pages into a single result.
+        #[arg(long, conflicts_with = "stream")]
+        merge: bool,
+        /// Print each page as a line of JSON as it downloads.
+        #[arg(long)]
+        stream: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ResourceCommand {
+    /// List them all.
+    List,
+    /// Show one by ID.
+    Get {
+        /// ID to look up.
+        id: String,
+    },
+}
+
+const JOB_COLUMNS: &[&str] = &["id", "status", "type", "url", "page_count", "created_at"];
+const SCHEMA_COLUMNS: &[&str] = &["id", "name", "category", "usage_count", "updated_at"];
+const SITE_COLUMNS: &[&str] = &["id", "name", "url", "fetch_mode", "updated_at"];
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Error> {
+    let client = client(&cli)?;
+    let output = cli.output;
+    match cli.command {
+        Command::Extract(args) => {
+            let response = client
+                .extract(ExtractRequest {
+                    url: args.url,
+                    schema: schema_arg(&args.schema)?,
+                    fetch_mode: args.fetch_mode.as_deref().map(enum_arg).transpose()?,
+                    ..Default::default()
+                })
+                .await?;
+            print_json(&response.data)
+        }
+        Command::Crawl(args) => {
+            let created = client
+                .crawl(CrawlRequest {
+                    url: args.url,
+                    schema: schema_arg(&args.schema)?,
+                    options: Some(CrawlOptions {
+                        max_depth: args.max_depth,
+                        max_pages: args.max_pages,
+                        ..CrawlOptions::builder().build()
+                    }),
+                    ..Default::default()
+                })
+                .await?;
+            if args.wait {
+                let job = client
+                    .jobs()
+                    .wait(&created.job_id, WaitOptions::new())
+                    .await?;
+                print_record(output, &job, JOB_COLUMNS)
+            } else {
+                print_record(output, &created, &["job_id", "status", "status_url"])
+            }
+        }
+        Command::Jobs(JobsCommand::List { limit, offset }) => {
+            let list = client.jobs().list(limit, offset).await?;
+            print_list(output, &list.jobs, JOB_COLUMNS)
+        }
+        Command::Jobs(JobsCommand::Get { id }) => {
+            print_record(output, &client.jobs().get(&id).await?, JOB_COLUMNS)
+        }
+        Command::Jobs(JobsCommand::Results {
+            id, stream: true, ..
+        }) => stream_results(client.jobs().stream_results(&id)).await,
+        Command::Jobs(JobsCommand::Results { id, merge, .. }) => {
+            print_json(&client.jobs().get_results(&id, merge).await?)
+        }
+        Command::Schemas(ResourceCommand::List) => {
+            let list = client.schemas().list().await?;
+            print_list(output, &list.schemas, SCHEMA_COLUMNS)
+        }
+        Command::Schemas(ResourceCommand::Get { id }) => {
+            print_record(output, &client.schemas().get(&id).await?, SCHEMA_COLUMNS)
+        }
+        Command::Sites(ResourceCommand::List) => {
+            let list = client.sites().list().await?;
+            print_list(output, &list.sites, SITE_COLUMNS)
+        }
+        Command::Sites(ResourceCommand::Get { id }) => {
+            print_record(output, &client.sites().get(&id).await?, SITE_COLUMNS)
+        }
+    }
+}
+
+/// Build the client from the profile or environment, then apply flags.
+fn client(cli: &Cli) -> Result<Client, Error> {
+    let mut builder = match &cli.profile {
+        Some(profile) => ClientBuilder::from_profile(profile)?,
+        None => ClientBuilder::from_env()?,
+    };
+    if let Some(url) = &cli.base_url {
+        builder = builder.base_url(url);
+    }
+    builder
+        .user_agent_suffix(concat!("refyne-cli/", env!("CARGO_PKG_VERSION")))
+        .build()
+}
+
+/// Read a `--schema` argument: `@path` reads a file, and anything that is
+/// not JSON is sent as text for the API to interpret.
+fn schema_arg(arg: &str) -> Result<Value, Error> {
+    let text = match arg.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => arg.to_string(),
+    };
+    Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+}
+
+/// Parse a lowercase enum argument such as `--fetch-mode dynamic`.
+fn enum_arg<T: serde::de::DeserializeOwned>(arg: &str) -> Result<T, Error> {
+    serde_json::from_value(Value::String(arg.to_lowercase()))
+        .map_err(|_| Error::Config(format!("unsupported value {:?}", arg)))
+}
+
+async fn stream_results(results: impl Stream<Item = Result<Value, Error>>) -> Result<(), Error> {
+    let mut results = pin!(results);
+    let mut out = std::io::stdout().lock();
+    while let Some(item) = std::future::poll_fn(|cx| results.as_mut().poll_next(cx)).await {
+        serde_json::to_writer(&mut out, &item?)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), Error> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Print one record, as `field  value` lines in table mode.
+fn print_record(output: Output, record: &impl Serialize, fields: &[&str]) -> Result<(), Error> {
+    if output == Output::Json {
+        return print_json(record);
+    }
+    let record = serde_json::to_value(record)?;
+    let rows: Vec<Vec<String>> = fields
+        .iter()
+        .map(|field| vec![field.to_string(), cell(&record[*field])])
+        .collect();
+    print!("{}", table(None, &rows));
+    Ok(())
+}
+
+/// Print a list, as a table of `columns` in table mode.
+fn print_list(output: Output, items: &Value, columns: &[&str]) -> Result<(), Error> {
+    if output == Output::Json {
+        return print_json(items);
+    }
+    let rows: Vec<Vec<String>> = items
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|item| columns.iter().map(|column| cell(&item[*column])).collect())
+        .collect();
+    print!("{}", table(Some(columns), &rows));
+    Ok(())
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Lay out rows in left-aligned columns, with an upper-case header if given.
+fn table(header: Option<&[&str]>, rows: &[Vec<String>]) -> String {
+    let header: Option<Vec<String>> =
+        header.map(|columns| columns.iter().map(|c| c.to_uppercase()).collect());
+    let all: Vec<&Vec<String>> = header.iter().chain(rows).collect();
+    let columns = all.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            all.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = String::new();
+    for row in all {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_arg() {
+        assert_eq!(
+            schema_arg(r#"{"name": "string"}"#).unwrap(),
+            json!({"name": "string"})
+        );
+        assert_eq!(
+            schema_arg("the product name and price").unwrap(),
+            json!("the product name and price")
+        );
+        assert!(matches!(
+            schema_arg("@/nonexistent/schema.json"),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_table() {
+        let rows = vec![
+            vec!["01HXYZ".to_string(), "completed".to_string()],
+            vec!["01HABCDEFG".to_string(), String::new()],
+        ];
+        assert_eq!(
+            table(Some(&["id", "status"]), &rows),
+            "ID          STATUS\n01HXYZ      completed\n01HABCDEFG\n"
+        );
+    }
+}
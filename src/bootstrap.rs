@@ -0,0 +1,65 @@
+//! One-call site onboarding.
+//!
+//! [`Client::bootstrap_site`](crate::Client::bootstrap_site) runs the documented
+//! onboarding flow: analyze a URL, save the suggested schema, and create a site
+//! that uses it.
+
+use crate::types::{
+    AnalysisResultInput, AnalyzeResponse, CreateSavedSiteInputBodyFetchMode,
+    CreateSchemaInputBodyVisibility, CreateSchemaRequest, CreateSiteRequest, Schema, Site,
+};
+
+/// Resources created by [`Client::bootstrap_site`](crate::Client::bootstrap_site).
+#[derive(Debug, Clone)]
+pub struct BootstrappedSite {
+    /// The analysis the schema and site were built from.
+    pub analysis: AnalyzeResponse,
+    /// The saved schema.
+    pub schema: Schema,
+    /// The saved site, with the schema as its default.
+    pub site: Site,
+}
+
+/// Build the schema request for an analysis.
+pub(crate) fn schema_request(analysis: &AnalyzeResponse, name: &str) -> CreateSchemaRequest {
+    CreateSchemaRequest {
+        category: None,
+        description: Some(analysis.site_summary.clone()).filter(|s| !s.is_empty()),
+        name: name.to_string(),
+        // The suggestion is JSON, which is also valid YAML.
+        schema_yaml: analysis.suggested_schema.clone(),
+        tags: None,
+        visibility: CreateSchemaInputBodyVisibility::Private,
+    }
+}
+
+/// Build the site request for an analysis, wired to a saved schema.
+pub(crate) fn site_request(
+    analysis: &AnalyzeResponse,
+    url: &str,
+    name: &str,
+    schema_id: &str,
+) -> CreateSiteRequest {
+    let fetch_mode = match analysis.recommended_fetch_mode.as_str() {
+        "static" => Some(CreateSavedSiteInputBodyFetchMode::Static),
+        "dynamic" => Some(CreateSavedSiteInputBodyFetchMode::Dynamic),
+        _ => None,
+    };
+
+    CreateSiteRequest {
+        analysis_result: Some(AnalysisResultInput {
+            detected_elements: Some(analysis.detected_elements.clone()),
+            follow_patterns: Some(analysis.follow_patterns.clone()),
+            page_type: Some(analysis.page_type.clone()),
+            recommended_fetch_mode: Some(analysis.recommended_fetch_mode.clone()),
+            sample_links: Some(analysis.sample_links.clone()),
+            site_summary: Some(analysis.site_summary.clone()),
+            suggested_schema: Some(analysis.suggested_schema.clone()),
+        }),
+        crawl_options: None,
+        default_schema_id: Some(schema_id.to_string()),
+        fetch_mode,
+        name: Some(name.to_string()),
+        url: url.to_string(),
+    }
+}
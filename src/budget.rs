@@ -0,0 +1,120 @@
+//! Monthly spend budgets.
+//!
+//! A budget caps what the account spends in a calendar month. The API sends
+//! alerts as spend crosses each threshold, and refuses new work once the
+//! budget is used up, which surfaces as [`Error::BudgetExceeded`]:
+//!
+//! ```rust,no_run
+//! use refyne::{Error, ExtractRequest};
+//!
+//! # async fn run(client: refyne::Client, request: ExtractRequest) -> Result<(), Error> {
+//! // Alert at half and at 90% of $500 a month.
+//! client.usage().set_budget(500.0, &[0.5, 0.9]).await?;
+//!
+//! match client.extract(request).await {
+//!     Err(Error::BudgetExceeded { .. }) => println!("monthly budget used up"),
+//!     other => println!("{:?}", other?.data),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Error::BudgetExceeded`]: crate::Error::BudgetExceeded
+
+use crate::error::{Error, Result};
+use crate::timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// The account's monthly budget and spend against it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Budget {
+    /// USD the account may spend per month. Zero when no budget is set.
+    pub monthly_usd: f64,
+    /// Shares of the budget, from 0.0 to 1.0, at which alerts are sent.
+    pub alert_thresholds: Vec<f64>,
+    /// USD spent so far this month.
+    pub spent_usd: f64,
+    /// Start of the current budget month.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period_start: Option<Timestamp>,
+    /// End of the current budget month, when the spend resets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period_end: Option<Timestamp>,
+}
+
+impl Budget {
+    /// Returns true if a budget is set.
+    pub fn is_set(&self) -> bool {
+        self.monthly_usd > 0.0
+    }
+
+    /// USD left to spend this month, or `None` without a budget.
+    pub fn remaining_usd(&self) -> Option<f64> {
+        self.is_set()
+            .then(|| (self.monthly_usd - self.spent_usd).max(0.0))
+    }
+
+    /// Share of the budget spent this month, or `None` without a budget.
+    pub fn fraction_used(&self) -> Option<f64> {
+        self.is_set().then(|| self.spent_usd / self.monthly_usd)
+    }
+}
+
+/// Body of a budget update.
+#[derive(Debug, Serialize)]
+pub(crate) struct BudgetInput<'a> {
+    monthly_usd: f64,
+    alert_thresholds: &'a [f64],
+}
+
+impl<'a> BudgetInput<'a> {
+    /// Fail with [`Error::Config`] on a negative budget or a threshold
+    /// outside 0.0 to 1.0.
+    pub(crate) fn new(monthly_usd: f64, alert_thresholds: &'a [f64]) -> Result<Self> {
+        if !monthly_usd.is_finite() || monthly_usd < 0.0 {
+            return Err(Error::Config(format!(
+                "Invalid monthly budget {}: expected zero or more USD",
+                monthly_usd
+            )));
+        }
+        if let Some(threshold) = alert_thresholds.iter().find(|t| !(**t > 0.0 && **t <= 1.0)) {
+            return Err(Error::Config(format!(
+                "Invalid alert threshold {}: expected a share of the budget above 0.0 and at most 1.0",
+                threshold
+            )));
+        }
+        Ok(Self {
+            monthly_usd,
+            alert_thresholds,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_input_checks_values() {
+        assert!(BudgetInput::new(100.0, &[0.5, 1.0]).is_ok());
+        for (monthly_usd, thresholds) in [(-1.0, &[][..]), (f64::NAN, &[]), (100.0, &[80.0])] {
+            assert!(matches!(
+                BudgetInput::new(monthly_usd, thresholds),
+                Err(Error::Config(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_budget_spend() {
+        let budget = Budget {
+            monthly_usd: 200.0,
+            spent_usd: 150.0,
+            ..Default::default()
+        };
+        assert_eq!(budget.remaining_usd(), Some(50.0));
+        assert_eq!(budget.fraction_used(), Some(0.75));
+        assert_eq!(Budget::default().remaining_usd(), None);
+    }
+}
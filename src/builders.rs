@@ -0,0 +1,525 @@
+//! Fluent builders for request types.
+//!
+//! Request structs are generated from the API specification, so most of
+//! their fields are optional and a struct literal lists many `None`s. Each
+//! request type has a `builder` taking its required fields, with a setter
+//! for the rest:
+//!
+//! ```rust
+//! use refyne::CrawlRequest;
+//! use serde_json::json;
+//!
+//! let request = CrawlRequest::builder("https://example.com/products", json!({"name": "string"}))
+//!     .max_pages(5)
+//!     .same_domain_only(true)
+//!     .webhook_url("https://hooks.example.com/refyne")
+//!     .build();
+//! assert_eq!(request.options.unwrap().max_pages, Some(5));
+//! ```
+//!
+//! Struct literals keep working, and `build()` returns the plain struct, so
+//! a built request can still be adjusted field by field.
+//! [`WebhookInput`](crate::WebhookInput) has chainable setters on the struct
+//! itself instead.
+
+use crate::content::ContentFormat;
+use crate::fetch::FetchOptions;
+use crate::json_schema::SchemaFormat;
+use crate::screenshot::ScreenshotOptions;
+use crate::secret::SecretString;
+use crate::types::{
+    AnalysisResultInput, AnalyzeInputBodyFetchMode, AnalyzeRequest, CrawlOptions,
+    CrawlOptionsFetchMode, CrawlOptionsInput, CrawlRequest, CreateSavedSiteInputBodyFetchMode,
+    CreateSchemaInputBodyVisibility, CreateSchemaRequest, CreateSiteRequest,
+    ExtractInputBodyFetchMode, ExtractRequest, InlineWebhookInput, LLMConfigInput,
+    UpsertLlmKeyRequest,
+};
+use serde_json::Value;
+
+/// Setters for optional fields of the request being built: a field of the
+/// builder, or what `fn` returns.
+macro_rules! setters {
+    (fn $target:ident; $($(#[$doc:meta])* $field:ident: $ty:ty;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $field(mut self, $field: impl Into<$ty>) -> Self {
+                self.$target().$field = Some($field.into());
+                self
+            }
+        )*
+    };
+    ($target:ident; $($(#[$doc:meta])* $field:ident: $ty:ty;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $field(mut self, $field: impl Into<$ty>) -> Self {
+                self.$target.$field = Some($field.into());
+                self
+            }
+        )*
+    };
+}
+
+/// Setters for every crawl option, on either crawl builder.
+macro_rules! crawl_option_setters {
+    ($($target:tt)+) => {
+        setters! { $($target)+;
+            /// Extract from this many pages at once.
+            concurrency: i64;
+            /// Wait at least the `Crawl-delay` in the site's robots.txt
+            /// between requests.
+            crawl_delay_from_robots: bool;
+            /// Wait between requests, such as `500ms` or `1s`.
+            delay: String;
+            /// Never crawl URLs matching this regex.
+            exclude_pattern: String;
+            /// Extract from the seed URL as well as the pages it links to.
+            extract_from_seeds: bool;
+            /// How pages are fetched.
+            fetch_mode: CrawlOptionsFetchMode;
+            /// Only crawl URLs matching this regex.
+            follow_pattern: String;
+            /// Follow links matching these CSS selectors.
+            follow_selector: String;
+            /// Follow links at most this deep.
+            max_depth: i64;
+            /// Stop after this many pages. 0 is the account's limit.
+            max_pages: i64;
+            /// Queue at most this many URLs.
+            max_urls: i64;
+            /// Follow pagination through this CSS selector.
+            next_selector: String;
+            /// Skip URLs the site's robots.txt disallows.
+            respect_robots_txt: bool;
+            /// Only follow links on the seed URL's domain.
+            same_domain_only: bool;
+            /// Read this sitemap with `use_sitemap`, instead of the site's
+            /// `/sitemap.xml`.
+            sitemap_url: String;
+            /// Find pages through the site's sitemap.
+            use_sitemap: bool;
+        }
+    };
+}
+
+/// A JSON array of strings, as the API takes tags and scopes.
+fn strings<S: Into<String>>(values: impl IntoIterator<Item = S>) -> Value {
+    Value::Array(
+        values
+            .into_iter()
+            .map(|value| Value::String(value.into()))
+            .collect(),
+    )
+}
+
+impl CrawlRequest {
+    /// Start building a crawl from `url`, extracting with `schema`.
+    ///
+    /// `schema` is a schema value or its YAML, or a prompt in plain language.
+    pub fn builder(url: impl Into<String>, schema: impl Into<Value>) -> CrawlRequestBuilder {
+        CrawlRequestBuilder {
+            request: CrawlRequest {
+                url: url.into(),
+                schema: schema.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder for [`CrawlRequest`]. Crawl options can be set here directly.
+#[derive(Debug, Clone)]
+pub struct CrawlRequestBuilder {
+    request: CrawlRequest,
+}
+
+impl CrawlRequestBuilder {
+    setters! { request;
+        /// Store the raw LLM request and response for troubleshooting.
+        capture_debug: bool;
+        /// Fetch each page with these headers, cookies, or proxy country.
+        fetch_options: FetchOptions;
+        /// Override the account's LLM configuration.
+        llm_config: LLMConfigInput;
+        /// Call a webhook created just for this job.
+        webhook: InlineWebhookInput;
+        /// Notify a saved webhook of the job's events.
+        webhook_id: String;
+        /// POST job events to this URL.
+        webhook_url: String;
+    }
+
+    /// Crawl from these seed URLs as well.
+    pub fn urls<S: Into<String>>(mut self, urls: impl IntoIterator<Item = S>) -> Self {
+        self.request.urls.extend(urls.into_iter().map(Into::into));
+        self
+    }
+
+    /// Clean pages with this cleaner chain instead of the default.
+    pub fn cleaner_chain(mut self, chain: Value) -> Self {
+        self.request.cleaner_chain = Some(chain);
+        self
+    }
+
+    /// Replace all crawl options.
+    pub fn options(mut self, options: CrawlOptions) -> Self {
+        self.request.options = Some(options);
+        self
+    }
+
+    crawl_option_setters! { fn options_mut }
+
+    /// The crawl request.
+    pub fn build(self) -> CrawlRequest {
+        self.request
+    }
+
+    /// Options to set one of, creating them if unset.
+    fn options_mut(&mut self) -> &mut CrawlOptions {
+        self.request
+            .options
+            .get_or_insert_with(|| CrawlOptions::builder().build())
+    }
+}
+
+impl CrawlOptions {
+    /// Start building crawl options, with every option unset.
+    pub fn builder() -> CrawlOptionsBuilder {
+        CrawlOptionsBuilder {
+            options: CrawlOptions {
+                concurrency: None,
+                crawl_delay_from_robots: None,
+                delay: None,
+                exclude_pattern: None,
+                extract_from_seeds: None,
+                fetch_mode: None,
+                follow_pattern: None,
+                follow_selector: None,
+                max_depth: None,
+                max_pages: None,
+                max_urls: None,
+                next_selector: None,
+                respect_robots_txt: None,
+                same_domain_only: None,
+                sitemap_url: None,
+                use_sitemap: None,
+            },
+        }
+    }
+}
+
+/// Builder for [`CrawlOptions`].
+#[derive(Debug, Clone)]
+pub struct CrawlOptionsBuilder {
+    options: CrawlOptions,
+}
+
+impl CrawlOptionsBuilder {
+    crawl_option_setters! { options }
+
+    /// The crawl options.
+    pub fn build(self) -> CrawlOptions {
+        self.options
+    }
+}
+
+impl ExtractRequest {
+    /// Start building an extraction from `url`. Set a schema with
+    /// [`schema`](ExtractRequestBuilder::schema) or
+    /// [`schema_id`](ExtractRequestBuilder::schema_id).
+    pub fn builder(url: impl Into<String>) -> ExtractRequestBuilder {
+        ExtractRequestBuilder {
+            request: ExtractRequest {
+                url: url.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder for [`ExtractRequest`].
+#[derive(Debug, Clone)]
+pub struct ExtractRequestBuilder {
+    request: ExtractRequest,
+}
+
+impl ExtractRequestBuilder {
+    /// Extract with an inline schema, its YAML, or a prompt in plain
+    /// language.
+    pub fn schema(mut self, schema: impl Into<Value>) -> Self {
+        self.request.schema = schema.into();
+        self
+    }
+
+    setters! { request;
+        /// Say how the schema is written, rather than detecting it.
+        schema_format: SchemaFormat;
+        /// Extract with a saved schema.
+        schema_id: String;
+        /// Store the raw LLM request and response for troubleshooting.
+        capture_debug: bool;
+        /// Screenshot the page, for [`Client::get_screenshot`](crate::Client::get_screenshot).
+        capture_screenshot: ScreenshotOptions;
+        /// How the page is fetched.
+        fetch_mode: ExtractInputBodyFetchMode;
+        /// Fetch the page with these headers, cookies, or proxy country.
+        fetch_options: FetchOptions;
+        /// Override the account's LLM configuration.
+        llm_config: LLMConfigInput;
+        /// Call a webhook created just for this extraction.
+        webhook: InlineWebhookInput;
+        /// Notify a saved webhook when the extraction finishes.
+        webhook_id: String;
+        /// POST the result to this URL.
+        webhook_url: String;
+    }
+
+    /// Return the fetched page in this form, as
+    /// [`ExtractResponse::content`](crate::ExtractResponse).
+    pub fn include_content(mut self, format: ContentFormat) -> Self {
+        self.request.include_content = Some(format);
+        self
+    }
+
+    /// Only extract these top-level schema fields.
+    pub fn fields<S: Into<String>>(mut self, fields: impl IntoIterator<Item = S>) -> Self {
+        self.request.fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Clean the page with this cleaner chain instead of the default.
+    pub fn cleaner_chain(mut self, chain: Value) -> Self {
+        self.request.cleaner_chain = Some(chain);
+        self
+    }
+
+    /// The extract request.
+    pub fn build(self) -> ExtractRequest {
+        self.request
+    }
+}
+
+impl AnalyzeRequest {
+    /// Start building an analysis of `url`.
+    pub fn builder(url: impl Into<String>) -> AnalyzeRequestBuilder {
+        AnalyzeRequestBuilder {
+            request: AnalyzeRequest {
+                url: url.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder for [`AnalyzeRequest`].
+#[derive(Debug, Clone)]
+pub struct AnalyzeRequestBuilder {
+    request: AnalyzeRequest,
+}
+
+impl AnalyzeRequestBuilder {
+    setters! { request;
+        /// Store the raw LLM request and response for troubleshooting.
+        capture_debug: bool;
+        /// Also analyze linked pages this deep. 0 is the page alone.
+        depth: i64;
+        /// How pages are fetched.
+        fetch_mode: AnalyzeInputBodyFetchMode;
+    }
+
+    /// The analyze request.
+    pub fn build(self) -> AnalyzeRequest {
+        self.request
+    }
+}
+
+impl CreateSchemaRequest {
+    /// Start building a private schema named `name`.
+    pub fn builder(
+        name: impl Into<String>,
+        schema_yaml: impl Into<String>,
+    ) -> CreateSchemaRequestBuilder {
+        CreateSchemaRequestBuilder {
+            request: CreateSchemaRequest {
+                category: None,
+                description: None,
+                name: name.into(),
+                schema_yaml: schema_yaml.into(),
+                tags: None,
+                visibility: CreateSchemaInputBodyVisibility::Private,
+            },
+        }
+    }
+}
+
+/// Builder for [`CreateSchemaRequest`].
+#[derive(Debug, Clone)]
+pub struct CreateSchemaRequestBuilder {
+    request: CreateSchemaRequest,
+}
+
+impl CreateSchemaRequestBuilder {
+    setters! { request;
+        /// File the schema under a category.
+        category: String;
+        /// Describe what the schema extracts.
+        description: String;
+    }
+
+    /// Tag the schema.
+    pub fn tags<S: Into<String>>(mut self, tags: impl IntoIterator<Item = S>) -> Self {
+        self.request.tags = Some(strings(tags));
+        self
+    }
+
+    /// Who can see the schema. Private by default.
+    pub fn visibility(mut self, visibility: CreateSchemaInputBodyVisibility) -> Self {
+        self.request.visibility = visibility;
+        self
+    }
+
+    /// The schema request.
+    pub fn build(self) -> CreateSchemaRequest {
+        self.request
+    }
+}
+
+impl CreateSiteRequest {
+    /// Start building a saved site for `url`.
+    pub fn builder(url: impl Into<String>) -> CreateSiteRequestBuilder {
+        CreateSiteRequestBuilder {
+            request: CreateSiteRequest {
+                url: url.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder for [`CreateSiteRequest`].
+#[derive(Debug, Clone)]
+pub struct CreateSiteRequestBuilder {
+    request: CreateSiteRequest,
+}
+
+impl CreateSiteRequestBuilder {
+    setters! { request;
+        /// Keep the result of analyzing the site.
+        analysis_result: AnalysisResultInput;
+        /// Crawl the site with these options.
+        crawl_options: CrawlOptionsInput;
+        /// Extract with this saved schema unless a run says otherwise.
+        default_schema_id: String;
+        /// How pages are fetched.
+        fetch_mode: CreateSavedSiteInputBodyFetchMode;
+        /// Name the site.
+        name: String;
+    }
+
+    /// The site request.
+    pub fn build(self) -> CreateSiteRequest {
+        self.request
+    }
+}
+
+impl UpsertLlmKeyRequest {
+    /// Start building an enabled key for `provider`.
+    pub fn builder(provider: impl Into<String>) -> UpsertLlmKeyRequestBuilder {
+        UpsertLlmKeyRequestBuilder {
+            request: UpsertLlmKeyRequest {
+                provider: provider.into(),
+                is_enabled: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder for [`UpsertLlmKeyRequest`].
+#[derive(Debug, Clone)]
+pub struct UpsertLlmKeyRequestBuilder {
+    request: UpsertLlmKeyRequest,
+}
+
+impl UpsertLlmKeyRequestBuilder {
+    setters! { request;
+        /// The provider's API key. Unset keeps the stored key.
+        api_key: SecretString;
+        /// Call the provider at this URL, such as a local Ollama.
+        base_url: String;
+    }
+
+    /// Enable or disable the provider.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.request.is_enabled = enabled;
+        self
+    }
+
+    /// The key request.
+    pub fn build(self) -> UpsertLlmKeyRequest {
+        self.request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_crawl_request_builder() {
+        let request = CrawlRequest::builder("https://example.com", "name: string\n")
+            .max_pages(5)
+            .same_domain_only(true)
+            .fetch_mode(CrawlOptionsFetchMode::Dynamic)
+            .respect_robots_txt(true)
+            .exclude_pattern("/cart/")
+            .webhook_url("https://hooks.example.com")
+            .build();
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "url": "https://example.com",
+                "schema": "name: string\n",
+                "webhook_url": "https://hooks.example.com",
+                "options": {
+                    "concurrency": null, "delay": null, "extract_from_seeds": null,
+                    "fetch_mode": "dynamic", "follow_pattern": null,
+                    "follow_selector": null, "max_depth": null, "max_pages": 5,
+                    "max_urls": null, "next_selector": null, "same_domain_only": true,
+                    "use_sitemap": null, "respect_robots_txt": true, "exclude_pattern": "/cart/",
+                },
+            })
+        );
+
+        let bare = CrawlRequest::builder("https://example.com", json!({})).build();
+        assert!(bare.options.is_none());
+    }
+
+    #[test]
+    fn test_request_builders_fill_required_fields() {
+        let extract = ExtractRequest::builder("https://example.com")
+            .schema_id("schema-1")
+            .fields(["name", "price"])
+            .build();
+        assert_eq!(
+            serde_json::to_value(&extract).unwrap(),
+            json!({
+                "url": "https://example.com",
+                "schema_id": "schema-1",
+                "fields": ["name", "price"],
+            })
+        );
+
+        let schema = CreateSchemaRequest::builder("Products", "name: string\n")
+            .tags(["retail"])
+            .build();
+        assert_eq!(schema.visibility, CreateSchemaInputBodyVisibility::Private);
+        assert_eq!(schema.tags, Some(json!(["retail"])));
+
+        let key = UpsertLlmKeyRequest::builder("openai")
+            .api_key("sk-test")
+            .build();
+        assert!(key.is_enabled);
+        assert_eq!(key.api_key.unwrap().expose_secret(), "sk-test");
+    }
+}
@@ -1,10 +1,12 @@
 //! Cache implementation that respects Cache-Control headers.
 
+use crate::clock::{Clock, SystemClock};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, VecDeque};
+use std::mem::size_of;
 use std::sync::{Arc, RwLock};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Trait for cache implementations.
 pub trait Cache: Send + Sync {
@@ -16,10 +18,17 @@ pub trait Cache: Send + Sync {
 
     /// Delete an entry from the cache.
     fn delete(&self, key: &str);
+
+    /// Delete every entry whose key starts with `prefix`.
+    ///
+    /// The client calls this after a request changes a resource, so cached
+    /// GET responses for it are not served stale. The default does nothing,
+    /// for caches that cannot enumerate their keys.
+    fn delete_prefix(&self, _prefix: &str) {}
 }
 
 /// A cached entry.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheEntry {
     /// The cached value.
     pub value: Value,
@@ -27,10 +36,63 @@ pub struct CacheEntry {
     pub expires_at: u64,
     /// Parsed Cache-Control directives.
     pub cache_control: CacheControlDirectives,
+    /// `ETag` of the response, sent as `If-None-Match` when revalidating.
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// `Last-Modified` of the response, sent as `If-Modified-Since` when
+    /// revalidating.
+    #[serde(default)]
+    pub last_modified: Option<String>,
 }
 
-/// Parsed Cache-Control header directives.
+/// Seconds an expired entry with validators is kept for conditional requests.
+pub const REVALIDATION_WINDOW: u64 = 24 * 60 * 60;
+
+impl CacheEntry {
+    /// Returns true if the entry has an `ETag` or `Last-Modified` to
+    /// revalidate with.
+    pub fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+
+    /// Returns true if the entry can be served without asking the API: it is
+    /// not `no-cache` and is within its max-age or stale-while-revalidate
+    /// window.
+    pub fn is_servable(&self, now: u64) -> bool {
+        let swr = self.cache_control.stale_while_revalidate.unwrap_or(0);
+        !self.cache_control.no_cache && self.expires_at.saturating_add(swr) >= now
+    }
+
+    /// Unix time after which the entry is of no further use.
+    ///
+    /// Entries with validators outlive their expiry by
+    /// [`REVALIDATION_WINDOW`] so they can still be revalidated.
+    pub fn retain_until(&self) -> u64 {
+        let swr = self.cache_control.stale_while_revalidate.unwrap_or(0);
+        let until = self.expires_at.saturating_add(swr);
+        if self.has_validators() {
+            until.max(self.expires_at.saturating_add(REVALIDATION_WINDOW))
+        } else {
+            until
+        }
+    }
+}
+
+/// `ETag` and `Last-Modified` values of a response.
 #[derive(Debug, Clone, Default)]
+pub(crate) struct Validators {
+    pub(crate) etag: Option<String>,
+    pub(crate) last_modified: Option<String>,
+}
+
+impl Validators {
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Parsed Cache-Control header directives.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CacheControlDirectives {
     /// Don't cache at all.
     pub no_store: bool,
@@ -79,7 +141,26 @@ pub fn parse_cache_control(header: Option<&str>) -> CacheControlDirectives {
 /// Create a cache entry from a response.
 ///
 /// Returns `None` if the response should not be cached.
+#[cfg(test)]
 pub fn create_cache_entry(value: Value, cache_control_header: Option<&str>) -> Option<CacheEntry> {
+    create_cache_entry_at(
+        value,
+        cache_control_header,
+        Validators::default(),
+        SystemClock.now_unix(),
+    )
+}
+
+/// Create a cache entry from a response received at `now` (Unix seconds).
+///
+/// Responses with validators are cached even without `max-age` or with
+/// `no-cache`, so they can be revalidated with a conditional request.
+pub(crate) fn create_cache_entry_at(
+    value: Value,
+    cache_control_header: Option<&str>,
+    validators: Validators,
+    now: u64,
+) -> Option<CacheEntry> {
     let cache_control = parse_cache_control(cache_control_header);
 
     // Don't cache if no-store
@@ -87,21 +168,39 @@ pub fn create_cache_entry(value: Value, cache_control_header: Option<&str>) -> O
         return None;
     }
 
-    // Need max-age to cache
-    let max_age = cache_control.max_age?;
-
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
+    // Without validators, only entries that can be served are worth keeping
+    if validators.is_empty() && (cache_control.no_cache || cache_control.max_age.is_none()) {
+        return None;
+    }
 
     Some(CacheEntry {
         value,
-        expires_at: now + max_age,
+        expires_at: now + cache_control.max_age.unwrap_or(0),
         cache_control,
+        etag: validators.etag,
+        last_modified: validators.last_modified,
     })
 }
 
+/// Refresh an entry after the API answered `304 Not Modified` at `now`.
+///
+/// New Cache-Control directives and validators replace the stored ones when
+/// the response carries them.
+pub(crate) fn revalidated_entry(
+    mut entry: CacheEntry,
+    cache_control_header: Option<&str>,
+    validators: Validators,
+    now: u64,
+) -> CacheEntry {
+    if cache_control_header.is_some() {
+        entry.cache_control = parse_cache_control(cache_control_header);
+    }
+    entry.expires_at = now + entry.cache_control.max_age.unwrap_or(0);
+    entry.etag = validators.etag.or(entry.etag);
+    entry.last_modified = validators.last_modified.or(entry.last_modified);
+    entry
+}
+
 /// Generate a cache key from request details.
 pub fn generate_cache_key(method: &str, url: &str, auth_hash: Option<&str>) -> String {
     let mut key = format!("{}:{}", method.to_uppercase(), url);
@@ -112,6 +211,57 @@ pub fn generate_cache_key(method: &str, url: &str, auth_hash: Option<&str>) -> S
     key
 }
 
+/// Collections whose cached GET responses a successful POST, PUT or DELETE
+/// can change. Requests such as extract and analyze leave cached responses
+/// alone, so they never scan the cache.
+const MUTABLE_COLLECTIONS: &[&str] = &[
+    "jobs",
+    "keys",
+    "llm",
+    "org",
+    "schedules",
+    "schemas",
+    "sites",
+    "usage",
+    "webhooks",
+];
+
+/// Endpoints that change a collection found under another name.
+const AFFECTED_COLLECTIONS: &[(&str, &str)] = &[
+    // Starting a crawl adds a job.
+    ("crawl", "jobs"),
+];
+
+/// Characters that may follow a resource's path in a cache key: a
+/// sub-path, a query, a header hash, or the auth hash ending the key.
+const KEY_BOUNDARIES: &[char] = &['/', '?', '#', ':'];
+
+/// Cache key prefixes covering the GET responses a change to `url` may
+/// affect. Empty if `url` does not change any resource the cache holds.
+///
+/// Mutating `/api/v1/schemas/abc` covers every cached GET under
+/// `/api/v1/schemas`, including listings, but not `/api/v1/schemasX`.
+pub(crate) fn invalidation_prefixes(base_url: &str, url: &str) -> Vec<String> {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let path = url.strip_prefix(base_url).unwrap_or(url);
+    let Some(rest) = path.strip_prefix("/api/v1/") else {
+        return Vec::new();
+    };
+    let collection = rest.split('/').next().unwrap_or(rest);
+    let collection = AFFECTED_COLLECTIONS
+        .iter()
+        .find(|(endpoint, _)| *endpoint == collection)
+        .map_or(collection, |(_, affected)| affected);
+    if !MUTABLE_COLLECTIONS.contains(&collection) {
+        return Vec::new();
+    }
+    let prefix = generate_cache_key("GET", &format!("{}/api/v1/{}", base_url, collection), None);
+    KEY_BOUNDARIES
+        .iter()
+        .map(|boundary| format!("{}{}", prefix, boundary))
+        .collect()
+}
+
 /// Hash a string using SHA-256 (truncated to 16 chars for cache keys).
 pub fn hash_string(s: &str) -> String {
     let mut hasher = Sha256::new();
@@ -121,11 +271,40 @@ pub fn hash_string(s: &str) -> String {
     hex::encode(&result[..8])
 }
 
+/// Why an entry left the cache before expiring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    /// Removed to make room for a new entry.
+    Capacity,
+    /// Removed by [`Cache::delete`].
+    Deleted,
+    /// Removed by [`MemoryCache::clear`].
+    Cleared,
+}
+
+/// Callbacks for cache activity.
+///
+/// Useful for mirroring the SDK cache into a higher-level cache, or for
+/// broadcasting invalidations to other replicas. Callbacks run after the
+/// cache has released its locks and should return quickly.
+pub trait CacheObserver: Send + Sync {
+    /// An entry was stored.
+    fn on_set(&self, _key: &str, _entry: &CacheEntry) {}
+
+    /// An entry was removed before it expired.
+    fn on_evict(&self, _key: &str, _reason: EvictionReason) {}
+
+    /// An expired entry was removed.
+    fn on_expire(&self, _key: &str) {}
+}
+
 /// In-memory cache implementation with O(1) eviction.
 pub struct MemoryCache {
     store: Arc<RwLock<HashMap<String, CacheEntry>>>,
     order: Arc<RwLock<VecDeque<String>>>,
     max_entries: usize,
+    observer: Option<Arc<dyn CacheObserver>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl MemoryCache {
@@ -135,9 +314,28 @@ impl MemoryCache {
             store: Arc::new(RwLock::new(HashMap::with_capacity(max_entries))),
             order: Arc::new(RwLock::new(VecDeque::with_capacity(max_entries))),
             max_entries,
+            observer: None,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Read the time for expiry checks from a custom clock.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        self.clocked(clock)
+    }
+
+    pub(crate) fn clocked(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Notify an observer of entries being stored and removed.
+    pub fn with_observer(mut self, observer: Arc<dyn CacheObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
     /// Get the current number of entries.
     pub fn size(&self) -> usize {
         self.store.read().unwrap().len()
@@ -145,10 +343,93 @@ impl MemoryCache {
 
     /// Clear all entries.
     pub fn clear(&self) {
-        let mut store = self.store.write().unwrap();
-        let mut order = self.order.write().unwrap();
-        store.clear();
-        order.clear();
+        let removed: Vec<String> = {
+            let mut store = self.store.write().unwrap();
+            let mut order = self.order.write().unwrap();
+            order.clear();
+            store.drain().map(|(key, _)| key).collect()
+        };
+
+        if let Some(observer) = &self.observer {
+            for key in &removed {
+                observer.on_evict(key, EvictionReason::Cleared);
+            }
+        }
+    }
+
+    /// Approximate heap usage of the cache in bytes.
+    ///
+    /// Counts the allocated capacity of the backing collections plus the keys
+    /// and values they hold. Values are estimated from their JSON structure,
+    /// so the figure is a guide rather than an exact measurement.
+    pub fn memory_usage(&self) -> usize {
+        let store = self.store.read().unwrap();
+        let order = self.order.read().unwrap();
+
+        let slots = store.capacity() * (size_of::<String>() + size_of::<CacheEntry>())
+            + order.capacity() * size_of::<String>();
+        let contents: usize = store
+            .iter()
+            .map(|(key, entry)| key.capacity() + value_heap_size(&entry.value))
+            .sum::<usize>()
+            + order.iter().map(String::capacity).sum::<usize>();
+
+        slots + contents
+    }
+
+    /// Drop expired entries and release unused capacity.
+    ///
+    /// Entries still inside their stale-while-revalidate window are kept.
+    /// Long-running hosts can call this on a timer so the cache does not hold
+    /// on to memory from a historical peak. Returns the number of entries
+    /// removed.
+    pub fn maintain(&self) -> usize {
+        let now = self.clock.now_unix();
+
+        let mut expired = Vec::new();
+        {
+            let mut store = self.store.write().unwrap();
+            let mut order = self.order.write().unwrap();
+
+            store.retain(|key, entry| {
+                let live = !is_fully_expired(entry, now);
+                if !live {
+                    expired.push(key.clone());
+                }
+                live
+            });
+            order.retain(|key| store.contains_key(key));
+
+            store.shrink_to_fit();
+            order.shrink_to_fit();
+        }
+
+        if let Some(observer) = &self.observer {
+            for key in &expired {
+                observer.on_expire(key);
+            }
+        }
+        expired.len()
+    }
+}
+
+/// True if an entry can neither be served nor revalidated any more.
+pub(crate) fn is_fully_expired(entry: &CacheEntry, now: u64) -> bool {
+    entry.retain_until() < now
+}
+
+/// Estimate the heap memory owned by a JSON value.
+fn value_heap_size(value: &Value) -> usize {
+    match value {
+        Value::String(s) => s.capacity(),
+        Value::Array(items) => {
+            items.capacity() * size_of::<Value>() + items.iter().map(value_heap_size).sum::<usize>()
+        }
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, v)| key.capacity() + size_of::<Value>() + value_heap_size(v))
+            .sum(),
+        _ => 0,
     }
 }
 
@@ -157,22 +438,14 @@ impl Cache for MemoryCache {
         let store = self.store.read().unwrap();
         let entry = store.get(key)?;
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        // Check if expired
-        if entry.expires_at < now {
-            // Check stale-while-revalidate
-            if let Some(swr) = entry.cache_control.stale_while_revalidate {
-                let stale_deadline = entry.expires_at + swr;
-                if now < stale_deadline {
-                    return Some(entry.clone());
-                }
-            }
+        let now = self.clock.now_unix();
 
-            // Fully expired - caller should call delete
+        // Entries past expiry are still returned while they can be served
+        // stale or revalidated
+        if is_fully_expired(entry, now) {
+            // Fully expired - drop it so observers hear about it once
+            drop(store);
+            self.remove_expired(key, now);
             return None;
         }
 
@@ -184,34 +457,95 @@ impl Cache for MemoryCache {
             return;
         }
 
-        let mut store = self.store.write().unwrap();
-        let mut order = self.order.write().unwrap();
+        let mut evicted = Vec::new();
+        {
+            let mut store = self.store.write().unwrap();
+            let mut order = self.order.write().unwrap();
+
+            // Evict oldest if at capacity (O(1) with VecDeque)
+            while store.len() >= self.max_entries {
+                if let Some(oldest) = order.pop_front() {
+                    store.remove(&oldest);
+                    evicted.push(oldest);
+                } else {
+                    break;
+                }
+            }
 
-        // Evict oldest if at capacity (O(1) with VecDeque)
-        while store.len() >= self.max_entries {
-            if let Some(oldest) = order.pop_front() {
-                store.remove(&oldest);
-            } else {
-                break;
+            // Check if key exists - if so, it's already in order
+            if !store.contains_key(key) {
+                order.push_back(key.to_string());
             }
-        }
 
-        // Check if key exists - if so, it's already in order
-        if !store.contains_key(key) {
-            order.push_back(key.to_string());
+            store.insert(key.to_string(), entry.clone());
         }
 
-        store.insert(key.to_string(), entry);
+        if let Some(observer) = &self.observer {
+            for oldest in &evicted {
+                observer.on_evict(oldest, EvictionReason::Capacity);
+            }
+            observer.on_set(key, &entry);
+        }
     }
 
     fn delete(&self, key: &str) {
-        let mut store = self.store.write().unwrap();
-        let mut order = self.order.write().unwrap();
+        let removed = {
+            let mut store = self.store.write().unwrap();
+            let mut order = self.order.write().unwrap();
+
+            let removed = store.remove(key).is_some();
+            // Note: This is still O(n), but delete is infrequent
+            // For true O(1) delete, we'd need a linked hash map
+            order.retain(|k| k != key);
+            removed
+        };
+
+        if let (true, Some(observer)) = (removed, &self.observer) {
+            observer.on_evict(key, EvictionReason::Deleted);
+        }
+    }
+
+    fn delete_prefix(&self, prefix: &str) {
+        let mut removed = Vec::new();
+        {
+            let mut store = self.store.write().unwrap();
+            let mut order = self.order.write().unwrap();
+
+            store.retain(|key, _| {
+                let matches = key.starts_with(prefix);
+                if matches {
+                    removed.push(key.clone());
+                }
+                !matches
+            });
+            if !removed.is_empty() {
+                order.retain(|key| store.contains_key(key));
+            }
+        }
 
-        store.remove(key);
-        // Note: This is still O(n), but delete is infrequent
-        // For true O(1) delete, we'd need a linked hash map
-        order.retain(|k| k != key);
+        if let Some(observer) = &self.observer {
+            for key in &removed {
+                observer.on_evict(key, EvictionReason::Deleted);
+            }
+        }
+    }
+}
+
+impl MemoryCache {
+    /// Remove an entry found fully expired, unless it was replaced meanwhile.
+    fn remove_expired(&self, key: &str, now: u64) {
+        {
+            let mut store = self.store.write().unwrap();
+            if !store.get(key).is_some_and(|e| is_fully_expired(e, now)) {
+                return;
+            }
+            store.remove(key);
+            self.order.write().unwrap().retain(|k| k != key);
+        }
+
+        if let Some(observer) = &self.observer {
+            observer.on_expire(key);
+        }
     }
 }
 
@@ -255,6 +589,26 @@ mod tests {
         assert_eq!(entry.value, json!({"test": true}));
     }
 
+    #[test]
+    fn test_entries_with_validators() {
+        let validators = || Validators {
+            etag: Some("\"v1\"".into()),
+            last_modified: None,
+        };
+        let entry = create_cache_entry_at(json!(1), Some("no-cache"), validators(), 1_000).unwrap();
+        assert!(!entry.is_servable(1_000));
+        assert_eq!(entry.retain_until(), 1_000 + REVALIDATION_WINDOW);
+        assert!(
+            create_cache_entry_at(json!(1), Some("no-cache"), Validators::default(), 1_000)
+                .is_none()
+        );
+
+        let refreshed = revalidated_entry(entry, Some("max-age=60"), Validators::default(), 2_000);
+        assert!(refreshed.is_servable(2_060));
+        assert!(!refreshed.is_servable(2_061));
+        assert_eq!(refreshed.etag.as_deref(), Some("\"v1\""));
+    }
+
     #[test]
     fn test_memory_cache() {
         let cache = MemoryCache::new(2);
@@ -269,6 +623,156 @@ mod tests {
         assert!(cache.get("k1").is_none());
     }
 
+    #[test]
+    fn test_memory_cache_maintain() {
+        let cache = MemoryCache::new(100);
+        let empty_usage = cache.memory_usage();
+
+        for i in 0..50 {
+            let entry = create_cache_entry(json!({"i": i}), Some("max-age=3600")).unwrap();
+            cache.set(&format!("k{}", i), entry);
+        }
+        let mut expired = create_cache_entry(json!("old"), Some("max-age=1")).unwrap();
+        expired.expires_at = 0;
+        cache.set("expired", expired);
+        assert!(cache.memory_usage() > empty_usage);
+
+        assert_eq!(cache.maintain(), 1);
+        assert_eq!(cache.size(), 50);
+        assert!(cache.get("k0").is_some());
+
+        cache.clear();
+        cache.maintain();
+        assert!(cache.memory_usage() < empty_usage);
+    }
+
+    #[test]
+    fn test_memory_cache_observer() {
+        #[derive(Default)]
+        struct Recorder(std::sync::Mutex<Vec<String>>);
+        impl CacheObserver for Recorder {
+            fn on_set(&self, key: &str, _entry: &CacheEntry) {
+                self.0.lock().unwrap().push(format!("set {}", key));
+            }
+            fn on_evict(&self, key: &str, reason: EvictionReason) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("evict {} {:?}", key, reason));
+            }
+            fn on_expire(&self, key: &str) {
+                self.0.lock().unwrap().push(format!("expire {}", key));
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let cache = MemoryCache::new(2).with_observer(recorder.clone());
+        let entry = || create_cache_entry(json!("v"), Some("max-age=3600")).unwrap();
+
+        cache.set("k1", entry());
+        cache.set("k2", entry());
+        cache.set("k3", entry());
+        cache.delete("k2");
+        cache.delete("missing");
+
+        let mut expired = entry();
+        expired.expires_at = 0;
+        cache.set("old", expired);
+        assert!(cache.get("old").is_none());
+        assert!(cache.get("old").is_none());
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                "set k1",
+                "set k2",
+                "evict k1 Capacity",
+                "set k3",
+                "evict k2 Deleted",
+                "set old",
+                "expire old",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_memory_cache_expiry_with_mock_clock() {
+        use crate::clock::MockClock;
+        use std::time::Duration;
+
+        let clock = Arc::new(MockClock::new(1_000));
+        let cache = MemoryCache::new(10).with_clock(clock.clone());
+        let header = Some("max-age=60, stale-while-revalidate=30");
+        cache.set(
+            "k",
+            create_cache_entry_at(json!("v"), header, Validators::default(), clock.now_unix())
+                .unwrap(),
+        );
+
+        clock.advance(Duration::from_secs(60));
+        assert!(cache.get("k").is_some());
+        clock.advance(Duration::from_secs(29));
+        assert!(cache.get("k").is_some(), "inside stale-while-revalidate");
+        clock.advance(Duration::from_secs(2));
+        assert!(cache.get("k").is_none());
+        assert_eq!(cache.size(), 0);
+    }
+
+    #[test]
+    fn test_invalidation_prefixes() {
+        let base = "https://api.refyne.uk";
+        let prefixes = |collection: &str| -> Vec<String> {
+            ['/', '?', '#', ':']
+                .iter()
+                .map(|c| format!("GET:https://api.refyne.uk/api/v1/{}{}", collection, c))
+                .collect()
+        };
+        assert_eq!(
+            invalidation_prefixes(base, "https://api.refyne.uk/api/v1/schemas/abc?x=1"),
+            prefixes("schemas")
+        );
+        assert_eq!(
+            invalidation_prefixes(base, "https://api.refyne.uk/api/v1/jobs/j1/cancel"),
+            prefixes("jobs")
+        );
+        assert_eq!(
+            invalidation_prefixes(base, "https://api.refyne.uk/api/v1/crawl"),
+            prefixes("jobs")
+        );
+        for url in [
+            "https://api.refyne.uk/api/v1/extract",
+            "https://api.refyne.uk/api/v1/analyze",
+            "https://api.refyne.uk/health",
+        ] {
+            assert!(invalidation_prefixes(base, url).is_empty(), "{}", url);
+        }
+
+        let key = |path: &str| format!("GET:https://api.refyne.uk{}:h", path);
+        let covered = |path: &str| {
+            prefixes("schemas")
+                .iter()
+                .any(|prefix| key(path).starts_with(prefix.as_str()))
+        };
+        assert!(covered("/api/v1/schemas"));
+        assert!(covered("/api/v1/schemas/abc"));
+        assert!(covered("/api/v1/schemas?limit=5"));
+        assert!(!covered("/api/v1/schemasX"));
+    }
+
+    #[test]
+    fn test_memory_cache_delete_prefix() {
+        let cache = MemoryCache::new(10);
+        for key in ["GET:/schemas:h", "GET:/schemas/a:h", "GET:/sites:h"] {
+            cache.set(
+                key,
+                create_cache_entry(json!(key), Some("max-age=60")).unwrap(),
+            );
+        }
+        cache.delete_prefix("GET:/schemas");
+        assert_eq!(cache.size(), 1);
+        assert!(cache.get("GET:/sites:h").is_some());
+    }
+
     #[test]
     fn test_hash_string() {
         let h1 = hash_string("test");
@@ -0,0 +1,69 @@
+//! Server-side abort of cancelled extractions.
+//!
+//! With [`ClientBuilder::abort_on_cancel`](crate::ClientBuilder::abort_on_cancel),
+//! each extraction carries an operation ID. If the `extract` future is dropped
+//! before the response arrives, for example by a timeout or `select!`, the
+//! client asks the API to abort the operation so the LLM work stops and is not
+//! billed.
+
+use crate::client::Client;
+use crate::runtime;
+use rand::Rng;
+use tracing::debug;
+
+/// Header carrying the operation ID of an extraction.
+pub(crate) const OPERATION_ID_HEADER: &str = "X-Operation-ID";
+
+/// Generate a random operation ID.
+pub(crate) fn new_operation_id() -> String {
+    hex::encode(rand::rng().random::<[u8; 16]>())
+}
+
+/// Aborts an operation on the server if dropped while still armed.
+pub(crate) struct AbortOnDrop {
+    client: Client,
+    operation_id: String,
+    armed: bool,
+}
+
+impl AbortOnDrop {
+    pub(crate) fn new(client: Client, operation_id: String) -> Self {
+        Self {
+            client,
+            operation_id,
+            armed: true,
+        }
+    }
+
+    /// The operation finished; nothing to abort.
+    pub(crate) fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let client = self.client.clone();
+        let operation_id = std::mem::take(&mut self.operation_id);
+        runtime::spawn(async move {
+            if let Err(e) = client.abort_operation(&operation_id).await {
+                debug!(operation_id = %operation_id, error = %e, "Failed to abort operation");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_ids_are_unique() {
+        let a = new_operation_id();
+        assert_eq!(a.len(), 32);
+        assert_ne!(a, new_operation_id());
+    }
+}
@@ -0,0 +1,87 @@
+//! Description of what this build of the SDK supports.
+//!
+//! Platform tooling can serialize [`Capabilities`] to check that a deployed
+//! service was compiled with the features it needs.
+
+use crate::version::{MAX_KNOWN_API_VERSION, MIN_API_VERSION, SDK_VERSION};
+use serde::Serialize;
+
+/// Features, transports, and API versions supported by this build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    /// SDK version.
+    pub sdk_version: &'static str,
+    /// Oldest API version this SDK accepts.
+    pub min_api_version: &'static str,
+    /// Newest API version this SDK was built against.
+    pub max_known_api_version: &'static str,
+    /// Enabled cargo features.
+    pub features: Vec<&'static str>,
+    /// Response formats the client can decode.
+    pub response_formats: Vec<&'static str>,
+    /// Job progress transports.
+    pub progress_sources: Vec<&'static str>,
+    /// Cache backends. `custom` means any [`Cache`](crate::Cache)
+    /// implementation can be plugged in.
+    pub cache_backends: Vec<&'static str>,
+    /// Sub-clients available on [`Client`](crate::Client).
+    pub sub_clients: Vec<&'static str>,
+}
+
+impl Capabilities {
+    /// Capabilities of the running build.
+    pub fn current() -> Self {
+        let features = [
+            ("compression", cfg!(feature = "compression")),
+            ("ffi", cfg!(feature = "ffi")),
+            ("msgpack", cfg!(feature = "msgpack")),
+            ("profiles", cfg!(feature = "profiles")),
+            ("recording", cfg!(feature = "recording")),
+            ("snapshot", cfg!(feature = "snapshot")),
+            ("test-util", cfg!(feature = "test-util")),
+        ];
+        let mut response_formats = vec!["json"];
+        if cfg!(feature = "msgpack") {
+            response_formats.push("msgpack");
+        }
+
+        Self {
+            sdk_version: SDK_VERSION,
+            min_api_version: MIN_API_VERSION,
+            max_known_api_version: MAX_KNOWN_API_VERSION,
+            features: features
+                .into_iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| name)
+                .collect(),
+            response_formats,
+            progress_sources: vec!["polling", "sse", "webhook"],
+            cache_backends: vec!["memory", "custom"],
+            sub_clients: vec![
+                "jobs", "schemas", "sites", "keys", "llm", "usage", "org", "webhooks",
+            ],
+        }
+    }
+
+    /// True if the named cargo feature is enabled.
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.contains(&name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current() {
+        let caps = Capabilities::current();
+        assert_eq!(caps.sdk_version, SDK_VERSION);
+        assert_eq!(caps.has_feature("msgpack"), cfg!(feature = "msgpack"));
+        assert!(!caps.has_feature("redis"));
+        assert!(caps.response_formats.contains(&"json"));
+
+        let json = serde_json::to_value(&caps).unwrap();
+        assert_eq!(json["sub_clients"][0], "jobs");
+    }
+}
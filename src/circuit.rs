@@ -0,0 +1,161 @@
+//! Circuit breaker for a persistently failing API.
+//!
+//! With [`ClientBuilder::circuit_breaker`](crate::ClientBuilder::circuit_breaker),
+//! consecutive network failures and server errors open the circuit. While
+//! open, requests fail immediately with [`Error::CircuitOpen`] instead of
+//! going through the retry loop. After the cool-down one trial request is let
+//! through: if it succeeds the circuit closes, otherwise it opens again.
+
+use crate::error::Error;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Thresholds for the circuit breaker.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed attempts, including retries, that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial request is let through.
+    pub cool_down: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cool_down: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: u64,
+    },
+    /// A trial request was let through at `since`.
+    HalfOpen {
+        since: u64,
+    },
+}
+
+/// Tracks failures and decides whether requests may be sent.
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    fn cool_down_secs(&self) -> u64 {
+        self.config.cool_down.as_secs_f64().ceil() as u64
+    }
+
+    /// Check whether a request may be sent at `now` (Unix seconds).
+    pub(crate) fn check(&self, now: u64) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap();
+        let reopens_at = match *state {
+            State::Closed { .. } => return Ok(()),
+            State::Open { until } => until,
+            // A trial that never reports back, e.g. because its future was
+            // dropped, blocks the circuit for at most another cool-down.
+            State::HalfOpen { since } => since + self.cool_down_secs(),
+        };
+        if now < reopens_at {
+            return Err(Error::CircuitOpen {
+                retry_after: reopens_at - now,
+            });
+        }
+        *state = State::HalfOpen { since: now };
+        Ok(())
+    }
+
+    /// The API responded without a server error.
+    pub(crate) fn record_success(&self) {
+        *self.state.lock().unwrap() = State::Closed { failures: 0 };
+    }
+
+    /// A request failed with a network or server error at `now`.
+    pub(crate) fn record_failure(&self, now: u64) {
+        let mut state = self.state.lock().unwrap();
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            State::Open { .. } | State::HalfOpen { .. } => self.config.failure_threshold,
+        };
+        *state = if failures >= self.config.failure_threshold {
+            State::Open {
+                until: now + self.cool_down_secs(),
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cool_down: Duration::from_secs(10),
+        })
+    }
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = breaker();
+        breaker.record_failure(100);
+        breaker.record_failure(100);
+        assert!(breaker.check(100).is_ok());
+        breaker.record_success();
+        breaker.record_failure(100);
+        breaker.record_failure(100);
+        assert!(breaker.check(100).is_ok());
+        breaker.record_failure(100);
+        assert!(matches!(
+            breaker.check(104),
+            Err(Error::CircuitOpen { retry_after: 6 })
+        ));
+    }
+
+    #[test]
+    fn test_half_open_trial() {
+        let breaker = breaker();
+        for _ in 0..3 {
+            breaker.record_failure(100);
+        }
+
+        // One trial after the cool-down; others wait for its outcome.
+        assert!(breaker.check(110).is_ok());
+        assert!(breaker.check(111).is_err());
+        breaker.record_failure(111);
+        assert!(breaker.check(120).is_err());
+
+        assert!(breaker.check(121).is_ok());
+        breaker.record_success();
+        assert!(breaker.check(121).is_ok());
+    }
+
+    #[test]
+    fn test_abandoned_trial_expires() {
+        let breaker = breaker();
+        for _ in 0..3 {
+            breaker.record_failure(100);
+        }
+        assert!(breaker.check(110).is_ok());
+        assert!(breaker.check(119).is_err());
+        assert!(breaker.check(120).is_ok());
+    }
+}
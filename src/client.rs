@@ -1,16 +1,88 @@
 //! Main Refyne client implementation.
 
-use crate::cache::{create_cache_entry, generate_cache_key, hash_string, Cache, MemoryCache};
-use crate::error::{Error, Result};
+use crate::batch::{
+    back_off, BatchResult, ExtractMany, Failure, FailureKind, FailureQueue, RetryPolicy,
+};
+use crate::bootstrap::{schema_request, site_request, BootstrappedSite};
+use crate::budget::{Budget, BudgetInput};
+use crate::cache::{
+    create_cache_entry_at, generate_cache_key, hash_string, invalidation_prefixes,
+    revalidated_entry, Cache, CacheObserver, MemoryCache, Validators,
+};
+use crate::cancel::{new_operation_id, AbortOnDrop, OPERATION_ID_HEADER};
+use crate::capabilities::Capabilities;
+use crate::circuit::{CircuitBreaker, CircuitBreakerConfig};
+use crate::clock::{Clock, SystemClock};
+use crate::credentials::{ApiKey, ApiKeyProvider};
+use crate::dedup::{
+    crawl_fingerprint, existing_job_response, is_active_status, DuplicateCrawlPolicy, JobRegistry,
+};
+use crate::document::ExtractDocumentRequest;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::download::{
+    content_range_total, part_path, range_header, range_validator, validator_path,
+    DOWNLOAD_PART_SIZE,
+};
+use crate::error::{Error, ErrorCode, ErrorMessages, Result};
+use crate::estimate::{Estimate, EstimateRequest};
+use crate::fairness::{FairPermit, FairScheduler, FairnessConfig, DEFAULT_TENANT};
+use crate::health::{
+    endpoint_key, DegradedCallback, Event, HealthConfig, HealthSnapshot, HealthTracker,
+};
+use crate::job_errors::JobErrors;
+use crate::job_filter::{encode, JobFilter};
+use crate::job_progress::JobProgress;
+use crate::llm::{check_chain, ChainProblem, LlmKeyTest};
+use crate::meta::{self, ResponseMeta};
+use crate::middleware::{Middleware, MiddlewareFuture, Next};
+use crate::observer::{Observer, RequestOutcome};
+use crate::options::RequestOptions;
+use crate::org::{InviteInput, OrgInvitation, OrgMember, OrgMemberList, OrgRole, Organization};
+use crate::pagination::Paginator;
+use crate::prepared::{ApiRequest, PreparedRequest};
+use crate::progress::{ProgressSource, WaitOptions};
+use crate::quota::{QuotaLimits, QuotaSnapshot, RateLimitTracker};
+use crate::request::RequestBuilder;
+use crate::rerun::SubmittedCrawls;
+use crate::results::{JobResultsExt, ResultItemError, ResultStream};
+use crate::runtime;
+use crate::secret::REDACTED;
+use crate::sitemap::SitemapUrls;
+use crate::sites::{check_cron, RunOptions, Schedule, ScheduleList};
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{
+    SnapshotHeader, SnapshotSection, SnapshotSummary, SnapshotWriter, SNAPSHOT_FORMAT_VERSION,
+};
+use crate::stale::{last_good_entry, last_good_key, Extraction};
+use crate::stats::{JobStats, StatsRange};
+use crate::transforms::{Transform, TransformPipeline};
 use crate::types::*;
-use crate::version::{build_user_agent, check_api_version_compatibility};
+use crate::usage::{UsageBreakdown, UsageQuery};
+use crate::version::{
+    build_user_agent, check_compatibility, ApiFeatures, ApiVersionStatus, Feature,
+    VersionCheckMode, API_FEATURES_HEADER, MIN_API_VERSION,
+};
+use bytes::Bytes;
 use rand::Rng;
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, ETAG,
+    IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, USER_AGENT,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::redirect::Policy;
+use reqwest::StatusCode;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::{Certificate, Identity};
+use std::fmt;
+use std::future::Future;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use std::sync::atomic::{AtomicU16, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
-use tokio::time::sleep;
-use tracing::warn;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, field, info_span, warn, Instrument, Span};
 
 /// Calculate exponential backoff with jitter.
 fn calculate_backoff(attempt: u32) -> Duration {
@@ -21,19 +93,104 @@ fn calculate_backoff(attempt: u32) -> Duration {
     Duration::from_millis(base_secs * 1000 + jitter_ms)
 }
 
+/// Whether repeating a request cannot apply it twice. The API may already be
+/// processing a POST or PATCH that timed out.
+fn is_idempotent(method: &str) -> bool {
+    !matches!(method, "POST" | "PATCH")
+}
+
+/// Whether a request failed before a connection was made.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect_error(e: &reqwest::Error) -> bool {
+    e.is_connect()
+}
+
+/// The fetch API does not report connection failures separately.
+#[cfg(target_arch = "wasm32")]
+fn is_connect_error(_: &reqwest::Error) -> bool {
+    false
+}
+
+/// Whether `url` is on the API at `base_url`: same scheme, host, and port,
+/// and a path under the base URL's path.
+fn is_api_url(base_url: &str, url: &str) -> bool {
+    let (Ok(base), Ok(url)) = (reqwest::Url::parse(base_url), reqwest::Url::parse(url)) else {
+        return false;
+    };
+    let prefix = base.path().trim_end_matches('/');
+    let path = url.path();
+    base.scheme() == url.scheme()
+        && base.host_str() == url.host_str()
+        && base.port_or_known_default() == url.port_or_known_default()
+        && url.username().is_empty()
+        && url.password().is_none()
+        && path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
 const DEFAULT_BASE_URL: &str = "https://api.refyne.uk";
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 const DEFAULT_MAX_RETRIES: u32 = 3;
 
+/// Environment variable that turns on [`ClientBuilder::require_https`].
+const REQUIRE_HTTPS_ENV: &str = "REFYNE_REQUIRE_HTTPS";
+/// Environment variables read by [`ClientBuilder::from_env`].
+const API_KEY_ENV: &str = "REFYNE_API_KEY";
+const BASE_URL_ENV: &str = "REFYNE_BASE_URL";
+const TIMEOUT_ENV: &str = "REFYNE_TIMEOUT";
+const MAX_RETRIES_ENV: &str = "REFYNE_MAX_RETRIES";
+
+/// Whether an environment flag value means "on".
+fn env_flag(value: Option<&str>) -> bool {
+    value.is_some_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
 /// Builder for constructing a [`Client`].
 pub struct ClientBuilder {
     api_key: String,
+    api_key_provider: Option<(String, Arc<dyn ApiKeyProvider>)>,
     base_url: String,
+    require_https: bool,
     timeout: Duration,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    retry_post_read_timeouts: bool,
     max_retries: u32,
+    max_retry_duration: Option<Duration>,
+    http_client: Option<reqwest::Client>,
     cache: Option<Arc<dyn Cache>>,
+    cache_observer: Option<Arc<dyn CacheObserver>>,
     cache_enabled: bool,
     user_agent_suffix: Option<String>,
+    duplicate_crawl_policy: DuplicateCrawlPolicy,
+    version_check: VersionCheckMode,
+    health_config: HealthConfig,
+    on_degraded: Option<DegradedCallback>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    error_messages: Option<ErrorMessages>,
+    fairness: Option<FairnessConfig>,
+    default_llm_config: Option<LLMConfigInput>,
+    #[cfg(not(target_arch = "wasm32"))]
+    redirect: Option<Policy>,
+    #[cfg(not(target_arch = "wasm32"))]
+    root_certificates: Vec<Certificate>,
+    #[cfg(not(target_arch = "wasm32"))]
+    identity: Option<Identity>,
+    #[cfg(feature = "danger-insecure-tls")]
+    accept_invalid_certs: bool,
+    quota_limits: QuotaLimits,
+    clock: Arc<dyn Clock>,
+    transforms: TransformPipeline,
+    middleware: Vec<Arc<dyn Middleware>>,
+    abort_on_cancel: bool,
+    strict_schema: bool,
+    tracing_spans: bool,
+    observer: Option<Arc<dyn Observer>>,
+    #[cfg(feature = "msgpack")]
+    prefer_msgpack: bool,
+    #[cfg(feature = "compression")]
+    compression: bool,
 }
 
 impl ClientBuilder {
@@ -41,13 +198,146 @@ impl ClientBuilder {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
+            api_key_provider: None,
             base_url: DEFAULT_BASE_URL.to_string(),
+            require_https: env_flag(std::env::var(REQUIRE_HTTPS_ENV).ok().as_deref()),
             timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            connect_timeout: None,
+            read_timeout: None,
+            retry_post_read_timeouts: false,
             max_retries: DEFAULT_MAX_RETRIES,
+            max_retry_duration: None,
+            http_client: None,
             cache: None,
+            cache_observer: None,
             cache_enabled: true,
             user_agent_suffix: None,
+            duplicate_crawl_policy: DuplicateCrawlPolicy::default(),
+            version_check: VersionCheckMode::default(),
+            health_config: HealthConfig::default(),
+            on_degraded: None,
+            circuit_breaker: None,
+            error_messages: None,
+            fairness: None,
+            default_llm_config: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            redirect: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            root_certificates: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            identity: None,
+            #[cfg(feature = "danger-insecure-tls")]
+            accept_invalid_certs: false,
+            quota_limits: QuotaLimits::default(),
+            clock: Arc::new(SystemClock),
+            transforms: TransformPipeline::default(),
+            middleware: Vec::new(),
+            abort_on_cancel: false,
+            strict_schema: false,
+            tracing_spans: true,
+            observer: None,
+            #[cfg(feature = "msgpack")]
+            prefer_msgpack: false,
+            #[cfg(feature = "compression")]
+            compression: true,
+        }
+    }
+
+    /// Create a builder configured from environment variables.
+    ///
+    /// Reads the API key from `REFYNE_API_KEY`, and optionally the base URL
+    /// from `REFYNE_BASE_URL`, the request timeout in seconds from
+    /// `REFYNE_TIMEOUT`, and the retry count from `REFYNE_MAX_RETRIES`. Fails
+    /// with [`Error::Config`] if the API key is missing or a value cannot be
+    /// parsed.
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Configure a builder from variables returned by `lookup`, treating
+    /// empty values as unset.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| lookup(name).filter(|v| !v.trim().is_empty());
+
+        let api_key =
+            var(API_KEY_ENV).ok_or_else(|| Error::Config(format!("{} is not set", API_KEY_ENV)))?;
+        let mut builder = Self::new(api_key.trim());
+        if let Some(url) = var(BASE_URL_ENV) {
+            builder = builder.base_url(url.trim());
         }
+        if let Some(value) = var(TIMEOUT_ENV) {
+            let timeout = value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .filter(|timeout| !timeout.is_zero())
+                .ok_or_else(|| {
+                    Error::Config(format!(
+                        "{} must be a positive number of seconds, got {:?}",
+                        TIMEOUT_ENV, value
+                    ))
+                })?;
+            builder = builder.timeout(timeout);
+        }
+        if let Some(value) = var(MAX_RETRIES_ENV) {
+            let retries = value.trim().parse().map_err(|_| {
+                Error::Config(format!(
+                    "{} must be a non-negative integer, got {:?}",
+                    MAX_RETRIES_ENV, value
+                ))
+            })?;
+            builder = builder.max_retries(retries);
+        }
+        Ok(builder)
+    }
+
+    /// Create a builder from a named profile in the user's config file.
+    ///
+    /// The file is `$XDG_CONFIG_HOME/refyne/config.toml`, falling back to
+    /// `~/.config/refyne/config.toml`, unless `REFYNE_CONFIG_FILE` names
+    /// another. Each table is a profile; only `api_key` is required:
+    ///
+    /// ```toml
+    /// [staging]
+    /// api_key = "rf_test_..."
+    /// base_url = "https://staging.api.refyne.uk"
+    /// timeout = 60          # seconds
+    /// max_retries = 5
+    ///
+    /// [staging.llm_config]  # becomes the default LLM config
+    /// provider = "openrouter"
+    /// model = "anthropic/claude-sonnet-4"
+    /// ```
+    ///
+    /// Fails with [`Error::Config`] if the file or profile cannot be read.
+    #[cfg(feature = "profiles")]
+    pub fn from_profile(name: &str) -> Result<Self> {
+        let path = crate::profile::default_config_path().ok_or_else(|| {
+            Error::Config("cannot locate the config file: HOME is not set".to_string())
+        })?;
+        crate::profile::load(&path, name)
+    }
+
+    /// Create a builder from a named profile in the config file at `path`.
+    #[cfg(feature = "profiles")]
+    pub fn from_profile_file(path: impl AsRef<std::path::Path>, name: &str) -> Result<Self> {
+        crate::profile::load(path.as_ref(), name)
+    }
+
+    /// Fetch the API key from `provider` before each request.
+    ///
+    /// `account` names the account whose keys the provider returns, and
+    /// must not be empty. It replaces the key given to [`new`](Self::new),
+    /// which may then be empty, in partitioning shared caches, duplicate
+    /// crawl detection, and last-good results between accounts.
+    pub fn api_key_provider(
+        mut self,
+        account: impl Into<String>,
+        provider: impl ApiKeyProvider + 'static,
+    ) -> Self {
+        self.api_key_provider = Some((account.into(), Arc::new(provider)));
+        self
     }
 
     /// Set the API base URL.
@@ -56,24 +346,105 @@ impl ClientBuilder {
         self
     }
 
+    /// Reject base URLs that are not HTTPS.
+    ///
+    /// By default a non-HTTPS base URL only logs a warning, which suits local
+    /// development. When enabled, [`build`](Self::build) fails with
+    /// [`Error::Config`] instead. Defaults to on when the
+    /// `REFYNE_REQUIRE_HTTPS` environment variable is `1`, `true`, or `yes`.
+    pub fn require_https(mut self, enabled: bool) -> Self {
+        self.require_https = enabled;
+        self
+    }
+
+    /// Set the LLM configuration for extractions and crawls that do not set
+    /// their own `llm_config`.
+    pub fn default_llm_config(mut self, config: LLMConfigInput) -> Self {
+        self.default_llm_config = Some(config);
+        self
+    }
+
     /// Set the request timeout.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
         self
     }
 
+    /// Set the timeout for establishing a connection.
+    ///
+    /// Connect timeouts fail with [`Error::ConnectTimeout`] and are always
+    /// retried, since the request never reached the API. Ignored on `wasm32`,
+    /// where only the overall [`timeout`](Self::timeout) applies.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for each read of the response.
+    ///
+    /// Timeouts after connecting fail with [`Error::ReadTimeout`]. They are
+    /// retried for idempotent requests only, because a timed-out POST may
+    /// still have been processed; see
+    /// [`retry_post_read_timeouts`](Self::retry_post_read_timeouts).
+    /// Ignored on `wasm32`, where only the overall [`timeout`](Self::timeout)
+    /// applies.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Also retry POST requests that hit a read timeout.
+    ///
+    /// Off by default: the API may have started the extraction or crawl
+    /// before the response timed out, so a retry can run and bill it twice.
+    pub fn retry_post_read_timeouts(mut self, enabled: bool) -> Self {
+        self.retry_post_read_timeouts = enabled;
+        self
+    }
+
     /// Set the maximum retry attempts.
     pub fn max_retries(mut self, retries: u32) -> Self {
         self.max_retries = retries;
         self
     }
 
+    /// Cap the total time spent waiting between retries of one request.
+    ///
+    /// A retry whose delay would take the total past `duration` is not made.
+    /// When that happens on a 429, the request fails with
+    /// [`Error::RateLimit`] reporting the retries made and the time waited,
+    /// so callers can schedule the work themselves. Unlimited by default.
+    pub fn max_retry_duration(mut self, duration: Duration) -> Self {
+        self.max_retry_duration = Some(duration);
+        self
+    }
+
+    /// Send requests with an existing `reqwest` client.
+    ///
+    /// Use this to share connection pools, proxies, and TLS settings with the
+    /// rest of an application. The client's own configuration applies, so
+    /// [`timeout`](Self::timeout), [`connect_timeout`](Self::connect_timeout),
+    /// [`read_timeout`](Self::read_timeout), `redirect`, and the TLS settings
+    /// have no effect.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
     /// Set a custom cache implementation.
     pub fn cache(mut self, cache: Arc<dyn Cache>) -> Self {
         self.cache = Some(cache);
         self
     }
 
+    /// Observe activity in the default in-memory cache.
+    ///
+    /// Has no effect when a custom cache is set with [`cache`](Self::cache).
+    pub fn cache_observer(mut self, observer: Arc<dyn CacheObserver>) -> Self {
+        self.cache_observer = Some(observer);
+        self
+    }
+
     /// Enable or disable caching.
     pub fn cache_enabled(mut self, enabled: bool) -> Self {
         self.cache_enabled = enabled;
@@ -86,42 +457,361 @@ impl ClientBuilder {
         self
     }
 
+    /// Guard against submitting a crawl identical to one still in progress.
+    ///
+    /// Crawls are considered identical when they share the seed URL and schema.
+    /// Only crawls started through this client are tracked.
+    pub fn duplicate_crawl_policy(mut self, policy: DuplicateCrawlPolicy) -> Self {
+        self.duplicate_crawl_policy = policy;
+        self
+    }
+
+    /// Set how the client reacts to an API version it does not support.
+    ///
+    /// The version is read from the first response. With
+    /// [`VersionCheckMode::Strict`], [`connect`](Self::connect) checks it up
+    /// front so an incompatible API fails before any real work is sent.
+    pub fn version_check(mut self, mode: VersionCheckMode) -> Self {
+        self.version_check = mode;
+        self
+    }
+
+    /// Set the thresholds used for retry storm detection.
+    pub fn health_config(mut self, config: HealthConfig) -> Self {
+        self.health_config = config;
+        self
+    }
+
+    /// Stop sending requests while the API is persistently failing.
+    ///
+    /// After `failure_threshold` consecutive network or server errors,
+    /// counting retries, requests fail with [`Error::CircuitOpen`] until the
+    /// cool-down has passed. A single trial request then decides whether the
+    /// circuit closes again. Disabled by default.
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Set a callback invoked when an endpoint becomes retry-heavy.
+    ///
+    /// The callback fires once each time an endpoint crosses the thresholds
+    /// configured with [`health_config`](Self::health_config).
+    pub fn on_degraded<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&crate::health::EndpointHealth) + Send + Sync + 'static,
+    {
+        self.on_degraded = Some(Arc::new(callback));
+        self
+    }
+
+    /// Map error codes to user-facing messages.
+    ///
+    /// Used by [`Client::user_message`] so products can show localized text
+    /// instead of raw API messages. Return `None` to fall back to the
+    /// original message. Errors themselves are unchanged, so `Display` and
+    /// `Debug` still show what the API returned.
+    pub fn error_messages<F>(mut self, messages: F) -> Self
+    where
+        F: Fn(ErrorCode) -> Option<String> + Send + Sync + 'static,
+    {
+        self.error_messages = Some(Arc::new(messages));
+        self
+    }
+
+    /// Share request slots fairly between tenants.
+    ///
+    /// At most `max_concurrent` requests are in flight at once; when all slots
+    /// are busy, queued requests are admitted by weighted fair queuing across
+    /// the tenants set with [`Client::tenant`].
+    pub fn fair_scheduling(mut self, config: FairnessConfig) -> Self {
+        self.fairness = Some(config);
+        self
+    }
+
+    /// Set how the HTTP client follows redirects from the API.
+    ///
+    /// Defaults to following up to 10 redirects. Redirects of extracted pages
+    /// are followed by the API and reported in the extraction metadata.
+    /// On `wasm32` the browser or runtime follows redirects itself.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn redirect(mut self, policy: Policy) -> Self {
+        self.redirect = Some(policy);
+        self
+    }
+
+    /// Trust an additional root certificate, such as an internal CA in
+    /// front of a self-hosted deployment.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Present a client certificate for mutual TLS.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Skip verification of the server's TLS certificate.
+    ///
+    /// Any certificate is accepted, including expired and self-signed ones,
+    /// so traffic can be intercepted. Only for local development; requires
+    /// the `danger-insecure-tls` feature.
+    #[cfg(feature = "danger-insecure-tls")]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Set the account's billing-period limits for [`Client::remaining_quota`].
+    pub fn quota_limits(mut self, limits: QuotaLimits) -> Self {
+        self.quota_limits = limits;
+        self
+    }
+
+    /// Use a custom clock for cache expiry and retry backoff.
+    ///
+    /// Applies to the default in-memory cache; a custom cache set with
+    /// [`cache`](Self::cache) keeps its own time source.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Add a transform applied to extracted data.
+    ///
+    /// Transforms run in the order they were added, on the `data` of every
+    /// extraction and on each item of job results.
+    pub fn transform(mut self, transform: impl Transform + 'static) -> Self {
+        self.transforms.push(Arc::new(transform));
+        self
+    }
+
+    /// Add middleware around every HTTP request the client sends.
+    ///
+    /// Middleware runs in the order it is added and sees each retry as a
+    /// separate request. See [`Middleware`].
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Wrap each API call in a `refyne.request` tracing span.
+    ///
+    /// Spans are on by default at `INFO` level. They carry `method` and
+    /// `path`, and record `status`, the retry `attempt`, `duration_ms`,
+    /// `cache` (`hit`, `miss`, or `revalidated` for cacheable GETs), and
+    /// `error` as the call progresses.
+    pub fn tracing_spans(mut self, enabled: bool) -> Self {
+        self.tracing_spans = enabled;
+        self
+    }
+
+    /// Report API calls, retries, and cache hits to an observer, e.g. to
+    /// feed metrics. See [`Observer`].
+    pub fn observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Abort extractions on the server when their future is dropped.
+    ///
+    /// Each extraction is sent with an operation ID. If the caller stops
+    /// waiting before the response arrives, the client asks the API to abort
+    /// the operation so the work is not finished and billed. Requires a Tokio
+    /// runtime to send the abort in the background.
+    pub fn abort_on_cancel(mut self, enabled: bool) -> Self {
+        self.abort_on_cancel = enabled;
+        self
+    }
+
+    /// Fail extractions whose data does not match the request's schema.
+    ///
+    /// The data is checked as by
+    /// [`ExtractResponse::validate`](crate::ExtractResponse::validate), and
+    /// any mismatch is returned as [`Error::SchemaMismatch`] carrying the
+    /// data. Override per call with
+    /// [`RequestOptions::strict_schema`](crate::RequestOptions::strict_schema).
+    pub fn strict_schema(mut self, enabled: bool) -> Self {
+        self.strict_schema = enabled;
+        self
+    }
+
+    /// Request job results as MessagePack instead of JSON.
+    ///
+    /// Large result sets are smaller and faster to parse in MessagePack. The
+    /// server may still answer with JSON, which is then parsed as usual.
+    #[cfg(feature = "msgpack")]
+    pub fn prefer_msgpack(mut self, enabled: bool) -> Self {
+        self.prefer_msgpack = enabled;
+        self
+    }
+
+    /// Ask for gzip or Brotli compressed responses, and decompress them.
+    ///
+    /// On by default. Large job results are several times smaller
+    /// compressed. Has no effect with a client passed to
+    /// [`http_client`](Self::http_client), which keeps its own setting, or in
+    /// the browser, which always negotiates compression.
+    #[cfg(feature = "compression")]
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Build the client, and with [`VersionCheckMode::Strict`] check the
+    /// API version with a request to the health endpoint.
+    ///
+    /// Fails with [`Error::UnsupportedApiVersion`] if the API is older than
+    /// this SDK supports or does not report its version (as `"unknown"`), or
+    /// with the health check's error if the API cannot be reached.
+    pub async fn connect(self) -> Result<Client> {
+        let client = self.build()?;
+        if client.version_check == VersionCheckMode::Strict {
+            let _: HealthCheckOutputBody = client.get_skip_cache("/health").await?;
+            if *client.api_version.lock().unwrap() == ApiVersionStatus::Unchecked {
+                return Err(Error::UnsupportedApiVersion {
+                    api_version: "unknown".into(),
+                    min_version: client.min_api_version.to_string(),
+                    max_known_version: crate::MAX_KNOWN_API_VERSION.to_string(),
+                });
+            }
+        }
+        Ok(client)
+    }
+
     /// Build the client.
     pub fn build(self) -> Result<Client> {
-        if self.api_key.is_empty() {
-            return Err(Error::Config("API key is required".into()));
+        match &self.api_key_provider {
+            None if self.api_key.is_empty() => {
+                return Err(Error::Config("API key is required".into()));
+            }
+            Some((account, _)) if account.is_empty() => {
+                return Err(Error::Config(
+                    "An account is required with an API key provider".into(),
+                ));
+            }
+            _ => {}
         }
 
-        // Warn about insecure connections
+        // Reject or warn about insecure connections
         if !self.base_url.starts_with("https://") {
+            if self.require_https {
+                return Err(Error::Config(format!(
+                    "API base URL must use HTTPS: {}",
+                    self.base_url
+                )));
+            }
             warn!(
                 base_url = %self.base_url,
                 "API base URL is not using HTTPS. This is insecure."
             );
         }
 
-        let http_client = reqwest::Client::builder()
-            .timeout(self.timeout)
-            .build()
-            .map_err(Error::Http)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let http_client = {
+            let mut http_client = reqwest::Client::builder().timeout(self.timeout);
+            if let Some(timeout) = self.connect_timeout {
+                http_client = http_client.connect_timeout(timeout);
+            }
+            if let Some(timeout) = self.read_timeout {
+                http_client = http_client.read_timeout(timeout);
+            }
+            if let Some(policy) = self.redirect {
+                http_client = http_client.redirect(policy);
+            }
+            for certificate in self.root_certificates {
+                http_client = http_client.add_root_certificate(certificate);
+            }
+            if let Some(identity) = self.identity {
+                http_client = http_client.identity(identity);
+            }
+            #[cfg(feature = "compression")]
+            {
+                http_client = http_client.gzip(self.compression).brotli(self.compression);
+            }
+            #[cfg(feature = "danger-insecure-tls")]
+            if self.accept_invalid_certs {
+                warn!("TLS certificate verification is disabled. This is insecure.");
+                http_client = http_client.danger_accept_invalid_certs(true);
+            }
+            http_client
+        };
+        // The fetch-based client only supports per-request timeouts.
+        #[cfg(target_arch = "wasm32")]
+        let http_client = {
+            let _ = (self.connect_timeout, self.read_timeout);
+            #[cfg(feature = "compression")]
+            let _ = self.compression;
+            reqwest::Client::builder()
+        };
+        let http_client = match self.http_client {
+            Some(client) => client,
+            None => http_client.build().map_err(Error::Http)?,
+        };
 
-        let cache: Arc<dyn Cache> = self
-            .cache
-            .unwrap_or_else(|| Arc::new(MemoryCache::default()));
+        let cache_observer = self.cache_observer;
+        let clock = self.clock.clone();
+        let cache: Arc<dyn Cache> = self.cache.unwrap_or_else(|| {
+            let cache = MemoryCache::default().clocked(clock);
+            Arc::new(match cache_observer {
+                Some(observer) => cache.with_observer(observer),
+                None => cache,
+            })
+        });
 
         let user_agent = build_user_agent(self.user_agent_suffix.as_deref());
-        let auth_hash = hash_string(&self.api_key);
+        let (auth_hash, provider) = match self.api_key_provider {
+            Some((account, provider)) => {
+                (hash_string(&format!("account:{}", account)), Some(provider))
+            }
+            None => (hash_string(&self.api_key), None),
+        };
 
         Ok(Client {
-            api_key: self.api_key,
+            api_key: Arc::new(ApiKey::new(self.api_key, provider)),
             base_url: self.base_url,
             http_client,
+            #[cfg(target_arch = "wasm32")]
+            timeout: self.timeout,
+            request_timeout: None,
             cache,
             cache_enabled: self.cache_enabled,
             user_agent,
             max_retries: self.max_retries,
+            max_retry_duration: self.max_retry_duration,
+            retry_post_read_timeouts: self.retry_post_read_timeouts,
             auth_hash,
-            api_version_checked: Arc::new(AtomicBool::new(false)),
+            api_version: Arc::new(Mutex::new(ApiVersionStatus::Unchecked)),
+            min_api_version: MIN_API_VERSION,
+            api_features: Arc::new(OnceLock::new()),
+            duplicate_crawl_policy: self.duplicate_crawl_policy,
+            version_check: self.version_check,
+            job_registry: Arc::new(JobRegistry::default()),
+            submitted_crawls: Arc::new(SubmittedCrawls::default()),
+            health: Arc::new(HealthTracker::new(self.health_config, self.on_degraded)),
+            rate_limits: Arc::new(RateLimitTracker::default()),
+            circuit: self
+                .circuit_breaker
+                .map(|c| Arc::new(CircuitBreaker::new(c))),
+            error_messages: self.error_messages,
+            clock: self.clock,
+            transforms: self.transforms,
+            middleware: self.middleware,
+            abort_on_cancel: self.abort_on_cancel,
+            strict_schema: self.strict_schema,
+            tracing_spans: self.tracing_spans,
+            observer: self.observer,
+            quota_limits: self.quota_limits,
+            scheduler: self.fairness.map(|c| Arc::new(FairScheduler::new(c))),
+            default_llm_config: self.default_llm_config,
+            tenant: DEFAULT_TENANT.to_string(),
+            #[cfg(feature = "msgpack")]
+            prefer_msgpack: self.prefer_msgpack,
         })
     }
 }
@@ -148,16 +838,62 @@ impl ClientBuilder {
 ///     Ok(())
 /// }
 /// ```
+#[derive(Clone)]
 pub struct Client {
-    api_key: String,
+    api_key: Arc<ApiKey>,
     base_url: String,
     http_client: reqwest::Client,
+    #[cfg(target_arch = "wasm32")]
+    timeout: Duration,
+    request_timeout: Option<Duration>,
     cache: Arc<dyn Cache>,
     cache_enabled: bool,
     user_agent: String,
     max_retries: u32,
+    max_retry_duration: Option<Duration>,
+    retry_post_read_timeouts: bool,
     auth_hash: String,
-    api_version_checked: Arc<AtomicBool>,
+    api_version: Arc<Mutex<ApiVersionStatus>>,
+    min_api_version: &'static str,
+    api_features: Arc<OnceLock<ApiFeatures>>,
+    version_check: VersionCheckMode,
+    duplicate_crawl_policy: DuplicateCrawlPolicy,
+    job_registry: Arc<JobRegistry>,
+    submitted_crawls: Arc<SubmittedCrawls>,
+    health: Arc<HealthTracker>,
+    rate_limits: Arc<RateLimitTracker>,
+    circuit: Option<Arc<CircuitBreaker>>,
+    error_messages: Option<ErrorMessages>,
+    clock: Arc<dyn Clock>,
+    transforms: TransformPipeline,
+    middleware: Vec<Arc<dyn Middleware>>,
+    abort_on_cancel: bool,
+    strict_schema: bool,
+    tracing_spans: bool,
+    observer: Option<Arc<dyn Observer>>,
+    quota_limits: QuotaLimits,
+    scheduler: Option<Arc<FairScheduler>>,
+    default_llm_config: Option<LLMConfigInput>,
+    tenant: String,
+    #[cfg(feature = "msgpack")]
+    prefer_msgpack: bool,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("api_key", &REDACTED)
+            .field("base_url", &self.base_url)
+            .field("request_timeout", &self.request_timeout)
+            .field("cache_enabled", &self.cache_enabled)
+            .field("user_agent", &self.user_agent)
+            .field("max_retries", &self.max_retries)
+            .field("max_retry_duration", &self.max_retry_duration)
+            .field("duplicate_crawl_policy", &self.duplicate_crawl_policy)
+            .field("default_llm_config", &self.default_llm_config)
+            .field("tenant", &self.tenant)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Client {
@@ -166,6 +902,83 @@ impl Client {
         ClientBuilder::new(api_key)
     }
 
+    /// Create a client configured from environment variables.
+    ///
+    /// See [`ClientBuilder::from_env`] for the variables read.
+    pub fn from_env() -> Result<Client> {
+        ClientBuilder::from_env()?.build()
+    }
+
+    /// Get a handle that tags every request with a tenant ID.
+    ///
+    /// The handle shares the connection pool, cache, and scheduler with this
+    /// client. Tenant IDs only affect ordering when
+    /// [`fair_scheduling`](ClientBuilder::fair_scheduling) is enabled.
+    pub fn tenant(&self, tenant_id: impl Into<String>) -> Client {
+        Client {
+            tenant: tenant_id.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Get a handle that applies [`RequestOptions`] to every request it makes.
+    ///
+    /// The handle shares this client's connection pool, cache and rate
+    /// limits; only the overridden settings differ.
+    ///
+    /// ```rust,no_run
+    /// # use refyne::{Client, RequestOptions};
+    /// # use std::time::Duration;
+    /// # async fn run(client: Client) -> Result<(), refyne::Error> {
+    /// let slow = client.with_options(RequestOptions::default().timeout(Duration::from_secs(300)));
+    /// let keys = slow.list_keys().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(&self, options: RequestOptions) -> Client {
+        let mut client = Client {
+            request_timeout: options.timeout.or(self.request_timeout),
+            strict_schema: options.strict_schema.unwrap_or(self.strict_schema),
+            ..self.clone()
+        };
+        if let Some(api_key) = options.api_key_override {
+            client.auth_hash = hash_string(&api_key);
+            client.api_key = Arc::new(ApiKey::new(api_key, None));
+        }
+        if let Some(tenant) = options.tenant {
+            client.tenant = tenant;
+        }
+        client
+    }
+
+    /// Replace the API key used for subsequent requests.
+    ///
+    /// Applies to every handle sharing this client, such as those from
+    /// [`tenant`](Self::tenant). The connection pool and cache are kept;
+    /// cached responses remain partitioned under the key the client was built
+    /// with, so rotate only between keys of the same account. Has no effect
+    /// when an [`ApiKeyProvider`] is configured.
+    pub fn set_api_key(&self, api_key: impl Into<String>) {
+        self.api_key.set(api_key.into());
+    }
+
+    /// Get the message to show an end user for an error.
+    ///
+    /// Uses the mapping set with
+    /// [`error_messages`](ClientBuilder::error_messages), falling back to the
+    /// error's own message.
+    pub fn user_message(&self, error: &Error) -> String {
+        self.error_messages
+            .as_ref()
+            .and_then(|messages| messages(error.code()))
+            .unwrap_or_else(|| error.to_string())
+    }
+
+    /// Describe what this build of the SDK supports.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::current()
+    }
+
     /// Access job-related operations.
     pub fn jobs(&self) -> JobsClient<'_> {
         JobsClient { client: self }
@@ -191,92 +1004,900 @@ impl Client {
         LlmClient { client: self }
     }
 
+    /// Access usage and spend reporting.
+    pub fn usage(&self) -> UsageClient<'_> {
+        UsageClient { client: self }
+    }
+
+    /// Access organization and member management.
+    pub fn org(&self) -> OrgClient<'_> {
+        OrgClient { client: self }
+    }
+
     /// Access webhook operations.
     pub fn webhooks(&self) -> WebhooksClient<'_> {
         WebhooksClient { client: self }
     }
 
     /// Extract structured data from a single web page.
-    pub async fn extract(&self, request: ExtractRequest) -> Result<ExtractResponse> {
-        self.post("/api/v1/extract", &request).await
+    ///
+    /// With [`strict_schema`](ClientBuilder::strict_schema), data that does
+    /// not match the request's schema fails with [`Error::SchemaMismatch`].
+    /// With [`abort_on_cancel`](ClientBuilder::abort_on_cancel), dropping the
+    /// returned future before it completes aborts the extraction on the server.
+    pub async fn extract(&self, mut request: ExtractRequest) -> Result<ExtractResponse> {
+        crate::json_schema::resolve(&mut request)?;
+        if let Some(fetch) = &request.fetch_options {
+            fetch.check()?;
+        }
+        self.apply_default_llm_config(&mut request.llm_config);
+        let response: ExtractResponse = if self.abort_on_cancel {
+            let operation_id = new_operation_id();
+            let url = format!("{}/api/v1/extract", self.base_url);
+            let mut prepared = self.prepare_raw("POST", &url, Some(&request))?;
+            prepared.set_header(OPERATION_ID_HEADER, operation_id.as_str());
+
+            let guard = AbortOnDrop::new(self.clone(), operation_id);
+            let response = self.execute_cached(&prepared, true).await;
+            guard.disarm();
+            response?
+        } else {
+            self.post("/api/v1/extract", &request).await?
+        };
+        self.finish_extraction(response, &request.schema)
     }
 
-    /// Start an asynchronous crawl job.
-    pub async fn crawl(&self, request: CrawlRequest) -> Result<CrawlJobCreated> {
-        self.post("/api/v1/crawl", &request).await
+    /// Extract structured data from a PDF or Word document, at a URL or
+    /// uploaded. See [`ExtractDocumentRequest`].
+    ///
+    /// Schemas and [`strict_schema`](ClientBuilder::strict_schema) work as
+    /// for [`extract`](Self::extract).
+    pub async fn extract_document(
+        &self,
+        mut request: ExtractDocumentRequest,
+    ) -> Result<ExtractResponse> {
+        request.check()?;
+        crate::json_schema::resolve_schema(&mut request.schema, &mut request.schema_format)?;
+        self.apply_default_llm_config(&mut request.llm_config);
+        let url = format!("{}/api/v1/extract/document", self.base_url);
+        let mut prepared = self.prepare_raw("POST", &url, Some(&request))?;
+        if let Some((content_type, body)) = request.multipart()? {
+            prepared.set_header(CONTENT_TYPE.as_str(), content_type);
+            prepared.body = Some(body);
+        }
+        let response = self.execute_cached(&prepared, true).await?;
+        self.finish_extraction(response, &request.schema)
     }
 
-    /// Analyze a website to detect structure and suggest schemas.
-    pub async fn analyze(&self, request: AnalyzeRequest) -> Result<AnalyzeResponse> {
-        self.post("/api/v1/analyze", &request).await
+    /// Apply transforms to extracted data, and check it against `schema`
+    /// with [`strict_schema`](ClientBuilder::strict_schema).
+    fn finish_extraction(
+        &self,
+        mut response: ExtractResponse,
+        schema: &serde_json::Value,
+    ) -> Result<ExtractResponse> {
+        self.transforms.apply(&mut response.data);
+        if self.strict_schema {
+            let violations = response.validate(schema);
+            if !violations.is_empty() {
+                return Err(Error::SchemaMismatch {
+                    violations,
+                    data: response.data,
+                });
+            }
+        }
+        Ok(response)
     }
 
-    /// Get usage statistics for the current billing period.
-    pub async fn get_usage(&self) -> Result<GetUsageOutputBody> {
-        self.get("/api/v1/usage").await
+    /// Extract a page and deserialize the data into your own type.
+    ///
+    /// Fails with [`Error::Deserialize`] carrying the extracted data if it
+    /// does not match `T`.
+    pub async fn extract_as<T: serde::de::DeserializeOwned>(
+        &self,
+        request: ExtractRequest,
+    ) -> Result<T> {
+        let response = self.extract(request).await?;
+        serde_json::from_value(response.data.clone())
+            .map_err(|e| Error::deserialize(e, response.data))
     }
 
-    // === Jobs ===
+    /// Ask the API to abort an extraction by operation ID.
+    pub(crate) async fn abort_operation(&self, operation_id: &str) -> Result<()> {
+        self.delete(&format!("/api/v1/extract/{}", operation_id))
+            .await
+    }
 
-    /// List all jobs.
-    pub async fn list_jobs(&self, limit: Option<u32>, offset: Option<u32>) -> Result<JobList> {
-        let mut path = "/api/v1/jobs".to_string();
-        let mut params = vec![];
-        if let Some(l) = limit {
-            params.push(format!("limit={}", l));
+    /// Extract structured data, falling back to the last-good result on failure.
+    ///
+    /// Successful results are remembered per URL and schema in the client's
+    /// cache. If the call fails with a timeout, network, or server error and
+    /// a previous result exists, it is returned as an [`Extraction`] with
+    /// `stale` set instead of the error. Other errors, such as a rejected
+    /// request or exhausted quota, are always returned.
+    pub async fn extract_or_stale(&self, mut request: ExtractRequest) -> Result<Extraction> {
+        crate::json_schema::resolve(&mut request)?;
+        if let Some(fetch) = &request.fetch_options {
+            fetch.check()?;
         }
-        if let Some(o) = offset {
-            params.push(format!("offset={}", o));
+        self.apply_default_llm_config(&mut request.llm_config);
+        let key = last_good_key(&request, &self.auth_hash);
+
+        let error = match self
+            .post::<serde_json::Value, _>("/api/v1/extract", &request)
+            .await
+        {
+            Ok(mut value) => {
+                if let Some(data) = value.get_mut("data") {
+                    self.transforms.apply(data);
+                }
+                let response: ExtractResponse = serde_json::from_value(value.clone())?;
+                if self.cache_enabled {
+                    self.cache.set(&key, last_good_entry(value));
+                }
+                return Ok(Extraction::fresh(response));
+            }
+            Err(e) if e.is_retryable() || e.code() == ErrorCode::Timeout => e,
+            Err(e) => return Err(e),
+        };
+
+        let last_good = self
+            .cache
+            .get(&key)
+            .and_then(|entry| serde_json::from_value(entry.value).ok());
+        match last_good {
+            Some(response) => {
+                warn!(url = %request.url, error = %error, "Extraction failed. Serving stale result");
+                Ok(Extraction::stale(response, error))
+            }
+            None => Err(error),
         }
-        if !params.is_empty() {
-            path.push('?');
-            path.push_str(&params.join("&"));
+    }
+
+    /// Extract data from several pages, collecting failures for triage.
+    ///
+    /// Requests run one after another. When the API rate limits a request, the
+    /// batch waits for the advertised Retry-After before continuing. Failed
+    /// requests are returned in a classified [`FailureQueue`].
+    pub async fn extract_batch(&self, requests: Vec<ExtractRequest>) -> BatchResult {
+        let max_pause = RetryPolicy::default().max_delay;
+        let mut result = BatchResult::default();
+        let mut pause = Duration::ZERO;
+
+        for request in requests {
+            if !pause.is_zero() {
+                self.clock.sleep(pause).await;
+            }
+            match self.extract(request.clone()).await {
+                Ok(response) => {
+                    result.succeeded.push(response);
+                    pause = Duration::ZERO;
+                }
+                Err(e) => {
+                    pause = back_off(&e, pause, max_pause);
+                    result.failures.push(Failure::new(request, e, 1));
+                }
+            }
         }
-        self.get(&path).await
+        result
     }
 
-    /// Get a job by ID.
-    pub async fn get_job(&self, id: &str) -> Result<Job> {
-        self.get_skip_cache(&format!("/api/v1/jobs/{}", id)).await
+    /// Extract data from several pages, `concurrency` at a time.
+    ///
+    /// Results are streamed as each extraction completes, tagged with the
+    /// position and URL of its request. A failed extraction is yielded as an
+    /// error item and does not stop the rest of the batch; the stream's
+    /// [`usage`](ExtractMany::usage) sums tokens and cost as it goes.
+    pub fn extract_many(&self, requests: Vec<ExtractRequest>, concurrency: usize) -> ExtractMany {
+        let client = self.clone();
+        ExtractMany::new(requests, concurrency, move |request| {
+            let client = client.clone();
+            Box::pin(async move { client.extract(request).await })
+        })
     }
 
-    /// Get job results.
-    pub async fn get_job_results(&self, id: &str, merge: bool) -> Result<JobResults> {
-        let path = if merge {
-            format!("/api/v1/jobs/{}/results?merge=true", id)
-        } else {
-            format!("/api/v1/jobs/{}/results", id)
+    /// Re-run the retryable failures from a batch.
+    ///
+    /// Blocked and invalid failures are carried over untouched. Retryable ones
+    /// are attempted up to `policy.max_attempts` times, pausing `policy.delay`
+    /// before each request; the pause grows while the API is rate limiting.
+    pub async fn retry_failures(&self, failures: FailureQueue, policy: RetryPolicy) -> BatchResult {
+        let (retryable, rest) = failures.split_retryable();
+        let mut result = BatchResult {
+            succeeded: Vec::new(),
+            failures: rest,
         };
-        self.get_skip_cache(&path).await
-    }
+        let mut delay = policy.delay;
+
+        for failure in retryable {
+            let Failure {
+                request,
+                mut error,
+                mut attempts,
+                ..
+            } = failure;
+            let mut succeeded = false;
+
+            for _ in 0..policy.max_attempts {
+                self.clock.sleep(delay).await;
+                attempts += 1;
+                match self.extract(request.clone()).await {
+                    Ok(response) => {
+                        result.succeeded.push(response);
+                        succeeded = true;
+                        break;
+                    }
+                    Err(e) => {
+                        delay = back_off(&e, delay, policy.max_delay);
+                        let retryable = FailureKind::of(&e) == FailureKind::Retryable;
+                        error = e;
+                        if !retryable {
+                            break;
+                        }
+                    }
+                }
+            }
 
-    /// Get a presigned download URL for job results.
-    pub async fn download_job(&self, id: &str) -> Result<GetJobResultsDownloadOutputBody> {
-        self.get(&format!("/api/v1/jobs/{}/download", id)).await
+            if !succeeded {
+                result.failures.push(Failure::new(request, error, attempts));
+            }
+        }
+        result
     }
 
-    /// Get the crawl map for a job.
-    pub async fn get_job_crawl_map(&self, id: &str) -> Result<GetCrawlMapOutputBody> {
-        self.get(&format!("/api/v1/jobs/{}/crawl-map", id)).await
+    /// Start an asynchronous crawl job.
+    ///
+    /// When a [`DuplicateCrawlPolicy`] other than `Allow` is configured, an
+    /// identical crawl still pending or running is rejected or returned instead.
+    /// The request is remembered so the job can be [retried](JobsClient::retry).
+    pub async fn crawl(&self, mut request: CrawlRequest) -> Result<CrawlJobCreated> {
+        if let Some(fetch) = &request.fetch_options {
+            fetch.check()?;
+        }
+        self.apply_default_llm_config(&mut request.llm_config);
+        if self.duplicate_crawl_policy == DuplicateCrawlPolicy::Allow {
+            let created: CrawlJobCreated = self.post("/api/v1/crawl", &request).await?;
+            self.submitted_crawls
+                .insert(created.job_id.clone(), request);
+            return Ok(created);
+        }
+
+        // Jobs are only visible to the account that created them.
+        let fingerprint = format!("{}:{}", self.auth_hash, crawl_fingerprint(&request));
+        // Held until the job is recorded, so an identical concurrent crawl
+        // waits here and then finds it.
+        let _reservation = self.job_registry.reserve(&fingerprint).await;
+        if let Some(job_id) = self.job_registry.get(&fingerprint) {
+            match self.get_job(&job_id).await {
+                Ok(job) if is_active_status(&job.status) => {
+                    return match self.duplicate_crawl_policy {
+                        DuplicateCrawlPolicy::ReturnExisting => Ok(existing_job_response(job)),
+                        _ => Err(Error::DuplicateCrawl {
+                            job_id: job.id,
+                            status: job.status,
+                        }),
+                    };
+                }
+                Ok(_) | Err(Error::NotFound { .. }) => self.job_registry.remove(&fingerprint),
+                Err(e) => return Err(e),
+            }
+        }
+
+        let created: CrawlJobCreated = self.post("/api/v1/crawl", &request).await?;
+        if is_active_status(&created.status) {
+            self.job_registry
+                .insert(fingerprint, created.job_id.clone());
+        }
+        self.submitted_crawls
+            .insert(created.job_id.clone(), request);
+        Ok(created)
     }
 
-    /// Get debug capture data for a job.
-    pub async fn get_job_debug_capture(&self, id: &str) -> Result<GetJobDebugCaptureOutputBody> {
-        self.get(&format!("/api/v1/jobs/{}/debug-capture", id)).await
+    /// Estimate the pages, tokens, and USD cost of an extraction or crawl
+    /// without running it; see [`EstimateRequest`].
+    pub async fn estimate(&self, mut request: EstimateRequest) -> Result<Estimate> {
+        crate::json_schema::resolve_schema(&mut request.schema, &mut request.schema_format)?;
+        self.apply_default_llm_config(&mut request.llm_config);
+        self.post("/api/v1/estimate", &request).await
     }
 
-    /// Get webhook deliveries for a job.
-    pub async fn get_job_webhook_deliveries(
-        &self,
-        id: &str,
-    ) -> Result<GetJobWebhookDeliveriesOutputBody> {
-        self.get(&format!("/api/v1/jobs/{}/webhooks", id)).await
+    /// Analyze a website to detect structure and suggest schemas.
+    pub async fn analyze(&self, request: AnalyzeRequest) -> Result<AnalyzeResponse> {
+        self.post("/api/v1/analyze", &request).await
     }
 
-    // === Schemas ===
+    /// Analyze a URL and save its suggested schema and a site using it.
+    ///
+    /// Runs [`analyze`](Self::analyze), persists the suggested schema with
+    /// [`create_schema`](Self::create_schema), then creates a site with that
+    /// schema as its default and the analysis' follow patterns. If creating the
+    /// site fails, the new schema is deleted again before the error is returned.
+    pub async fn bootstrap_site(&self, url: &str, name: &str) -> Result<BootstrappedSite> {
+        let analysis = self
+            .analyze(AnalyzeRequest {
+                url: url.to_string(),
+                ..Default::default()
+            })
+            .await?;
+        let schema = self.create_schema(schema_request(&analysis, name)).await?;
 
-    /// List all schemas.
-    pub async fn list_schemas(&self) -> Result<SchemaList> {
+        let site = match self
+            .create_site(site_request(&analysis, url, name, &schema.id))
+            .await
+        {
+            Ok(site) => site,
+            Err(e) => {
+                if let Err(cleanup) = self.delete_schema(&schema.id).await {
+                    warn!(schema_id = %schema.id, error = %cleanup, "Failed to delete schema after site creation failed");
+                }
+                return Err(e);
+            }
+        };
+
+        Ok(BootstrappedSite {
+            analysis,
+            schema,
+            site,
+        })
+    }
+
+    /// Start a request to any API path, such as a beta endpoint.
+    ///
+    /// Authentication, retries, the API version check, and caching of GET
+    /// responses apply as for typed methods. See [`RequestBuilder`].
+    ///
+    /// `path` must start with `/`.
+    pub fn request_builder(&self, method: &str, path: &str) -> RequestBuilder<'_> {
+        if !path.starts_with('/') {
+            let error = Error::Config(format!("request path must start with '/': {}", path));
+            return RequestBuilder::new(self, Err(error));
+        }
+        let url = format!("{}{}", self.base_url, path);
+        RequestBuilder::new(self, self.prepare_raw(method, &url, None::<&()>))
+    }
+
+    /// Prepare a request without sending it.
+    ///
+    /// The result holds the method, URL, headers, and body but not the API key,
+    /// so it can be queued or passed through a signing or egress gateway before
+    /// being executed with [`send_prepared`](Self::send_prepared).
+    pub fn prepare<R: ApiRequest>(&self, request: &R) -> Result<PreparedRequest> {
+        let url = format!("{}{}", self.base_url, R::PATH);
+        self.prepare_raw(R::METHOD, &url, Some(request))
+    }
+
+    /// Send a request created by [`prepare`](Self::prepare).
+    ///
+    /// Headers added after preparation are sent as they are. The API key is
+    /// added unless an `Authorization` header is already present, and only
+    /// when the URL points at the configured base URL. Retries, health
+    /// tracking, and fair scheduling apply as for any other request.
+    pub async fn send_prepared<T: serde::de::DeserializeOwned>(
+        &self,
+        prepared: &PreparedRequest,
+    ) -> Result<T> {
+        let mut slot = self.acquire_slot().await;
+        let response = self.execute_with_retry(prepared, &mut slot).await?;
+        self.check_api_version(&response)?;
+
+        if !response.status().is_success() {
+            return Err(Error::from_response(response).await);
+        }
+        response.json().await.map_err(Error::Http)
+    }
+
+    /// Get usage statistics for the current billing period.
+    pub async fn get_usage(&self) -> Result<GetUsageOutputBody> {
+        self.get("/api/v1/usage").await
+    }
+
+    /// Get daily jobs, tokens, and cost for a period, optionally split by API
+    /// key, model, or job type.
+    pub async fn get_usage_breakdown(&self, query: &UsageQuery) -> Result<UsageBreakdown> {
+        self.get(&format!("/api/v1/usage/breakdown?{}", query.query()))
+            .await
+    }
+
+    /// Get the account's monthly budget and spend against it.
+    pub async fn get_budget(&self) -> Result<Budget> {
+        self.get_skip_cache("/api/v1/usage/budget").await
+    }
+
+    /// Set the account's monthly budget in USD, with alerts sent as spend
+    /// crosses each of `alert_thresholds`, given as shares of the budget
+    /// such as `0.8`. A budget of zero removes it.
+    ///
+    /// Once the budget is spent, new work fails with
+    /// [`Error::BudgetExceeded`].
+    pub async fn set_budget(&self, monthly_usd: f64, alert_thresholds: &[f64]) -> Result<Budget> {
+        let input = BudgetInput::new(monthly_usd, alert_thresholds)?;
+        self.put("/api/v1/usage/budget", &input).await
+    }
+
+    /// Get a snapshot of the quota left for new work.
+    ///
+    /// Fetches current usage, which also refreshes the rate-limit window, and
+    /// combines it with the limits set by
+    /// [`quota_limits`](ClientBuilder::quota_limits). Batch schedulers can use
+    /// it to decide how much work to enqueue.
+    pub async fn remaining_quota(&self) -> Result<QuotaSnapshot> {
+        let usage: GetUsageOutputBody = self.get_skip_cache("/api/v1/usage").await?;
+        let window = self.rate_limits.latest();
+
+        Ok(QuotaSnapshot {
+            requests_remaining: window.and_then(|w| w.remaining),
+            requests_limit: window.and_then(|w| w.limit),
+            window_resets_in: window.and_then(|w| w.resets_in()),
+            jobs_used: usage.total_jobs,
+            jobs_remaining: self.quota_limits.jobs.map(|limit| limit - usage.total_jobs),
+            spent_usd: usage.total_charged_usd,
+            budget_remaining_usd: self
+                .quota_limits
+                .budget_usd
+                .map(|budget| budget - usage.total_charged_usd),
+        })
+    }
+
+    // === Jobs ===
+
+    /// List all jobs.
+    pub async fn list_jobs(&self, limit: Option<u32>, offset: Option<u32>) -> Result<JobList> {
+        self.list_jobs_filtered(&JobFilter::default(), limit, offset)
+            .await
+    }
+
+    /// List jobs matching `filter`, in the filter's order.
+    pub async fn list_jobs_filtered(
+        &self,
+        filter: &JobFilter,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<JobList> {
+        let mut path = "/api/v1/jobs".to_string();
+        let mut params = vec![];
+        if let Some(l) = limit {
+            params.push(format!("limit={}", l));
+        }
+        if let Some(o) = offset {
+            params.push(format!("offset={}", o));
+        }
+        params.extend(filter.query_params());
+        if !params.is_empty() {
+            path.push('?');
+            path.push_str(&params.join("&"));
+        }
+        self.get(&path).await
+    }
+
+    /// Stream every job, fetching pages of
+    /// [`DEFAULT_PAGE_SIZE`](crate::DEFAULT_PAGE_SIZE) as needed.
+    pub fn list_all_jobs(&self) -> Paginator<Job> {
+        self.list_all_jobs_filtered(JobFilter::default())
+    }
+
+    /// Stream every job matching `filter`, paging as needed.
+    pub fn list_all_jobs_filtered(&self, filter: JobFilter) -> Paginator<Job> {
+        let client = self.clone();
+        Paginator::new(move |limit, offset| {
+            let client = client.clone();
+            let filter = filter.clone();
+            Box::pin(async move {
+                let list = client
+                    .list_jobs_filtered(&filter, Some(limit), Some(offset))
+                    .await?;
+                match list.jobs {
+                    serde_json::Value::Null => Ok(Vec::new()),
+                    jobs => serde_json::from_value(jobs.clone())
+                        .map_err(|e| Error::deserialize(e, jobs)),
+                }
+            })
+        })
+    }
+
+    /// Get a job by ID.
+    pub async fn get_job(&self, id: &str) -> Result<Job> {
+        self.get_skip_cache(&format!("/api/v1/jobs/{}", id)).await
+    }
+
+    /// Get where the URLs of a crawl stand: counts by state, example URLs,
+    /// and an estimate of the time left.
+    pub async fn get_job_progress(&self, id: &str) -> Result<JobProgress> {
+        self.get_skip_cache(&format!("/api/v1/jobs/{}/progress", id))
+            .await
+    }
+
+    /// List the URLs of a job that failed, with why and how often they were
+    /// retried.
+    pub async fn get_job_errors(&self, id: &str) -> Result<JobErrors> {
+        self.get_skip_cache(&format!("/api/v1/jobs/{}/errors", id))
+            .await
+    }
+
+    /// Cancel a pending or running job.
+    ///
+    /// Sends `POST /api/v1/jobs/{id}/cancel` and returns the job as the API
+    /// reports it afterwards. Waiters on the job return once it is reported
+    /// as [`JobStatus::Cancelled`](crate::JobStatus::Cancelled).
+    pub async fn cancel_job(&self, id: &str) -> Result<Job> {
+        let job = self
+            .post(
+                &format!("/api/v1/jobs/{}/cancel", id),
+                &serde_json::json!({}),
+            )
+            .await?;
+        self.job_registry.remove_job(id);
+        Ok(job)
+    }
+
+    /// Continue a stopped crawl from its last checkpoint, rather than
+    /// starting again.
+    ///
+    /// A crawl stopped by cancellation or an exhausted quota keeps a
+    /// checkpoint of the URLs it processed, reported as
+    /// [`Job::resume_token`]. Resuming sends that token with
+    /// `POST /api/v1/jobs/{id}/resume` and returns the job as the API reports
+    /// it afterwards. Fails with [`Error::Config`] if the job is still
+    /// pending or running, or has no checkpoint.
+    pub async fn resume_job(&self, id: &str) -> Result<Job> {
+        let job = self.get_job(id).await?;
+        if job.job_status().is_active() {
+            return Err(Error::Config(format!(
+                "Job {} is still {} and cannot be resumed",
+                id, job.status
+            )));
+        }
+        let Some(token) = job.resume_token else {
+            return Err(Error::Config(format!(
+                "Job {} has no checkpoint to resume from. Use rerun_job to start it again",
+                id
+            )));
+        };
+        self.post(
+            &format!("/api/v1/jobs/{}/resume", id),
+            &serde_json::json!({ "resume_token": token }),
+        )
+        .await
+    }
+
+    /// Delete a job and its results.
+    pub async fn delete_job(&self, id: &str) -> Result<()> {
+        self.delete(&format!("/api/v1/jobs/{}", id)).await?;
+        self.job_registry.remove_job(id);
+        self.submitted_crawls.remove(id);
+        Ok(())
+    }
+
+    /// Start a new crawl with the request that started job `id`.
+    ///
+    /// See [`rerun_job`](Self::rerun_job).
+    pub async fn retry_job(&self, id: &str) -> Result<CrawlJobCreated> {
+        self.rerun_job(id, RunOptions::new()).await
+    }
+
+    /// Start a new crawl with the request that started job `id`, changed by
+    /// `run`.
+    ///
+    /// The original request is known for crawls submitted through this
+    /// client or its clones. For other crawl jobs, `run` must give a schema,
+    /// and the job's URL is crawled with it. Fails with [`Error::Config`] if
+    /// the job is still pending or running, or if its request is unknown and
+    /// no schema is given.
+    pub async fn rerun_job(&self, id: &str, run: RunOptions) -> Result<CrawlJobCreated> {
+        let job = self.get_job(id).await?;
+        if job.job_status().is_active() {
+            return Err(Error::Config(format!(
+                "Job {} is still {}. Cancel it or wait for it to finish first",
+                id, job.status
+            )));
+        }
+        let request = match self.submitted_crawls.get(id) {
+            Some(request) => request,
+            None if run.has_schema() && job.r#type == "crawl" => CrawlRequest {
+                url: job.url,
+                ..Default::default()
+            },
+            None => {
+                return Err(Error::Config(format!(
+                "Job {} was not started by this client. Pass a schema in RunOptions to re-run it",
+                id
+            )))
+            }
+        };
+        let saved_schema = match run.saved_schema_id() {
+            Some(schema_id) => Some(self.get_schema(schema_id).await?.schema_yaml),
+            None => None,
+        };
+        self.crawl(run.apply(request, saved_schema)).await
+    }
+
+    /// Wait until a job is no longer pending or running.
+    ///
+    /// Progress is read from `source`; see [`ProgressSource`] for the
+    /// available transports.
+    pub async fn wait_for_job<S: ProgressSource>(&self, id: &str, source: &mut S) -> Result<Job> {
+        self.watch_job(id, source, |_| {}).await
+    }
+
+    /// Poll a job until it is no longer pending or running.
+    ///
+    /// Returns [`Error::Timeout`] if the job is still active after
+    /// [`WaitOptions::max_wait`], measured with the client's clock.
+    pub async fn wait_for_job_with(&self, id: &str, options: WaitOptions) -> Result<Job> {
+        let (mut polling, max_wait, on_progress) = options.into_parts();
+        if let Some(max_wait) = max_wait {
+            polling = polling.until(self.clock.now().saturating_add(max_wait));
+        }
+        loop {
+            let job = polling.next(self, id).await?;
+            if let Some(callback) = &on_progress {
+                callback(&job);
+            }
+            if !is_active_status(&job.status) {
+                return Ok(job);
+            }
+            if polling.is_past_deadline(self) {
+                return Err(Error::Timeout);
+            }
+        }
+    }
+
+    /// Wait for a job to finish, calling `on_update` with every state seen.
+    pub async fn watch_job<S, F>(&self, id: &str, source: &mut S, mut on_update: F) -> Result<Job>
+    where
+        S: ProgressSource,
+        F: FnMut(&Job),
+    {
+        loop {
+            let job = source.next(self, id).await?;
+            on_update(&job);
+            if !is_active_status(&job.status) {
+                return Ok(job);
+            }
+        }
+    }
+
+    /// Start a crawl and wait for it to finish.
+    pub async fn crawl_and_wait<S: ProgressSource>(
+        &self,
+        request: CrawlRequest,
+        source: &mut S,
+    ) -> Result<Job> {
+        let created = self.crawl(request).await?;
+        self.wait_for_job(&created.job_id, source).await
+    }
+
+    /// Get job results.
+    pub async fn get_job_results(&self, id: &str, merge: bool) -> Result<JobResults> {
+        let path = if merge {
+            format!("/api/v1/jobs/{}/results?merge=true", id)
+        } else {
+            format!("/api/v1/jobs/{}/results", id)
+        };
+        #[cfg(feature = "msgpack")]
+        let mut results: JobResults = if self.prefer_msgpack {
+            self.get_msgpack(&path).await?
+        } else {
+            self.get_skip_cache(&path).await?
+        };
+        #[cfg(not(feature = "msgpack"))]
+        let mut results: JobResults = self.get_skip_cache(&path).await?;
+
+        self.transforms.apply_to_results(&mut results, merge);
+        Ok(results)
+    }
+
+    /// Stream job results one page at a time.
+    ///
+    /// Results are requested as newline-delimited JSON and each page is
+    /// yielded as its line arrives; see [`ResultStream`].
+    pub fn stream_job_results(&self, id: &str) -> ResultStream {
+        let client = self.clone();
+        let path = format!("/api/v1/jobs/{}/results", id);
+        ResultStream::new(
+            Box::pin(async move {
+                client
+                    .open_stream(&path, "application/x-ndjson, application/json;q=0.9")
+                    .await
+            }),
+            self.transforms.clone(),
+        )
+    }
+
+    /// Get job results with each page's data deserialized into `T`.
+    ///
+    /// Pages that do not match `T` are reported individually with their
+    /// position and URL; see [`JobResultsExt::deserialize_each`].
+    pub async fn get_job_results_as<T: serde::de::DeserializeOwned>(
+        &self,
+        id: &str,
+    ) -> Result<Vec<std::result::Result<T, ResultItemError>>> {
+        Ok(self.get_job_results(id, false).await?.deserialize_each())
+    }
+
+    /// List the page URLs in the sitemaps of `domain`, which a crawl with
+    /// [`use_sitemap`](crate::CrawlRequestBuilder::use_sitemap) would cover.
+    pub async fn list_sitemap_urls(&self, domain: &str) -> Result<SitemapUrls> {
+        self.get(&format!("/api/v1/sitemap?domain={}", encode(domain)))
+            .await
+    }
+
+    /// Get aggregate job statistics for the account.
+    pub async fn get_job_stats(&self, range: &StatsRange) -> Result<JobStats> {
+        self.get(&format!("/api/v1/jobs/stats?{}", range.query()))
+            .await
+    }
+
+    /// Download a page screenshot by the artifact ID in
+    /// [`ExtractResponse::screenshot_id`]. Returns the image in the format
+    /// requested in [`ScreenshotOptions`](crate::ScreenshotOptions).
+    pub async fn get_screenshot(&self, artifact_id: &str) -> Result<Bytes> {
+        let url = format!("{}/api/v1/artifacts/{}", self.base_url, artifact_id);
+        let mut prepared = self.prepare_raw("GET", &url, None::<&()>)?;
+        prepared.set_header(ACCEPT.as_str(), "image/*");
+
+        let mut slot = self.acquire_slot().await;
+        let response = self.execute_with_retry(&prepared, &mut slot).await?;
+        if !response.status().is_success() {
+            return Err(Error::from_response(response).await);
+        }
+        response.bytes().await.map_err(Error::Http)
+    }
+
+    /// Get a presigned download URL for job results.
+    pub async fn download_job(&self, id: &str) -> Result<GetJobResultsDownloadOutputBody> {
+        self.get(&format!("/api/v1/jobs/{}/download", id)).await
+    }
+
+    /// Download a job's results file to `path`, resuming after failures.
+    ///
+    /// The file is fetched with range requests into `<path>.part`. If the
+    /// connection drops, the download continues from the last byte written,
+    /// up to the configured retry limit. A response that ends without adding
+    /// any bytes counts against the same limit. A `.part` file left by an
+    /// earlier call is resumed as well. Resumed requests carry `If-Range`, so if the
+    /// file changed on the server it is downloaded again from the start. The
+    /// partial file is renamed to `path` once complete. Returns the size of
+    /// the file in bytes. Not available on `wasm32`, which has no file system.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn download_results(&self, id: &str, path: impl AsRef<Path>) -> Result<u64> {
+        let link: GetJobResultsDownloadOutputBody = self
+            .get_skip_cache(&format!("/api/v1/jobs/{}/download", id))
+            .await?;
+        let path = path.as_ref();
+        let partial = part_path(path);
+        let validator_file = validator_path(path);
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&partial)
+            .await?;
+        let mut offset = file.metadata().await?.len();
+        // A partial file is only resumed if it can be checked against the server's.
+        let mut validator = tokio::fs::read_to_string(&validator_file).await.ok();
+        if validator.is_none() && offset > 0 {
+            file.set_len(0).await?;
+            offset = 0;
+        }
+        let mut total = None;
+        let mut failures = 0;
+
+        while total.is_none_or(|total| offset < total) {
+            let mut prepared = self.prepare_raw("GET", &link.download_url, None::<&()>)?;
+            prepared.set_header(ACCEPT.as_str(), "*/*");
+            prepared.set_header(reqwest::header::RANGE.as_str(), range_header(offset));
+            if let Some(validator) = validator.as_deref().filter(|_| offset > 0) {
+                prepared.set_header(reqwest::header::IF_RANGE.as_str(), validator);
+            }
+            let mut response = self.execute_with_retry(&prepared, &mut None).await?;
+
+            match response.status() {
+                StatusCode::PARTIAL_CONTENT => total = content_range_total(response.headers()),
+                StatusCode::RANGE_NOT_SATISFIABLE
+                    if content_range_total(response.headers()) == Some(offset) =>
+                {
+                    break;
+                }
+                StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+                    // The partial file does not match the results; start over.
+                    file.set_len(0).await?;
+                    offset = 0;
+                    continue;
+                }
+                status if status.is_success() => {
+                    // The range was ignored, or the file changed since the
+                    // partial download; start over with the full body.
+                    file.set_len(0).await?;
+                    offset = 0;
+                    total = response.content_length();
+                }
+                _ => return Err(Error::from_response(response).await),
+            }
+            if offset == 0 {
+                validator = range_validator(response.headers());
+                match &validator {
+                    Some(validator) => tokio::fs::write(&validator_file, validator).await?,
+                    None => {
+                        let _ = tokio::fs::remove_file(&validator_file).await;
+                    }
+                }
+            }
+
+            let started_at = offset;
+            let requested_end = offset + DOWNLOAD_PART_SIZE;
+            let full_body = response.status() == StatusCode::OK;
+            let interrupted = loop {
+                match response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        file.write_all(&chunk).await?;
+                        offset += chunk.len() as u64;
+                    }
+                    Ok(None) => {
+                        // Without a known size, a short or unranged body is the end.
+                        if total.is_none() && (full_body || offset < requested_end) {
+                            total = Some(offset);
+                        }
+                        break None;
+                    }
+                    Err(e) => break Some(Error::Http(e)),
+                }
+            };
+            // A body that ends before adding anything would be requested again forever.
+            let stalled = offset == started_at && total.is_some_and(|total| offset < total);
+            let interrupted = interrupted.or_else(|| {
+                stalled.then(|| {
+                    Error::Io(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "download response ended without data",
+                    ))
+                })
+            });
+            if let Some(error) = interrupted {
+                failures += 1;
+                if failures > self.max_retries {
+                    return Err(error);
+                }
+                let backoff = calculate_backoff(failures);
+                warn!(
+                    error = %error,
+                    offset = offset,
+                    "Download interrupted. Resuming in {:?}",
+                    backoff
+                );
+                self.clock.sleep(backoff).await;
+            }
+        }
+
+        file.flush().await?;
+        file.sync_all().await?;
+        drop(file);
+        tokio::fs::rename(&partial, path).await?;
+        let _ = tokio::fs::remove_file(&validator_file).await;
+        Ok(offset)
+    }
+
+    /// Get the crawl map for a job.
+    pub async fn get_job_crawl_map(&self, id: &str) -> Result<GetCrawlMapOutputBody> {
+        self.get(&format!("/api/v1/jobs/{}/crawl-map", id)).await
+    }
+
+    /// Get debug capture data for a job.
+    pub async fn get_job_debug_capture(&self, id: &str) -> Result<GetJobDebugCaptureOutputBody> {
+        self.get(&format!("/api/v1/jobs/{}/debug-capture", id))
+            .await
+    }
+
+    /// Get webhook deliveries for a job.
+    pub async fn get_job_webhook_deliveries(
+        &self,
+        id: &str,
+    ) -> Result<GetJobWebhookDeliveriesOutputBody> {
+        self.get(&format!("/api/v1/jobs/{}/webhooks", id)).await
+    }
+
+    // === Schemas ===
+
+    /// List all schemas.
+    pub async fn list_schemas(&self) -> Result<SchemaList> {
         self.get("/api/v1/schemas").await
     }
 
@@ -327,6 +1948,63 @@ impl Client {
         self.delete(&format!("/api/v1/sites/{}", id)).await
     }
 
+    /// Start a crawl of a saved site with its URL, default schema, and crawl
+    /// options, overridden by `run`.
+    ///
+    /// Fails with [`Error::Config`] if the site has no default schema and
+    /// `run` does not name one.
+    pub async fn run_site(&self, id: &str, run: RunOptions) -> Result<CrawlJobCreated> {
+        let site = self.get_site(id).await?;
+        let saved_schema = match run.schema_id_for(&site)? {
+            Some(schema_id) => Some(self.get_schema(schema_id).await?.schema_yaml),
+            None => None,
+        };
+        self.crawl(run.crawl_request(&site, saved_schema)).await
+    }
+
+    // === Schedules ===
+
+    /// Run a saved site on a cron schedule, evaluated in UTC.
+    ///
+    /// Fails with [`Error::Config`] without calling the API if `cron` is not
+    /// five or six fields or a macro such as `@daily`.
+    pub async fn create_schedule(&self, site_id: &str, cron: &str) -> Result<Schedule> {
+        check_cron(cron)?;
+        self.post(
+            "/api/v1/schedules",
+            &serde_json::json!({"site_id": site_id, "cron": cron}),
+        )
+        .await
+    }
+
+    /// List schedules for all sites.
+    pub async fn list_schedules(&self) -> Result<ScheduleList> {
+        self.get("/api/v1/schedules").await
+    }
+
+    /// Stop a schedule from starting runs until it is resumed.
+    pub async fn pause_schedule(&self, id: &str) -> Result<Schedule> {
+        self.post(
+            &format!("/api/v1/schedules/{}/pause", id),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    /// Resume a paused schedule.
+    pub async fn resume_schedule(&self, id: &str) -> Result<Schedule> {
+        self.post(
+            &format!("/api/v1/schedules/{}/resume", id),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    /// Delete a schedule. Jobs it started are kept.
+    pub async fn delete_schedule(&self, id: &str) -> Result<()> {
+        self.delete(&format!("/api/v1/schedules/{}", id)).await
+    }
+
     // === Keys ===
 
     /// List all API keys.
@@ -345,6 +2023,42 @@ impl Client {
         self.delete(&format!("/api/v1/keys/{}", id)).await
     }
 
+    // === Organization ===
+
+    /// Get the account's organization.
+    pub async fn get_org(&self) -> Result<Organization> {
+        self.get("/api/v1/org").await
+    }
+
+    /// List the members of the organization.
+    pub async fn list_org_members(&self) -> Result<Vec<OrgMember>> {
+        let list: OrgMemberList = self.get_skip_cache("/api/v1/org/members").await?;
+        Ok(list.members)
+    }
+
+    /// Invite `email` to the organization with `role`.
+    ///
+    /// Fails with [`Error::Config`] without calling the API if `email` is
+    /// not an email address, or `role` is [`OrgRole::Owner`].
+    pub async fn invite_org_member(&self, email: &str, role: OrgRole) -> Result<OrgInvitation> {
+        let input = InviteInput::new(email, &role)?;
+        self.post("/api/v1/org/invitations", &input).await
+    }
+
+    /// Remove a member from the organization.
+    pub async fn remove_org_member(&self, id: &str) -> Result<()> {
+        self.delete(&format!("/api/v1/org/members/{}", id)).await
+    }
+
+    /// Change a member's role.
+    pub async fn update_org_member_role(&self, id: &str, role: OrgRole) -> Result<OrgMember> {
+        self.put(
+            &format!("/api/v1/org/members/{}", id),
+            &serde_json::json!({ "role": role }),
+        )
+        .await
+    }
+
     // === LLM ===
 
     /// List available LLM providers.
@@ -362,6 +2076,26 @@ impl Client {
         self.put("/api/v1/llm/keys", &request).await
     }
 
+    /// Check the stored key for `provider` with a cheap call to the
+    /// provider.
+    ///
+    /// A key the provider rejects is reported in [`LlmKeyTest::valid`],
+    /// not as an error.
+    pub async fn test_llm_key(&self, provider: &str) -> Result<LlmKeyTest> {
+        self.post(
+            "/api/v1/llm/keys/test",
+            &serde_json::json!({ "provider": provider }),
+        )
+        .await
+    }
+
+    /// Check a key before saving it, with a cheap call to its provider.
+    ///
+    /// Takes the request that would save the key; nothing is stored.
+    pub async fn test_llm_key_raw(&self, request: UpsertLlmKeyRequest) -> Result<LlmKeyTest> {
+        self.post("/api/v1/llm/keys/test", &request).await
+    }
+
     /// Delete an LLM key.
     pub async fn delete_llm_key(&self, id: &str) -> Result<()> {
         self.delete(&format!("/api/v1/llm/keys/{}", id)).await
@@ -378,11 +2112,54 @@ impl Client {
             .await
     }
 
+    /// Check a fallback chain before [`set_llm_chain`](Self::set_llm_chain):
+    /// that each entry's provider is offered and has an enabled key, that the
+    /// provider lists its model and has not deprecated it, and that its
+    /// settings are in range.
+    ///
+    /// Returns every problem found, or nothing if the chain is usable.
+    pub async fn validate_llm_chain(&self, chain: Vec<LlmChainEntry>) -> Result<Vec<ChainProblem>> {
+        let providers = self.list_providers().await?.providers;
+        let keys = match self.list_llm_keys().await?.keys {
+            serde_json::Value::Null => Vec::new(),
+            keys => {
+                serde_json::from_value(keys.clone()).map_err(|e| Error::deserialize(e, keys))?
+            }
+        };
+        let mut models = std::collections::HashMap::new();
+        for entry in &chain {
+            if providers.contains(&entry.provider) && !models.contains_key(&entry.provider) {
+                let listed = self.list_models(&entry.provider).await?.to_models()?;
+                models.insert(entry.provider.clone(), listed);
+            }
+        }
+        Ok(check_chain(&chain, &providers, &keys, &models))
+    }
+
     /// List available models for a provider.
     pub async fn list_models(&self, provider: &str) -> Result<ModelList> {
         self.get(&format!("/api/v1/llm/models/{}", provider)).await
     }
 
+    /// List the models of every available provider, with their pricing and
+    /// capabilities.
+    ///
+    /// Calls [`list_models`](Self::list_models) once per provider from
+    /// [`list_providers`](Self::list_providers).
+    pub async fn list_all_models(&self) -> Result<Vec<Model>> {
+        let mut all = Vec::new();
+        for provider in self.list_providers().await?.providers {
+            let mut models = self.list_models(&provider).await?.to_models()?;
+            for model in &mut models {
+                if model.provider.is_empty() {
+                    model.provider = provider.clone();
+                }
+            }
+            all.append(&mut models);
+        }
+        Ok(all)
+    }
+
     // === Webhooks ===
 
     /// List all webhooks.
@@ -410,6 +2187,15 @@ impl Client {
         self.delete(&format!("/api/v1/webhooks/{}", id)).await
     }
 
+    /// Send a test event to a webhook and return the delivery record.
+    pub async fn test_webhook(&self, id: &str) -> Result<WebhookDeliveryResponse> {
+        self.post(
+            &format!("/api/v1/webhooks/{}/test", id),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
     /// List webhook deliveries.
     pub async fn list_webhook_deliveries(
         &self,
@@ -444,30 +2230,169 @@ impl Client {
         self.get("/api/v1/cleaners").await
     }
 
-    /// Get pricing tiers and limits.
-    pub async fn get_pricing_tiers(&self) -> Result<ListTierLimitsOutputBody> {
-        self.get("/api/v1/pricing/tiers").await
+    /// Optional features offered by the API.
+    ///
+    /// Read from the `X-API-Features` header of the first response that has
+    /// one, or fetched from `/api/v1/features`, and cached on the client. An
+    /// API without either reports no features.
+    pub async fn api_features(&self) -> Result<ApiFeatures> {
+        if let Some(features) = self.api_features.get() {
+            return Ok(features.clone());
+        }
+        let features = match self.get_skip_cache("/api/v1/features").await {
+            Ok(features) => features,
+            Err(Error::NotFound { .. }) => ApiFeatures::unadvertised(),
+            Err(e) => return Err(e),
+        };
+        Ok(self.api_features.get_or_init(|| features).clone())
     }
 
-    // === Internal methods ===
-
-    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
-        self.request("GET", path, None::<&()>, false).await
+    /// Returns true if the API offers `feature`.
+    ///
+    /// False when the features cannot be fetched, so callers fall back to
+    /// what every deployment supports.
+    pub async fn supports(&self, feature: Feature) -> bool {
+        match self.api_features().await {
+            Ok(features) => features.supports(&feature),
+            Err(e) => {
+                warn!(feature = %feature, error = %e, "Could not fetch API features");
+                false
+            }
+        }
     }
 
-    async fn get_skip_cache<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
-        self.request("GET", path, None::<&()>, true).await
+    /// Get pricing tiers and limits.
+    pub async fn get_pricing_tiers(&self) -> Result<ListTierLimitsOutputBody> {
+        self.get("/api/v1/pricing/tiers").await
     }
 
-    async fn post<T: serde::de::DeserializeOwned, B: serde::Serialize>(
-        &self,
-        path: &str,
-        body: &B,
-    ) -> Result<T> {
-        self.request("POST", path, Some(body), false).await
+    /// Get rolling retry and rate-limit statistics per endpoint.
+    pub fn health_snapshot(&self) -> HealthSnapshot {
+        self.health.snapshot()
     }
 
-    async fn put<T: serde::de::DeserializeOwned, B: serde::Serialize>(
+    /// Export schemas, saved sites, job metadata, usage, and account
+    /// configuration to a gzip-compressed snapshot file.
+    ///
+    /// Items are written as they are fetched, bypassing the cache. Job results
+    /// are not included. Read the file back with
+    /// [`inspect_snapshot`](crate::inspect_snapshot).
+    #[cfg(feature = "snapshot")]
+    pub async fn export_account_snapshot(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<SnapshotSummary> {
+        const JOB_PAGE_SIZE: usize = 100;
+
+        let header = SnapshotHeader {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            created_at: self.clock.now_unix(),
+            sdk_version: crate::version::SDK_VERSION.to_string(),
+            base_url: self.base_url.clone(),
+        };
+        let mut writer = SnapshotWriter::create(path.as_ref(), header).await?;
+
+        let schemas: SchemaList = self.get_skip_cache("/api/v1/schemas").await?;
+        writer
+            .write(SnapshotSection::Schema, schemas.schemas)
+            .await?;
+        let sites: SiteList = self.get_skip_cache("/api/v1/sites").await?;
+        writer.write(SnapshotSection::Site, sites.sites).await?;
+
+        let mut offset = 0;
+        loop {
+            let page: JobList = self
+                .get_skip_cache(&format!(
+                    "/api/v1/jobs?limit={}&offset={}",
+                    JOB_PAGE_SIZE, offset
+                ))
+                .await?;
+            // The API may cap the page size, so only an empty page ends the list.
+            let written = writer.write(SnapshotSection::Job, page.jobs).await?;
+            if written == 0 {
+                break;
+            }
+            offset += written;
+        }
+
+        let usage: serde_json::Value = self.get_skip_cache("/api/v1/usage").await?;
+        writer.write(SnapshotSection::Usage, usage).await?;
+        let webhooks: ListWebhooksOutputBody = self.get_skip_cache("/api/v1/webhooks").await?;
+        writer
+            .write(SnapshotSection::Webhook, webhooks.webhooks)
+            .await?;
+        let chain: LlmChain = self.get_skip_cache("/api/v1/llm/chain").await?;
+        writer.write(SnapshotSection::LlmChain, chain.chain).await?;
+
+        writer.finish().await
+    }
+
+    // === Internal methods ===
+
+    /// Fill in the default LLM configuration if a request has none.
+    fn apply_default_llm_config(&self, llm_config: &mut Option<LLMConfigInput>) {
+        if llm_config.is_none() {
+            llm_config.clone_from(&self.default_llm_config);
+        }
+    }
+
+    pub(crate) fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Open a server-sent event stream.
+    pub(crate) async fn open_event_stream(&self, path: &str) -> Result<reqwest::Response> {
+        self.open_stream(path, "text/event-stream").await
+    }
+
+    /// Send a GET whose response body is read incrementally by the caller.
+    async fn open_stream(&self, path: &str, accept: &str) -> Result<reqwest::Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut prepared = self.prepare_raw("GET", &url, None::<&()>)?;
+        prepared.set_header(ACCEPT.as_str(), accept);
+
+        let response = self.execute_with_retry(&prepared, &mut None).await?;
+        self.check_api_version(&response)?;
+        if !response.status().is_success() {
+            return Err(Error::from_response(response).await);
+        }
+        Ok(response)
+    }
+
+    async fn acquire_slot(&self) -> Option<FairPermit> {
+        match &self.scheduler {
+            Some(scheduler) => Some(scheduler.acquire(&self.tenant).await),
+            None => None,
+        }
+    }
+
+    /// Wait before a retry without holding a fair-scheduling slot, so other
+    /// tenants' requests run meanwhile, then queue for a slot again.
+    async fn retry_sleep(&self, delay: Duration, slot: &mut Option<FairPermit>) {
+        let held = slot.take().is_some();
+        self.clock.sleep(delay).await;
+        if held {
+            *slot = self.acquire_slot().await;
+        }
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.request("GET", path, None::<&()>, false).await
+    }
+
+    async fn get_skip_cache<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.request("GET", path, None::<&()>, true).await
+    }
+
+    async fn post<T: serde::de::DeserializeOwned, B: serde::Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        self.request("POST", path, Some(body), false).await
+    }
+
+    async fn put<T: serde::de::DeserializeOwned, B: serde::Serialize>(
         &self,
         path: &str,
         body: &B,
@@ -475,19 +2400,62 @@ impl Client {
         self.request("PUT", path, Some(body), false).await
     }
 
+    /// GET a resource, asking for MessagePack and accepting JSON.
+    #[cfg(feature = "msgpack")]
+    async fn get_msgpack<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut prepared = self.prepare_raw("GET", &url, None::<&()>)?;
+        prepared.set_header(
+            ACCEPT.as_str(),
+            "application/msgpack, application/json;q=0.9",
+        );
+
+        let mut slot = self.acquire_slot().await;
+        let response = self.execute_with_retry(&prepared, &mut slot).await?;
+        self.check_api_version(&response)?;
+
+        if !response.status().is_success() {
+            return Err(Error::from_response(response).await);
+        }
+
+        let is_msgpack = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("msgpack"));
+        let body = response.bytes().await.map_err(Error::Http)?;
+        if is_msgpack {
+            Ok(rmp_serde::from_slice(&body)?)
+        } else {
+            serde_json::from_slice(&body).map_err(Error::Json)
+        }
+    }
+
     async fn delete(&self, path: &str) -> Result<()> {
         let url = format!("{}{}", self.base_url, path);
-        let response = self
-            .execute_with_retry("DELETE", &url, None::<&()>, 1)
-            .await?;
+        let prepared = self.prepare_raw("DELETE", &url, None::<&()>)?;
+        let mut slot = self.acquire_slot().await;
+        let response = self.execute_with_retry(&prepared, &mut slot).await?;
 
         if !response.status().is_success() {
             return Err(Error::from_response(response).await);
         }
 
+        self.invalidate_cached(&prepared.url);
         Ok(())
     }
 
+    /// Drop cached GET responses that a successful change to `url` may
+    /// have made stale.
+    fn invalidate_cached(&self, url: &str) {
+        if !self.cache_enabled {
+            return;
+        }
+        for prefix in invalidation_prefixes(&self.base_url, url) {
+            self.cache.delete_prefix(&prefix);
+        }
+    }
+
     async fn request<T, B>(
         &self,
         method: &str,
@@ -500,121 +2468,438 @@ impl Client {
         B: serde::Serialize,
     {
         let url = format!("{}{}", self.base_url, path);
-        let cache_key = generate_cache_key(method, &url, Some(&self.auth_hash));
+        let prepared = self.prepare_raw(method, &url, body)?;
+        self.execute_cached(&prepared, skip_cache).await
+    }
+
+    /// Send a request, serving and storing GET responses in the cache.
+    ///
+    /// With `skip_cache`, a cached response is not used but the new response
+    /// is still stored.
+    ///
+    /// The body is always read fully into memory. Responses that will not be
+    /// stored are deserialized straight from it, without the intermediate
+    /// [`Value`](serde_json::Value) kept for the cache.
+    pub(crate) async fn execute_cached<T: serde::de::DeserializeOwned>(
+        &self,
+        prepared: &PreparedRequest,
+        skip_cache: bool,
+    ) -> Result<T> {
+        let call = self.start_call(prepared);
+        let response = self.send_cached(prepared, skip_cache, &call);
+        self.traced(&call, response).await
+    }
+
+    async fn send_cached<T: serde::de::DeserializeOwned>(
+        &self,
+        prepared: &PreparedRequest,
+        skip_cache: bool,
+        call: &Call,
+    ) -> Result<T> {
+        let is_get = prepared.method == "GET";
+        let cache_key = self.cache_key(prepared);
 
         // Check cache for GET requests
-        if method == "GET" && self.cache_enabled && !skip_cache {
-            if let Some(entry) = self.cache.get(&cache_key) {
-                return serde_json::from_value(entry.value).map_err(Error::Json);
+        let cached = if is_get && self.cache_enabled {
+            self.cache.get(&cache_key)
+        } else {
+            None
+        };
+        if let Some(entry) = &cached {
+            if !skip_cache && entry.is_servable(self.clock.now_unix()) {
+                call.span.record("cache", "hit");
+                call.span.record("status", 200);
+                call.status.store(200, Ordering::Relaxed);
+                if let Some(observer) = &self.observer {
+                    observer.on_cache_hit(&call.endpoint);
+                }
+                meta::record(ResponseMeta::cached());
+                return serde_json::from_value(entry.value.clone()).map_err(Error::Json);
             }
         }
 
-        let response = self.execute_with_retry(method, &url, body, 1).await?;
-
-        // Check API version on first request
-        if !self.api_version_checked.swap(true, Ordering::SeqCst) {
-            if let Some(api_version) = response.headers().get("X-API-Version") {
-                if let Ok(v) = api_version.to_str() {
-                    check_api_version_compatibility(v)?;
+        // Revalidate a stored entry with a conditional request
+        let conditional;
+        let prepared = match cached.as_ref().filter(|entry| entry.has_validators()) {
+            Some(entry) => {
+                let mut request = prepared.clone();
+                if let Some(etag) = &entry.etag {
+                    request.set_header(IF_NONE_MATCH.as_str(), etag.as_str());
                 }
-            } else {
-                warn!("API did not return X-API-Version header");
+                if let Some(last_modified) = &entry.last_modified {
+                    request.set_header(IF_MODIFIED_SINCE.as_str(), last_modified.as_str());
+                }
+                conditional = request;
+                &conditional
+            }
+            None => prepared,
+        };
+
+        if is_get && self.cache_enabled {
+            call.span.record("cache", "miss");
+        }
+        let mut slot = self.acquire_slot().await;
+        let response = self
+            .send_with_retry(prepared, call, &mut slot, 1, Duration::ZERO)
+            .await?;
+        self.check_api_version(&response)?;
+
+        // Get cache headers before consuming response
+        let header = |name: HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let cache_control = header(CACHE_CONTROL);
+        let validators = Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                call.span.record("cache", "revalidated");
+                let entry = revalidated_entry(
+                    entry,
+                    cache_control.as_deref(),
+                    validators,
+                    self.clock.now_unix(),
+                );
+                self.cache.set(&cache_key, entry.clone());
+                return serde_json::from_value(entry.value).map_err(Error::Json);
             }
         }
 
         if !response.status().is_success() {
             return Err(Error::from_response(response).await);
         }
+        if !is_get {
+            self.invalidate_cached(&prepared.url);
+        }
 
-        // Get cache control header before consuming response
-        let cache_control = response
-            .headers()
-            .get("Cache-Control")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
+        let body = response.bytes().await.map_err(Error::Http)?;
+        let entry = if is_get && self.cache_enabled {
+            create_cache_entry_at(
+                serde_json::Value::Null,
+                cache_control.as_deref(),
+                validators,
+                self.clock.now_unix(),
+            )
+        } else {
+            None
+        };
+        let Some(mut entry) = entry else {
+            return serde_json::from_slice(&body).map_err(Error::Json);
+        };
 
         // Parse response as Value first for caching, then deserialize
-        let value: serde_json::Value = response.json().await.map_err(Error::Http)?;
+        let value: serde_json::Value = serde_json::from_slice(&body).map_err(Error::Json)?;
+        drop(body);
+        entry.value = value.clone();
+        self.cache.set(&cache_key, entry);
 
-        // Cache GET responses
-        if method == "GET" && self.cache_enabled {
-            if let Some(entry) = create_cache_entry(value.clone(), cache_control.as_deref()) {
-                self.cache.set(&cache_key, entry);
-            }
+        serde_json::from_value(value).map_err(Error::Json)
+    }
+
+    /// Cache key of a request. Headers other than those every request
+    /// carries may change the response, so they are part of the key.
+    fn cache_key(&self, prepared: &PreparedRequest) -> String {
+        let mut extra: Vec<String> = prepared
+            .headers
+            .iter()
+            .filter(|(name, value)| {
+                let name = name.to_ascii_lowercase();
+                let default = match name.as_str() {
+                    "content-type" | "accept" => "application/json",
+                    "user-agent" => self.user_agent.as_str(),
+                    _ => return true,
+                };
+                value != default
+            })
+            .map(|(name, value)| format!("{}:{}", name.to_ascii_lowercase(), value))
+            .collect();
+        if extra.is_empty() {
+            return generate_cache_key(&prepared.method, &prepared.url, Some(&self.auth_hash));
         }
+        extra.sort();
+        // Hashed so header values such as credentials are not stored in keys.
+        let url = format!("{}#{}", prepared.url, hash_string(&extra.join("\n")));
+        generate_cache_key(&prepared.method, &url, Some(&self.auth_hash))
+    }
 
-        serde_json::from_value(value).map_err(Error::Json)
+    /// Check the API version on the first response, and keep the first
+    /// advertised features.
+    fn check_api_version(&self, response: &reqwest::Response) -> Result<()> {
+        if self.api_features.get().is_none() {
+            if let Some(features) = response
+                .headers()
+                .get(API_FEATURES_HEADER)
+                .and_then(|value| value.to_str().ok())
+            {
+                let _ = self.api_features.set(ApiFeatures::from_header(features));
+            }
+        }
+        if self.version_check == VersionCheckMode::Disabled {
+            return Ok(());
+        }
+        let mut status = self.api_version.lock().unwrap();
+        match &*status {
+            ApiVersionStatus::Accepted => return Ok(()),
+            // Only stored in strict mode; the API will not have changed.
+            ApiVersionStatus::Unsupported(v) => {
+                return check_compatibility(v, self.min_api_version)
+            }
+            ApiVersionStatus::Unchecked => {}
+        }
+        let api_version = response
+            .headers()
+            .get("X-API-Version")
+            .and_then(|v| v.to_str().ok());
+        match api_version {
+            Some(v) => match check_compatibility(v, self.min_api_version) {
+                Err(e) if self.version_check == VersionCheckMode::Strict => {
+                    *status = ApiVersionStatus::Unsupported(v.to_string());
+                    return Err(e);
+                }
+                result => {
+                    if let Err(e) = result {
+                        warn!(error = %e, "Continuing with an unsupported API version");
+                    }
+                    *status = ApiVersionStatus::Accepted;
+                }
+            },
+            // Check the next response instead.
+            None if self.version_check == VersionCheckMode::Strict => {
+                debug!("API did not return X-API-Version header");
+            }
+            None => {
+                warn!("API did not return X-API-Version header");
+                *status = ApiVersionStatus::Accepted;
+            }
+        }
+        Ok(())
     }
 
-    async fn execute_with_retry<B: serde::Serialize>(
+    pub(crate) fn prepare_raw<B: serde::Serialize>(
         &self,
         method: &str,
         url: &str,
         body: Option<&B>,
-        attempt: u32,
+    ) -> Result<PreparedRequest> {
+        let headers = vec![
+            (CONTENT_TYPE.to_string(), "application/json".to_string()),
+            (ACCEPT.to_string(), "application/json".to_string()),
+            (USER_AGENT.to_string(), self.user_agent.clone()),
+        ];
+        let body = body.map(serde_json::to_vec).transpose()?;
+
+        Ok(PreparedRequest {
+            method: method.to_string(),
+            url: url.to_string(),
+            headers,
+            body,
+        })
+    }
+
+    /// Send a request with retries in a `refyne.request` span.
+    async fn execute_with_retry(
+        &self,
+        prepared: &PreparedRequest,
+        slot: &mut Option<FairPermit>,
     ) -> Result<reqwest::Response> {
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).unwrap(),
-        );
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-        headers.insert(USER_AGENT, HeaderValue::from_str(&self.user_agent).unwrap());
+        let call = self.start_call(prepared);
+        let response = self.send_with_retry(prepared, &call, slot, 1, Duration::ZERO);
+        self.traced(&call, response).await
+    }
 
-        let mut req = self.http_client.request(method.parse().unwrap(), url);
-        req = req.headers(headers);
+    /// Set up the span and metrics for an API call.
+    fn start_call(&self, prepared: &PreparedRequest) -> Call {
+        let url = prepared.url.as_str();
+        let path = url.strip_prefix(&self.base_url).unwrap_or(url);
+        let endpoint = endpoint_key(&prepared.method, path);
+        if let Some(observer) = &self.observer {
+            observer.on_request_start(&endpoint);
+        }
+        Call {
+            span: self.request_span(&prepared.method, path),
+            endpoint,
+            started: runtime::Instant::now(),
+            attempts: AtomicU32::new(0),
+            status: AtomicU16::new(0),
+        }
+    }
+
+    /// Create the `refyne.request` span for a call, unless spans are off.
+    fn request_span(&self, method: &str, path: &str) -> Span {
+        if !self.tracing_spans {
+            return Span::none();
+        }
+        info_span!(
+            "refyne.request",
+            method = %method,
+            path = path.split('?').next().unwrap_or(path),
+            status = field::Empty,
+            attempt = field::Empty,
+            duration_ms = field::Empty,
+            cache = field::Empty,
+            error = field::Empty,
+        )
+    }
+
+    /// Run a call in its span, then report how it ended.
+    async fn traced<T>(&self, call: &Call, future: impl Future<Output = Result<T>>) -> Result<T> {
+        let result = future.instrument(call.span.clone()).await;
+        let duration = call.started.elapsed();
+        call.span.record("duration_ms", duration.as_millis() as u64);
+        if let Err(error) = &result {
+            call.span.record("error", field::display(error));
+        }
+        if let Some(observer) = &self.observer {
+            let status = call.status.load(Ordering::Relaxed);
+            observer.on_request_end(
+                &call.endpoint,
+                &RequestOutcome {
+                    status: (status != 0).then_some(status),
+                    attempts: call.attempts.load(Ordering::Relaxed),
+                    duration,
+                    error: result.as_ref().err().map(Error::code),
+                },
+            );
+        }
+        result
+    }
 
-        if let Some(b) = body {
-            req = req.json(b);
+    /// Note that attempt `attempt` of a call will be retried after `delay`.
+    fn record_retry(&self, call: &Call, attempt: u32, delay: Duration) {
+        self.health.record(&call.endpoint, Event::Retry);
+        if let Some(observer) = &self.observer {
+            observer.on_retry(&call.endpoint, attempt, delay);
+        }
+    }
+
+    async fn send_with_retry(
+        &self,
+        prepared: &PreparedRequest,
+        call: &Call,
+        slot: &mut Option<FairPermit>,
+        attempt: u32,
+        waited: Duration,
+    ) -> Result<reqwest::Response> {
+        call.span.record("attempt", attempt);
+        let endpoint = &call.endpoint;
+        if let Some(circuit) = &self.circuit {
+            circuit.check(self.clock.now_unix())?;
         }
+        self.health.record(endpoint, Event::Request);
+        call.attempts.store(attempt, Ordering::Relaxed);
 
-        let response = match req.send().await {
+        let send = |request: PreparedRequest| -> MiddlewareFuture<'_> {
+            Box::pin(self.send_request(request))
+        };
+        let response = match Next::new(&self.middleware, &send)
+            .run(prepared.clone())
+            .await
+        {
             Ok(r) => r,
-            Err(e) => {
-                if e.is_timeout() {
-                    return Err(Error::Timeout);
+            Err(error @ (Error::ConnectTimeout | Error::ReadTimeout { .. } | Error::Http(_))) => {
+                if let Some(circuit) = &self.circuit {
+                    circuit.record_failure(self.clock.now_unix());
+                }
+                if matches!(error, Error::ReadTimeout { idempotent: false })
+                    && !self.retry_post_read_timeouts
+                {
+                    return Err(error);
                 }
                 // Retry on network errors
-                if attempt <= self.max_retries {
-                    let backoff = calculate_backoff(attempt);
+                let backoff = calculate_backoff(attempt);
+                if attempt <= self.max_retries && self.within_retry_budget(waited + backoff) {
                     warn!(
-                        error = %e,
+                        error = %error,
                         attempt = attempt,
                         max_retries = self.max_retries,
                         "Network error. Retrying in {:?}",
                         backoff
                     );
-                    sleep(backoff).await;
-                    return Box::pin(self.execute_with_retry(method, url, body, attempt + 1)).await;
+                    self.record_retry(call, attempt, backoff);
+                    self.retry_sleep(backoff, slot).await;
+                    return Box::pin(self.send_with_retry(
+                        prepared,
+                        call,
+                        slot,
+                        attempt + 1,
+                        waited + backoff,
+                    ))
+                    .await;
                 }
-                return Err(Error::Http(e));
+                return Err(error);
             }
+            Err(error) => return Err(error),
         };
 
+        meta::record(ResponseMeta::from_response(&response));
+        self.rate_limits.observe(response.headers());
         let status = response.status();
+        call.span.record("status", status.as_u16());
+        call.status.store(status.as_u16(), Ordering::Relaxed);
+        if let Some(circuit) = &self.circuit {
+            if status.is_server_error() {
+                circuit.record_failure(self.clock.now_unix());
+            } else if status.as_u16() != 429 {
+                circuit.record_success();
+            }
+        }
+        if status.as_u16() == 429 {
+            self.health.record(endpoint, Event::RateLimited);
+        }
 
         // Handle rate limiting
-        if status.as_u16() == 429 && attempt <= self.max_retries {
+        if status.as_u16() == 429 {
             let retry_after: u64 = response
                 .headers()
                 .get("Retry-After")
                 .and_then(|v| v.to_str().ok())
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1);
+            let delay = Duration::from_secs(retry_after);
+            if attempt > self.max_retries || !self.within_retry_budget(waited + delay) {
+                let mut error = Error::from_response(response).await;
+                if let Error::RateLimit {
+                    retries,
+                    waited: total,
+                    ..
+                } = &mut error
+                {
+                    *retries = attempt - 1;
+                    *total = waited;
+                }
+                return Err(error);
+            }
             warn!(
                 retry_after = retry_after,
                 attempt = attempt,
                 max_retries = self.max_retries,
                 "Rate limited. Retrying"
             );
-            sleep(Duration::from_secs(retry_after)).await;
-            return Box::pin(self.execute_with_retry(method, url, body, attempt + 1)).await;
+            self.record_retry(call, attempt, delay);
+            self.retry_sleep(delay, slot).await;
+            return Box::pin(self.send_with_retry(
+                prepared,
+                call,
+                slot,
+                attempt + 1,
+                waited + delay,
+            ))
+            .await;
         }
 
         // Handle server errors
-        if status.is_server_error() && attempt <= self.max_retries {
-            let backoff = calculate_backoff(attempt);
+        let backoff = calculate_backoff(attempt);
+        if status.is_server_error()
+            && attempt <= self.max_retries
+            && self.within_retry_budget(waited + backoff)
+        {
             warn!(
                 status = %status,
                 attempt = attempt,
@@ -622,12 +2907,88 @@ impl Client {
                 "Server error. Retrying in {:?}",
                 backoff
             );
-            sleep(backoff).await;
-            return Box::pin(self.execute_with_retry(method, url, body, attempt + 1)).await;
+            self.record_retry(call, attempt, backoff);
+            self.retry_sleep(backoff, slot).await;
+            return Box::pin(self.send_with_retry(
+                prepared,
+                call,
+                slot,
+                attempt + 1,
+                waited + backoff,
+            ))
+            .await;
         }
 
         Ok(response)
     }
+
+    /// Send a request at the end of the middleware chain.
+    async fn send_request(&self, prepared: PreparedRequest) -> Result<reqwest::Response> {
+        let url = prepared.url.as_str();
+        let method: reqwest::Method = prepared
+            .method
+            .parse()
+            .map_err(|_| Error::Config(format!("invalid HTTP method: {}", prepared.method)))?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &prepared.headers {
+            let name: HeaderName = name
+                .parse()
+                .map_err(|_| Error::Config(format!("invalid header name: {}", name)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| Error::Config(format!("invalid value for header {}", name)))?;
+            headers.append(name, value);
+        }
+        // Only send credentials to the configured API.
+        if !headers.contains_key(AUTHORIZATION) && is_api_url(&self.base_url, url) {
+            let api_key = self.api_key.get().await?;
+            let value = HeaderValue::from_str(&format!("Bearer {}", api_key))
+                .map_err(|_| Error::Config("API key contains invalid characters".into()))?;
+            headers.insert(AUTHORIZATION, value);
+        }
+
+        let mut req = self.http_client.request(method, url);
+        req = req.headers(headers);
+        #[cfg(target_arch = "wasm32")]
+        {
+            req = req.timeout(self.request_timeout.unwrap_or(self.timeout));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = self.request_timeout {
+            req = req.timeout(timeout);
+        }
+
+        if let Some(b) = prepared.body {
+            req = req.body(b);
+        }
+
+        req.send().await.map_err(|e| {
+            if e.is_timeout() && is_connect_error(&e) {
+                Error::ConnectTimeout
+            } else if e.is_timeout() {
+                Error::ReadTimeout {
+                    idempotent: is_idempotent(&prepared.method),
+                }
+            } else {
+                Error::Http(e)
+            }
+        })
+    }
+
+    /// Whether retries may wait `waited` in total for one request.
+    fn within_retry_budget(&self, waited: Duration) -> bool {
+        self.max_retry_duration.is_none_or(|max| waited <= max)
+    }
+}
+
+/// Tracing and metrics state of one API call, shared by its attempts.
+struct Call {
+    span: Span,
+    endpoint: String,
+    started: runtime::Instant,
+    attempts: AtomicU32,
+    /// Status of the last response, or zero.
+    status: AtomicU16,
 }
 
 // =============================================================================
@@ -645,16 +3006,101 @@ impl<'a> JobsClient<'a> {
         self.client.list_jobs(limit, offset).await
     }
 
+    /// List jobs matching `filter`; see [`JobFilter`].
+    pub async fn list_filtered(
+        &self,
+        filter: &JobFilter,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<JobList> {
+        self.client.list_jobs_filtered(filter, limit, offset).await
+    }
+
+    /// Stream every job, paging through the listing as needed.
+    pub fn list_all(&self) -> Paginator<Job> {
+        self.client.list_all_jobs()
+    }
+
+    /// Stream every job matching `filter`, paging as needed.
+    pub fn list_all_filtered(&self, filter: JobFilter) -> Paginator<Job> {
+        self.client.list_all_jobs_filtered(filter)
+    }
+
     /// Get a job by ID.
     pub async fn get(&self, id: &str) -> Result<Job> {
         self.client.get_job(id).await
     }
 
+    /// Get counts of a crawl's URLs by state, example URLs in each, and an
+    /// estimate of the time left; see [`JobProgress`].
+    pub async fn progress(&self, id: &str) -> Result<JobProgress> {
+        self.client.get_job_progress(id).await
+    }
+
+    /// List the failed URLs of a job by category, with retry counts; see
+    /// [`JobErrors`].
+    pub async fn get_errors(&self, id: &str) -> Result<JobErrors> {
+        self.client.get_job_errors(id).await
+    }
+
+    /// Cancel a pending or running job.
+    pub async fn cancel(&self, id: &str) -> Result<Job> {
+        self.client.cancel_job(id).await
+    }
+
+    /// Continue a stopped crawl from its last checkpoint; see
+    /// [`Client::resume_job`].
+    pub async fn resume(&self, id: &str) -> Result<Job> {
+        self.client.resume_job(id).await
+    }
+
+    /// Delete a job and its results.
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        self.client.delete_job(id).await
+    }
+
+    /// Start a new crawl with the request that started a failed or finished
+    /// job; see [`Client::rerun_job`].
+    pub async fn retry(&self, id: &str) -> Result<CrawlJobCreated> {
+        self.client.retry_job(id).await
+    }
+
+    /// Start a new crawl with a job's request, changed by `overrides`; see
+    /// [`Client::rerun_job`].
+    pub async fn rerun(&self, id: &str, overrides: RunOptions) -> Result<CrawlJobCreated> {
+        self.client.rerun_job(id, overrides).await
+    }
+
+    /// Poll a job until it is no longer pending or running.
+    ///
+    /// See [`WaitOptions`] for the interval, backoff, time limit, and progress
+    /// callback.
+    pub async fn wait(&self, id: &str, options: WaitOptions) -> Result<Job> {
+        self.client.wait_for_job_with(id, options).await
+    }
+
     /// Get job results.
     pub async fn get_results(&self, id: &str, merge: bool) -> Result<JobResults> {
         self.client.get_job_results(id, merge).await
     }
 
+    /// Stream job results one page at a time, without loading them all into
+    /// memory.
+    pub fn stream_results(&self, id: &str) -> ResultStream {
+        self.client.stream_job_results(id)
+    }
+
+    /// Get job results with each page's data deserialized into `T`.
+    ///
+    /// Pages that do not match `T` are reported individually; see
+    /// [`JobResultsExt::deserialize_each`].
+    pub async fn get_results_as<T: serde::de::DeserializeOwned>(
+        &self,
+        id: &str,
+    ) -> Result<Vec<std::result::Result<T, ResultItemError>>> {
+        self.client.get_job_results_as(id).await
+    }
+
     /// Get a presigned download URL for job results.
     pub async fn download(&self, id: &str) -> Result<GetJobResultsDownloadOutputBody> {
         self.client.download_job(id).await
@@ -677,6 +3123,12 @@ impl<'a> JobsClient<'a> {
     ) -> Result<GetJobWebhookDeliveriesOutputBody> {
         self.client.get_job_webhook_deliveries(id).await
     }
+
+    /// Get aggregate job statistics: jobs per day, success rates, pages per
+    /// job, p95 duration, and cost per page.
+    pub async fn stats(&self, range: StatsRange) -> Result<JobStats> {
+        self.client.get_job_stats(&range).await
+    }
 }
 
 /// Sub-client for schema operations.
@@ -705,6 +3157,32 @@ impl<'a> SchemasClient<'a> {
         self.client.update_schema(id, request).await
     }
 
+    /// Update a schema unless the change would break its consumers.
+    ///
+    /// Fetches the current version and compares it with `request`'s
+    /// [`schema_yaml`](CreateSchemaRequest::schema_yaml). If fields are
+    /// removed or retyped, fails with [`Error::BreakingSchemaChange`] and
+    /// leaves the schema as it was. With `force`, updates without checking.
+    #[cfg(feature = "yaml")]
+    pub async fn update_with_check(
+        &self,
+        id: &str,
+        request: CreateSchemaRequest,
+        force: bool,
+    ) -> Result<Schema> {
+        if !force {
+            let current = self.get(id).await?;
+            let diff = crate::schemas::diff(&current.schema_yaml, &request.schema_yaml)?;
+            if diff.is_breaking() {
+                return Err(Error::BreakingSchemaChange {
+                    schema_id: id.to_string(),
+                    diff,
+                });
+            }
+        }
+        self.update(id, request).await
+    }
+
     /// Delete a schema.
     pub async fn delete(&self, id: &str) -> Result<()> {
         self.client.delete_schema(id).await
@@ -741,6 +3219,49 @@ impl<'a> SitesClient<'a> {
     pub async fn delete(&self, id: &str) -> Result<()> {
         self.client.delete_site(id).await
     }
+
+    /// Start a crawl of a saved site; see [`Client::run_site`].
+    pub async fn run(&self, id: &str, options: RunOptions) -> Result<CrawlJobCreated> {
+        self.client.run_site(id, options).await
+    }
+
+    /// Start a crawl of a saved site and wait for it to finish.
+    ///
+    /// Returns the job in its final state; see [`JobsClient::wait`].
+    pub async fn run_and_wait(
+        &self,
+        id: &str,
+        options: RunOptions,
+        wait: WaitOptions,
+    ) -> Result<Job> {
+        let created = self.run(id, options).await?;
+        self.client.wait_for_job_with(&created.job_id, wait).await
+    }
+
+    /// Run a site on a cron schedule; see [`Client::create_schedule`].
+    pub async fn schedule(&self, id: &str, cron_expr: &str) -> Result<Schedule> {
+        self.client.create_schedule(id, cron_expr).await
+    }
+
+    /// List schedules for all sites.
+    pub async fn list_schedules(&self) -> Result<ScheduleList> {
+        self.client.list_schedules().await
+    }
+
+    /// Pause a schedule.
+    pub async fn pause_schedule(&self, id: &str) -> Result<Schedule> {
+        self.client.pause_schedule(id).await
+    }
+
+    /// Resume a paused schedule.
+    pub async fn resume_schedule(&self, id: &str) -> Result<Schedule> {
+        self.client.resume_schedule(id).await
+    }
+
+    /// Delete a schedule.
+    pub async fn delete_schedule(&self, id: &str) -> Result<()> {
+        self.client.delete_schedule(id).await
+    }
 }
 
 /// Sub-client for API key operations.
@@ -781,6 +3302,12 @@ impl<'a> LlmClient<'a> {
         self.client.list_models(provider).await
     }
 
+    /// List the models of every provider, with context window, pricing,
+    /// capabilities, and deprecation; see [`Model`].
+    pub async fn list_all_models(&self) -> Result<Vec<Model>> {
+        self.client.list_all_models().await
+    }
+
     /// List configured LLM keys.
     pub async fn list_keys(&self) -> Result<LlmKeyList> {
         self.client.list_llm_keys().await
@@ -791,6 +3318,17 @@ impl<'a> LlmClient<'a> {
         self.client.upsert_llm_key(request).await
     }
 
+    /// Check the stored key for `provider`: whether it works, the latency of
+    /// a test call, and the models it can use.
+    pub async fn test_key(&self, provider: &str) -> Result<LlmKeyTest> {
+        self.client.test_llm_key(provider).await
+    }
+
+    /// Check a key before saving it with [`upsert_key`](Self::upsert_key).
+    pub async fn test_key_raw(&self, request: UpsertLlmKeyRequest) -> Result<LlmKeyTest> {
+        self.client.test_llm_key_raw(request).await
+    }
+
     /// Delete an LLM key.
     pub async fn delete_key(&self, id: &str) -> Result<()> {
         self.client.delete_llm_key(id).await
@@ -801,25 +3339,98 @@ impl<'a> LlmClient<'a> {
         self.client.get_llm_chain().await
     }
 
+    /// Check a fallback chain against the configured keys and each
+    /// provider's models before [`set_chain`](Self::set_chain); see
+    /// [`Client::validate_llm_chain`].
+    pub async fn validate_chain(&self, chain: Vec<LlmChainEntry>) -> Result<Vec<ChainProblem>> {
+        self.client.validate_llm_chain(chain).await
+    }
+
     /// Set the LLM fallback chain.
     pub async fn set_chain(&self, chain: Vec<LlmChainEntry>) -> Result<()> {
         self.client.set_llm_chain(chain).await
     }
 }
 
-/// Sub-client for webhook operations.
-pub struct WebhooksClient<'a> {
+/// Sub-client for usage and spend reporting.
+pub struct UsageClient<'a> {
     client: &'a Client,
 }
 
-impl<'a> WebhooksClient<'a> {
-    /// List all webhooks.
-    pub async fn list(&self) -> Result<ListWebhooksOutputBody> {
-        self.client.list_webhooks().await
+impl<'a> UsageClient<'a> {
+    /// Get usage totals for the current billing period.
+    pub async fn get(&self) -> Result<GetUsageOutputBody> {
+        self.client.get_usage().await
     }
 
-    /// Get a webhook by ID.
-    pub async fn get(&self, id: &str) -> Result<WebhookResponse> {
+    /// Get a snapshot of the quota left for new work.
+    pub async fn remaining_quota(&self) -> Result<QuotaSnapshot> {
+        self.client.remaining_quota().await
+    }
+
+    /// Get daily jobs, tokens, and cost for a period, optionally in one
+    /// series per API key, model, or job type; see [`UsageQuery`].
+    pub async fn breakdown(&self, query: &UsageQuery) -> Result<UsageBreakdown> {
+        self.client.get_usage_breakdown(query).await
+    }
+
+    /// Get the account's monthly budget and spend against it.
+    pub async fn get_budget(&self) -> Result<Budget> {
+        self.client.get_budget().await
+    }
+
+    /// Set the monthly budget in USD and the shares of it at which to alert;
+    /// see [`Client::set_budget`].
+    pub async fn set_budget(&self, monthly_usd: f64, alert_thresholds: &[f64]) -> Result<Budget> {
+        self.client.set_budget(monthly_usd, alert_thresholds).await
+    }
+}
+
+/// Sub-client for organization and member management.
+pub struct OrgClient<'a> {
+    client: &'a Client,
+}
+
+impl<'a> OrgClient<'a> {
+    /// Get the account's organization.
+    pub async fn get(&self) -> Result<Organization> {
+        self.client.get_org().await
+    }
+
+    /// List the members of the organization.
+    pub async fn list_members(&self) -> Result<Vec<OrgMember>> {
+        self.client.list_org_members().await
+    }
+
+    /// Invite `email` to the organization with `role`.
+    pub async fn invite(&self, email: &str, role: OrgRole) -> Result<OrgInvitation> {
+        self.client.invite_org_member(email, role).await
+    }
+
+    /// Remove a member from the organization.
+    pub async fn remove_member(&self, id: &str) -> Result<()> {
+        self.client.remove_org_member(id).await
+    }
+
+    /// Change a member's role.
+    pub async fn update_role(&self, id: &str, role: OrgRole) -> Result<OrgMember> {
+        self.client.update_org_member_role(id, role).await
+    }
+}
+
+/// Sub-client for webhook operations.
+pub struct WebhooksClient<'a> {
+    client: &'a Client,
+}
+
+impl<'a> WebhooksClient<'a> {
+    /// List all webhooks.
+    pub async fn list(&self) -> Result<ListWebhooksOutputBody> {
+        self.client.list_webhooks().await
+    }
+
+    /// Get a webhook by ID.
+    pub async fn get(&self, id: &str) -> Result<WebhookResponse> {
         self.client.get_webhook(id).await
     }
 
@@ -838,6 +3449,11 @@ impl<'a> WebhooksClient<'a> {
         self.client.delete_webhook(id).await
     }
 
+    /// Send a test event to a webhook.
+    pub async fn test(&self, id: &str) -> Result<WebhookDeliveryResponse> {
+        self.client.test_webhook(id).await
+    }
+
     /// List webhook deliveries.
     pub async fn list_deliveries(
         &self,
@@ -852,6 +3468,7 @@ impl<'a> WebhooksClient<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types_ext::JobStatus;
 
     #[test]
     fn test_client_builder_requires_api_key() {
@@ -864,6 +3481,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_api_url() {
+        let base = "https://api.refyne.uk";
+        assert!(is_api_url(base, "https://api.refyne.uk/api/v1/jobs"));
+        assert!(is_api_url(base, "https://api.refyne.uk:443/health"));
+        assert!(!is_api_url(
+            base,
+            "https://api.refyne.uk.evil.com/api/v1/jobs"
+        ));
+        assert!(!is_api_url(base, "https://api.refyne.uk@evil.com/x"));
+        assert!(!is_api_url(base, "http://api.refyne.uk/api/v1/jobs"));
+        assert!(!is_api_url(base, "https://api.refyne.uk:8443/api/v1/jobs"));
+
+        let base = "https://gateway.example.com/refyne";
+        assert!(is_api_url(
+            base,
+            "https://gateway.example.com/refyne/api/v1/jobs"
+        ));
+        assert!(!is_api_url(
+            base,
+            "https://gateway.example.com/refyne-other/x"
+        ));
+        assert!(!is_api_url(base, "https://gateway.example.com/other"));
+    }
+
+    #[test]
+    fn test_client_debug_redacts_credentials() {
+        let client = Client::builder("sk-live-client")
+            .base_url("https://refyne.example.com")
+            .default_llm_config(LLMConfigInput {
+                api_key: Some("sk-or-provider".into()),
+                model: Some("some/model".into()),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let debug = format!("{:?}", client);
+        assert!(debug.contains("https://refyne.example.com"));
+        assert!(debug.contains("some/model"));
+        assert!(!debug.contains("sk-live-client"));
+        assert!(!debug.contains("sk-or-provider"));
+    }
+
+    #[test]
+    fn test_client_builder_tls_settings() {
+        const TEST_CA: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIBiTCCAS+gAwIBAgIUWKK0cTopG8/bLsS+J5lLpJ3eZ8IwCgYIKoZIzj0EAwIw\n\
+GTEXMBUGA1UEAwwOcmVmeW5lLXRlc3QtY2EwIBcNMjYxMDE2MDkxMjQyWhgPMjEy\n\
+NjA5MjIwOTEyNDJaMBkxFzAVBgNVBAMMDnJlZnluZS10ZXN0LWNhMFkwEwYHKoZI\n\
+zj0CAQYIKoZIzj0DAQcDQgAEvEAavHSLRdVqIxU4q97Df/tu9uqRCjVFSS3vdC8W\n\
+bndZoUznmPrbzDELwHKSfdHLhb6Q1mOD02bka4vadeOEwqNTMFEwHQYDVR0OBBYE\n\
+FGRVFU4Q0gp9AZzHi1ZqvLbhuSAiMB8GA1UdIwQYMBaAFGRVFU4Q0gp9AZzHi1Zq\n\
+vLbhuSAiMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIgdFF8DGJU\n\
+gn6lAJEStpHvxyE8MNU3o2X9qjB/JWC/dMECIQDCwP30OM6rSYoeEi1eC7qvDaKN\n\
+oH3klO78/v4jJZ6Gow==\n\
+-----END CERTIFICATE-----\n\
+";
+        let certificate = reqwest::Certificate::from_pem(TEST_CA.as_bytes()).unwrap();
+        let builder = ClientBuilder::new("test-key").add_root_certificate(certificate);
+        assert_eq!(builder.root_certificates.len(), 1);
+        assert!(builder.build().is_ok());
+
+        #[cfg(feature = "danger-insecure-tls")]
+        {
+            let builder = ClientBuilder::new("test-key").danger_accept_invalid_certs(true);
+            assert!(builder.accept_invalid_certs);
+            assert!(builder.build().is_ok());
+        }
+    }
+
     #[test]
     fn test_client_builder_default_values() {
         let builder = ClientBuilder::new("test-key");
@@ -899,6 +3587,80 @@ mod tests {
         assert_eq!(builder.timeout, Duration::from_secs(60));
     }
 
+    #[test]
+    fn test_builder_from_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        let builder = ClientBuilder::from_lookup(env(&[
+            ("REFYNE_API_KEY", "env-key"),
+            ("REFYNE_BASE_URL", "https://refyne.example.com/"),
+            ("REFYNE_TIMEOUT", "2.5"),
+            ("REFYNE_MAX_RETRIES", "7"),
+        ]))
+        .unwrap();
+        assert_eq!(builder.api_key, "env-key");
+        assert_eq!(builder.base_url, "https://refyne.example.com");
+        assert_eq!(builder.timeout, Duration::from_millis(2500));
+        assert_eq!(builder.max_retries, 7);
+
+        let builder = ClientBuilder::from_lookup(env(&[
+            ("REFYNE_API_KEY", "env-key"),
+            ("REFYNE_TIMEOUT", ""),
+        ]))
+        .unwrap();
+        assert_eq!(builder.base_url, DEFAULT_BASE_URL);
+        assert_eq!(builder.timeout, Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+
+        let message = |result: Result<ClientBuilder>| match result {
+            Err(Error::Config(message)) => message,
+            _ => panic!("expected a config error"),
+        };
+        assert_eq!(
+            message(ClientBuilder::from_lookup(env(&[]))),
+            "REFYNE_API_KEY is not set"
+        );
+        assert!(message(ClientBuilder::from_lookup(env(&[
+            ("REFYNE_API_KEY", "env-key"),
+            ("REFYNE_TIMEOUT", "soon"),
+        ])))
+        .starts_with("REFYNE_TIMEOUT"));
+        assert!(message(ClientBuilder::from_lookup(env(&[
+            ("REFYNE_API_KEY", "env-key"),
+            ("REFYNE_MAX_RETRIES", "-1"),
+        ])))
+        .starts_with("REFYNE_MAX_RETRIES"));
+    }
+
+    #[test]
+    fn test_require_https() {
+        let result = Client::builder("test-key")
+            .base_url("http://localhost:8080")
+            .require_https(true)
+            .build();
+        assert!(matches!(result, Err(Error::Config(_))));
+
+        assert!(Client::builder("test-key")
+            .base_url("http://localhost:8080")
+            .require_https(false)
+            .build()
+            .is_ok());
+        assert!(Client::builder("test-key")
+            .require_https(true)
+            .build()
+            .is_ok());
+
+        assert!(env_flag(Some("TRUE")));
+        assert!(env_flag(Some("1")));
+        assert!(!env_flag(Some("0")));
+        assert!(!env_flag(None));
+    }
+
     #[test]
     fn test_client_builder_custom_max_retries() {
         let builder = ClientBuilder::new("test-key").max_retries(5);
@@ -942,6 +3704,3775 @@ mod tests {
         let _ = client.webhooks();
     }
 
+    #[test]
+    fn test_user_message() {
+        let client = Client::builder("test-key")
+            .error_messages(|code| match code {
+                ErrorCode::RateLimited => Some("Trop de requêtes".into()),
+                _ => None,
+            })
+            .build()
+            .unwrap();
+
+        let rate_limited = Error::RateLimit {
+            retry_after: 5,
+            message: "Too many requests".into(),
+            retries: 0,
+            waited: Duration::ZERO,
+            request_id: None,
+        };
+        assert_eq!(client.user_message(&rate_limited), "Trop de requêtes");
+        assert!(format!("{:?}", rate_limited).contains("Too many requests"));
+        assert_eq!(client.user_message(&Error::Timeout), "Request timed out");
+    }
+
+    #[test]
+    fn test_client_builder_duplicate_crawl_policy() {
+        let builder = ClientBuilder::new("test-key");
+        assert_eq!(builder.duplicate_crawl_policy, DuplicateCrawlPolicy::Allow);
+
+        let client = ClientBuilder::new("test-key")
+            .duplicate_crawl_policy(DuplicateCrawlPolicy::Reject)
+            .build()
+            .unwrap();
+        assert_eq!(client.duplicate_crawl_policy, DuplicateCrawlPolicy::Reject);
+    }
+
+    #[test]
+    fn test_analyze_page_type_suggestions() {
+        let response: AnalyzeResponse = crate::golden::load("0.1.80", "analyze_depth1")
+            .and_then(|v| Ok(serde_json::from_value(v)?))
+            .unwrap();
+        let suggestions = response.suggestions();
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[1].page_type, "detail");
+
+        let request = suggestions[1].to_site_request("https://example.com", Some("Shop".into()));
+        assert_eq!(request.url, "https://example.com");
+        let analysis = request.analysis_result.unwrap();
+        assert_eq!(analysis.page_type.as_deref(), Some("detail"));
+        assert_eq!(
+            analysis.follow_patterns.unwrap()[0]["pattern"],
+            "/products/*"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_site() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/analyze"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "analyze_depth1").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/schemas"))
+            .and(body_partial_json(serde_json::json!({"name": "Shop"})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "schema").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/sites"))
+            .and(body_partial_json(serde_json::json!({
+                "default_schema_id": "01JC4X9V1B3N5M7Q9S1U3W5Y7A",
+                "fetch_mode": "static",
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "created_at": "2026-01-12T09:30:00Z", "default_schema_id": "01JC4X9V1B3N5M7Q9S1U3W5Y7A",
+                "domain": "example.com", "fetch_mode": "static", "id": "site-1", "name": "Shop",
+                "updated_at": "2026-01-12T09:30:00Z", "url": "https://example.com", "user_id": "u",
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let created = client
+            .bootstrap_site("https://example.com", "Shop")
+            .await
+            .unwrap();
+        assert_eq!(created.analysis.page_type, "listing");
+        assert_eq!(created.schema.name, "products");
+        assert_eq!(created.site.id, "site-1");
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_site_deletes_schema_when_site_fails() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/analyze"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "analyze_depth1").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/schemas"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "schema").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/sites"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "title": "Bad Request", "detail": "invalid url",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/schemas/01JC4X9V1B3N5M7Q9S1U3W5Y7A"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let result = client.bootstrap_site("https://example.com", "Shop").await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prepare_and_send_prepared() {
+        use wiremock::matchers::{body_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/analyze"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(header("x-signature", "signed"))
+            .and(body_json(serde_json::json!({"url": "https://example.com"})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "analyze_depth1").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let mut prepared = client
+            .prepare(&AnalyzeRequest {
+                url: "https://example.com".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(prepared.method, "POST");
+        assert_eq!(prepared.url, format!("{}/api/v1/analyze", server.uri()));
+        assert_eq!(prepared.header("Content-Type"), Some("application/json"));
+        assert!(prepared.header("Authorization").is_none());
+
+        prepared.set_header("X-Signature", "signed");
+        let response: AnalyzeResponse = client.send_prepared(&prepared).await.unwrap();
+        assert_eq!(response.page_type, "listing");
+    }
+
+    #[tokio::test]
+    async fn test_extract_many_isolates_failures() {
+        use futures::StreamExt;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(
+                serde_json::json!({"url": "https://missing.example"}),
+            ))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "extract").unwrap()),
+            )
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let requests = [
+            "https://a.example",
+            "https://missing.example",
+            "https://b.example",
+            "https://c.example",
+        ]
+        .into_iter()
+        .map(|url| ExtractRequest {
+            url: url.into(),
+            schema: serde_json::json!({"title": "string"}),
+            ..Default::default()
+        })
+        .collect();
+
+        let mut results = client.extract_many(requests, 2);
+        let mut failed = Vec::new();
+        while let Some(item) = results.next().await {
+            if let Err(e) = item.result {
+                assert!(matches!(e, Error::NotFound { .. }));
+                failed.push((item.index, item.url));
+            }
+        }
+        assert_eq!(failed, [(1, "https://missing.example".to_string())]);
+        assert_eq!(results.usage().succeeded, 3);
+        assert_eq!(results.usage().failed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_extract_batch_and_retry_failures() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let extracted = |url: &str| {
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {}, "fetched_at": "2024-01-01T00:00:00Z",
+                "input_format": "schema", "job_id": "job-1", "url": url,
+                "metadata": {"extract_duration_ms": 1, "fetch_duration_ms": 1, "model": "m", "provider": "p"},
+                "usage": {"cost_usd": 0.0, "input_tokens": 1, "is_byok": false, "llm_cost_usd": 0.0, "output_tokens": 1},
+            }))
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(
+                serde_json::json!({"url": "https://ok.example"}),
+            ))
+            .respond_with(extracted("https://ok.example"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(
+                serde_json::json!({"url": "https://blocked.example"}),
+            ))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(
+                serde_json::json!({"url": "https://flaky.example"}),
+            ))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(
+                serde_json::json!({"url": "https://flaky.example"}),
+            ))
+            .respond_with(extracted("https://flaky.example"))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let requests = [
+            "https://ok.example",
+            "https://blocked.example",
+            "https://flaky.example",
+        ]
+        .into_iter()
+        .map(|url| ExtractRequest {
+            url: url.into(),
+            schema: serde_json::json!({"title": "string"}),
+            ..Default::default()
+        })
+        .collect();
+
+        let batch = client.extract_batch(requests).await;
+        assert_eq!(batch.succeeded.len(), 1);
+        assert_eq!(
+            batch.failures.urls(FailureKind::Retryable),
+            vec!["https://flaky.example"]
+        );
+        assert_eq!(
+            batch.failures.urls(FailureKind::Blocked),
+            vec!["https://blocked.example"]
+        );
+
+        let policy = RetryPolicy {
+            delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let retried = client.retry_failures(batch.failures, policy).await;
+        assert_eq!(retried.succeeded[0].url, "https://flaky.example");
+        assert_eq!(retried.failures.len(), 1);
+        assert_eq!(retried.failures.count(FailureKind::Blocked), 1);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn test_job_results_msgpack_with_json_fallback() {
+        use wiremock::matchers::{headers, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let results = serde_json::json!({"results": [{"title": "Hello"}]});
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/packed/results"))
+            .and(headers(
+                "accept",
+                vec!["application/msgpack", "application/json;q=0.9"],
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                rmp_serde::to_vec_named(&results).unwrap(),
+                "application/msgpack",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/plain/results"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(results.clone()))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .prefer_msgpack(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.get_job_results("packed", false).await.unwrap(),
+            results
+        );
+        assert_eq!(
+            client.get_job_results("plain", false).await.unwrap(),
+            results
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_policy_and_canonical_url() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/moved"))
+            .respond_with(
+                ResponseTemplate::new(301).insert_header("Location", "/api/v1/jobs/other"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .redirect(Policy::none())
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.get_job("moved").await,
+            Err(Error::Api { status: 301, .. })
+        ));
+
+        let mut fixture = crate::golden::load("0.1.80", "extract").unwrap();
+        let response: ExtractResponse = serde_json::from_value(fixture.clone()).unwrap();
+        assert_eq!(response.canonical_url(), response.url);
+        assert!(!response.was_redirected());
+
+        fixture["metadata"]["final_url"] = "https://example.com/p/pour-over-set".into();
+        fixture["metadata"]["redirect_chain"] = serde_json::json!([
+            "https://example.com/products/pour-over",
+            "https://example.com/p/pour-over-set"
+        ]);
+        let response: ExtractResponse = serde_json::from_value(fixture).unwrap();
+        assert_eq!(
+            response.canonical_url(),
+            "https://example.com/p/pour-over-set"
+        );
+        assert!(response.was_redirected());
+    }
+
+    #[tokio::test]
+    async fn test_remaining_quota() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-RateLimit-Remaining", "42")
+                    .insert_header("X-RateLimit-Limit", "60")
+                    .insert_header("X-RateLimit-Reset", "20")
+                    .set_body_json(serde_json::json!({
+                        "byok_jobs": 0, "total_charged_usd": 7.5, "total_jobs": 90,
+                    })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .quota_limits(QuotaLimits {
+                jobs: Some(100),
+                budget_usd: Some(10.0),
+            })
+            .build()
+            .unwrap();
+        let quota = client.remaining_quota().await.unwrap();
+        assert_eq!(quota.requests_remaining, Some(42));
+        assert_eq!(quota.requests_limit, Some(60));
+        assert_eq!(quota.jobs_remaining, Some(10));
+        assert_eq!(quota.budget_remaining_usd, Some(2.5));
+        assert_eq!(quota.capacity(), Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_mock_clock_drives_backoff_and_cache_expiry() {
+        use crate::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=60")
+                    .set_body_json(serde_json::json!({
+                        "byok_jobs": 0, "total_charged_usd": 1.0, "total_jobs": 3,
+                    })),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(1_000));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(2)
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        client.get_usage().await.unwrap();
+        let sleeps = clock.sleeps();
+        assert_eq!(sleeps.len(), 2);
+        assert!(sleeps[0] >= Duration::from_secs(1) && sleeps[0] <= Duration::from_millis(1_250));
+        assert!(sleeps[1] >= Duration::from_secs(2) && sleeps[1] <= Duration::from_millis(2_500));
+
+        // Served from cache until the clock passes max-age.
+        client.get_usage().await.unwrap();
+        clock.advance(Duration::from_secs(61));
+        client.get_usage().await.unwrap();
+    }
+
+    #[cfg(feature = "snapshot")]
+    #[tokio::test]
+    async fn test_export_account_snapshot() {
+        use crate::snapshot::{inspect_snapshot, SnapshotSection};
+        use serde_json::json;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let ok = |body: serde_json::Value| ResponseTemplate::new(200).set_body_json(body);
+        // The server caps pages at 50 jobs, below the requested 100.
+        let jobs = |ids: std::ops::Range<usize>| -> Vec<_> {
+            ids.map(|i| json!({"id": format!("job-{}", i)})).collect()
+        };
+        for (route, body) in [
+            ("/api/v1/schemas", json!({"schemas": [{"id": "schema-1"}]})),
+            (
+                "/api/v1/sites",
+                json!({"sites": [{"id": "site-1"}, {"id": "site-2"}]}),
+            ),
+            ("/api/v1/usage", json!({"total_jobs": 101})),
+            ("/api/v1/webhooks", json!({"webhooks": null})),
+            (
+                "/api/v1/llm/chain",
+                json!({"chain": [{"provider": "openai"}]}),
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(route))
+                .respond_with(ok(body))
+                .mount(&server)
+                .await;
+        }
+        for (offset, page) in [
+            ("0", jobs(0..50)),
+            ("50", jobs(50..100)),
+            ("100", jobs(100..101)),
+        ] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/jobs"))
+                .and(query_param("offset", offset))
+                .respond_with(ok(json!({"jobs": page})))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs"))
+            .and(query_param("offset", "101"))
+            .respond_with(ok(json!({"jobs": []})))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let file = std::env::temp_dir().join(format!("refyne-export-{}.gz", std::process::id()));
+        let summary = client.export_account_snapshot(&file).await.unwrap();
+        assert_eq!(summary.counts[&SnapshotSection::Job], 101);
+
+        let snapshot = inspect_snapshot(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(snapshot.header.base_url, server.uri());
+        assert_eq!(snapshot.count(SnapshotSection::Schema), 1);
+        assert_eq!(snapshot.count(SnapshotSection::Site), 2);
+        assert_eq!(snapshot.count(SnapshotSection::Job), 101);
+        assert_eq!(snapshot.count(SnapshotSection::Usage), 1);
+        assert_eq!(snapshot.count(SnapshotSection::Webhook), 0);
+        assert_eq!(snapshot.count(SnapshotSection::LlmChain), 1);
+    }
+
+    #[tokio::test]
+    async fn test_progress_sources() {
+        use crate::clock::MockClock;
+        use crate::progress::{Polling, ServerSentEvents, WebhookProgress};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let job = |status: &str| {
+            serde_json::json!({
+                "id": "job-1", "status": status, "type": "crawl",
+                "url": "https://example.com", "capture_debug": false, "cost_usd": 0.0,
+                "created_at": "2024-01-01T00:00:00Z", "page_count": 2, "queue_position": 0,
+                "token_usage_input": 0, "token_usage_output": 0, "urls_queued": 0,
+            })
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/crawl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "job_id": "job-1",
+                "status": "pending",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(job("running")))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(job("completed")))
+            .mount(&server)
+            .await;
+        let events = format!(
+            ": ping\n\ndata: {}\n\ndata: {}\n\n",
+            job("running"),
+            job("completed")
+        );
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(events, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(0));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let request = CrawlRequest {
+            url: "https://example.com".into(),
+            schema: serde_json::json!({"title": "string"}),
+            ..Default::default()
+        };
+
+        let mut polling = Polling::every(Duration::from_secs(5));
+        let done = client.crawl_and_wait(request, &mut polling).await.unwrap();
+        assert_eq!(done.status, "completed");
+        assert_eq!(clock.sleeps(), vec![Duration::from_secs(5); 2]);
+
+        let mut statuses = Vec::new();
+        let mut sse = ServerSentEvents::new("/api/v1/jobs/{id}/events");
+        client
+            .watch_job("job-1", &mut sse, |job| statuses.push(job.status.clone()))
+            .await
+            .unwrap();
+        assert_eq!(statuses, vec!["running", "completed"]);
+
+        let (feed, mut webhooks) = WebhookProgress::channel();
+        let mut other: Job = serde_json::from_value(job("completed")).unwrap();
+        other.id = "job-2".into();
+        assert!(feed.push(other));
+        assert!(feed.push(serde_json::from_value(job("failed")).unwrap()));
+        let done = client.wait_for_job("job-1", &mut webhooks).await.unwrap();
+        assert_eq!(done.status, "failed");
+    }
+
+    #[tokio::test]
+    async fn test_extract_saved_schema_fields() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_json(serde_json::json!({
+                "url": "https://shop.example/item",
+                "schema_id": "schema-1",
+                "fields": ["price", "stock"],
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "extract").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let request = ExtractRequest::with_saved_schema("https://shop.example/item", "schema-1")
+            .only_fields(["price", "stock"]);
+        assert!(client.extract(request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_job_stats() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/stats"))
+            .and(query_param("range", "7d"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "from": "2026-01-01",
+                "to": "2026-01-07",
+                "totals": {
+                    "jobs": 10, "succeeded": 9, "failed": 1, "pages": 50,
+                    "cost_usd": 5.0, "p95_duration_ms": 30000,
+                },
+                "daily": [{
+                    "date": "2026-01-07", "jobs": 10, "succeeded": 9, "failed": 1,
+                    "pages": 50, "cost_usd": 5.0, "p95_duration_ms": 30000,
+                }],
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let stats = client.jobs().stats(StatsRange::Last7Days).await.unwrap();
+        assert_eq!(stats.totals.success_rate(), Some(0.9));
+        assert_eq!(stats.totals.cost_per_page(), Some(0.1));
+        assert_eq!(stats.jobs_per_day(), vec![("2026-01-07", 10)]);
+    }
+
+    #[tokio::test]
+    async fn test_job_progress() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1/progress"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "job_id": "job-1",
+                "status": "running",
+                "discovered": 20, "queued": 8, "in_flight": 2,
+                "succeeded": 9, "failed": 1, "skipped": 0,
+                "samples": {
+                    "in_flight": ["https://example.com/a", "https://example.com/b"],
+                    "failed": ["https://example.com/broken"],
+                },
+                "eta_seconds": 45,
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let progress = client.jobs().progress("job-1").await.unwrap();
+        assert_eq!(progress.fraction_complete(), Some(0.5));
+        assert_eq!(progress.samples.in_flight.len(), 2);
+        assert_eq!(progress.eta(), Some(Duration::from_secs(45)));
+    }
+
+    #[tokio::test]
+    async fn test_job_errors() {
+        use crate::job_errors::UrlErrorCategory;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1/errors"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "job_id": "job-1",
+                "errors": [{
+                    "url": "https://example.com/products/9",
+                    "category": "schema_mismatch",
+                    "message": "price: expected number",
+                    "retries": 1,
+                    "failed_at": "2026-01-12T09:15:10Z",
+                }],
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let errors = client.jobs().get_errors("job-1").await.unwrap();
+        assert_eq!(errors.errors[0].category, UrlErrorCategory::SchemaMismatch);
+        assert_eq!(errors.errors[0].retries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_resume_job_sends_checkpoint() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let mut job = crate::golden::load("0.1.80", "job").unwrap();
+        job["status"] = "cancelled".into();
+        job["resume_token"] = "ckpt-42".into();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .mount(&server)
+            .await;
+        let mut resumed = job.clone();
+        resumed["status"] = "running".into();
+        Mock::given(method("POST"))
+            .and(path("/api/v1/jobs/job-1/resume"))
+            .and(body_json(serde_json::json!({"resume_token": "ckpt-42"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&resumed))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let job = client.jobs().resume("job-1").await.unwrap();
+        assert_eq!(job.job_status(), JobStatus::Running);
+        assert!(!job.can_resume());
+    }
+
+    #[tokio::test]
+    async fn test_resume_job_needs_checkpoint() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let mut job = crate::golden::load("0.1.80", "job").unwrap();
+        job["status"] = "failed".into();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let err = client.jobs().resume("job-1").await.unwrap_err();
+        assert!(matches!(err, Error::Config(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_estimate() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/estimate"))
+            .and(body_json(serde_json::json!({
+                "url": "https://example.com",
+                "schema": {"title": "string"},
+                "sample_pages": 2,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "pages": {"low": 1, "high": 1},
+                "sampled_pages": 2,
+                "schema_fields": 1,
+                "model": "gpt-4o-mini",
+                "input_tokens": {"low": 3000, "high": 4500},
+                "output_tokens": {"low": 40, "high": 80},
+                "cost_usd": {"low": 0.002, "high": 0.004},
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let request = EstimateRequest::extract("https://example.com")
+            .schema(serde_json::json!({"title": "string"}))
+            .sample_pages(2);
+        let estimate = client.estimate(request).await.unwrap();
+        assert_eq!(estimate.input_tokens.high, 4500);
+        assert!(estimate.within_budget(0.01));
+        assert!(!estimate.within_budget(0.003));
+    }
+
+    #[tokio::test]
+    async fn test_usage_breakdown() {
+        use crate::usage::UsageGroupBy;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage/breakdown"))
+            .and(query_param("from", "2026-01-01"))
+            .and(query_param("to", "2026-01-02"))
+            .and(query_param("group_by", "model"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "from": "2026-01-01",
+                "to": "2026-01-02",
+                "series": [{
+                    "key": "gpt-4o-mini",
+                    "totals": {"jobs": 3, "input_tokens": 9000, "output_tokens": 300, "cost_usd": 1.5},
+                    "points": [
+                        {"date": "2026-01-01", "jobs": 1, "cost_usd": 0.5},
+                        {"date": "2026-01-02", "jobs": 2, "cost_usd": 1.0},
+                    ],
+                }, {
+                    "key": "claude-haiku",
+                    "totals": {"jobs": 1, "cost_usd": 0.25},
+                }],
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let query = UsageQuery::new("2026-01-01", "2026-01-02").group_by(UsageGroupBy::Model);
+        let usage = client.usage().breakdown(&query).await.unwrap();
+        assert_eq!(usage.total_cost_usd(), 1.75);
+        assert_eq!(usage.get("gpt-4o-mini").unwrap().points.len(), 2);
+        assert!(usage.get("claude-haiku").unwrap().points.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_budget() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/usage/budget"))
+            .and(body_json(serde_json::json!({
+                "monthly_usd": 500.0,
+                "alert_thresholds": [0.5, 0.9],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "monthly_usd": 500.0,
+                "alert_thresholds": [0.5, 0.9],
+                "spent_usd": 125.0,
+                "period_end": "2026-02-01T00:00:00Z",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let budget = client.usage().set_budget(500.0, &[0.5, 0.9]).await.unwrap();
+        assert_eq!(budget.remaining_usd(), Some(375.0));
+
+        let err = client.usage().set_budget(500.0, &[90.0]).await.unwrap_err();
+        assert!(matches!(err, Error::Config(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_budget_exceeded_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+                "error": "Monthly budget of $500.00 reached",
+                "code": "budget_exceeded",
+                "monthly_usd": 500.0,
+                "spent_usd": 500.12,
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        let err = client
+            .extract(ExtractRequest::builder("https://example.com").build())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::BudgetExceeded {
+                    monthly_usd: Some(500.0),
+                    ..
+                }
+            ),
+            "{:?}",
+            err
+        );
+        assert_eq!(err.code(), ErrorCode::BudgetExceeded);
+        assert!(err.is_quota());
+        assert!(!err.is_retryable());
+
+        // A bare 402 is a spent budget; a more specific quota code is kept.
+        Mock::given(method("POST"))
+            .and(path("/api/v1/analyze"))
+            .respond_with(
+                ResponseTemplate::new(402)
+                    .set_body_json(serde_json::json!({"error": "Payment required"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+                "error": "Out of credits",
+                "code": "insufficient_credits",
+            })))
+            .mount(&server)
+            .await;
+        let err = client.analyze(AnalyzeRequest::default()).await.unwrap_err();
+        assert!(matches!(err, Error::BudgetExceeded { .. }), "{:?}", err);
+        let err = client.get_usage().await.unwrap_err();
+        assert!(matches!(err, Error::Api { status: 402, .. }), "{:?}", err);
+        assert!(err.is_quota());
+    }
+
+    #[tokio::test]
+    async fn test_llm_key_test() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/llm/keys/test"))
+            .and(body_json(serde_json::json!({"provider": "openai"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "provider": "openai",
+                "valid": true,
+                "latency_ms": 180,
+                "models": ["gpt-4o", "gpt-4o-mini"],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/llm/keys/test"))
+            .and(body_json(serde_json::json!({
+                "provider": "anthropic",
+                "api_key": "sk-bad",
+                "is_enabled": true,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "provider": "anthropic",
+                "valid": false,
+                "message": "invalid x-api-key",
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let stored = client.llm().test_key("openai").await.unwrap();
+        assert!(stored.valid && stored.has_model("gpt-4o-mini"));
+        assert_eq!(stored.latency(), Some(Duration::from_millis(180)));
+
+        let request = UpsertLlmKeyRequest::builder("anthropic")
+            .api_key("sk-bad")
+            .build();
+        let raw = client.llm().test_key_raw(request).await.unwrap();
+        assert!(!raw.valid);
+        assert!(raw.models.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_all_models() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/llm/providers"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"providers": ["openai", "ollama"]})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/llm/models/openai"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [{
+                    "id": "gpt-4o-mini",
+                    "name": "GPT-4o mini",
+                    "context_window": 128000,
+                    "input_price_per_token": 0.00000015,
+                    "output_price_per_token": 0.0000006,
+                    "supports_vision": true,
+                    "supports_json_mode": true,
+                }, {
+                    "id": "gpt-3.5-turbo",
+                    "name": "GPT-3.5 Turbo",
+                    "deprecated": true,
+                    "sunset_at": "2026-06-30",
+                }],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/llm/models/ollama"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [{"id": "llama3", "name": "Llama 3", "context_size": 8192}],
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let models = client.llm().list_all_models().await.unwrap();
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[0].provider, "openai");
+        assert_eq!(models[0].cost_usd(1_000_000, 0), Some(0.15));
+        assert!(models[1].deprecated && models[1].cost_usd(1, 1).is_none());
+        assert_eq!(models[2].context_window, Some(8192));
+
+        let vision: Vec<_> = models
+            .iter()
+            .filter(|m| m.supports_vision && !m.deprecated)
+            .map(Model::chain_entry)
+            .collect();
+        assert_eq!(vision.len(), 1);
+        assert_eq!(vision[0].model, "gpt-4o-mini");
+    }
+
+    #[tokio::test]
+    async fn test_validate_llm_chain() {
+        use crate::llm::ChainProblemKind;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/llm/providers"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"providers": ["openai", "ollama"]})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/llm/keys"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "keys": [{
+                    "id": "k1", "provider": "openai", "has_key": true, "is_enabled": true,
+                    "created_at": "2026-01-12T09:00:00Z", "updated_at": "2026-01-12T09:00:00Z",
+                }],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/llm/models/openai"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [{"id": "gpt-4o-mini", "name": "GPT-4o mini"}],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/llm/models/ollama"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [{"id": "llama3", "name": "Llama 3"}],
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        let entry = |provider: &str, model: &str| LlmChainEntry {
+            is_enabled: true,
+            provider: provider.into(),
+            model: model.into(),
+            ..Default::default()
+        };
+        let problems = client
+            .llm()
+            .validate_chain(vec![
+                entry("openai", "gpt-4o-mini"),
+                entry("openai", "gpt4o-mini"),
+                entry("ollama", "llama3"),
+            ])
+            .await
+            .unwrap();
+        let kinds: Vec<_> = problems.iter().map(|p| (p.index, p.kind.clone())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (1, ChainProblemKind::UnknownModel),
+                (2, ChainProblemKind::MissingKey),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_org_members() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/org/members"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "members": [
+                    {"id": "mem_1", "email": "owner@example.com", "role": "owner"},
+                    {"id": "mem_2", "email": "dev@example.com", "role": "member"},
+                ],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/org/invitations"))
+            .and(body_json(
+                serde_json::json!({"email": "new@example.com", "role": "admin"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "inv_1", "email": "new@example.com", "role": "admin",
+                "expires_at": "2026-01-19T09:00:00Z",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/org/members/mem_2"))
+            .and(body_json(serde_json::json!({"role": "admin"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "mem_2", "email": "dev@example.com", "role": "admin",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/org/members/mem_2"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let org = client.org();
+        let members = org.list_members().await.unwrap();
+        assert_eq!(members[0].role, OrgRole::Owner);
+
+        let invitation = org.invite("new@example.com", OrgRole::Admin).await.unwrap();
+        assert_eq!(invitation.role, OrgRole::Admin);
+        assert!(matches!(
+            org.invite("not-an-email", OrgRole::Member).await,
+            Err(Error::Config(_))
+        ));
+
+        let member = org.update_role("mem_2", OrgRole::Admin).await.unwrap();
+        assert_eq!(member.role, OrgRole::Admin);
+        org.remove_member("mem_2").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_features_fall_back_from_sse() {
+        use crate::progress::ServerSentEvents;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-API-Features", "screenshots, estimates")
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1/events"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/features"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"features": ["sse"]})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        client.get_job("job-1").await.unwrap();
+        assert!(client.supports(Feature::Screenshots).await);
+        assert!(!client.supports(Feature::Sse).await);
+        let mut sse = ServerSentEvents::new("/api/v1/jobs/{id}/events");
+        assert!(client.wait_for_job("job-1", &mut sse).await.is_ok());
+
+        let fresh = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        assert!(fresh.supports(Feature::Sse).await);
+        assert!(fresh.supports(Feature::Sse).await);
+    }
+
+    #[tokio::test]
+    async fn test_connect_checks_version_when_strict() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-API-Version", crate::MAX_KNOWN_API_VERSION)
+                    .set_body_json(serde_json::json!({"status": "ok", "version": "1.0.0"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .connect()
+            .await
+            .unwrap();
+        assert_eq!(
+            *client.api_version.lock().unwrap(),
+            ApiVersionStatus::Accepted
+        );
+
+        // Without the header, connecting fails; a lazily checked client waits
+        // for a response that has it.
+        let unversioned = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-API-Version", crate::MAX_KNOWN_API_VERSION)
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .mount(&unversioned)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"status": "ok", "version": "1.0.0"})),
+            )
+            .mount(&unversioned)
+            .await;
+        let connected = Client::builder("test-key")
+            .base_url(unversioned.uri())
+            .connect()
+            .await;
+        assert!(matches!(
+            connected,
+            Err(Error::UnsupportedApiVersion { api_version, .. }) if api_version == "unknown"
+        ));
+        let client = Client::builder("test-key")
+            .base_url(unversioned.uri())
+            .build()
+            .unwrap();
+        client.health().await.unwrap();
+        assert_eq!(
+            *client.api_version.lock().unwrap(),
+            ApiVersionStatus::Unchecked
+        );
+        client.get_job("job-1").await.unwrap();
+        assert_eq!(
+            *client.api_version.lock().unwrap(),
+            ApiVersionStatus::Accepted
+        );
+
+        for mode in [VersionCheckMode::WarnOnly, VersionCheckMode::Disabled] {
+            let client = Client::builder("test-key")
+                .base_url(unversioned.uri())
+                .version_check(mode)
+                .connect()
+                .await;
+            assert!(client.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strict_version_check_keeps_failing() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-API-Version", "1.0.0")
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .mount(&server)
+            .await;
+
+        let mut client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        client.min_api_version = "2.0.0";
+        for _ in 0..2 {
+            let result = client.get_job("job-1").await;
+            assert!(matches!(
+                result,
+                Err(Error::UnsupportedApiVersion { api_version, .. }) if api_version == "1.0.0"
+            ));
+        }
+
+        let mut client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .version_check(VersionCheckMode::WarnOnly)
+            .build()
+            .unwrap();
+        client.min_api_version = "2.0.0";
+        client.get_job("job-1").await.unwrap();
+        client.get_job("job-1").await.unwrap();
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_compression_negotiated() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .mount(&server)
+            .await;
+
+        for enabled in [true, false] {
+            let client = Client::builder("test-key")
+                .base_url(server.uri())
+                .compression(enabled)
+                .build()
+                .unwrap();
+            client.get_job("job-1").await.unwrap();
+        }
+        let compressed: Vec<bool> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                request
+                    .headers
+                    .get("accept-encoding")
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.contains("gzip") && value.contains("br"))
+            })
+            .collect();
+        assert_eq!(compressed, vec![true, false]);
+    }
+
+    #[tokio::test]
+    async fn test_skip_cache_response_still_stored() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=300")
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let path = "/api/v1/jobs/job-1";
+        let cached: Job = client.get(path).await.unwrap();
+        let fresh: Job = client.get_skip_cache(path).await.unwrap();
+        assert_eq!(fresh.id, cached.id);
+        // The skip-cache response replaced the cached job, so this is a hit
+        let stored: Job = client.get(path).await.unwrap();
+        assert_eq!(stored.id, cached.id);
+
+        // Uncacheable responses are parsed straight from the body, not stored
+        for _ in 0..2 {
+            let uncached: Job = client.get("/api/v1/jobs/job-2").await.unwrap();
+            assert_eq!(uncached.id, cached.id);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_sitemap_urls() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/sitemap"))
+            .and(query_param("domain", "shop.example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "domain": "shop.example.com",
+                "sitemaps": ["https://shop.example.com/sitemap.xml"],
+                "urls": [
+                    {"url": "https://shop.example.com/products/1", "last_modified": "2026-01-12"},
+                    {"url": "https://shop.example.com/about"},
+                ],
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let sitemap = client.list_sitemap_urls("shop.example.com").await.unwrap();
+        assert_eq!(sitemap.urls.len(), 2);
+        assert!(!sitemap.truncated);
+        assert_eq!(sitemap.urls[0].last_modified.as_deref(), Some("2026-01-12"));
+        assert!(sitemap.urls[1].last_modified.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transforms_apply_to_extractions_and_results() {
+        use crate::transforms::KeyCase;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let mut extracted = crate::golden::load("0.1.80", "extract").unwrap();
+        extracted["data"]["name"] = "  Ceramic Pour-Over Set ".into();
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(extracted))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1/results"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"url": "https://example.com/a", "data": {"in_stock": " yes"}},
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .transform(crate::transforms::TrimStrings)
+            .transform(KeyCase::Camel)
+            .build()
+            .unwrap();
+
+        let response = client
+            .extract(ExtractRequest {
+                url: "https://example.com/products/pour-over".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.data["name"], "Ceramic Pour-Over Set");
+        assert_eq!(response.data["inStock"], true);
+
+        let results = client.get_job_results("job-1", false).await.unwrap();
+        assert_eq!(results[0]["data"], serde_json::json!({"inStock": "yes"}));
+        assert_eq!(results[0]["url"], "https://example.com/a");
+    }
+
+    #[tokio::test]
+    async fn test_get_results_as_reports_items() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Row {
+            name: String,
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/01HXYZABCD1234/results"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"url": "https://example.com/a", "data": {"name": "A"}},
+                {"url": "https://example.com/b", "data": {"name": null}},
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let rows = client
+            .jobs()
+            .get_results_as::<Row>("01HXYZABCD1234")
+            .await
+            .unwrap();
+        assert_eq!(rows[0].as_ref().unwrap().name, "A");
+        let error = rows[1].as_ref().unwrap_err();
+        assert_eq!(error.index, 1);
+        assert_eq!(error.url.as_deref(), Some("https://example.com/b"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_results() {
+        use futures::{StreamExt, TryStreamExt};
+        use wiremock::matchers::{header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/01HXYZABCD0001/results"))
+            .and(header_regex("accept", "^application/x-ndjson"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "{\"url\":\"https://example.com/a\",\"data\":{\"name\":\"A\"}}\n\n\
+                 {\"url\":\"https://example.com/b\",\"data\":{\"name\":\"B\"}}",
+                "application/x-ndjson",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/01HXYZABCD0002/results"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{"url": "https://example.com/c", "data": {"name": "C"}}],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/01HXYZABCD0003/results"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "{\"data\":{}}\nnot json\n{\"data\":{}}\n",
+                "application/x-ndjson",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let pages: Vec<serde_json::Value> = client
+            .jobs()
+            .stream_results("01HXYZABCD0001")
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1]["data"]["name"], "B");
+
+        let pages: Vec<serde_json::Value> = client
+            .jobs()
+            .stream_results("01HXYZABCD0002")
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(pages[0]["url"], "https://example.com/c");
+
+        let items: Vec<_> = client
+            .jobs()
+            .stream_results("01HXYZABCD0003")
+            .collect()
+            .await;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[1], Err(Error::Json(_))));
+    }
+
+    #[tokio::test]
+    async fn test_read_timeouts_retry_only_idempotent_requests() {
+        use crate::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let slow = ResponseTemplate::new(200)
+            .set_body_json(serde_json::json!({}))
+            .set_delay(Duration::from_millis(500));
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(slow.clone())
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(slow)
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .read_timeout(Duration::from_millis(50))
+            .max_retries(1)
+            .clock(Arc::new(MockClock::new(0)))
+            .build()
+            .unwrap();
+
+        let extracted = client.extract(ExtractRequest::default()).await;
+        let extracted = extracted.unwrap_err();
+        assert!(matches!(
+            extracted,
+            Error::ReadTimeout { idempotent: false }
+        ));
+        assert!(!extracted.is_retryable());
+        assert_eq!(extracted.code(), ErrorCode::Timeout);
+        let usage = client.get_usage().await.unwrap_err();
+        assert!(matches!(usage, Error::ReadTimeout { idempotent: true }));
+        assert!(usage.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_download_results_resumes_partial_file() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for job in ["ranged", "unranged", "changed"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v1/jobs/{}/download", job)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "download_url": format!("{}/files/{}.jsonl", server.uri(), job),
+                    "expires_at": "2026-01-01T01:00:00Z",
+                    "job_id": job,
+                })))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/files/ranged.jsonl"))
+            .and(header("range", "bytes=6-8388613"))
+            .and(header("if-range", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("Content-Range", "bytes 6-10/11")
+                    .set_body_string("world"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/unranged.jsonl"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello world"))
+            .mount(&server)
+            .await;
+        // If-Range did not match: the whole new file is sent.
+        Mock::given(method("GET"))
+            .and(path("/files/changed.jsonl"))
+            .and(header("if-range", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v2\"")
+                    .set_body_string("HELLO WORLD"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("refyne-download-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for (job, expected) in [
+            ("ranged", "hello world"),
+            ("unranged", "hello world"),
+            ("changed", "HELLO WORLD"),
+        ] {
+            let target = dir.join(format!("{}.jsonl", job));
+            std::fs::write(part_path(&target), "hello ").unwrap();
+            std::fs::write(validator_path(&target), "\"v1\"").unwrap();
+            let size = client.download_results(job, &target).await.unwrap();
+            assert_eq!(size, 11);
+            assert_eq!(std::fs::read_to_string(&target).unwrap(), expected);
+            assert!(!part_path(&target).exists());
+            assert!(!validator_path(&target).exists());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_results_fails_when_range_body_is_empty() {
+        use crate::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/empty/download"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "download_url": format!("{}/files/empty.jsonl", server.uri()),
+                "expires_at": "2026-01-01T01:00:00Z",
+                "job_id": "empty",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/empty.jsonl"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("Content-Range", "bytes 0-10/11")
+                    .insert_header("ETag", "\"v1\""),
+            )
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(0));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(2)
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("refyne-empty-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("empty.jsonl");
+
+        let result = client.download_results("empty", &target).await;
+        assert!(matches!(result, Err(Error::Io(_))));
+        assert_eq!(clock.sleeps().len(), 2);
+        assert!(!target.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_builder() {
+        use wiremock::matchers::{body_json, header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/beta/preview"))
+            .and(query_param("mode", "fast & cheap"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(header("x-beta-feature", "preview"))
+            .and(body_json(serde_json::json!({"url": "https://example.com"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/beta/flags"))
+            .and(header("x-beta-feature", "flags"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=60")
+                    .set_body_json(serde_json::json!(["b"])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/beta/flags"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=60")
+                    .set_body_json(serde_json::json!(["a"])),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let preview: serde_json::Value = client
+            .request_builder("POST", "/api/v1/beta/preview")
+            .query("mode", "fast & cheap")
+            .header("X-Beta-Feature", "preview")
+            .json(&serde_json::json!({"url": "https://example.com"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(preview["ok"], true);
+
+        // Cached on the first call, refreshed with skip_cache.
+        for skip in [false, false, true] {
+            let mut request = client.request_builder("GET", "/api/v1/beta/flags");
+            if skip {
+                request = request.skip_cache();
+            }
+            let flags: Vec<String> = request.send().await.unwrap();
+            assert_eq!(flags, vec!["a"]);
+        }
+
+        // Requests with their own headers are cached apart from the rest.
+        for _ in 0..2 {
+            let flags: Vec<String> = client
+                .request_builder("GET", "/api/v1/beta/flags")
+                .header("X-Beta-Feature", "flags")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(flags, vec!["b"]);
+        }
+
+        let relative = client
+            .request_builder("GET", "api/v1/beta/flags")
+            .send::<serde_json::Value>()
+            .await;
+        assert!(matches!(relative, Err(Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_extract_aborts_operation() {
+        use wiremock::matchers::{method, path, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "extract").unwrap())
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path_regex("^/api/v1/extract/[0-9a-f]{32}$"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .abort_on_cancel(true)
+            .build()
+            .unwrap();
+        let extract = client.extract(ExtractRequest::default());
+        assert!(tokio::time::timeout(Duration::from_millis(100), extract)
+            .await
+            .is_err());
+
+        let requests = server.received_requests().await.unwrap();
+        let operation_id = requests[0].headers["x-operation-id"].to_str().unwrap();
+        for _ in 0..50 {
+            let requests = server.received_requests().await.unwrap();
+            if let Some(abort) = requests.iter().find(|r| r.method.as_str() == "DELETE") {
+                assert!(abort.url.path().ends_with(operation_id));
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("abort was not sent");
+    }
+
+    #[tokio::test]
+    async fn test_extract_as() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Product {
+            name: String,
+            price: f64,
+        }
+        #[derive(Debug, serde::Deserialize)]
+        struct Article {
+            #[allow(dead_code)]
+            headline: String,
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "extract").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let product: Product = client.extract_as(ExtractRequest::default()).await.unwrap();
+        assert_eq!(product.name, "Ceramic Pour-Over Set");
+        assert_eq!(product.price, 42.5);
+
+        match client
+            .extract_as::<Article>(ExtractRequest::default())
+            .await
+        {
+            Err(Error::Deserialize { payload, .. }) => assert_eq!(payload["price"], 42.5),
+            other => panic!("expected a deserialize error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jobs_wait_backs_off_until_terminal() {
+        use crate::clock::MockClock;
+        use std::sync::Mutex;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let completed = crate::golden::load("0.1.80", "job").unwrap();
+        let mut running = completed.clone();
+        running["status"] = "running".into();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&running))
+            .up_to_n_times(4)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&completed))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&running))
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(0));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+        let options = WaitOptions::new()
+            .interval(Duration::from_secs(1))
+            .backoff(2.0, Duration::from_secs(5))
+            .on_progress(move |job| recorder.lock().unwrap().push(job.status.clone()));
+        let job = client.jobs().wait("job-1", options).await.unwrap();
+        assert_eq!(job.status, "completed");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["running", "running", "running", "running", "completed"]
+        );
+        assert_eq!(
+            clock.sleeps(),
+            [1, 2, 4, 5].map(Duration::from_secs).to_vec()
+        );
+
+        let clock = Arc::new(MockClock::new(0));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let options = WaitOptions::new()
+            .interval(Duration::from_secs(2))
+            .max_wait(Duration::from_secs(5));
+        let waited = client.jobs().wait("job-2", options).await;
+        assert!(matches!(waited, Err(Error::Timeout)));
+        assert_eq!(clock.sleeps(), [2, 2, 1].map(Duration::from_secs).to_vec());
+
+        // A factor too large for a duration waits the longest interval.
+        let clock = Arc::new(MockClock::new(0));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let options = WaitOptions::new()
+            .interval(Duration::from_secs(1))
+            .backoff(f64::INFINITY, Duration::from_secs(30))
+            .max_wait(Duration::from_secs(60));
+        let waited = client.jobs().wait("job-2", options).await;
+        assert!(matches!(waited, Err(Error::Timeout)));
+        assert_eq!(
+            clock.sleeps(),
+            [1, 30, 29].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_delete_job() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let mut cancelled = crate::golden::load("0.1.80", "job").unwrap();
+        cancelled["status"] = "cancelled".into();
+        Mock::given(method("POST"))
+            .and(path("/api/v1/jobs/job-1/cancel"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&cancelled))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&cancelled))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        client.job_registry.insert("fp".into(), "job-1".into());
+
+        let job = client.jobs().cancel("job-1").await.unwrap();
+        assert_eq!(job.job_status(), JobStatus::Cancelled);
+        assert!(client.job_registry.get("fp").is_none());
+
+        let waited = client
+            .jobs()
+            .wait("job-1", WaitOptions::new().max_wait(Duration::from_secs(5)))
+            .await
+            .unwrap();
+        assert_eq!(waited.job_status(), JobStatus::Cancelled);
+
+        client.jobs().delete("job-1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_all_jobs_pages_through_listing() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let job = crate::golden::load("0.1.80", "job").unwrap();
+        let page = |ids: &[&str]| {
+            let jobs: Vec<_> = ids
+                .iter()
+                .map(|id| {
+                    let mut job = job.clone();
+                    job["id"] = (*id).into();
+                    job
+                })
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "jobs": jobs }))
+        };
+        for (offset, ids) in [("0", &["a", "b"][..]), ("2", &["c"][..]), ("3", &[][..])] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/jobs"))
+                .and(query_param("limit", "2"))
+                .and(query_param("offset", offset))
+                .respond_with(page(ids))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let jobs: Vec<Job> = client
+            .jobs()
+            .list_all()
+            .page_size(2)
+            .try_collect()
+            .await
+            .unwrap();
+        let ids: Vec<_> = jobs.iter().map(|job| job.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_jobs_filtered() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let job = crate::golden::load("0.1.80", "job").unwrap();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs"))
+            .and(query_param("limit", "10"))
+            .and(query_param("status", "running,pending"))
+            .and(query_param("created_after", "2026-01-12T09:00:00+00:00"))
+            .and(query_param("url", "example.com/shop"))
+            .and(query_param("sort", "-created_at"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"jobs": [job]})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let filter = JobFilter::new()
+            .status(JobStatus::Running)
+            .status(JobStatus::Pending)
+            .created_after("2026-01-12T09:00:00+00:00")
+            .url_contains("example.com/shop")
+            .sort(crate::JobSort::NewestFirst);
+        let list = client
+            .jobs()
+            .list_filtered(&filter, Some(10), None)
+            .await
+            .unwrap();
+        assert_eq!(list.jobs.as_array().map(Vec::len), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_revalidation_with_etag() {
+        use crate::clock::MockClock;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304).insert_header("Cache-Control", "max-age=60"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "no-cache")
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_json(serde_json::json!({"total_jobs": 3})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(1_000));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let fetch = || async {
+            client
+                .request_builder("GET", "/api/v1/usage")
+                .send::<serde_json::Value>()
+                .await
+                .unwrap()
+        };
+
+        // Fetched, revalidated with a 304, then served from the refreshed entry.
+        for _ in 0..3 {
+            assert_eq!(fetch().await["total_jobs"], 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mutations_invalidate_cached_gets() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let schema = crate::golden::load("0.1.80", "schema").unwrap();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/s1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=300")
+                    .set_body_json(&schema),
+            )
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/schemas/s1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&schema))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/schemas/s1"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        client.get_schema("s1").await.unwrap();
+        client.get_schema("s1").await.unwrap();
+        client
+            .update_schema("s1", crate::SchemaPreset::product().create_request())
+            .await
+            .unwrap();
+        client.get_schema("s1").await.unwrap();
+        client.delete_schema("s1").await.unwrap();
+        client.get_schema("s1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_crawl_invalidates_cached_job_listing() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=300")
+                    .set_body_json(serde_json::json!({"jobs": []})),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/crawl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "job_id": "job-1",
+                "status": "pending",
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        client.list_jobs(None, None).await.unwrap();
+        client
+            .crawl(CrawlRequest {
+                url: "https://example.com".into(),
+                schema: serde_json::json!({"title": "string"}),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        client.list_jobs(None, None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_site_and_wait() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let job = crate::golden::load("0.1.80", "job").unwrap();
+        let mut schema = crate::golden::load("0.1.80", "schema").unwrap();
+        schema["schema_yaml"] = "name: string\n".into();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/sites/site-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "site-1", "url": "https://example.com/products", "domain": "example.com",
+                "fetch_mode": "static", "default_schema_id": "schema-1",
+                "crawl_options": {"follow_selector": "a.product", "max_pages": 50},
+                "created_at": "2026-01-12T09:15:10Z", "updated_at": "2026-01-12T09:15:10Z",
+                "user_id": "user-1",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/schema-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&schema))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/crawl"))
+            .and(body_partial_json(serde_json::json!({
+                "url": "https://example.com/products",
+                "schema": "name: string\n",
+                "options": {"fetch_mode": "static", "follow_selector": "a.product", "max_pages": 5},
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "crawl_job_created").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/api/v1/jobs/{}",
+                job["id"].as_str().unwrap()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let finished = client
+            .sites()
+            .run_and_wait("site-1", RunOptions::new().max_pages(5), WaitOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(finished.job_status(), JobStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_retry_and_rerun_job() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let created = crate::golden::load("0.1.80", "crawl_job_created").unwrap();
+        let job_id = created["job_id"].as_str().unwrap().to_string();
+        let mut failed = crate::golden::load("0.1.80", "job").unwrap();
+        failed["status"] = "failed".into();
+        let mut other = failed.clone();
+        other["id"] = "job-other".into();
+        let mut running = failed.clone();
+        running["id"] = "job-running".into();
+        running["status"] = "running".into();
+        for job in [&failed, &other, &running] {
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "/api/v1/jobs/{}",
+                    job["id"].as_str().unwrap()
+                )))
+                .respond_with(ResponseTemplate::new(200).set_body_json(job))
+                .mount(&server)
+                .await;
+        }
+
+        let schema = serde_json::json!({"title": "string"});
+        let original = serde_json::json!({"url": "https://example.com/products", "schema": schema});
+        let mut smaller = original.clone();
+        smaller["options"] = serde_json::json!({
+            "concurrency": null, "delay": null, "extract_from_seeds": null, "fetch_mode": null,
+            "follow_pattern": null, "follow_selector": null, "max_depth": null,
+            "max_pages": 10, "max_urls": null, "next_selector": null,
+            "same_domain_only": null, "use_sitemap": null,
+        });
+        for (body, times) in [(&original, 3), (&smaller, 1)] {
+            Mock::given(method("POST"))
+                .and(path("/api/v1/crawl"))
+                .and(body_json(body))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&created))
+                .expect(times)
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let request = CrawlRequest {
+            url: "https://example.com/products".into(),
+            schema: schema.clone(),
+            ..Default::default()
+        };
+        client.crawl(request).await.unwrap();
+        let jobs = client.jobs();
+        assert_eq!(jobs.retry(&job_id).await.unwrap().job_id, job_id);
+        jobs.rerun(&job_id, RunOptions::new().max_pages(10))
+            .await
+            .unwrap();
+
+        // Jobs from elsewhere need a schema; active jobs are refused.
+        assert!(matches!(
+            jobs.retry("job-other").await,
+            Err(Error::Config(_))
+        ));
+        jobs.rerun("job-other", RunOptions::new().schema(schema))
+            .await
+            .unwrap();
+        assert!(matches!(
+            jobs.retry("job-running").await,
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_schedules() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let schedule = serde_json::json!({
+            "id": "sched-1", "site_id": "site-1", "cron": "0 6 * * *", "paused": false,
+            "next_run_at": "2026-01-13T06:00:00Z", "last_run_at": null,
+            "created_at": "2026-01-12T09:15:10Z", "updated_at": "2026-01-12T09:15:10Z",
+        });
+        let mut paused = schedule.clone();
+        paused["paused"] = true.into();
+        paused["next_run_at"] = serde_json::Value::Null;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/schedules"))
+            .and(body_json(
+                serde_json::json!({"site_id": "site-1", "cron": "0 6 * * *"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&schedule))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schedules"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"schedules": [&schedule]})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/schedules/sched-1/pause"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&paused))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/schedules/sched-1/resume"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&schedule))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/schedules/sched-1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let sites = client.sites();
+        assert!(matches!(
+            sites.schedule("site-1", "daily").await,
+            Err(Error::Config(_))
+        ));
+        let created = sites.schedule("site-1", "0 6 * * *").await.unwrap();
+        assert_eq!(created.next_run_at.as_deref(), Some("2026-01-13T06:00:00Z"));
+        assert_eq!(sites.list_schedules().await.unwrap().schedules.len(), 1);
+
+        let paused = sites.pause_schedule("sched-1").await.unwrap();
+        assert!(paused.paused);
+        assert_eq!(paused.next_run_at, None);
+        assert!(!sites.resume_schedule("sched-1").await.unwrap().paused);
+        sites.delete_schedule("sched-1").await.unwrap();
+    }
+
+    #[cfg(feature = "yaml")]
+    #[tokio::test]
+    async fn test_update_with_check_refuses_breaking_changes() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let mut schema = crate::golden::load("0.1.80", "schema").unwrap();
+        schema["schema_yaml"] = "name: string\nprice: number\n".into();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/s1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&schema))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/schemas/s1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&schema))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        let request = |yaml: &str| CreateSchemaRequest {
+            category: None,
+            description: None,
+            name: "Product".into(),
+            schema_yaml: yaml.into(),
+            tags: None,
+            visibility: crate::CreateSchemaInputBodyVisibility::Private,
+        };
+
+        let breaking = request("name: string\nprice: string\n");
+        match client
+            .schemas()
+            .update_with_check("s1", breaking.clone(), false)
+            .await
+        {
+            Err(Error::BreakingSchemaChange { schema_id, diff }) => {
+                assert_eq!(schema_id, "s1");
+                assert_eq!(diff.to_string(), "~ price: number -> string\n");
+            }
+            other => panic!("expected a breaking change, got {:?}", other),
+        }
+        client
+            .schemas()
+            .update_with_check(
+                "s1",
+                request("name: string\nprice: number\nsku: string\n"),
+                false,
+            )
+            .await
+            .unwrap();
+        client
+            .schemas()
+            .update_with_check("s1", breaking, true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_stops_retries() {
+        use crate::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "byok_jobs": 0, "total_charged_usd": 0.0, "total_jobs": 0,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(1_000));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(5)
+            .cache_enabled(false)
+            .circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 2,
+                cool_down: Duration::from_secs(60),
+            })
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        let err = client.get_usage().await.unwrap_err();
+        assert!(matches!(err, Error::CircuitOpen { .. }));
+        assert_eq!(err.code(), ErrorCode::CircuitOpen);
+        assert!(matches!(
+            client.get_usage().await,
+            Err(Error::CircuitOpen { .. })
+        ));
+
+        // After the cool-down a trial request goes through and closes the circuit.
+        clock.advance(Duration::from_secs(60));
+        client.get_usage().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_returns_rate_limit() {
+        use crate::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "5"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(1_000));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(5)
+            .max_retry_duration(Duration::from_secs(8))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        match client.get_usage().await {
+            Err(Error::RateLimit {
+                retry_after,
+                retries,
+                waited,
+                ..
+            }) => {
+                assert_eq!(retry_after, 5);
+                assert_eq!(retries, 1);
+                assert_eq!(waited, Duration::from_secs(5));
+            }
+            other => panic!("expected rate limit error, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(clock.sleeps(), [Duration::from_secs(5)]);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_sees_each_attempt() {
+        use crate::clock::MockClock;
+        use crate::middleware::{Middleware, MiddlewareFuture, Next};
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        struct Sign(Arc<AtomicU32>);
+
+        impl Middleware for Sign {
+            fn handle<'a>(
+                &'a self,
+                mut request: PreparedRequest,
+                next: Next<'a>,
+            ) -> MiddlewareFuture<'a> {
+                let attempt = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+                request.set_header("X-Signature", format!("sig-{}", attempt));
+                next.run(request)
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .and(header("X-Signature", "sig-1"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .and(header("X-Signature", "sig-2"))
+            .and(header("Authorization", "Bearer test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "byok_jobs": 0, "total_charged_usd": 0.0, "total_jobs": 0,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .with_middleware(Sign(calls.clone()))
+            .clock(Arc::new(MockClock::new(0)))
+            .build()
+            .unwrap();
+
+        client.get_usage().await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_ids_on_errors_and_responses() {
+        use crate::meta::capture_meta;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/missing"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .insert_header("X-Request-Id", "req-404")
+                    .set_body_json(serde_json::json!({"error": "Job not found"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/schema-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-Request-Id", "req-200")
+                    .insert_header("Cache-Control", "max-age=60")
+                    .set_body_json(crate::golden::load("0.1.80", "schema").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let err = client.get_job("missing").await.unwrap_err();
+        assert!(matches!(err, Error::NotFound { .. }));
+        assert_eq!(err.request_id(), Some("req-404"));
+
+        let (schema, meta) = capture_meta(client.get_schema("schema-1")).await;
+        schema.unwrap();
+        let meta = meta.unwrap();
+        assert_eq!(meta.request_id.as_deref(), Some("req-200"));
+        assert_eq!(meta.status, 200);
+        assert!(!meta.cached);
+
+        let (_, meta) = capture_meta(client.get_schema("schema-1")).await;
+        assert!(meta.unwrap().cached);
+    }
+
+    #[tokio::test]
+    async fn test_injected_http_client() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .and(header("X-App", "shared"))
+            .and(header("Authorization", "Bearer test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "byok_jobs": 0, "total_charged_usd": 0.0, "total_jobs": 0,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-App", HeaderValue::from_static("shared"));
+        let http_client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .http_client(http_client)
+            .build()
+            .unwrap();
+
+        client.get_usage().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_options_override_timeout() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/keys"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"keys": []}))
+                    .set_delay(Duration::from_millis(300)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .timeout(Duration::from_secs(5))
+            .max_retries(0)
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+
+        assert!(client.list_keys().await.is_ok());
+        let fast = RequestOptions::default().timeout(Duration::from_millis(50));
+        assert!(matches!(
+            client.with_options(fast).list_keys().await,
+            Err(Error::ReadTimeout { .. })
+        ));
+        assert!(client.list_keys().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_default_llm_config() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for model in ["default/model", "own/model"] {
+            Mock::given(method("POST"))
+                .and(path("/api/v1/extract"))
+                .and(body_partial_json(
+                    serde_json::json!({"llm_config": {"model": model}}),
+                ))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(crate::golden::load("0.1.80", "extract").unwrap()),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .default_llm_config(LLMConfigInput {
+                model: Some("default/model".into()),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let request = |model: Option<&str>| ExtractRequest {
+            url: "https://example.com".into(),
+            llm_config: model.map(|m| LLMConfigInput {
+                model: Some(m.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        client.extract(request(None)).await.unwrap();
+        client.extract(request(Some("own/model"))).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_key_rotation() {
+        use crate::runtime::BoxFuture;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        struct Vault;
+
+        impl ApiKeyProvider for Vault {
+            fn api_key(&self) -> BoxFuture<'_, Result<String>> {
+                Box::pin(async { Ok("vault-key".to_string()) })
+            }
+        }
+
+        let server = MockServer::start().await;
+        for key in ["old-key", "new-key", "vault-key"] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/keys"))
+                .and(header("Authorization", format!("Bearer {}", key).as_str()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "keys": [],
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::builder("old-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        client.list_keys().await.unwrap();
+        client.set_api_key("new-key");
+        client.tenant("acme").list_keys().await.unwrap();
+
+        let client = Client::builder("")
+            .base_url(server.uri())
+            .api_key_provider("acme", Vault)
+            .build()
+            .unwrap();
+        client.list_keys().await.unwrap();
+        let other = Client::builder("")
+            .api_key_provider("globex", Vault)
+            .build()
+            .unwrap();
+        assert_ne!(client.auth_hash, other.auth_hash);
+        assert!(matches!(
+            Client::builder("").api_key_provider("", Vault).build(),
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_override_partitions_cache() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for key in ["client-key", "customer-key"] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/schemas/schema-1"))
+                .and(header("Authorization", format!("Bearer {}", key).as_str()))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("Cache-Control", "max-age=60")
+                        .set_body_json(crate::golden::load("0.1.80", "schema").unwrap()),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::builder("client-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let customer = || RequestOptions::default().api_key_override("customer-key");
+        client.get_schema("schema-1").await.unwrap();
+        client
+            .with_options(customer())
+            .get_schema("schema-1")
+            .await
+            .unwrap();
+        // Both responses are now cached under their own key.
+        client.get_schema("schema-1").await.unwrap();
+        client
+            .with_options(customer())
+            .get_schema("schema-1")
+            .await
+            .unwrap();
+
+        assert!(!format!("{:?}", customer()).contains("customer-key"));
+    }
+
+    #[tokio::test]
+    async fn test_tracing_spans() {
+        use std::collections::HashMap;
+        use std::sync::Mutex;
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        /// Collects the fields of every span.
+        #[derive(Clone, Default)]
+        struct Spans(Arc<Mutex<Vec<HashMap<String, String>>>>);
+
+        struct Fields<'a>(&'a mut HashMap<String, String>);
+
+        impl field::Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &field::Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{:?}", value));
+            }
+        }
+
+        impl Subscriber for Spans {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut spans = self.0.lock().unwrap();
+                let mut fields = HashMap::new();
+                span.record(&mut Fields(&mut fields));
+                fields.insert("name".into(), span.metadata().name().into());
+                spans.push(fields);
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, span: &Id, values: &Record<'_>) {
+                let mut spans = self.0.lock().unwrap();
+                values.record(&mut Fields(&mut spans[span.into_u64() as usize - 1]));
+            }
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schemas/schema-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=60")
+                    .set_body_json(crate::golden::load("0.1.80", "schema").unwrap()),
+            )
+            .mount(&server)
+            .await;
+
+        let spans = Spans::default();
+        let _guard = tracing::subscriber::set_default(spans.clone());
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        client.get_schema("schema-1").await.unwrap();
+        client.get_schema("schema-1").await.unwrap();
+
+        let recorded = spans.0.lock().unwrap().clone();
+        let requests: Vec<_> = recorded
+            .iter()
+            .filter(|span| span["name"] == "refyne.request")
+            .collect();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0]["method"], "GET");
+        assert_eq!(requests[0]["path"], "\"/api/v1/schemas/schema-1\"");
+        assert_eq!(requests[0]["status"], "200");
+        assert_eq!(requests[0]["attempt"], "1");
+        assert_eq!(requests[0]["cache"], "\"miss\"");
+        assert!(requests[0].contains_key("duration_ms"));
+        assert_eq!(requests[1]["cache"], "\"hit\"");
+        drop(recorded);
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .tracing_spans(false)
+            .build()
+            .unwrap();
+        spans.0.lock().unwrap().clear();
+        client.get_schema("schema-1").await.unwrap();
+        assert!(spans
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|span| span["name"] != "refyne.request"));
+    }
+
+    #[tokio::test]
+    async fn test_observer_callbacks() {
+        use crate::clock::MockClock;
+        use crate::error::ErrorCode;
+        use std::sync::Mutex;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<String>>);
+
+        impl Observer for Recorder {
+            fn on_request_start(&self, endpoint: &str) {
+                self.0.lock().unwrap().push(format!("start {}", endpoint));
+            }
+
+            fn on_request_end(&self, endpoint: &str, outcome: &RequestOutcome) {
+                self.0.lock().unwrap().push(format!(
+                    "end {} {:?} {} {:?}",
+                    endpoint, outcome.status, outcome.attempts, outcome.error
+                ));
+            }
+
+            fn on_retry(&self, endpoint: &str, attempt: u32, delay: Duration) {
+                assert!(delay >= Duration::from_secs(1));
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("retry {} {}", endpoint, attempt));
+            }
+
+            fn on_cache_hit(&self, endpoint: &str) {
+                self.0.lock().unwrap().push(format!("hit {}", endpoint));
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/usage"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Cache-Control", "max-age=60")
+                    .set_body_json(serde_json::json!({
+                        "byok_jobs": 0, "total_charged_usd": 1.0, "total_jobs": 3,
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/01HXYZABCD1234"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": "job not found",
+            })))
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(Recorder::default());
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(2)
+            .clock(Arc::new(MockClock::new(0)))
+            .observer(recorder.clone())
+            .build()
+            .unwrap();
+
+        client.get_usage().await.unwrap();
+        client.get_usage().await.unwrap();
+        assert!(client.get_job("01HXYZABCD1234").await.is_err());
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                "start GET /api/v1/usage".to_string(),
+                "retry GET /api/v1/usage 1".to_string(),
+                "end GET /api/v1/usage Some(200) 2 None".to_string(),
+                "start GET /api/v1/usage".to_string(),
+                "hit GET /api/v1/usage".to_string(),
+                "end GET /api/v1/usage Some(200) 0 None".to_string(),
+                "start GET /api/v1/jobs/{id}".to_string(),
+                format!(
+                    "end GET /api/v1/jobs/{{id}} Some(404) 1 {:?}",
+                    Some(ErrorCode::NotFound)
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_create_and_test() {
+        use crate::webhooks::WebhookEventType;
+        use serde_json::json;
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/webhooks"))
+            .and(body_json(json!({
+                "name": "jobs",
+                "url": "https://example.com/hooks",
+                "is_active": true,
+                "events": ["job.completed"],
+                "secret": "whsec_abc",
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "wh-1",
+                "name": "jobs",
+                "url": "https://example.com/hooks",
+                "events": ["job.completed"],
+                "has_secret": true,
+                "headers": null,
+                "is_active": true,
+                "created_at": "2026-01-12T09:00:00Z",
+                "updated_at": "2026-01-12T09:00:00Z",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/webhooks/wh-1/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "del-1",
+                "webhook_id": "wh-1",
+                "job_id": "",
+                "event_type": "test",
+                "url": "https://example.com/hooks",
+                "status": "success",
+                "status_code": 204,
+                "attempt_number": 1,
+                "max_attempts": 3,
+                "response_time_ms": 42,
+                "error_message": null,
+                "next_retry_at": null,
+                "created_at": "2026-01-12T09:00:01Z",
+                "delivered_at": "2026-01-12T09:00:01Z",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let webhook = client
+            .webhooks()
+            .create(
+                WebhookInput::new("jobs", "https://example.com/hooks")
+                    .events([WebhookEventType::JobCompleted])
+                    .secret("whsec_abc"),
+            )
+            .await
+            .unwrap();
+        assert!(webhook.has_secret);
+        let delivery = client.webhooks().test(&webhook.id).await.unwrap();
+        assert_eq!(delivery.status, "success");
+        assert_eq!(delivery.status_code, Some(204));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_returns_existing_job_for_duplicate() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/crawl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "job_id": "job-1",
+                "status": "pending",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "job-1", "status": "running", "type": "crawl",
+                "url": "https://example.com", "capture_debug": false, "cost_usd": 0.0,
+                "created_at": "2024-01-01T00:00:00Z", "page_count": 2, "queue_position": 0,
+                "token_usage_input": 0, "token_usage_output": 0, "urls_queued": 3,
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .duplicate_crawl_policy(DuplicateCrawlPolicy::ReturnExisting)
+            .build()
+            .unwrap();
+        let request = CrawlRequest {
+            url: "https://example.com".into(),
+            schema: serde_json::json!({"title": "string"}),
+            ..Default::default()
+        };
+
+        let first = client.crawl(request.clone()).await.unwrap();
+        let second = client.crawl(request).await.unwrap();
+        assert_eq!(first.job_id, "job-1");
+        assert_eq!(second.job_id, "job-1");
+        assert_eq!(second.status, "running");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_duplicate_crawls_submit_once() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/crawl"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"job_id": "job-1", "status": "pending"}))
+                    .set_delay(Duration::from_millis(100)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "job-1", "status": "pending", "type": "crawl",
+                "url": "https://example.com", "capture_debug": false, "cost_usd": 0.0,
+                "created_at": "2024-01-01T00:00:00Z", "page_count": 0, "queue_position": 1,
+                "token_usage_input": 0, "token_usage_output": 0, "urls_queued": 0,
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .duplicate_crawl_policy(DuplicateCrawlPolicy::Reject)
+            .build()
+            .unwrap();
+        let request = CrawlRequest {
+            url: "https://example.com".into(),
+            schema: serde_json::json!({"title": "string"}),
+            ..Default::default()
+        };
+
+        let (first, second) = tokio::join!(client.crawl(request.clone()), client.crawl(request));
+        let (created, rejected) = if first.is_ok() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        assert_eq!(created.unwrap().job_id, "job-1");
+        assert!(matches!(rejected, Err(Error::DuplicateCrawl { job_id, .. }) if job_id == "job-1"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_or_stale_serves_last_good_result() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"title": "Hello"}, "fetched_at": "2024-01-01T00:00:00Z",
+                "input_format": "schema", "job_id": "job-1", "url": "https://example.com",
+                "metadata": {"extract_duration_ms": 1, "fetch_duration_ms": 1, "model": "m", "provider": "p"},
+                "usage": {"cost_usd": 0.0, "input_tokens": 1, "is_byok": false, "llm_cost_usd": 0.0, "output_tokens": 1},
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(
+                ResponseTemplate::new(503).set_body_json(serde_json::json!({"error": "down"})),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_json(serde_json::json!({"error": "gone"})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let request = ExtractRequest {
+            url: "https://example.com".into(),
+            schema: serde_json::json!({"title": "string"}),
+            ..Default::default()
+        };
+
+        let fresh = client.extract_or_stale(request.clone()).await.unwrap();
+        assert!(!fresh.stale);
+
+        let stale = client.extract_or_stale(request.clone()).await.unwrap();
+        assert!(stale.stale);
+        assert_eq!(stale.response.data["title"], "Hello");
+        assert!(matches!(stale.error, Some(Error::Api { status: 503, .. })));
+
+        // Errors the API would repeat are not hidden behind stale data.
+        let gone = client.extract_or_stale(request).await;
+        assert!(matches!(gone, Err(Error::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_strict_schema_rejects_mismatched_data() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"title": "Hello", "price": "£5"}, "fetched_at": "2024-01-01T00:00:00Z",
+                "input_format": "schema", "job_id": "job-1", "url": "https://example.com",
+                "metadata": {"extract_duration_ms": 1, "fetch_duration_ms": 1, "model": "m", "provider": "p"},
+                "usage": {"cost_usd": 0.0, "input_tokens": 1, "is_byok": false, "llm_cost_usd": 0.0, "output_tokens": 1},
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .strict_schema(true)
+            .build()
+            .unwrap();
+        let request = ExtractRequest {
+            url: "https://example.com".into(),
+            schema: serde_json::json!({"title": "string", "price": "number"}),
+            ..Default::default()
+        };
+
+        match client.extract(request.clone()).await {
+            Err(Error::SchemaMismatch { violations, data }) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].path, "price");
+                assert_eq!(data["price"], "£5");
+            }
+            other => panic!("expected a schema mismatch, got {:?}", other),
+        }
+
+        let lenient = RequestOptions::default().strict_schema(false);
+        let response = client
+            .with_options(lenient)
+            .extract(request.clone())
+            .await
+            .unwrap();
+        assert_eq!(response.validate(&request.schema).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_extract_converts_json_schema() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(serde_json::json!({
+                "schema": {"title": "string", "tags": ["string"]},
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"title": "Hello", "tags": ["a"]}, "fetched_at": "2024-01-01T00:00:00Z",
+                "input_format": "schema", "job_id": "job-1", "url": "https://example.com",
+                "metadata": {"extract_duration_ms": 1, "fetch_duration_ms": 1, "model": "m", "provider": "p"},
+                "usage": {"cost_usd": 0.0, "input_tokens": 1, "is_byok": false, "llm_cost_usd": 0.0, "output_tokens": 1},
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .strict_schema(true)
+            .build()
+            .unwrap();
+        let response = client
+            .extract(ExtractRequest {
+                url: "https://example.com".into(),
+                schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "title": {"type": "string"},
+                        "tags": {"type": "array", "items": {"type": "string"}},
+                    },
+                }),
+                schema_format: Some(crate::SchemaFormat::JsonSchema),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.data["title"], "Hello");
+
+        let invalid = ExtractRequest {
+            url: "https://example.com".into(),
+            schema: serde_json::json!({"type": "string"}),
+            schema_format: Some(crate::SchemaFormat::JsonSchema),
+            ..Default::default()
+        };
+        assert!(matches!(
+            client.extract(invalid).await,
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_extract_forwards_fetch_options() {
+        use crate::FetchOptions;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(serde_json::json!({
+                "fetch_options": {
+                    "cookies": {"session": "abc123"},
+                    "user_agent": "OrdersBot/1.0",
+                    "proxy_country": "DE",
+                },
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "extract").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        let fetch = FetchOptions::new()
+            .cookie("session", "abc123")
+            .user_agent("OrdersBot/1.0")
+            .proxy_country("de");
+        client
+            .extract(
+                ExtractRequest::builder("https://example.com")
+                    .schema(serde_json::json!({"title": "string"}))
+                    .fetch_options(fetch)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let invalid = ExtractRequest::builder("https://example.com")
+            .schema(serde_json::json!({"title": "string"}))
+            .fetch_options(FetchOptions::new().header("X-Test", "a\nb"))
+            .build();
+        assert!(matches!(
+            client.extract(invalid).await,
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_extract_document() {
+        use crate::{DocumentUpload, ExtractDocumentRequest};
+        use wiremock::matchers::{body_json, body_string_contains, header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract/document"))
+            .and(body_json(serde_json::json!({
+                "url": "https://example.com/invoice.pdf",
+                "schema": {"total": "number"},
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "extract").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract/document"))
+            .and(header_regex(
+                "content-type",
+                "^multipart/form-data; boundary=refyne-",
+            ))
+            .and(body_string_contains("filename=\"invoice.pdf\""))
+            .and(body_string_contains("%PDF-1.7"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "extract").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        let schema = serde_json::json!({"total": "number"});
+        client
+            .extract_document(
+                ExtractDocumentRequest::url("https://example.com/invoice.pdf")
+                    .schema(schema.clone()),
+            )
+            .await
+            .unwrap();
+        client
+            .extract_document(
+                ExtractDocumentRequest::upload(DocumentUpload::new(
+                    "invoice.pdf",
+                    &b"%PDF-1.7"[..],
+                ))
+                .schema(schema),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extract_with_screenshot() {
+        use crate::{ScreenshotFormat, ScreenshotOptions};
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let mut extracted = crate::golden::load("0.1.80", "extract").unwrap();
+        extracted["screenshot_id"] = serde_json::json!("art-1");
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(serde_json::json!({
+                "capture_screenshot": {
+                    "full_page": true,
+                    "viewport": {"width": 1280, "height": 800},
+                    "format": "jpeg",
+                },
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(extracted))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/artifacts/art-1"))
+            .and(header("accept", "image/*"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "image/jpeg")
+                    .set_body_bytes(vec![0xFF, 0xD8, 0xFF]),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .build()
+            .unwrap();
+        let response = client
+            .extract(
+                ExtractRequest::builder("https://example.com")
+                    .schema(serde_json::json!({"title": "string"}))
+                    .capture_screenshot(
+                        ScreenshotOptions::new()
+                            .full_page(true)
+                            .viewport(1280, 800)
+                            .format(ScreenshotFormat::Jpeg),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let id = response.screenshot_id.unwrap();
+        let image = client.get_screenshot(&id).await.unwrap();
+        assert_eq!(&image[..], [0xFF, 0xD8, 0xFF]);
+    }
+
+    #[test]
+    fn test_health_snapshot_starts_empty() {
+        let client = Client::builder("test-key")
+            .health_config(HealthConfig {
+                min_requests: 1,
+                ..Default::default()
+            })
+            .on_degraded(|_| {})
+            .build()
+            .unwrap();
+        let snapshot = client.health_snapshot();
+        assert!(snapshot.endpoints.is_empty());
+        assert!(!snapshot.is_degraded());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_tenant_releases_its_slot() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/job-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/keys"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"keys": []})))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder("test-key")
+            .base_url(server.uri())
+            .cache_enabled(false)
+            .fair_scheduling(FairnessConfig::new(1))
+            .build()
+            .unwrap();
+        let throttled = client.tenant("throttled");
+        let waiting = tokio::spawn(async move { throttled.get_job("job-1").await });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let other = tokio::time::timeout(
+            Duration::from_millis(500),
+            client.tenant("other").list_keys(),
+        )
+        .await;
+        assert!(other.expect("blocked by a rate-limited tenant").is_ok());
+        waiting.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_client_tenant_handle() {
+        let client = Client::builder("test-key")
+            .fair_scheduling(FairnessConfig::new(4).weight("acme", 2))
+            .build()
+            .unwrap();
+        assert_eq!(client.tenant, DEFAULT_TENANT);
+
+        let acme = client.tenant("acme");
+        assert_eq!(acme.tenant, "acme");
+        assert!(Arc::ptr_eq(
+            acme.scheduler.as_ref().unwrap(),
+            client.scheduler.as_ref().unwrap()
+        ));
+    }
+
     #[test]
     fn test_constants() {
         assert_eq!(DEFAULT_BASE_URL, "https://api.refyne.uk");
@@ -0,0 +1,121 @@
+//! Time source for cache expiry and retry backoff.
+//!
+//! The client and [`MemoryCache`](crate::MemoryCache) read the time and sleep
+//! through a [`Clock`]. With the `test-util` feature, a [`MockClock`] can be
+//! injected so tests advance time deterministically and check TTL expiry,
+//! stale-while-revalidate windows, and backoff schedules without real sleeps.
+
+use crate::runtime::{self, BoxFuture, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
+
+#[cfg(any(test, feature = "test-util"))]
+use std::sync::Mutex;
+
+/// Future returned by [`Clock::sleep`].
+pub type Sleep = BoxFuture<'static, ()>;
+
+/// Source of wall-clock time and delays.
+pub trait Clock: Send + Sync {
+    /// Current Unix time in seconds.
+    fn now_unix(&self) -> u64;
+
+    /// Current time since the Unix epoch, as precisely as the clock keeps it.
+    fn now(&self) -> Duration {
+        Duration::from_secs(self.now_unix())
+    }
+
+    /// Wait for a duration.
+    fn sleep(&self, duration: Duration) -> Sleep;
+}
+
+/// The real clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_unix(&self) -> u64 {
+        self.now().as_secs()
+    }
+
+    fn now(&self) -> Duration {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        runtime::sleep(duration)
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Sleeping returns immediately, advances the clock by the requested
+/// duration, and records it, so retry backoff can be inspected with
+/// [`sleeps`](Self::sleeps).
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Default)]
+pub struct MockClock {
+    state: Mutex<MockState>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Default)]
+struct MockState {
+    now: Duration,
+    sleeps: Vec<Duration>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockClock {
+    /// Create a clock set to the given Unix time in seconds.
+    pub fn new(now_unix: u64) -> Self {
+        let clock = Self::default();
+        clock.state.lock().unwrap().now = Duration::from_secs(now_unix);
+        clock
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&self, duration: Duration) {
+        self.state.lock().unwrap().now += duration;
+    }
+
+    /// Durations passed to [`Clock::sleep`], in order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.state.lock().unwrap().sleeps.clone()
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Clock for MockClock {
+    fn now_unix(&self) -> u64 {
+        self.state.lock().unwrap().now.as_secs()
+    }
+
+    fn now(&self) -> Duration {
+        self.state.lock().unwrap().now
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let mut state = self.state.lock().unwrap();
+        state.now += duration;
+        state.sleeps.push(duration);
+        Box::pin(std::future::ready(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_clock() {
+        let clock = MockClock::new(1_000);
+        assert_eq!(clock.now_unix(), 1_000);
+
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(clock.now_unix(), 1_030);
+
+        clock.sleep(Duration::from_millis(1_500)).await;
+        assert_eq!(clock.now_unix(), 1_031);
+        assert_eq!(clock.sleeps(), vec![Duration::from_millis(1_500)]);
+    }
+}
@@ -0,0 +1,283 @@
+//! Job results as Arrow record batches and Parquet files.
+//!
+//! With the `arrow` feature, [`JobResultsExt::to_record_batch`](crate::JobResultsExt::to_record_batch)
+//! converts results into an Arrow `RecordBatch` (arrow 54) whose columns
+//! follow the extraction schema, ready for DataFusion, Polars, or anything
+//! else that reads Arrow. The `parquet` feature adds `write_parquet`:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, JobResultsExt};
+//! use serde_json::json;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let schema = json!({"name": "string", "price": "number", "tags": ["string"]});
+//! let results = client.jobs().get_results("job-id", false).await?;
+//! let batch = results.to_record_batch(&schema)?;
+//! println!("{} rows", batch.num_rows());
+//! # #[cfg(feature = "parquet")]
+//! results.write_parquet("products.parquet", &schema)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each page is a row. `"string"`, `"integer"`, `"number"`, and `"boolean"`
+//! fields become `Utf8`, `Int64`, `Float64`, and `Boolean` columns, nested
+//! objects become structs, and one-item lists become lists. JSON Schema
+//! `properties` and `items` are understood too. Every column is nullable,
+//! and a `url` column holds the page URL unless the schema has its own.
+
+use crate::error::Result;
+use arrow_array::RecordBatch;
+use arrow_schema::{ArrowError, DataType, Field, Fields, Schema, SchemaRef};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Keys that, next to `type`, mark a field's settings rather than its
+/// nested fields.
+const KEYWORDS: &[&str] = &[
+    "description",
+    "properties",
+    "items",
+    "required",
+    "format",
+    "enum",
+    "title",
+];
+
+/// Infer the Arrow schema of job results from an extraction schema.
+///
+/// The schema must describe an object. A nullable `url` column comes first
+/// unless the schema defines `url` itself.
+pub fn arrow_schema(schema: &Value) -> Result<Schema> {
+    let fields = match data_type(schema)? {
+        DataType::Struct(fields) => fields,
+        other => {
+            return Err(ArrowError::SchemaError(format!(
+                "extraction schema must describe an object, not {}",
+                other
+            ))
+            .into())
+        }
+    };
+    let mut columns: Vec<Arc<Field>> = Vec::with_capacity(fields.len() + 1);
+    if fields.find("url").is_none() {
+        columns.push(Arc::new(Field::new("url", DataType::Utf8, true)));
+    }
+    columns.extend(fields.iter().cloned());
+    Ok(Schema::new(columns))
+}
+
+/// Convert pages of results into a record batch with `schema`'s columns.
+pub(crate) fn to_record_batch(items: &[Value], schema: SchemaRef) -> Result<RecordBatch> {
+    let rows: Vec<Value> = items
+        .iter()
+        .map(|item| {
+            let mut row = item.get("data").unwrap_or(item).clone();
+            if let (Value::Object(map), Some(url)) = (&mut row, item.get("url")) {
+                map.entry("url").or_insert_with(|| url.clone());
+            }
+            row
+        })
+        .collect();
+
+    let mut decoder = arrow_json::ReaderBuilder::new(schema.clone())
+        .with_batch_size(rows.len().max(1))
+        .with_coerce_primitive(true)
+        .build_decoder()?;
+    decoder.serialize(&rows)?;
+    Ok(decoder
+        .flush()?
+        .unwrap_or_else(|| RecordBatch::new_empty(schema)))
+}
+
+/// Write a record batch as a Parquet file.
+#[cfg(feature = "parquet")]
+pub(crate) fn write_parquet(batch: &RecordBatch, out: impl std::io::Write + Send) -> Result<()> {
+    let mut writer = parquet::arrow::ArrowWriter::try_new(out, batch.schema(), None)?;
+    writer.write(batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// The Arrow type of a field schema.
+fn data_type(schema: &Value) -> Result<DataType> {
+    Ok(match schema {
+        Value::String(name) => scalar(name),
+        Value::Array(items) => list(match items.first() {
+            Some(item) => data_type(item)?,
+            None => DataType::Utf8,
+        }),
+        Value::Object(map) if is_settings(map) => match &map["type"] {
+            Value::String(name) if name == "object" => {
+                struct_type(map.get("properties").and_then(Value::as_object))?
+            }
+            Value::String(name) if name == "array" => list(match map.get("items") {
+                Some(items) => data_type(items)?,
+                None => DataType::Utf8,
+            }),
+            // `["string", "null"]` in JSON Schema.
+            Value::Array(names) => scalar(
+                names
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .find(|name| *name != "null")
+                    .unwrap_or("string"),
+            ),
+            other => data_type(other)?,
+        },
+        Value::Object(map) => struct_type(Some(map))?,
+        other => {
+            return Err(
+                ArrowError::SchemaError(format!("unsupported field schema: {}", other)).into(),
+            )
+        }
+    })
+}
+
+fn scalar(name: &str) -> DataType {
+    match name {
+        "integer" => DataType::Int64,
+        "number" => DataType::Float64,
+        "boolean" => DataType::Boolean,
+        _ => DataType::Utf8,
+    }
+}
+
+fn list(item: DataType) -> DataType {
+    DataType::List(Arc::new(Field::new_list_field(item, true)))
+}
+
+fn struct_type(fields: Option<&serde_json::Map<String, Value>>) -> Result<DataType> {
+    let fields = fields
+        .into_iter()
+        .flatten()
+        .map(|(name, schema)| Ok(Field::new(name, data_type(schema)?, true)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DataType::Struct(Fields::from(fields)))
+}
+
+/// Whether an object is `{"type": ..., ...}` settings for one field rather
+/// than an object of fields.
+fn is_settings(map: &serde_json::Map<String, Value>) -> bool {
+    map.contains_key("type")
+        && map
+            .keys()
+            .all(|key| key == "type" || KEYWORDS.contains(&key.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Float64Type, Int64Type};
+    use arrow_array::Array;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "name": "string",
+            "price": {"type": {"amount": "number", "currency": "string"}, "description": "Price"},
+            "stock": "integer",
+            "tags": ["string"],
+        })
+    }
+
+    #[test]
+    fn test_arrow_schema_from_extraction_schema() {
+        let schema = arrow_schema(&schema()).unwrap();
+        let columns: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type().clone()))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("url", DataType::Utf8),
+                ("name", DataType::Utf8),
+                (
+                    "price",
+                    DataType::Struct(Fields::from(vec![
+                        Field::new("amount", DataType::Float64, true),
+                        Field::new("currency", DataType::Utf8, true),
+                    ]))
+                ),
+                ("stock", DataType::Int64),
+                ("tags", list(DataType::Utf8)),
+            ]
+        );
+
+        let json_schema = json!({
+            "type": "object",
+            "properties": {
+                "url": {"type": "string"},
+                "rating": {"type": ["number", "null"]},
+                "sizes": {"type": "array", "items": {"type": "integer"}},
+            },
+        });
+        let schema = arrow_schema(&json_schema).unwrap();
+        assert_eq!(schema.fields().len(), 3);
+        assert!(schema.field_with_name("url").is_ok());
+        assert_eq!(
+            schema.field_with_name("rating").unwrap().data_type(),
+            &DataType::Float64
+        );
+        assert_eq!(
+            schema.field_with_name("sizes").unwrap().data_type(),
+            &list(DataType::Int64)
+        );
+
+        assert!(arrow_schema(&json!(["string"])).is_err());
+    }
+
+    #[test]
+    fn test_to_record_batch() {
+        let items = vec![
+            json!({"url": "https://example.com/a", "data": {
+                "name": "Kettle",
+                "price": {"amount": 49.5, "currency": "GBP"},
+                "stock": 3,
+                "tags": ["brew", "steel"],
+            }}),
+            json!({"url": "https://example.com/b", "data": {"name": "Filter", "extra": true}}),
+        ];
+        let schema = Arc::new(arrow_schema(&schema()).unwrap());
+        let batch = to_record_batch(&items, schema).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let urls = batch.column(0).as_string::<i32>();
+        assert_eq!(urls.value(1), "https://example.com/b");
+        let stock = batch.column(3).as_primitive::<Int64Type>();
+        assert_eq!(stock.value(0), 3);
+        assert!(stock.is_null(1));
+        let amount = batch.column(2).as_struct().column(0);
+        assert_eq!(amount.as_primitive::<Float64Type>().value(0), 49.5);
+        let tags = batch.column(4).as_list::<i32>();
+        assert_eq!(tags.value(0).len(), 2);
+
+        let empty = to_record_batch(&[], batch.schema()).unwrap();
+        assert_eq!(empty.num_rows(), 0);
+        assert_eq!(empty.num_columns(), 5);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_write_parquet() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let items = vec![json!({"url": "https://example.com/a", "data": {"name": "Kettle"}})];
+        let schema = Arc::new(arrow_schema(&schema()).unwrap());
+        let batch = to_record_batch(&items, schema).unwrap();
+
+        let path = std::env::temp_dir().join(format!("refyne-{}.parquet", std::process::id()));
+        write_parquet(&batch, std::fs::File::create(&path).unwrap()).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(batches, vec![batch]);
+    }
+}
@@ -0,0 +1,66 @@
+//! Page content returned alongside extracted data.
+//!
+//! Set [`include_content`](crate::ExtractRequestBuilder::include_content) to
+//! get the fetched page back in [`ExtractResponse::content`](crate::ExtractResponse),
+//! to archive the source of the data or to run your own processing on it:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, ContentFormat, ExtractRequest};
+//! use serde_json::json;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let request = ExtractRequest::builder("https://example.com/products/1")
+//!     .schema(json!({"name": "string", "price": "number"}))
+//!     .include_content(ContentFormat::Markdown)
+//!     .build();
+//! let response = client.extract(request).await?;
+//! if let Some(markdown) = &response.content {
+//!     std::fs::write("product-1.md", markdown)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Form of the page content returned with an extraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    /// The HTML as fetched.
+    Html,
+    /// The page's text after cleaning.
+    Text,
+    /// The cleaned page as markdown, as the LLM saw it.
+    Markdown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExtractRequest, ExtractResponse};
+    use serde_json::json;
+
+    #[test]
+    fn test_include_content_serializes() {
+        let request = ExtractRequest::builder("https://example.com")
+            .schema_id("schema-1")
+            .include_content(ContentFormat::Html)
+            .build();
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["include_content"],
+            json!("html")
+        );
+
+        let mut fixture = crate::golden::load("0.1.80", "extract").unwrap();
+        let response: ExtractResponse = serde_json::from_value(fixture.clone()).unwrap();
+        assert!(response.content.is_none());
+        fixture["content"] = json!("<html><h1>Product</h1></html>");
+        let response: ExtractResponse = serde_json::from_value(fixture).unwrap();
+        assert_eq!(
+            response.content.as_deref(),
+            Some("<html><h1>Product</h1></html>")
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! API keys that can change while the client is running.
+//!
+//! A long-running service can rotate its key with
+//! [`Client::set_api_key`](crate::Client::set_api_key), or fetch it on demand
+//! from a secret store by implementing [`ApiKeyProvider`]. Either way the
+//! connection pool, cache, and other client state are kept.
+//!
+//! ```rust,no_run
+//! use refyne::{ApiKeyProvider, BoxFuture, Client};
+//!
+//! struct Vault;
+//!
+//! impl ApiKeyProvider for Vault {
+//!     fn api_key(&self) -> BoxFuture<'_, refyne::Result<String>> {
+//!         Box::pin(async {
+//!             // Read the current key from the secret store, caching it as needed.
+//!             Ok(std::env::var("REFYNE_API_KEY").unwrap_or_default())
+//!         })
+//!     }
+//! }
+//!
+//! # fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("")
+//!     .api_key_provider("acme", Vault)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::runtime::BoxFuture;
+use std::sync::{Arc, RwLock};
+
+/// Supplies the API key sent with each request.
+pub trait ApiKeyProvider: Send + Sync {
+    /// Get the key to send.
+    ///
+    /// Called before every request attempt, so implementations backed by a
+    /// remote store should cache the key. An error fails the request without
+    /// sending it.
+    fn api_key(&self) -> BoxFuture<'_, Result<String>>;
+}
+
+/// The client's API key, shared by all its handles.
+pub(crate) struct ApiKey {
+    current: RwLock<String>,
+    provider: Option<Arc<dyn ApiKeyProvider>>,
+}
+
+impl ApiKey {
+    pub(crate) fn new(key: String, provider: Option<Arc<dyn ApiKeyProvider>>) -> Self {
+        Self {
+            current: RwLock::new(key),
+            provider,
+        }
+    }
+
+    /// Get the key for the next request.
+    pub(crate) async fn get(&self) -> Result<String> {
+        match &self.provider {
+            Some(provider) => provider.api_key().await,
+            None => Ok(self.current.read().unwrap().clone()),
+        }
+    }
+
+    /// Replace the static key.
+    pub(crate) fn set(&self, key: String) {
+        *self.current.write().unwrap() = key;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(&'static str);
+
+    impl ApiKeyProvider for Fixed {
+        fn api_key(&self) -> BoxFuture<'_, Result<String>> {
+            Box::pin(async { Ok(self.0.to_string()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provider_takes_precedence() {
+        let key = ApiKey::new("static".into(), None);
+        key.set("rotated".into());
+        assert_eq!(key.get().await.unwrap(), "rotated");
+
+        let key = ApiKey::new("static".into(), Some(Arc::new(Fixed("vault"))));
+        key.set("rotated".into());
+        assert_eq!(key.get().await.unwrap(), "vault");
+    }
+}
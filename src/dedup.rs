@@ -0,0 +1,199 @@
+//! Duplicate-submission guard for crawl jobs.
+//!
+//! The job listing returned by the API does not carry the schema a crawl was
+//! started with, so the guard tracks crawls submitted through the client in a
+//! local registry keyed by a fingerprint of the seed URL and schema.
+
+use crate::cache::hash_string;
+use crate::types::{CrawlJobCreated, CrawlRequest, Job};
+use crate::types_ext::JobStatus;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// What to do when a crawl identical to a pending or running one is submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateCrawlPolicy {
+    /// Always submit the crawl (default).
+    #[default]
+    Allow,
+    /// Refuse the crawl with [`Error::DuplicateCrawl`](crate::Error::DuplicateCrawl).
+    Reject,
+    /// Return the existing job instead of starting a new one.
+    ReturnExisting,
+}
+
+/// Compute the fingerprint identifying a crawl (seed URLs + schema hash).
+pub fn crawl_fingerprint(request: &CrawlRequest) -> String {
+    // serde_json maps are sorted, so the serialized schema is canonical
+    let schema = serde_json::to_string(&request.schema).unwrap_or_default();
+    let mut seeds = request.url.clone();
+    for url in &request.urls {
+        seeds.push('\n');
+        seeds.push_str(url);
+    }
+    hash_string(&format!("{}\n{}", seeds, hash_string(&schema)))
+}
+
+/// Returns true if the job status means the job is still in progress.
+pub(crate) fn is_active_status(status: &str) -> bool {
+    JobStatus::from(status).is_active()
+}
+
+/// Build a crawl creation response describing an already existing job.
+pub(crate) fn existing_job_response(job: Job) -> CrawlJobCreated {
+    CrawlJobCreated {
+        cost_usd: Some(job.cost_usd),
+        data: None,
+        duration_ms: None,
+        error_message: job.error_message,
+        job_id: job.id,
+        page_count: Some(job.page_count),
+        queue_position: Some(job.queue_position),
+        status: job.status,
+        status_url: None,
+        token_usage: None,
+    }
+}
+
+/// Registry of crawls submitted through a client, keyed by fingerprint.
+#[derive(Debug, Default)]
+pub(crate) struct JobRegistry {
+    jobs: RwLock<HashMap<String, String>>,
+    submitting: Mutex<HashMap<String, Arc<AsyncMutex<()>>>>,
+}
+
+/// Exclusive hold on a fingerprint while a crawl is checked and submitted.
+pub(crate) struct Reservation<'a> {
+    registry: &'a JobRegistry,
+    fingerprint: String,
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        let mut submitting = self.registry.submitting.lock().unwrap();
+        // Only the map and this reservation hold the lock when nobody waits.
+        if submitting
+            .get(&self.fingerprint)
+            .is_some_and(|lock| Arc::strong_count(lock) <= 2)
+        {
+            submitting.remove(&self.fingerprint);
+        }
+    }
+}
+
+impl JobRegistry {
+    /// Reserve a fingerprint, waiting for any other submission of the same
+    /// crawl to record its job first.
+    pub(crate) async fn reserve(&self, fingerprint: &str) -> Reservation<'_> {
+        let lock = self
+            .submitting
+            .lock()
+            .unwrap()
+            .entry(fingerprint.to_string())
+            .or_default()
+            .clone();
+        Reservation {
+            registry: self,
+            fingerprint: fingerprint.to_string(),
+            _guard: lock.lock_owned().await,
+        }
+    }
+
+    /// Get the job ID recorded for a fingerprint.
+    pub(crate) fn get(&self, fingerprint: &str) -> Option<String> {
+        self.jobs.read().unwrap().get(fingerprint).cloned()
+    }
+
+    /// Record the job started for a fingerprint.
+    pub(crate) fn insert(&self, fingerprint: String, job_id: String) {
+        self.jobs.write().unwrap().insert(fingerprint, job_id);
+    }
+
+    /// Forget the job recorded for a fingerprint.
+    pub(crate) fn remove(&self, fingerprint: &str) {
+        self.jobs.write().unwrap().remove(fingerprint);
+    }
+
+    /// Forget a job, whatever its fingerprint.
+    pub(crate) fn remove_job(&self, job_id: &str) {
+        self.jobs.write().unwrap().retain(|_, id| id != job_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(url: &str, schema: serde_json::Value) -> CrawlRequest {
+        CrawlRequest {
+            url: url.into(),
+            schema,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_crawl_fingerprint_is_stable() {
+        let a = request(
+            "https://example.com",
+            json!({"title": "string", "price": "number"}),
+        );
+        let b = request(
+            "https://example.com",
+            json!({"price": "number", "title": "string"}),
+        );
+        assert_eq!(crawl_fingerprint(&a), crawl_fingerprint(&b));
+    }
+
+    #[test]
+    fn test_crawl_fingerprint_differs() {
+        let a = request("https://example.com", json!({"title": "string"}));
+        let b = request("https://example.com/other", json!({"title": "string"}));
+        let c = request("https://example.com", json!({"name": "string"}));
+        assert_ne!(crawl_fingerprint(&a), crawl_fingerprint(&b));
+        assert_ne!(crawl_fingerprint(&a), crawl_fingerprint(&c));
+
+        let mut d = a.clone();
+        d.urls.push("https://example.com/more".into());
+        assert_ne!(crawl_fingerprint(&a), crawl_fingerprint(&d));
+    }
+
+    #[test]
+    fn test_is_active_status() {
+        assert!(is_active_status("pending"));
+        assert!(is_active_status("running"));
+        assert!(!is_active_status("completed"));
+        assert!(!is_active_status("failed"));
+        assert!(!is_active_status("cancelled"));
+    }
+
+    #[test]
+    fn test_job_registry() {
+        let registry = JobRegistry::default();
+        registry.insert("fp".into(), "job-1".into());
+        assert_eq!(registry.get("fp").as_deref(), Some("job-1"));
+        registry.remove("fp");
+        assert!(registry.get("fp").is_none());
+
+        registry.insert("fp".into(), "job-2".into());
+        registry.remove_job("job-2");
+        assert!(registry.get("fp").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reservation_waits_for_holder() {
+        let registry = JobRegistry::default();
+        let held = registry.reserve("fp").await;
+        let mut waiting = Box::pin(registry.reserve("fp"));
+        assert!(futures::poll!(&mut waiting).is_pending());
+        // Other crawls are not held up.
+        drop(registry.reserve("other").await);
+
+        drop(held);
+        assert!(futures::poll!(&mut waiting).is_ready());
+        assert!(registry.submitting.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,367 @@
+//! Typed extraction schemas.
+//!
+//! A [`SchemaDefinition`] is the parsed form of a schema: its fields in
+//! order, with their kinds and descriptions. It converts to and from the
+//! JSON form accepted by [`ExtractRequest::schema`](crate::ExtractRequest::schema),
+//! and with the `yaml` feature to and from the YAML stored in
+//! [`Schema::schema_yaml`](crate::Schema), so a saved schema can be used for
+//! extraction in one step:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, ExtractRequest};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let saved = client.schemas().get("schema-id").await?;
+//! # #[cfg(feature = "yaml")]
+//! let result = client
+//!     .extract(ExtractRequest {
+//!         url: "https://example.com/product".into(),
+//!         schema: saved.as_extract_schema()?,
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// A parsed extraction schema.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDefinition {
+    /// Fields in the order they were defined.
+    pub fields: Vec<FieldDefinition>,
+}
+
+/// One field of a [`SchemaDefinition`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDefinition {
+    /// Name of the field in extracted data.
+    pub name: String,
+    /// What the field holds.
+    pub kind: FieldKind,
+    /// Guidance for extraction, if any.
+    pub description: Option<String>,
+}
+
+/// What a [`FieldDefinition`] holds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    /// `string`
+    String,
+    /// `number`
+    Number,
+    /// `integer`
+    Integer,
+    /// `boolean`
+    Boolean,
+    /// A one-item list: items of one kind.
+    Array(Box<FieldKind>),
+    /// A nested object.
+    Object(SchemaDefinition),
+    /// A type name this SDK version does not know, kept as written.
+    Other(String),
+}
+
+impl SchemaDefinition {
+    /// Parse the JSON form of a schema.
+    ///
+    /// Fails with [`Error::Config`] if the schema is not an object of
+    /// fields, such as a freeform prompt.
+    pub fn from_value(schema: &Value) -> Result<Self> {
+        Self::deserialize(schema).map_err(|e| Error::Config(format!("Invalid schema: {}", e)))
+    }
+
+    /// The JSON form accepted by extraction requests.
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).expect("schema definitions serialize to JSON")
+    }
+
+    /// Parse a schema written as YAML, or as JSON, which YAML includes.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// The schema as YAML, as stored by saved schemas.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Look up a top-level field by name.
+    pub fn field(&self, name: &str) -> Option<&FieldDefinition> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl FieldKind {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "string" => FieldKind::String,
+            "number" => FieldKind::Number,
+            "integer" => FieldKind::Integer,
+            "boolean" => FieldKind::Boolean,
+            other => FieldKind::Other(other.to_string()),
+        }
+    }
+
+    /// The type name of a scalar kind.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            FieldKind::String => Some("string"),
+            FieldKind::Number => Some("number"),
+            FieldKind::Integer => Some("integer"),
+            FieldKind::Boolean => Some("boolean"),
+            FieldKind::Other(name) => Some(name),
+            FieldKind::Array(_) | FieldKind::Object(_) => None,
+        }
+    }
+}
+
+impl Serialize for SchemaDefinition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for field in &self.fields {
+            match &field.description {
+                Some(description) => map.serialize_entry(
+                    &field.name,
+                    &Described {
+                        kind: &field.kind,
+                        description,
+                    },
+                )?,
+                None => map.serialize_entry(&field.name, &field.kind)?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for FieldKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            FieldKind::Array(item) => {
+                let mut seq = serializer.serialize_seq(Some(1))?;
+                seq.serialize_element(item)?;
+                seq.end()
+            }
+            FieldKind::Object(schema) => schema.serialize(serializer),
+            scalar => serializer.serialize_str(scalar.name().unwrap_or_default()),
+        }
+    }
+}
+
+/// `{"type": ..., "description": ...}`
+#[derive(Serialize)]
+struct Described<'a> {
+    #[serde(rename = "type")]
+    kind: &'a FieldKind,
+    description: &'a str,
+}
+
+impl<'de> Deserialize<'de> for SchemaDefinition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match Node::deserialize(deserializer)? {
+            Node::Map(entries) => definition(entries).map_err(de::Error::custom),
+            _ => Err(de::Error::custom("a schema must be an object of fields")),
+        }
+    }
+}
+
+/// A schema as written, before its fields are interpreted.
+enum Node {
+    Name(String),
+    List(Vec<Node>),
+    /// Entries in the order written.
+    Map(Vec<(String, Node)>),
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct NodeVisitor;
+
+        impl<'de> Visitor<'de> for NodeVisitor {
+            type Value = Node;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a type name, a one-item list, or an object of fields")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> std::result::Result<Node, E> {
+                Ok(Node::Name(name.to_string()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Node, A::Error> {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Node::List(items))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Node, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Node::Map(entries))
+            }
+        }
+
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+fn definition(entries: Vec<(String, Node)>) -> std::result::Result<SchemaDefinition, String> {
+    let fields = entries
+        .into_iter()
+        .map(|(name, node)| {
+            let (kind, description) = described(node).map_err(|e| format!("{}: {}", name, e))?;
+            Ok(FieldDefinition {
+                name,
+                kind,
+                description,
+            })
+        })
+        .collect::<std::result::Result<_, String>>()?;
+    Ok(SchemaDefinition { fields })
+}
+
+/// Split `{"type": ..., "description": ...}` into its kind and description.
+fn described(node: Node) -> std::result::Result<(FieldKind, Option<String>), String> {
+    match node {
+        Node::Map(entries)
+            if entries.iter().any(|(key, _)| key == "type")
+                && entries
+                    .iter()
+                    .all(|(key, _)| key == "type" || key == "description") =>
+        {
+            let mut kind = None;
+            let mut description = None;
+            for (key, value) in entries {
+                match (key.as_str(), value) {
+                    ("type", value) => kind = Some(field_kind(value)?),
+                    ("description", Node::Name(text)) => description = Some(text),
+                    _ => return Err("description must be text".to_string()),
+                }
+            }
+            Ok((kind.expect("type is present"), description))
+        }
+        node => Ok((field_kind(node)?, None)),
+    }
+}
+
+fn field_kind(node: Node) -> std::result::Result<FieldKind, String> {
+    match node {
+        Node::Name(name) => Ok(FieldKind::from_name(&name)),
+        Node::List(items) => {
+            let [item]: [Node; 1] = items
+                .try_into()
+                .map_err(|_| "a list must have exactly one item".to_string())?;
+            Ok(FieldKind::Array(Box::new(described(item)?.0)))
+        }
+        Node::Map(entries) => Ok(FieldKind::Object(definition(entries)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn product() -> SchemaDefinition {
+        SchemaDefinition {
+            fields: vec![
+                FieldDefinition {
+                    name: "name".into(),
+                    kind: FieldKind::String,
+                    description: None,
+                },
+                FieldDefinition {
+                    name: "price".into(),
+                    kind: FieldKind::Number,
+                    description: Some("Price in GBP".into()),
+                },
+                FieldDefinition {
+                    name: "reviews".into(),
+                    kind: FieldKind::Array(Box::new(FieldKind::Object(SchemaDefinition {
+                        fields: vec![FieldDefinition {
+                            name: "rating".into(),
+                            kind: FieldKind::Integer,
+                            description: None,
+                        }],
+                    }))),
+                    description: None,
+                },
+                FieldDefinition {
+                    name: "released".into(),
+                    kind: FieldKind::Other("date".into()),
+                    description: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = json!({
+            "name": "string",
+            "price": {"type": "number", "description": "Price in GBP"},
+            "reviews": [{"rating": "integer"}],
+            "released": "date",
+        });
+        let schema = SchemaDefinition::from_value(&value).unwrap();
+        assert_eq!(schema.field("price"), product().field("price"));
+        assert_eq!(schema.fields.len(), 4);
+        assert_eq!(schema.to_value(), value);
+        assert_eq!(product().to_value(), value);
+    }
+
+    #[test]
+    fn test_from_value_rejects_non_schemas() {
+        for bad in [
+            json!("the product name and price"),
+            json!({"tags": ["string", "number"]}),
+            json!({"price": 3}),
+        ] {
+            assert!(
+                matches!(SchemaDefinition::from_value(&bad), Err(Error::Config(_))),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_round_trip() {
+        let yaml = "\
+name: string
+price:
+  type: number
+  description: Price in GBP
+reviews:
+- rating: integer
+released: date
+";
+        let schema = SchemaDefinition::from_yaml(yaml).unwrap();
+        assert_eq!(schema, product());
+        assert_eq!(schema.to_yaml().unwrap(), yaml);
+
+        let json = r#"{"name": "string", "price": "number"}"#;
+        assert_eq!(SchemaDefinition::from_yaml(json).unwrap().fields.len(), 2);
+    }
+}
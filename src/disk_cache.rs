@@ -0,0 +1,363 @@
+//! Response cache persisted to a directory.
+//!
+//! [`DiskCache`] stores each entry as a JSON file, so cached responses
+//! survive restarts of CLI tools and batch jobs. Expired entries are pruned
+//! when the cache is opened, and the oldest entries are evicted once the
+//! files exceed a size limit.
+//!
+//! ```rust,no_run
+//! use refyne::{Client, DiskCache};
+//! use std::sync::Arc;
+//!
+//! # fn run() -> Result<(), refyne::Error> {
+//! let cache = DiskCache::open("/var/cache/refyne", 64 * 1024 * 1024)?;
+//! let client = Client::builder("your-api-key")
+//!     .cache(Arc::new(cache))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cache::{is_fully_expired, Cache, CacheEntry};
+use crate::clock::{Clock, SystemClock};
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tracing::warn;
+
+const ENTRY_EXTENSION: &str = "json";
+
+/// Cache implementation storing entries as files in a directory.
+///
+/// File errors are logged and treated as cache misses. Only one process
+/// should use a directory at a time, since size accounting is kept in memory.
+pub struct DiskCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    index: Mutex<DiskIndex>,
+    clock: Arc<dyn Clock>,
+}
+
+/// Contents of an entry file.
+#[derive(Serialize, Deserialize)]
+struct DiskRecord {
+    key: String,
+    entry: CacheEntry,
+}
+
+/// Cache keys and sizes of the entry files, oldest first.
+#[derive(Debug, Default)]
+struct DiskIndex {
+    files: HashMap<String, IndexedFile>,
+    order: VecDeque<String>,
+    total_bytes: u64,
+}
+
+#[derive(Debug)]
+struct IndexedFile {
+    key: String,
+    size: u64,
+}
+
+impl DiskIndex {
+    fn insert(&mut self, name: String, key: String, size: u64) {
+        self.remove(&name);
+        self.total_bytes += size;
+        self.files.insert(name.clone(), IndexedFile { key, size });
+        self.order.push_back(name);
+    }
+
+    fn remove(&mut self, name: &str) {
+        if let Some(file) = self.files.remove(name) {
+            self.total_bytes -= file.size;
+            self.order.retain(|n| n != name);
+        }
+    }
+}
+
+impl DiskCache {
+    /// Open the cache in `dir`, creating the directory if needed.
+    ///
+    /// Expired entries left by earlier runs are deleted, and the cache is
+    /// trimmed to `max_bytes`.
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64) -> Result<Self> {
+        Self::open_clocked(dir.into(), max_bytes, Arc::new(SystemClock))
+    }
+
+    /// Open the cache, reading the time for expiry checks from a custom clock.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn open_with_clock(
+        dir: impl Into<PathBuf>,
+        max_bytes: u64,
+        clock: Arc<dyn Clock>,
+    ) -> Result<Self> {
+        Self::open_clocked(dir.into(), max_bytes, clock)
+    }
+
+    fn open_clocked(dir: PathBuf, max_bytes: u64, clock: Arc<dyn Clock>) -> Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let cache = Self {
+            dir,
+            max_bytes,
+            index: Mutex::new(DiskIndex::default()),
+            clock,
+        };
+        cache.load()?;
+        Ok(cache)
+    }
+
+    /// Total size of the entry files in bytes.
+    pub fn size_bytes(&self) -> u64 {
+        self.index.lock().unwrap().total_bytes
+    }
+
+    /// Number of entries on disk.
+    pub fn len(&self) -> usize {
+        self.index.lock().unwrap().files.len()
+    }
+
+    /// Returns true if the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Delete entries past their expiry and stale-while-revalidate window.
+    ///
+    /// Returns the number of entries removed.
+    pub fn prune(&self) -> usize {
+        let now = self.clock.now_unix();
+        let names: Vec<String> = self.index.lock().unwrap().order.iter().cloned().collect();
+        names
+            .into_iter()
+            .filter(|name| match self.read(name) {
+                Some(record) if !is_fully_expired(&record.entry, now) => false,
+                _ => {
+                    self.remove(name);
+                    true
+                }
+            })
+            .count()
+    }
+
+    /// Index the entries already on disk, oldest first, then prune and trim.
+    ///
+    /// Files that cannot be read are removed.
+    fn load(&self) -> Result<()> {
+        let mut found = Vec::new();
+        for file in std::fs::read_dir(&self.dir)? {
+            let file = file?;
+            let path = file.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(ENTRY_EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let metadata = file.metadata()?;
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            found.push((modified, name.to_string(), metadata.len()));
+        }
+        found.sort();
+
+        for (_, name, size) in found {
+            match self.read(&name) {
+                Some(record) => self.index.lock().unwrap().insert(name, record.key, size),
+                None => remove_file(&self.path(&name)),
+            }
+        }
+        self.prune();
+        self.evict_to_fit();
+        Ok(())
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name).with_extension(ENTRY_EXTENSION)
+    }
+
+    fn read(&self, name: &str) -> Option<DiskRecord> {
+        let contents = std::fs::read(self.path(name)).ok()?;
+        match serde_json::from_slice(&contents) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warn!(error = %e, "Ignoring unreadable disk cache entry");
+                None
+            }
+        }
+    }
+
+    fn remove(&self, name: &str) {
+        self.index.lock().unwrap().remove(name);
+        remove_file(&self.path(name));
+    }
+
+    /// Delete the oldest entries until the cache fits `max_bytes`.
+    fn evict_to_fit(&self) {
+        loop {
+            let oldest = {
+                let index = self.index.lock().unwrap();
+                if index.total_bytes <= self.max_bytes {
+                    return;
+                }
+                match index.order.front() {
+                    Some(name) => name.clone(),
+                    None => return,
+                }
+            };
+            self.remove(&oldest);
+        }
+    }
+}
+
+/// File name for a cache key.
+fn entry_name(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+fn remove_file(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            warn!(path = %path.display(), error = %e, "Failed to remove disk cache entry");
+        }
+    }
+}
+
+impl Cache for DiskCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let name = entry_name(key);
+        let record = self.read(&name)?;
+        if record.key != key || is_fully_expired(&record.entry, self.clock.now_unix()) {
+            self.remove(&name);
+            return None;
+        }
+        Some(record.entry)
+    }
+
+    fn set(&self, key: &str, entry: CacheEntry) {
+        if entry.cache_control.no_store {
+            return;
+        }
+        let record = DiskRecord {
+            key: key.to_string(),
+            entry,
+        };
+        let Ok(contents) = serde_json::to_vec(&record) else {
+            return;
+        };
+        let name = entry_name(key);
+        let path = self.path(&name);
+        // Write to a temporary file first so readers never see a partial entry.
+        let temp = path.with_extension("tmp");
+        if let Err(e) = std::fs::write(&temp, &contents).and_then(|_| std::fs::rename(&temp, &path))
+        {
+            warn!(path = %path.display(), error = %e, "Failed to write disk cache entry");
+            remove_file(&temp);
+            return;
+        }
+        self.index
+            .lock()
+            .unwrap()
+            .insert(name, record.key, contents.len() as u64);
+        self.evict_to_fit();
+    }
+
+    fn delete(&self, key: &str) {
+        self.remove(&entry_name(key));
+    }
+
+    fn delete_prefix(&self, prefix: &str) {
+        let names: Vec<String> = self
+            .index
+            .lock()
+            .unwrap()
+            .files
+            .iter()
+            .filter(|(_, file)| file.key.starts_with(prefix))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in names {
+            self.remove(&name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheControlDirectives;
+    use crate::clock::MockClock;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn entry(value: &str, expires_at: u64) -> CacheEntry {
+        CacheEntry {
+            value: json!({ "value": value }),
+            expires_at,
+            cache_control: CacheControlDirectives::default(),
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("refyne-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_entries_survive_reopen_and_expire() {
+        let dir = temp_dir("disk-cache-reopen");
+        let clock = Arc::new(MockClock::new(1_000));
+
+        let cache = DiskCache::open_with_clock(&dir, u64::MAX, clock.clone()).unwrap();
+        cache.set("GET:/a", entry("a", 1_060));
+        cache.set("GET:/b", entry("b", 1_010));
+        assert_eq!(cache.get("GET:/a").unwrap().value["value"], "a");
+        drop(cache);
+
+        clock.advance(Duration::from_secs(30));
+        let cache = DiskCache::open_with_clock(&dir, u64::MAX, clock.clone()).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("GET:/a").unwrap().value["value"], "a");
+        assert!(cache.get("GET:/b").is_none());
+
+        clock.advance(Duration::from_secs(60));
+        assert!(cache.get("GET:/a").is_none());
+        assert!(cache.is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_evicts_oldest_beyond_size_limit() {
+        let dir = temp_dir("disk-cache-evict");
+        let clock = Arc::new(MockClock::new(1_000));
+        let size = serde_json::to_vec(&DiskRecord {
+            key: "GET:/a".into(),
+            entry: entry("a", 2_000),
+        })
+        .unwrap()
+        .len() as u64;
+
+        let cache = DiskCache::open_with_clock(&dir, size * 2, clock).unwrap();
+        cache.set("GET:/a", entry("a", 2_000));
+        cache.set("GET:/b", entry("b", 2_000));
+        cache.set("GET:/c", entry("c", 2_000));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.size_bytes() <= size * 2);
+        assert!(cache.get("GET:/a").is_none());
+        assert!(cache.get("GET:/c").is_some());
+
+        cache.delete("GET:/c");
+        assert!(cache.get("GET:/c").is_none());
+        assert_eq!(cache.len(), 1);
+
+        cache.delete_prefix("GET:/");
+        assert!(cache.is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,281 @@
+//! Extracting from documents.
+//!
+//! [`Client::extract_document`](crate::Client::extract_document) extracts
+//! from a PDF or Word document with the same schemas as web pages. The
+//! document is either at a URL the API fetches, or uploaded from memory:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, DocumentUpload, ExtractDocumentRequest};
+//! use serde_json::json;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let schema = json!({"invoice_number": "string", "total": "number"});
+//!
+//! let remote = ExtractDocumentRequest::url("https://example.com/invoice-1001.pdf")
+//!     .schema(schema.clone());
+//! let invoice = client.extract_document(remote).await?;
+//!
+//! let bytes = std::fs::read("invoice-1002.pdf")?;
+//! let local = ExtractDocumentRequest::upload(DocumentUpload::new("invoice-1002.pdf", bytes))
+//!     .schema(schema);
+//! let invoice = client.extract_document(local).await?;
+//! println!("{}", invoice.data["total"]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Uploads are sent as `multipart/form-data`, with the request as JSON in a
+//! `request` part and the document in a `file` part.
+
+use crate::error::{Error, Result};
+use crate::json_schema::SchemaFormat;
+use crate::types::LLMConfigInput;
+use bytes::Bytes;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// A document uploaded for extraction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentUpload {
+    /// File name, sent to the API.
+    pub filename: String,
+    /// MIME type of the document.
+    pub content_type: String,
+    /// The document.
+    pub data: Bytes,
+}
+
+impl DocumentUpload {
+    /// A document named `filename`, with its type guessed from the extension.
+    pub fn new(filename: impl Into<String>, data: impl Into<Bytes>) -> Self {
+        let filename = filename.into();
+        Self {
+            content_type: content_type_for(&filename).to_string(),
+            filename,
+            data: data.into(),
+        }
+    }
+
+    /// Send the document as this MIME type instead.
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+}
+
+/// MIME type for a document file name.
+fn content_type_for(filename: &str) -> &'static str {
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("pdf") => "application/pdf",
+        Some("docx") => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        Some("doc") => "application/msword",
+        _ => "application/octet-stream",
+    }
+}
+
+/// A document to extract from, and how.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExtractDocumentRequest {
+    /// URL of the document, when the API fetches it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The document, when it is uploaded.
+    #[serde(skip)]
+    pub file: Option<DocumentUpload>,
+    /// Schema, its YAML, or a prompt in plain language, as for web pages.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub schema: Value,
+    /// How `schema` is written. See [`SchemaFormat`].
+    #[serde(skip)]
+    pub schema_format: Option<SchemaFormat>,
+    /// ID of a saved schema to use instead of an inline schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<String>,
+    /// Top-level schema fields to extract. All when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// Override the account's LLM configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_config: Option<LLMConfigInput>,
+    /// Store the raw LLM request and response for troubleshooting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_debug: Option<bool>,
+}
+
+impl ExtractDocumentRequest {
+    /// Extract from the document at `url`, such as a PDF link.
+    pub fn url(url: impl Into<String>) -> Self {
+        Self {
+            url: Some(url.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Extract from an uploaded document.
+    pub fn upload(file: DocumentUpload) -> Self {
+        Self {
+            file: Some(file),
+            ..Default::default()
+        }
+    }
+
+    /// Extract with an inline schema, its YAML, or a prompt.
+    pub fn schema(mut self, schema: impl Into<Value>) -> Self {
+        self.schema = schema.into();
+        self
+    }
+
+    /// Say how the schema is written, rather than detecting it.
+    pub fn schema_format(mut self, format: SchemaFormat) -> Self {
+        self.schema_format = Some(format);
+        self
+    }
+
+    /// Extract with a saved schema.
+    pub fn schema_id(mut self, id: impl Into<String>) -> Self {
+        self.schema_id = Some(id.into());
+        self
+    }
+
+    /// Only extract these top-level schema fields.
+    pub fn fields<S: Into<String>>(mut self, fields: impl IntoIterator<Item = S>) -> Self {
+        self.fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Override the account's LLM configuration.
+    pub fn llm_config(mut self, config: LLMConfigInput) -> Self {
+        self.llm_config = Some(config);
+        self
+    }
+
+    /// Fail with [`Error::Config`] unless exactly one of a URL or an upload
+    /// is set.
+    pub(crate) fn check(&self) -> Result<()> {
+        match (&self.url, &self.file) {
+            (Some(_), None) | (None, Some(_)) => Ok(()),
+            (None, None) => Err(Error::Config(
+                "ExtractDocumentRequest needs a document URL or upload".into(),
+            )),
+            (Some(_), Some(_)) => Err(Error::Config(
+                "ExtractDocumentRequest takes a document URL or an upload, not both".into(),
+            )),
+        }
+    }
+
+    /// The content type and `multipart/form-data` body of an upload, or
+    /// `None` when the document is at a URL.
+    pub(crate) fn multipart(&self) -> Result<Option<(String, Vec<u8>)>> {
+        let Some(file) = &self.file else {
+            return Ok(None);
+        };
+        // Derived from the document so the same upload has the same body,
+        // which recorded cassettes match on.
+        let digest = Sha256::digest(&file.data);
+        let boundary = format!("refyne-{}", hex::encode(&digest[..12]));
+        let mut body = Vec::with_capacity(file.data.len() + 1024);
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"request\"\r\n\
+                 Content-Type: application/json\r\n\r\n",
+                boundary
+            )
+            .as_bytes(),
+        );
+        serde_json::to_writer(&mut body, self)?;
+        body.extend_from_slice(
+            format!(
+                "\r\n--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\
+                 Content-Type: {}\r\n\r\n",
+                boundary,
+                quote(&file.filename),
+                file.content_type
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(&file.data);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+        Ok(Some((
+            format!("multipart/form-data; boundary={}", boundary),
+            body,
+        )))
+    }
+}
+
+/// Escape a file name for a quoted `Content-Disposition` parameter, as
+/// browsers do.
+fn quote(filename: &str) -> String {
+    filename
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_upload_guesses_content_type() {
+        assert_eq!(
+            DocumentUpload::new("Invoice.PDF", vec![]).content_type,
+            "application/pdf"
+        );
+        assert_eq!(
+            DocumentUpload::new("notes", vec![]).content_type,
+            "application/octet-stream"
+        );
+        assert_eq!(
+            DocumentUpload::new("scan.bin", vec![])
+                .content_type("image/tiff")
+                .content_type,
+            "image/tiff"
+        );
+    }
+
+    #[test]
+    fn test_multipart_body() {
+        let request = ExtractDocumentRequest::upload(DocumentUpload::new(
+            "a \"quoted\".pdf",
+            &b"%PDF-1.7"[..],
+        ))
+        .schema(json!({"total": "number"}));
+        assert!(request.check().is_ok());
+
+        let (content_type, body) = request.multipart().unwrap().unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        let expected = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"request\"\r\n\
+             Content-Type: application/json\r\n\r\n\
+             {{\"schema\":{{\"total\":\"number\"}}}}\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a %22quoted%22.pdf\"\r\n\
+             Content-Type: application/pdf\r\n\r\n\
+             %PDF-1.7\r\n--{b}--\r\n",
+            b = boundary
+        );
+        assert_eq!(String::from_utf8(body).unwrap(), expected);
+
+        let remote = ExtractDocumentRequest::url("https://example.com/a.pdf");
+        assert!(remote.multipart().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_check_needs_one_source() {
+        let neither = ExtractDocumentRequest::default();
+        let both = ExtractDocumentRequest {
+            file: Some(DocumentUpload::new("a.pdf", vec![])),
+            ..ExtractDocumentRequest::url("https://example.com/a.pdf")
+        };
+        for request in [neither, both] {
+            assert!(matches!(request.check(), Err(Error::Config(_))));
+        }
+    }
+}
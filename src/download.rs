@@ -0,0 +1,106 @@
+//! Resumable downloads of job results.
+//!
+//! [`Client::download_results`](crate::Client::download_results) fetches the
+//! results file in ranged parts into a `.part` file next to the destination,
+//! picks up where it left off after a failure, and renames the file into
+//! place once complete. The file's `ETag` (or `Last-Modified`) is kept in a
+//! `.part.validator` file and sent as `If-Range`, so a file that changed on
+//! the server is downloaded again from the start instead of being spliced.
+
+use reqwest::header::{HeaderMap, CONTENT_RANGE, ETAG, LAST_MODIFIED};
+use std::path::{Path, PathBuf};
+
+/// Bytes requested per range request.
+pub(crate) const DOWNLOAD_PART_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Path of the partial file used while downloading to `path`.
+pub(crate) fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Path of the file holding the validator of the partial file.
+pub(crate) fn validator_path(path: &Path) -> PathBuf {
+    let mut name = part_path(path).into_os_string();
+    name.push(".validator");
+    PathBuf::from(name)
+}
+
+/// Validator to send as `If-Range` when resuming: the strong `ETag`, or else
+/// `Last-Modified`.
+pub(crate) fn range_validator(headers: &HeaderMap) -> Option<String> {
+    let etag = headers.get(ETAG).and_then(|v| v.to_str().ok());
+    match etag {
+        // Weak validators cannot be used with If-Range.
+        Some(etag) if !etag.starts_with("W/") => Some(etag.to_string()),
+        _ => headers
+            .get(LAST_MODIFIED)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+    }
+}
+
+/// `Range` header value for the part starting at `offset`.
+pub(crate) fn range_header(offset: u64) -> String {
+    format!("bytes={}-{}", offset, offset + DOWNLOAD_PART_SIZE - 1)
+}
+
+/// Total size from a `Content-Range` header (`bytes 0-99/1234` or
+/// `bytes */1234`), if known.
+pub(crate) fn content_range_total(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit('/')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/tmp/results.jsonl")),
+            PathBuf::from("/tmp/results.jsonl.part")
+        );
+    }
+
+    #[test]
+    fn test_range_validator() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(range_validator(&headers), None);
+
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2026 07:28:00 GMT"),
+        );
+        headers.insert(ETAG, HeaderValue::from_static("W/\"weak\""));
+        assert_eq!(
+            range_validator(&headers).as_deref(),
+            Some("Wed, 21 Oct 2026 07:28:00 GMT")
+        );
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        assert_eq!(range_validator(&headers).as_deref(), Some("\"v1\""));
+    }
+
+    #[test]
+    fn test_content_range_total() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_range_total(&headers), None);
+
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-99/1234"));
+        assert_eq!(content_range_total(&headers), Some(1234));
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes */500"));
+        assert_eq!(content_range_total(&headers), Some(500));
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-99/*"));
+        assert_eq!(content_range_total(&headers), None);
+    }
+}
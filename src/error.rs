@@ -1,11 +1,124 @@
 //! Error types for the Refyne SDK.
 
+use crate::meta::request_id;
 use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type for Refyne operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Callback mapping an error code to a user-facing message.
+pub type ErrorMessages = std::sync::Arc<dyn Fn(ErrorCode) -> Option<String> + Send + Sync>;
+
+/// Stable classification of an [`Error`](enum@Error), for mapping to
+/// user-facing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// The request was rejected as invalid.
+    Validation,
+    /// The API key was missing or invalid.
+    Authentication,
+    /// The account may not perform the operation.
+    Forbidden,
+    /// The resource does not exist.
+    NotFound,
+    /// Too many requests.
+    RateLimited,
+    /// The API failed while handling the request.
+    Server,
+    /// Any other API error response.
+    Api,
+    /// The API version is not supported by this SDK.
+    UnsupportedApiVersion,
+    /// The API could not be reached.
+    Network,
+    /// A request or response could not be (de)serialized.
+    Serialization,
+    /// The client was misconfigured.
+    Configuration,
+    /// The request timed out.
+    Timeout,
+    /// A local file could not be read or written.
+    Io,
+    /// An identical crawl is already running.
+    DuplicateCrawl,
+    /// Extracted data did not match its schema.
+    SchemaMismatch,
+    /// A schema update would break consumers of the schema.
+    BreakingSchemaChange,
+    /// Requests are paused because the API keeps failing.
+    CircuitOpen,
+    /// A webhook delivery failed signature verification.
+    InvalidSignature,
+    /// The account's monthly budget is spent.
+    BudgetExceeded,
+}
+
+/// Machine-readable reason sent by the API in an error's `code` field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ApiErrorCode {
+    /// The account's usage quota is used up.
+    QuotaExceeded,
+    /// The account has no credits left.
+    InsufficientCredits,
+    /// The account's monthly budget is spent.
+    BudgetExceeded,
+    /// The extraction schema is invalid.
+    InvalidSchema,
+    /// The target URL is invalid or not allowed.
+    InvalidUrl,
+    /// The target site refused access.
+    FetchBlocked,
+    /// The target page could not be fetched.
+    FetchFailed,
+    /// The page was fetched but data could not be extracted.
+    ExtractionFailed,
+    /// A code this SDK version does not know.
+    Other(String),
+}
+
+impl ApiErrorCode {
+    /// The code as sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiErrorCode::QuotaExceeded => "quota_exceeded",
+            ApiErrorCode::InsufficientCredits => "insufficient_credits",
+            ApiErrorCode::BudgetExceeded => "budget_exceeded",
+            ApiErrorCode::InvalidSchema => "invalid_schema",
+            ApiErrorCode::InvalidUrl => "invalid_url",
+            ApiErrorCode::FetchBlocked => "fetch_blocked",
+            ApiErrorCode::FetchFailed => "fetch_failed",
+            ApiErrorCode::ExtractionFailed => "extraction_failed",
+            ApiErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for ApiErrorCode {
+    fn from(code: &str) -> Self {
+        match code.to_ascii_lowercase().as_str() {
+            "quota_exceeded" => ApiErrorCode::QuotaExceeded,
+            "insufficient_credits" => ApiErrorCode::InsufficientCredits,
+            "budget_exceeded" => ApiErrorCode::BudgetExceeded,
+            "invalid_schema" => ApiErrorCode::InvalidSchema,
+            "invalid_url" => ApiErrorCode::InvalidUrl,
+            "fetch_blocked" => ApiErrorCode::FetchBlocked,
+            "fetch_failed" => ApiErrorCode::FetchFailed,
+            "extraction_failed" => ApiErrorCode::ExtractionFailed,
+            _ => ApiErrorCode::Other(code.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Error types for the Refyne SDK.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -18,6 +131,10 @@ pub enum Error {
         message: String,
         /// Additional detail
         detail: Option<String>,
+        /// Machine-readable reason, if the API sent one
+        code: Option<ApiErrorCode>,
+        /// ID the API assigned to the request
+        request_id: Option<String>,
     },
 
     /// Rate limit exceeded.
@@ -27,6 +144,12 @@ pub enum Error {
         retry_after: u64,
         /// Error message
         message: String,
+        /// Retries made before giving up
+        retries: u32,
+        /// Total time spent waiting between those retries
+        waited: Duration,
+        /// ID the API assigned to the request
+        request_id: Option<String>,
     },
 
     /// Request validation failed.
@@ -36,19 +159,55 @@ pub enum Error {
         message: String,
         /// Field-level errors
         errors: HashMap<String, Vec<String>>,
+        /// Machine-readable reason, if the API sent one
+        code: Option<ApiErrorCode>,
+        /// ID the API assigned to the request
+        request_id: Option<String>,
+    },
+
+    /// The account's monthly budget is spent; see
+    /// [`UsageClient::set_budget`](crate::UsageClient::set_budget).
+    ///
+    /// Built from the `budget_exceeded` code, or from a 402 response without
+    /// a more specific quota code such as `quota_exceeded`.
+    #[error("Budget exceeded: {message}")]
+    BudgetExceeded {
+        /// Error message
+        message: String,
+        /// The monthly budget in USD, if the API sent it
+        monthly_usd: Option<f64>,
+        /// USD spent this month, if the API sent it
+        spent_usd: Option<f64>,
+        /// ID the API assigned to the request
+        request_id: Option<String>,
     },
 
     /// Authentication failed.
-    #[error("Authentication failed: {0}")]
-    Authentication(String),
+    #[error("Authentication failed: {message}")]
+    Authentication {
+        /// Error message
+        message: String,
+        /// ID the API assigned to the request
+        request_id: Option<String>,
+    },
 
     /// Access forbidden.
-    #[error("Access forbidden: {0}")]
-    Forbidden(String),
+    #[error("Access forbidden: {message}")]
+    Forbidden {
+        /// Error message
+        message: String,
+        /// ID the API assigned to the request
+        request_id: Option<String>,
+    },
 
     /// Resource not found.
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("Not found: {message}")]
+    NotFound {
+        /// Error message
+        message: String,
+        /// ID the API assigned to the request
+        request_id: Option<String>,
+    },
 
     /// API version is incompatible with this SDK.
     #[error("Unsupported API version {api_version}. This SDK requires >= {min_version}")]
@@ -69,19 +228,206 @@ pub enum Error {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// Response data did not match the requested type.
+    #[error("Could not deserialize response data: {source}")]
+    Deserialize {
+        /// The underlying serde error
+        #[source]
+        source: serde_json::Error,
+        /// The data that failed to deserialize
+        payload: serde_json::Value,
+    },
+
+    /// MessagePack deserialization error.
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack error: {0}")]
+    MsgPack(#[from] rmp_serde::decode::Error),
+
+    /// Arrow conversion error.
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    /// Parquet encoding error.
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    /// Object store error.
+    #[cfg(feature = "object-store")]
+    #[error("Object store error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    /// YAML error.
+    #[cfg(feature = "yaml")]
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    /// Local file error.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Configuration error.
     #[error("Configuration error: {0}")]
     Config(String),
 
-    /// Request timeout.
+    /// A deadline passed, such as while waiting for a job.
     #[error("Request timed out")]
     Timeout,
+
+    /// No connection could be established in time.
+    #[error("Connection timed out")]
+    ConnectTimeout,
+
+    /// The API did not respond in time after the request was sent.
+    #[error("Timed out waiting for the response")]
+    ReadTimeout {
+        /// False for POST and PATCH requests, which the API may still have
+        /// processed. Repeating those can run and bill them twice.
+        idempotent: bool,
+    },
+
+    /// An identical crawl is already pending or running.
+    #[error("Duplicate crawl: job {job_id} is already {status}")]
+    DuplicateCrawl {
+        /// ID of the existing job
+        job_id: String,
+        /// Status of the existing job
+        status: String,
+    },
+
+    /// Extracted data did not match the request's schema.
+    #[error("Extracted data does not match the schema: {}", violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    SchemaMismatch {
+        /// Each place the data differs from the schema
+        violations: Vec<crate::SchemaViolation>,
+        /// The extracted data
+        data: serde_json::Value,
+    },
+
+    /// A schema update removes or retypes fields and was not forced.
+    #[error("Updating schema {schema_id} would break its consumers:\n{diff}")]
+    BreakingSchemaChange {
+        /// The schema that was not updated
+        schema_id: String,
+        /// What the update would have changed
+        diff: crate::schemas::SchemaDiff,
+    },
+
+    /// Requests are paused after repeated failures.
+    #[error("Circuit open after repeated failures. Retry after {retry_after} seconds")]
+    CircuitOpen {
+        /// Seconds until a trial request is let through
+        retry_after: u64,
+    },
+
+    /// A webhook delivery failed signature verification.
+    #[error("Invalid webhook signature: {0}")]
+    InvalidSignature(String),
 }
 
 impl Error {
+    /// Create a [`Error::Deserialize`] for data that did not match a type.
+    pub(crate) fn deserialize(source: serde_json::Error, payload: serde_json::Value) -> Self {
+        Error::Deserialize { source, payload }
+    }
+
+    /// Get the stable code for this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::Api { status, .. } if *status >= 500 => ErrorCode::Server,
+            Error::Api { .. } => ErrorCode::Api,
+            Error::RateLimit { .. } => ErrorCode::RateLimited,
+            Error::Validation { .. } => ErrorCode::Validation,
+            Error::BudgetExceeded { .. } => ErrorCode::BudgetExceeded,
+            Error::Authentication { .. } => ErrorCode::Authentication,
+            Error::Forbidden { .. } => ErrorCode::Forbidden,
+            Error::NotFound { .. } => ErrorCode::NotFound,
+            Error::UnsupportedApiVersion { .. } => ErrorCode::UnsupportedApiVersion,
+            Error::Http(_) => ErrorCode::Network,
+            Error::Json(_) | Error::Deserialize { .. } => ErrorCode::Serialization,
+            #[cfg(feature = "msgpack")]
+            Error::MsgPack(_) => ErrorCode::Serialization,
+            #[cfg(feature = "yaml")]
+            Error::Yaml(_) => ErrorCode::Serialization,
+            #[cfg(feature = "arrow")]
+            Error::Arrow(_) => ErrorCode::Serialization,
+            #[cfg(feature = "parquet")]
+            Error::Parquet(_) => ErrorCode::Serialization,
+            Error::Io(_) => ErrorCode::Io,
+            #[cfg(feature = "object-store")]
+            Error::ObjectStore(_) => ErrorCode::Io,
+            Error::Config(_) => ErrorCode::Configuration,
+            Error::Timeout | Error::ConnectTimeout | Error::ReadTimeout { .. } => {
+                ErrorCode::Timeout
+            }
+            Error::DuplicateCrawl { .. } => ErrorCode::DuplicateCrawl,
+            Error::SchemaMismatch { .. } => ErrorCode::SchemaMismatch,
+            Error::BreakingSchemaChange { .. } => ErrorCode::BreakingSchemaChange,
+            Error::CircuitOpen { .. } => ErrorCode::CircuitOpen,
+            Error::InvalidSignature(_) => ErrorCode::InvalidSignature,
+        }
+    }
+
+    /// Get the machine-readable reason sent by the API, if any.
+    pub fn api_code(&self) -> Option<&ApiErrorCode> {
+        match self {
+            Error::Api { code, .. } | Error::Validation { code, .. } => code.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the same request may succeed when retried later.
+    ///
+    /// Covers rate limiting, timeouts, network errors, an open circuit, and
+    /// server errors other than exhausted quotas. Read timeouts of POST and
+    /// PATCH requests are not retryable, since the API may have processed
+    /// them.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::RateLimit { .. }
+            | Error::Timeout
+            | Error::ConnectTimeout
+            | Error::CircuitOpen { .. }
+            | Error::Http(_) => true,
+            Error::ReadTimeout { idempotent } => *idempotent,
+            Error::Api { status, .. } => (*status >= 500 || *status == 408) && !self.is_quota(),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the account's quota, credits, or budget are used up.
+    pub fn is_quota(&self) -> bool {
+        matches!(
+            self,
+            Error::Api { status: 402, .. } | Error::BudgetExceeded { .. }
+        ) || matches!(
+            self.api_code(),
+            Some(ApiErrorCode::QuotaExceeded | ApiErrorCode::InsufficientCredits)
+        )
+    }
+
+    /// Get the ID the API assigned to the failed request.
+    ///
+    /// Set for errors built from an API response that carried an
+    /// `X-Request-Id` header. Quote it in support tickets.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Error::Api { request_id, .. }
+            | Error::RateLimit { request_id, .. }
+            | Error::Validation { request_id, .. }
+            | Error::BudgetExceeded { request_id, .. }
+            | Error::Authentication { request_id, .. }
+            | Error::Forbidden { request_id, .. }
+            | Error::NotFound { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
     /// Create an API error from a response.
     pub(crate) async fn from_response(response: reqwest::Response) -> Self {
         let status = response.status().as_u16();
+        let request_id = request_id(response.headers());
 
         // Try to get retry-after header for rate limiting
         let retry_after = response
@@ -93,31 +439,63 @@ impl Error {
 
         // Try to parse error body
         let body: std::result::Result<ErrorResponse, _> = response.json().await;
-        let (message, detail, errors) = match body {
+        let (message, detail, errors, code, spend) = match body {
             Ok(err) => (
                 err.error.unwrap_or_else(|| "Unknown error".into()),
                 err.detail,
                 err.errors,
+                err.code.as_deref().map(ApiErrorCode::from),
+                (err.monthly_usd, err.spent_usd),
             ),
-            Err(_) => ("Unknown error".into(), None, None),
+            Err(_) => ("Unknown error".into(), None, None, None, (None, None)),
+        };
+
+        let budget = match &code {
+            Some(ApiErrorCode::BudgetExceeded) => true,
+            Some(ApiErrorCode::QuotaExceeded | ApiErrorCode::InsufficientCredits) => false,
+            _ => status == 402,
         };
+        if budget {
+            return Error::BudgetExceeded {
+                message,
+                monthly_usd: spend.0,
+                spent_usd: spend.1,
+                request_id,
+            };
+        }
 
         match status {
             400 => Error::Validation {
                 message,
                 errors: errors.unwrap_or_default(),
+                code,
+                request_id,
+            },
+            401 => Error::Authentication {
+                message,
+                request_id,
+            },
+            403 => Error::Forbidden {
+                message,
+                request_id,
+            },
+            404 => Error::NotFound {
+                message,
+                request_id,
             },
-            401 => Error::Authentication(message),
-            403 => Error::Forbidden(message),
-            404 => Error::NotFound(message),
             429 => Error::RateLimit {
                 retry_after,
                 message,
+                retries: 0,
+                waited: Duration::ZERO,
+                request_id,
             },
             _ => Error::Api {
                 status,
                 message,
                 detail,
+                code,
+                request_id,
             },
         }
     }
@@ -127,7 +505,10 @@ impl Error {
 struct ErrorResponse {
     error: Option<String>,
     detail: Option<String>,
+    code: Option<String>,
     errors: Option<HashMap<String, Vec<String>>>,
+    monthly_usd: Option<f64>,
+    spent_usd: Option<f64>,
 }
 
 #[cfg(test)]
@@ -140,6 +521,8 @@ mod tests {
             status: 500,
             message: "Internal server error".into(),
             detail: Some("Something went wrong".into()),
+            code: None,
+            request_id: None,
         };
         assert!(err.to_string().contains("500"));
         assert!(err.to_string().contains("Internal server error"));
@@ -150,6 +533,9 @@ mod tests {
         let err = Error::RateLimit {
             retry_after: 30,
             message: "Too many requests".into(),
+            retries: 0,
+            waited: Duration::ZERO,
+            request_id: None,
         };
         assert!(err.to_string().contains("30"));
         assert!(err.to_string().contains("Rate limited"));
@@ -162,26 +548,37 @@ mod tests {
         let err = Error::Validation {
             message: "Invalid input".into(),
             errors,
+            code: None,
+            request_id: None,
         };
         assert!(err.to_string().contains("Validation error"));
     }
 
     #[test]
     fn test_authentication_error_display() {
-        let err = Error::Authentication("Invalid API key".into());
+        let err = Error::Authentication {
+            message: "Invalid API key".into(),
+            request_id: None,
+        };
         assert!(err.to_string().contains("Authentication failed"));
         assert!(err.to_string().contains("Invalid API key"));
     }
 
     #[test]
     fn test_forbidden_error_display() {
-        let err = Error::Forbidden("Insufficient permissions".into());
+        let err = Error::Forbidden {
+            message: "Insufficient permissions".into(),
+            request_id: None,
+        };
         assert!(err.to_string().contains("Access forbidden"));
     }
 
     #[test]
     fn test_not_found_error_display() {
-        let err = Error::NotFound("Job not found".into());
+        let err = Error::NotFound {
+            message: "Job not found".into(),
+            request_id: None,
+        };
         assert!(err.to_string().contains("Not found"));
     }
 
@@ -209,12 +606,80 @@ mod tests {
         assert!(err.to_string().contains("timed out"));
     }
 
+    #[test]
+    fn test_duplicate_crawl_error_display() {
+        let err = Error::DuplicateCrawl {
+            job_id: "job-123".into(),
+            status: "running".into(),
+        };
+        assert!(err.to_string().contains("job-123"));
+        assert!(err.to_string().contains("running"));
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let server = Error::Api {
+            status: 503,
+            message: "Unavailable".into(),
+            detail: None,
+            code: None,
+            request_id: None,
+        };
+        assert_eq!(server.code(), ErrorCode::Server);
+        assert_eq!(Error::Timeout.code(), ErrorCode::Timeout);
+        assert_eq!(
+            Error::NotFound {
+                message: "Job not found".into(),
+                request_id: None,
+            }
+            .code(),
+            ErrorCode::NotFound
+        );
+    }
+
+    #[test]
+    fn test_api_error_codes() {
+        assert_eq!(
+            ApiErrorCode::from("quota_exceeded"),
+            ApiErrorCode::QuotaExceeded
+        );
+        assert_eq!(
+            ApiErrorCode::from("FETCH_BLOCKED"),
+            ApiErrorCode::FetchBlocked
+        );
+        assert_eq!(ApiErrorCode::from("new_code").as_str(), "new_code");
+
+        let api = |status, code: Option<&str>| Error::Api {
+            status,
+            message: "failed".into(),
+            detail: None,
+            code: code.map(ApiErrorCode::from),
+            request_id: None,
+        };
+        assert!(api(503, None).is_retryable());
+        assert!(!api(503, None).is_quota());
+        assert!(api(503, Some("quota_exceeded")).is_quota());
+        assert!(!api(503, Some("quota_exceeded")).is_retryable());
+        assert!(api(402, None).is_quota());
+        assert_eq!(
+            ApiErrorCode::from("budget_exceeded"),
+            ApiErrorCode::BudgetExceeded
+        );
+        assert!(!api(422, Some("invalid_schema")).is_retryable());
+        assert_eq!(
+            api(422, Some("invalid_schema")).api_code(),
+            Some(&ApiErrorCode::InvalidSchema)
+        );
+    }
+
     #[test]
     fn test_error_is_debug() {
         let err = Error::Api {
             status: 404,
             message: "Not found".into(),
             detail: None,
+            code: None,
+            request_id: None,
         };
         // Ensure Debug is implemented
         let debug_str = format!("{:?}", err);
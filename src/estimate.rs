@@ -0,0 +1,194 @@
+//! Cost estimates for extractions and crawls.
+//!
+//! [`Client::estimate`](crate::Client::estimate) asks the API what a request
+//! would cost before running it. The API samples a few pages, weighs the
+//! schema and the model, and returns ranges of pages, tokens, and USD, so an
+//! expensive crawl can be held back in code:
+//!
+//! ```rust,no_run
+//! use refyne::{CrawlRequest, EstimateRequest};
+//! use serde_json::json;
+//!
+//! # async fn run(client: refyne::Client) -> Result<(), refyne::Error> {
+//! let crawl = CrawlRequest::builder("https://shop.example.com", json!({"title": "string"}))
+//!     .max_pages(500)
+//!     .build();
+//! let estimate = client.estimate(EstimateRequest::from(&crawl)).await?;
+//! if estimate.within_budget(5.0) {
+//!     client.crawl(crawl).await?;
+//! } else {
+//!     println!("up to ${:.2}, not crawling", estimate.cost_usd.high);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::json_schema::SchemaFormat;
+use crate::types::{CrawlOptions, CrawlRequest, ExtractRequest, LLMConfigInput};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An extraction or crawl to estimate the cost of.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EstimateRequest {
+    /// URL to extract from, or the crawl's first seed.
+    pub url: String,
+    /// Further crawl seeds.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
+    /// Schema, its YAML, or a prompt in plain language.
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub schema: Value,
+    /// How `schema` is written. See [`SchemaFormat`].
+    #[serde(skip)]
+    pub schema_format: Option<SchemaFormat>,
+    /// ID of a saved schema to use instead of an inline schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<String>,
+    /// Crawl options, for a crawl. `None` estimates a single extraction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crawl: Option<CrawlOptions>,
+    /// Model to estimate for, instead of the account's.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_config: Option<LLMConfigInput>,
+    /// Pages to sample. The API picks a number when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_pages: Option<u32>,
+}
+
+impl EstimateRequest {
+    /// Estimate extracting the page at `url`.
+    pub fn extract(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Estimate crawling from `url` with `options`.
+    pub fn crawl(url: impl Into<String>, options: CrawlOptions) -> Self {
+        Self {
+            url: url.into(),
+            crawl: Some(options),
+            ..Default::default()
+        }
+    }
+
+    /// Estimate with an inline schema, its YAML, or a prompt.
+    pub fn schema(mut self, schema: impl Into<Value>) -> Self {
+        self.schema = schema.into();
+        self
+    }
+
+    /// Estimate with a saved schema.
+    pub fn schema_id(mut self, id: impl Into<String>) -> Self {
+        self.schema_id = Some(id.into());
+        self
+    }
+
+    /// Estimate for this model instead of the account's.
+    pub fn llm_config(mut self, config: LLMConfigInput) -> Self {
+        self.llm_config = Some(config);
+        self
+    }
+
+    /// Sample this many pages. More pages give a narrower range, and take
+    /// longer.
+    pub fn sample_pages(mut self, pages: u32) -> Self {
+        self.sample_pages = Some(pages);
+        self
+    }
+}
+
+impl From<&ExtractRequest> for EstimateRequest {
+    fn from(request: &ExtractRequest) -> Self {
+        Self {
+            url: request.url.clone(),
+            schema: request.schema.clone(),
+            schema_format: request.schema_format,
+            schema_id: request.schema_id.clone(),
+            llm_config: request.llm_config.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&CrawlRequest> for EstimateRequest {
+    fn from(request: &CrawlRequest) -> Self {
+        Self {
+            url: request.url.clone(),
+            urls: request.urls.clone(),
+            schema: request.schema.clone(),
+            crawl: Some(
+                request
+                    .options
+                    .clone()
+                    .unwrap_or_else(|| CrawlOptions::builder().build()),
+            ),
+            llm_config: request.llm_config.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A range from the cheapest to the most expensive likely outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EstimateRange<T> {
+    /// Low end of the range.
+    pub low: T,
+    /// High end of the range.
+    pub high: T,
+}
+
+/// Projected usage and cost of a request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Estimate {
+    /// Pages that would be extracted.
+    pub pages: EstimateRange<u64>,
+    /// Pages the API sampled for the estimate.
+    pub sampled_pages: u32,
+    /// Fields in the schema, nested fields included.
+    pub schema_fields: u32,
+    /// Model the estimate is for.
+    pub model: String,
+    /// LLM input tokens.
+    pub input_tokens: EstimateRange<u64>,
+    /// LLM output tokens.
+    pub output_tokens: EstimateRange<u64>,
+    /// USD charged.
+    pub cost_usd: EstimateRange<f64>,
+}
+
+impl Estimate {
+    /// Returns true if even the high end of the cost range is at most
+    /// `max_usd`.
+    pub fn within_budget(&self, max_usd: f64) -> bool {
+        self.cost_usd.high <= max_usd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_from_crawl() {
+        let crawl = CrawlRequest::builder("https://example.com", json!({"title": "string"}))
+            .max_pages(50)
+            .build();
+        let request = serde_json::to_value(EstimateRequest::from(&crawl).sample_pages(3)).unwrap();
+        assert_eq!(request["url"], "https://example.com");
+        assert_eq!(request["schema"], json!({"title": "string"}));
+        assert_eq!(request["crawl"]["max_pages"], 50);
+        assert_eq!(request["sample_pages"], 3);
+
+        let extract =
+            EstimateRequest::from(&ExtractRequest::builder("https://example.com").build());
+        assert_eq!(
+            serde_json::to_value(&extract).unwrap(),
+            json!({"url": "https://example.com"})
+        );
+    }
+}
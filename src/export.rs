@@ -0,0 +1,193 @@
+//! Writing job results to JSONL and CSV files.
+//!
+//! With the `export` feature, [`JobResultsExt`](crate::JobResultsExt) gains
+//! `write_jsonl` and `write_csv`:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, CsvOptions, JobResultsExt};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let results = client.jobs().get_results("job-id", false).await?;
+//! results.write_jsonl("products.jsonl")?;
+//! results.write_csv_with("products.csv", &CsvOptions { key_separator: "_".into(), ..Default::default() })?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! CSV files have one row per page. Nested objects and arrays are flattened
+//! into columns named by their path, such as `price.amount` or `tags.0`,
+//! and a `url` column holds the page URL when the results include it.
+
+use crate::error::Result;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+/// How results are laid out as CSV.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Joins the keys of nested values into a column name.
+    pub key_separator: String,
+    /// Separates fields within a row.
+    pub delimiter: char,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            key_separator: ".".into(),
+            delimiter: ',',
+        }
+    }
+}
+
+/// Write each page as a line of JSON.
+pub(crate) fn write_jsonl(items: &[Value], mut out: impl Write) -> Result<()> {
+    for item in items {
+        serde_json::to_writer(&mut out, item)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Write the pages as CSV, one row each.
+pub(crate) fn write_csv(items: &[Value], options: &CsvOptions, mut out: impl Write) -> Result<()> {
+    let mut columns = Vec::new();
+    let mut seen = HashSet::new();
+    let rows: Vec<HashMap<String, String>> = items
+        .iter()
+        .map(|item| {
+            let mut cells = Vec::new();
+            if let Some(url) = item.get("url").and_then(Value::as_str) {
+                cells.push(("url".to_string(), url.to_string()));
+            }
+            let data = item.get("data").unwrap_or(item);
+            flatten(data, None, &options.key_separator, &mut cells);
+            for (column, _) in &cells {
+                if seen.insert(column.clone()) {
+                    columns.push(column.clone());
+                }
+            }
+            cells.into_iter().collect()
+        })
+        .collect();
+
+    write_row(
+        &mut out,
+        columns.iter().map(String::as_str),
+        options.delimiter,
+    )?;
+    for row in &rows {
+        let cells = columns
+            .iter()
+            .map(|column| row.get(column).map_or("", String::as_str));
+        write_row(&mut out, cells, options.delimiter)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Flatten a value into `(column, text)` cells.
+fn flatten(value: &Value, path: Option<&str>, separator: &str, cells: &mut Vec<(String, String)>) {
+    let join = |key: &str| match path {
+        Some(path) => format!("{}{}{}", path, separator, key),
+        None => key.to_string(),
+    };
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                flatten(value, Some(&join(key)), separator, cells);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, value) in items.iter().enumerate() {
+                flatten(value, Some(&join(&index.to_string())), separator, cells);
+            }
+        }
+        scalar => {
+            let text = match scalar {
+                Value::Null | Value::Object(_) | Value::Array(_) => String::new(),
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            cells.push((path.unwrap_or("value").to_string(), text));
+        }
+    }
+}
+
+fn write_row<'a>(
+    out: &mut impl Write,
+    cells: impl Iterator<Item = &'a str>,
+    delimiter: char,
+) -> Result<()> {
+    let line: Vec<String> = cells.map(|cell| quote(cell, delimiter)).collect();
+    out.write_all(line.join(delimiter.encode_utf8(&mut [0; 4])).as_bytes())?;
+    out.write_all(b"\r\n")?;
+    Ok(())
+}
+
+/// Quote a field if it contains the delimiter, a quote, or a line break.
+fn quote(cell: &str, delimiter: char) -> String {
+    if cell.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn results() -> Vec<Value> {
+        vec![
+            json!({"url": "https://example.com/a", "data": {
+                "name": "Kettle, gooseneck",
+                "price": {"amount": 49.5, "currency": "GBP"},
+                "tags": ["brew", "steel"],
+            }}),
+            json!({"url": "https://example.com/b", "data": {
+                "name": "Filter \"V60\"",
+                "price": {"amount": null},
+                "stock": 3,
+            }}),
+        ]
+    }
+
+    #[test]
+    fn test_write_csv_flattens_nested_values() {
+        let mut out = Vec::new();
+        write_csv(&results(), &CsvOptions::default(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "url,name,price.amount,price.currency,tags.0,tags.1,stock\r\n\
+             https://example.com/a,\"Kettle, gooseneck\",49.5,GBP,brew,steel,\r\n\
+             https://example.com/b,\"Filter \"\"V60\"\"\",,,,,3\r\n"
+        );
+
+        let options = CsvOptions {
+            key_separator: "_".into(),
+            delimiter: ';',
+        };
+        let mut out = Vec::new();
+        write_csv(&results()[..1], &options, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("url;name;price_amount;price_currency;tags_0;tags_1\r\n"));
+        assert!(out.contains(";Kettle, gooseneck;"));
+    }
+
+    #[test]
+    fn test_write_jsonl() {
+        let mut out = Vec::new();
+        write_jsonl(&results(), &mut out).unwrap();
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, results());
+    }
+}
@@ -0,0 +1,294 @@
+//! Weighted fair sharing of a client between tenants.
+//!
+//! Multi-tenant services can bound the number of in-flight requests and have
+//! the client hand out free slots by weighted fair queuing, so a tenant
+//! submitting a large batch cannot starve everyone else. Requests are tagged
+//! with a tenant via [`Client::tenant`](crate::Client::tenant). A request
+//! waiting to be retried, for example after a 429, gives its slot up until it
+//! is sent again.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Tenant used for requests that were not tagged with one.
+pub const DEFAULT_TENANT: &str = "";
+
+/// Configuration for weighted fair scheduling.
+#[derive(Debug, Clone)]
+pub struct FairnessConfig {
+    /// Maximum requests in flight across all tenants.
+    pub max_concurrent: usize,
+    /// Per-tenant weights. A tenant with weight 2 gets twice the share of a
+    /// tenant with weight 1 when requests are queued.
+    pub weights: HashMap<String, u32>,
+    /// Weight for tenants not listed in `weights`.
+    pub default_weight: u32,
+}
+
+impl FairnessConfig {
+    /// Create a configuration with the given concurrency limit.
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent,
+            weights: HashMap::new(),
+            default_weight: 1,
+        }
+    }
+
+    /// Set the weight for a tenant.
+    pub fn weight(mut self, tenant: impl Into<String>, weight: u32) -> Self {
+        self.weights.insert(tenant.into(), weight);
+        self
+    }
+
+    fn weight_of(&self, tenant: &str) -> f64 {
+        let weight = self
+            .weights
+            .get(tenant)
+            .copied()
+            .unwrap_or(self.default_weight);
+        weight.max(1) as f64
+    }
+}
+
+impl Default for FairnessConfig {
+    fn default() -> Self {
+        Self::new(8)
+    }
+}
+
+struct Waiter {
+    finish: f64,
+    seq: u64,
+    tx: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    // BinaryHeap is a max-heap; invert so the smallest finish time pops first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .finish
+            .total_cmp(&self.finish)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+struct State {
+    available: usize,
+    virtual_time: f64,
+    last_finish: HashMap<String, f64>,
+    waiters: BinaryHeap<Waiter>,
+    seq: u64,
+}
+
+/// Weighted fair queue guarding the client's request slots.
+pub(crate) struct FairScheduler {
+    config: FairnessConfig,
+    state: Mutex<State>,
+}
+
+impl FairScheduler {
+    pub(crate) fn new(config: FairnessConfig) -> Self {
+        let available = config.max_concurrent.max(1);
+        Self {
+            config,
+            state: Mutex::new(State {
+                available,
+                virtual_time: 0.0,
+                last_finish: HashMap::new(),
+                waiters: BinaryHeap::new(),
+                seq: 0,
+            }),
+        }
+    }
+
+    /// Wait for a request slot on behalf of a tenant.
+    pub(crate) async fn acquire(self: &Arc<Self>, tenant: &str) -> FairPermit {
+        let rx = {
+            let mut state = self.state.lock().unwrap();
+            let start = state
+                .last_finish
+                .get(tenant)
+                .copied()
+                .unwrap_or(0.0)
+                .max(state.virtual_time);
+            let finish = start + 1.0 / self.config.weight_of(tenant);
+            state.last_finish.insert(tenant.to_string(), finish);
+
+            if state.available > 0 && state.waiters.is_empty() {
+                state.available -= 1;
+                None
+            } else {
+                let (tx, rx) = oneshot::channel();
+                state.seq += 1;
+                let seq = state.seq;
+                state.waiters.push(Waiter { finish, seq, tx });
+                Some(rx)
+            }
+        };
+
+        if let Some(rx) = rx {
+            // Hands the slot back if this future is dropped mid-handoff.
+            let mut handoff = Handoff {
+                scheduler: self.clone(),
+                rx: Some(rx),
+            };
+            if let Some(rx) = &mut handoff.rx {
+                // The sender is only dropped after handing over the slot.
+                let _ = rx.await;
+            }
+            handoff.rx = None;
+        }
+
+        FairPermit {
+            scheduler: self.clone(),
+        }
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(waiter) = state.waiters.pop() {
+            if waiter.finish > state.virtual_time {
+                state.virtual_time = waiter.finish;
+                // Finish times behind the virtual clock no longer matter.
+                let now = state.virtual_time;
+                state.last_finish.retain(|_, finish| *finish > now);
+            }
+            if waiter.tx.send(()).is_ok() {
+                return;
+            }
+        }
+        state.available += 1;
+        if state.available >= self.config.max_concurrent.max(1) {
+            // Idle: no tenant has a backlog left to account for.
+            state.last_finish.clear();
+            state.virtual_time = 0.0;
+        }
+    }
+
+    #[cfg(test)]
+    fn queued(&self) -> usize {
+        self.state.lock().unwrap().waiters.len()
+    }
+}
+
+/// A queued [`FairScheduler::acquire`] waiting for a slot to be handed over.
+struct Handoff {
+    scheduler: Arc<FairScheduler>,
+    rx: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for Handoff {
+    fn drop(&mut self) {
+        if let Some(mut rx) = self.rx.take() {
+            rx.close();
+            // A slot handed over before the close would otherwise leak.
+            if rx.try_recv().is_ok() {
+                self.scheduler.release();
+            }
+        }
+    }
+}
+
+/// A request slot, released when dropped.
+pub(crate) struct FairPermit {
+    scheduler: Arc<FairScheduler>,
+}
+
+impl Drop for FairPermit {
+    fn drop(&mut self) {
+        self.scheduler.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    #[test]
+    fn test_weights() {
+        let config = FairnessConfig::new(2).weight("big", 3);
+        assert_eq!(config.weight_of("big"), 3.0);
+        assert_eq!(config.weight_of("other"), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_without_contention() {
+        let scheduler = Arc::new(FairScheduler::new(FairnessConfig::new(2)));
+        let a = scheduler.acquire("a").await;
+        let b = scheduler.acquire("b").await;
+        assert_eq!(scheduler.queued(), 0);
+        drop(a);
+        drop(b);
+        assert_eq!(scheduler.state.lock().unwrap().available, 2);
+    }
+
+    #[tokio::test]
+    async fn test_fair_ordering_between_tenants() {
+        let scheduler = Arc::new(FairScheduler::new(FairnessConfig::new(1)));
+        let order = Arc::new(StdMutex::new(Vec::new()));
+        let held = scheduler.acquire("batch").await;
+
+        let mut handles = Vec::new();
+        // The batch tenant queues several requests before the interactive one.
+        for tenant in ["batch", "batch", "batch", "interactive"] {
+            let task_scheduler = scheduler.clone();
+            let order = order.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = task_scheduler.acquire(tenant).await;
+                order.lock().unwrap().push(tenant);
+            }));
+            while scheduler.queued() < handles.len() {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        drop(held);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let order = order.lock().unwrap();
+        assert!(scheduler.state.lock().unwrap().last_finish.is_empty());
+        let interactive = order.iter().position(|t| *t == "interactive").unwrap();
+        assert!(
+            interactive <= 1,
+            "interactive tenant was starved: {:?}",
+            order
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_waiter_returns_its_slot() {
+        let scheduler = Arc::new(FairScheduler::new(FairnessConfig::new(1)));
+        let held = scheduler.acquire("a").await;
+
+        let mut waiting = Box::pin(scheduler.acquire("b"));
+        assert!(futures::poll!(&mut waiting).is_pending());
+        assert_eq!(scheduler.queued(), 1);
+
+        // The slot is handed to the waiter, which is dropped before it runs.
+        drop(held);
+        drop(waiting);
+        assert_eq!(scheduler.state.lock().unwrap().available, 1);
+
+        let _permit = scheduler.acquire("c").await;
+    }
+}
@@ -0,0 +1,169 @@
+//! How the API fetches the target page.
+//!
+//! Some sites only show their content to a logged-in session, a particular
+//! browser, or visitors from one country. [`FetchOptions`] on an extract or
+//! crawl request passes headers, cookies, a user agent, a referer, and a
+//! proxy country on to the fetch of each page:
+//!
+//! ```rust
+//! use refyne::{ExtractRequest, FetchOptions};
+//!
+//! let request = ExtractRequest::builder("https://shop.example.com/account/orders")
+//!     .schema_id("orders")
+//!     .fetch_options(
+//!         FetchOptions::new()
+//!             .cookie("session", "abc123")
+//!             .header("Accept-Language", "de-DE")
+//!             .user_agent("Mozilla/5.0 (compatible; OrdersBot/1.0)")
+//!             .proxy_country("DE"),
+//!     )
+//!     .build();
+//! // Header and cookie values are redacted from Debug output.
+//! assert!(!format!("{:?}", request).contains("abc123"));
+//! ```
+
+use crate::error::{Error, Result};
+use crate::secret::SecretString;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Headers, cookies, and location used to fetch the target page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchOptions {
+    /// Extra request headers, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, SecretString>,
+    /// Cookies sent to the target site, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cookies: BTreeMap<String, SecretString>,
+    /// `User-Agent` to fetch with instead of the API's own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// `Referer` to fetch with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referer: Option<String>,
+    /// ISO 3166-1 alpha-2 country to fetch from through a proxy, such as `DE`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_country: Option<String>,
+}
+
+impl FetchOptions {
+    /// Fetch the way the API does by default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send a header, replacing any earlier value for `name`.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<SecretString>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Send a cookie, replacing any earlier value for `name`.
+    pub fn cookie(mut self, name: impl Into<String>, value: impl Into<SecretString>) -> Self {
+        self.cookies.insert(name.into(), value.into());
+        self
+    }
+
+    /// Fetch with this `User-Agent`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Fetch with this `Referer`.
+    pub fn referer(mut self, referer: impl Into<String>) -> Self {
+        self.referer = Some(referer.into());
+        self
+    }
+
+    /// Fetch through a proxy in this country, such as `DE` or `us`.
+    pub fn proxy_country(mut self, country: impl Into<String>) -> Self {
+        self.proxy_country = Some(country.into().to_ascii_uppercase());
+        self
+    }
+
+    /// Fail with [`Error::Config`] on a header or cookie that cannot be sent,
+    /// or a country that is not a two-letter code.
+    pub(crate) fn check(&self) -> Result<()> {
+        let mut names = self.headers.keys().chain(self.cookies.keys());
+        if let Some(name) = names.find(|name| !is_token(name)) {
+            return Err(Error::Config(format!(
+                "Invalid header or cookie name {:?} in FetchOptions",
+                name
+            )));
+        }
+        let values = self.headers.values().chain(self.cookies.values());
+        if values
+            .map(SecretString::expose_secret)
+            .any(|value| value.contains(['\r', '\n']))
+        {
+            return Err(Error::Config(
+                "Header and cookie values in FetchOptions cannot contain line breaks".into(),
+            ));
+        }
+        if let Some(country) = &self.proxy_country {
+            if country.len() != 2 || !country.bytes().all(|b| b.is_ascii_alphabetic()) {
+                return Err(Error::Config(format!(
+                    "Invalid proxy country {:?}: expected a two-letter code such as \"DE\"",
+                    country
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// True if `name` is an HTTP token, as header and cookie names must be.
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fetch_options_serialize() {
+        assert_eq!(
+            serde_json::to_value(FetchOptions::new()).unwrap(),
+            json!({})
+        );
+
+        let options = FetchOptions::new()
+            .header("Accept-Language", "de-DE")
+            .cookie("session", "abc123")
+            .referer("https://example.com/")
+            .proxy_country("de");
+        assert!(options.check().is_ok());
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({
+                "headers": {"Accept-Language": "de-DE"},
+                "cookies": {"session": "abc123"},
+                "referer": "https://example.com/",
+                "proxy_country": "DE",
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_rejects_unsendable_options() {
+        for options in [
+            FetchOptions::new().header("Bad Header", "x"),
+            FetchOptions::new().cookie("", "x"),
+            FetchOptions::new().header("X-Test", "a\r\nInjected: yes"),
+            FetchOptions::new().proxy_country("DEU"),
+        ] {
+            assert!(
+                matches!(options.check(), Err(Error::Config(_))),
+                "{:?}",
+                options
+            );
+        }
+    }
+}
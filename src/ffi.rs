@@ -0,0 +1,353 @@
+//! C-compatible bindings.
+//!
+//! Enabled with the `ffi` feature. The functions here wrap [`Client`] behind an
+//! opaque handle and exchange requests and responses as JSON strings, so they
+//! can be exposed to Swift, Kotlin, or Python through a header generated by
+//! cbindgen. Each handle owns a single-threaded runtime and blocks the calling
+//! thread until the request completes.
+//!
+//! Functions returning `*mut c_char` return null on failure; the error
+//! message is then available from [`refyne_last_error`] on the same thread.
+//! Returned strings must be released with [`refyne_string_free`].
+//!
+//! The symbols are exported when this crate is linked into a `cdylib` or
+//! `staticlib`.
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::progress::Polling;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Opaque client handle.
+pub struct RefyneClient {
+    client: Client,
+    runtime: Runtime,
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+/// Read a borrowed C string argument.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str> {
+    if ptr.is_null() {
+        return Err(Error::Config(format!("{} must not be null", name)));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| Error::Config(format!("{} is not valid UTF-8", name)))
+}
+
+/// Turn a result into an owned JSON string, or null with the error recorded.
+fn into_json<T: Serialize>(result: Result<T>) -> *mut c_char {
+    let json = result.and_then(|value| Ok(serde_json::to_string(&value)?));
+    match json {
+        Ok(json) => {
+            clear_last_error();
+            CString::new(json).map_or(std::ptr::null_mut(), CString::into_raw)
+        }
+        Err(e) => {
+            set_last_error(e.to_string());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Run a call against a client handle.
+unsafe fn with_client<T, F>(handle: *const RefyneClient, call: F) -> *mut c_char
+where
+    T: Serialize,
+    F: FnOnce(&RefyneClient) -> Result<T>,
+{
+    match handle.as_ref() {
+        Some(handle) => into_json(call(handle)),
+        None => into_json::<()>(Err(Error::Config("client must not be null".into()))),
+    }
+}
+
+/// Create a client.
+///
+/// `base_url` may be null to use the default. Returns null on failure.
+///
+/// # Safety
+///
+/// `api_key` must be a valid NUL-terminated string; `base_url` must be null
+/// or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_client_new(
+    api_key: *const c_char,
+    base_url: *const c_char,
+) -> *mut RefyneClient {
+    let build = || -> Result<RefyneClient> {
+        let mut builder = Client::builder(read_str(api_key, "api_key")?);
+        if !base_url.is_null() {
+            builder = builder.base_url(read_str(base_url, "base_url")?);
+        }
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| Error::Config(format!("cannot start runtime: {}", e)))?;
+        Ok(RefyneClient {
+            client: builder.build()?,
+            runtime,
+        })
+    };
+
+    match build() {
+        Ok(handle) => {
+            clear_last_error();
+            Box::into_raw(Box::new(handle))
+        }
+        Err(e) => {
+            set_last_error(e.to_string());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Free a client created by [`refyne_client_new`].
+///
+/// # Safety
+///
+/// `handle` must be null or a pointer returned by [`refyne_client_new`] that
+/// has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_client_free(handle: *mut RefyneClient) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Extract data from a page.
+///
+/// Takes an `ExtractRequest` as JSON and returns an `ExtractResponse` as JSON.
+///
+/// # Safety
+///
+/// `handle` must be a live client handle and `request_json` a valid
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_extract(
+    handle: *const RefyneClient,
+    request_json: *const c_char,
+) -> *mut c_char {
+    with_client(handle, |h| {
+        let request = serde_json::from_str(read_str(request_json, "request_json")?)?;
+        h.runtime.block_on(h.client.extract(request))
+    })
+}
+
+/// Start a crawl job.
+///
+/// Takes a `CrawlRequest` as JSON and returns a `CrawlJobCreated` as JSON.
+///
+/// # Safety
+///
+/// `handle` must be a live client handle and `request_json` a valid
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_crawl(
+    handle: *const RefyneClient,
+    request_json: *const c_char,
+) -> *mut c_char {
+    with_client(handle, |h| {
+        let request = serde_json::from_str(read_str(request_json, "request_json")?)?;
+        h.runtime.block_on(h.client.crawl(request))
+    })
+}
+
+/// Get a job by ID, returned as a `Job` in JSON.
+///
+/// # Safety
+///
+/// `handle` must be a live client handle and `job_id` a valid NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_get_job(
+    handle: *const RefyneClient,
+    job_id: *const c_char,
+) -> *mut c_char {
+    with_client(handle, |h| {
+        let id = read_str(job_id, "job_id")?;
+        h.runtime.block_on(h.client.get_job(id))
+    })
+}
+
+/// Poll a job until it leaves the pending and running states.
+///
+/// Returns the final `Job` as JSON, or null with [`Error::Timeout`] recorded
+/// if it is still active after `timeout_ms`.
+///
+/// # Safety
+///
+/// `handle` must be a live client handle and `job_id` a valid NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_wait_for_job(
+    handle: *const RefyneClient,
+    job_id: *const c_char,
+    poll_interval_ms: u64,
+    timeout_ms: u64,
+) -> *mut c_char {
+    with_client(handle, |h| {
+        let id = read_str(job_id, "job_id")?;
+        let mut polling = Polling::every(Duration::from_millis(poll_interval_ms));
+        h.runtime.block_on(async {
+            tokio::time::timeout(
+                Duration::from_millis(timeout_ms),
+                h.client.wait_for_job(id, &mut polling),
+            )
+            .await
+            .map_err(|_| Error::Timeout)?
+        })
+    })
+}
+
+/// Get job results as JSON, optionally merged into a single object.
+///
+/// # Safety
+///
+/// `handle` must be a live client handle and `job_id` a valid NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_get_job_results(
+    handle: *const RefyneClient,
+    job_id: *const c_char,
+    merge: bool,
+) -> *mut c_char {
+    with_client(handle, |h| {
+        let id = read_str(job_id, "job_id")?;
+        h.runtime.block_on(h.client.get_job_results(id, merge))
+    })
+}
+
+/// Message for the last failed call on this thread, or null.
+///
+/// The pointer stays valid until the next call on the same thread.
+#[no_mangle]
+pub extern "C" fn refyne_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Free a string returned by this library.
+///
+/// # Safety
+///
+/// `value` must be null or a string returned by a `refyne_*` function that has
+/// not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn refyne_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn c(value: &str) -> CString {
+        CString::new(value).unwrap()
+    }
+
+    unsafe fn take(value: *mut c_char) -> String {
+        assert!(!value.is_null(), "call failed: {:?}", last_error());
+        let owned = CStr::from_ptr(value).to_str().unwrap().to_string();
+        refyne_string_free(value);
+        owned
+    }
+
+    fn last_error() -> Option<String> {
+        let ptr = refyne_last_error();
+        (!ptr.is_null()).then(|| unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_client_new_requires_api_key() {
+        let handle = unsafe { refyne_client_new(std::ptr::null(), std::ptr::null()) };
+        assert!(handle.is_null());
+        assert!(last_error().unwrap().contains("api_key"));
+
+        let key = c("");
+        let handle = unsafe { refyne_client_new(key.as_ptr(), std::ptr::null()) };
+        assert!(handle.is_null());
+        assert!(last_error().is_some());
+    }
+
+    #[test]
+    fn test_get_job_and_wait() {
+        let server_runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = server_runtime.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/api/v1/jobs/job_1"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "capture_debug": false,
+                    "completed_at": null,
+                    "cost_usd": 0.0,
+                    "created_at": "2026-01-01T00:00:00Z",
+                    "error_category": null,
+                    "error_message": null,
+                    "id": "job_1",
+                    "page_count": 2,
+                    "queue_position": 0,
+                    "started_at": null,
+                    "status": "completed",
+                    "token_usage_input": 0,
+                    "token_usage_output": 0,
+                    "type": "crawl",
+                    "url": "https://example.com",
+                    "urls_queued": 2
+                })))
+                .mount(&server)
+                .await;
+            server
+        });
+
+        let key = c("test-key");
+        let url = c(&server.uri());
+        let job_id = c("job_1");
+        unsafe {
+            let handle = refyne_client_new(key.as_ptr(), url.as_ptr());
+            assert!(!handle.is_null());
+
+            let job: serde_json::Value =
+                serde_json::from_str(&take(refyne_get_job(handle, job_id.as_ptr()))).unwrap();
+            assert_eq!(job["id"], "job_1");
+
+            let job: serde_json::Value = serde_json::from_str(&take(refyne_wait_for_job(
+                handle,
+                job_id.as_ptr(),
+                10,
+                1000,
+            )))
+            .unwrap();
+            assert_eq!(job["status"], "completed");
+            assert!(last_error().is_none());
+
+            assert!(refyne_get_job(handle, std::ptr::null()).is_null());
+            assert!(last_error().unwrap().contains("job_id"));
+
+            refyne_client_free(handle);
+        }
+    }
+}
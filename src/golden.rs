@@ -0,0 +1,358 @@
+//! Golden fixtures for response type evolution.
+//!
+//! Captured API responses live under `fixtures/<api-version>/<name>.json` and
+//! ship with the crate, so downstream code can pin its own tests against the
+//! response shapes of a specific API version. The helpers here sanitize
+//! captured responses and check that SDK types deserialize them without
+//! losing data. Available with the `test-util` feature.
+//!
+//! ```rust,no_run
+//! use refyne::golden;
+//! use refyne::ExtractResponse;
+//!
+//! let fixture = golden::load("0.1.80", "extract").unwrap();
+//! golden::assert_lossless::<ExtractResponse>(&fixture);
+//! ```
+
+use crate::error::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Directory holding the published fixture set.
+pub const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+
+/// Placeholder written over sensitive values by [`sanitize`].
+pub const REDACTED: &str = "[redacted]";
+
+/// Field names whose values are replaced by [`sanitize`].
+const SENSITIVE_KEYS: &[&str] = &[
+    "api_key",
+    "authorization",
+    "email",
+    "key",
+    "organization_id",
+    "password",
+    "secret",
+    "token",
+    "user_id",
+];
+
+/// A deserialized value that keeps fields its type does not know about.
+///
+/// Serializing a `Preserved<T>` writes back the unknown fields alongside the
+/// known ones, so a response survives a round trip through an SDK type even
+/// when the API has added fields the SDK has not caught up with yet.
+#[derive(Debug, Clone)]
+pub struct Preserved<T> {
+    /// The typed value.
+    pub value: T,
+    raw: Value,
+}
+
+impl<T: Serialize> Preserved<T> {
+    /// JSON paths of fields present in the source but unknown to `T`.
+    pub fn unknown_fields(&self) -> Vec<String> {
+        match serde_json::to_value(&self.value) {
+            Ok(known) => {
+                let mut paths = Vec::new();
+                missing_paths(&self.raw, &known, "$", &mut paths);
+                paths
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Preserved<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let raw = Value::deserialize(deserializer)?;
+        let value = T::deserialize(&raw).map_err(serde::de::Error::custom)?;
+        Ok(Self { value, raw })
+    }
+}
+
+impl<T: Serialize> Serialize for Preserved<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut known = serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)?;
+        merge_missing(&mut known, &self.raw);
+        known.serialize(serializer)
+    }
+}
+
+/// Outcome of round-tripping a fixture through an SDK type.
+#[derive(Debug, Default)]
+pub struct RoundTrip {
+    /// Fields the type does not model; preserved by [`Preserved`].
+    pub unknown: Vec<String>,
+    /// Fields the type models but did not reproduce faithfully.
+    pub changed: Vec<String>,
+}
+
+impl RoundTrip {
+    /// True if every field the type models came back unchanged.
+    pub fn is_lossless(&self) -> bool {
+        self.changed.is_empty()
+    }
+}
+
+/// Round-trip a fixture through `T` and report what was lost.
+///
+/// A missing field and an explicit `null` are treated as equal, as are
+/// numbers with the same value (`1` and `1.0`).
+pub fn round_trip<T: DeserializeOwned + Serialize>(fixture: &Value) -> Result<RoundTrip> {
+    let value: T = serde_json::from_value(fixture.clone())?;
+    let known = serde_json::to_value(&value)?;
+
+    let mut report = RoundTrip::default();
+    missing_paths(fixture, &known, "$", &mut report.unknown);
+    changed_paths(fixture, &known, "$", &mut report.changed);
+    Ok(report)
+}
+
+/// Assert that `T` deserializes a fixture and reproduces it losslessly.
+///
+/// Fields unknown to `T` are allowed, but must survive a round trip through
+/// [`Preserved<T>`].
+///
+/// # Panics
+///
+/// Panics if the fixture does not deserialize, if a modeled field changes,
+/// or if unknown fields are dropped.
+pub fn assert_lossless<T: DeserializeOwned + Serialize>(fixture: &Value) {
+    let report =
+        round_trip::<T>(fixture).unwrap_or_else(|e| panic!("fixture does not deserialize: {}", e));
+    assert!(
+        report.is_lossless(),
+        "fields changed in round trip: {:?}",
+        report.changed
+    );
+
+    let preserved: Preserved<T> = serde_json::from_value(fixture.clone()).unwrap();
+    let written = serde_json::to_value(&preserved).unwrap();
+    let mut dropped = Vec::new();
+    missing_paths(fixture, &written, "$", &mut dropped);
+    assert!(dropped.is_empty(), "unknown fields dropped: {:?}", dropped);
+}
+
+/// Replace sensitive values in a captured response with [`REDACTED`].
+///
+/// Values under keys such as `email`, `token`, or `user_id` are redacted at
+/// any depth; `null` values are left as they are so optionality is kept.
+pub fn sanitize(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if is_sensitive(key) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    sanitize(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(sanitize),
+        _ => {}
+    }
+}
+
+/// Path of a fixture in the published set.
+pub fn fixture_path(api_version: &str, name: &str) -> PathBuf {
+    Path::new(FIXTURES_DIR)
+        .join(api_version)
+        .join(format!("{}.json", name))
+}
+
+/// Load a fixture from the published set.
+pub fn load(api_version: &str, name: &str) -> Result<Value> {
+    load_from(&fixture_path(api_version, name))
+}
+
+/// Load a fixture from a file.
+pub fn load_from(path: &Path) -> Result<Value> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| Error::Config(format!("cannot read {}: {}", path.display(), e)))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Sanitize a captured response and write it as a fixture.
+///
+/// Returns the sanitized value that was written.
+pub fn capture(dir: &Path, api_version: &str, name: &str, mut response: Value) -> Result<Value> {
+    sanitize(&mut response);
+    let dir = dir.join(api_version);
+    let path = dir.join(format!("{}.json", name));
+    let contents = serde_json::to_string_pretty(&response)?;
+    std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(&path, contents + "\n"))
+        .map_err(|e| Error::Config(format!("cannot write {}: {}", path.display(), e)))?;
+    Ok(response)
+}
+
+/// List the API versions in the published fixture set.
+pub fn api_versions() -> Vec<String> {
+    let mut versions: Vec<String> = std::fs::read_dir(FIXTURES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    versions.sort();
+    versions
+}
+
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEYS.contains(&key.as_str())
+        || key.ends_with("_token")
+        || key.ends_with("_secret")
+        || key.ends_with("api_key")
+}
+
+/// Collect paths present in `source` but absent from `written`.
+fn missing_paths(source: &Value, written: &Value, path: &str, out: &mut Vec<String>) {
+    match (source, written) {
+        (Value::Object(src), Value::Object(dst)) => {
+            for (key, value) in src {
+                let child = format!("{}.{}", path, key);
+                match dst.get(key) {
+                    Some(other) => missing_paths(value, other, &child, out),
+                    None if !value.is_null() => out.push(child),
+                    None => {}
+                }
+            }
+        }
+        (Value::Array(src), Value::Array(dst)) => {
+            for (i, (value, other)) in src.iter().zip(dst).enumerate() {
+                missing_paths(value, other, &format!("{}[{}]", path, i), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect paths that both values have but with different contents.
+fn changed_paths(source: &Value, written: &Value, path: &str, out: &mut Vec<String>) {
+    match (source, written) {
+        (Value::Object(src), Value::Object(dst)) => {
+            for (key, value) in src {
+                if let Some(other) = dst.get(key) {
+                    changed_paths(value, other, &format!("{}.{}", path, key), out);
+                }
+            }
+            for (key, value) in dst {
+                if !src.contains_key(key) && !value.is_null() {
+                    out.push(format!("{}.{}", path, key));
+                }
+            }
+        }
+        (Value::Array(src), Value::Array(dst)) if src.len() == dst.len() => {
+            for (i, (value, other)) in src.iter().zip(dst).enumerate() {
+                changed_paths(value, other, &format!("{}[{}]", path, i), out);
+            }
+        }
+        (Value::Number(a), Value::Number(b)) if a.as_f64() == b.as_f64() => {}
+        (a, b) if a != b => out.push(path.to_string()),
+        _ => {}
+    }
+}
+
+/// Copy fields from `source` that are missing in `target`.
+fn merge_missing(target: &mut Value, source: &Value) {
+    match (target, source) {
+        (Value::Object(dst), Value::Object(src)) => {
+            for (key, value) in src {
+                match dst.get_mut(key) {
+                    Some(existing) => merge_missing(existing, value),
+                    None => {
+                        dst.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (Value::Array(dst), Value::Array(src)) => {
+            for (existing, value) in dst.iter_mut().zip(src) {
+                merge_missing(existing, value);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AnalyzeResponse, CrawlJobCreated, ExtractResponse, Job, Schema};
+    use serde_json::json;
+
+    #[test]
+    fn test_sanitize() {
+        let mut value = json!({
+            "id": "job_1",
+            "user_id": "user_abc",
+            "organization_id": null,
+            "nested": [{"access_token": "tok", "url": "https://example.com"}]
+        });
+        sanitize(&mut value);
+        assert_eq!(value["id"], "job_1");
+        assert_eq!(value["user_id"], REDACTED);
+        assert!(value["organization_id"].is_null());
+        assert_eq!(value["nested"][0]["access_token"], REDACTED);
+        assert_eq!(value["nested"][0]["url"], "https://example.com");
+    }
+
+    #[test]
+    fn test_preserved_keeps_unknown_fields() {
+        let fixture = json!({
+            "name": "products",
+            "extra": {"added_in": "0.2.0"}
+        });
+        #[derive(Serialize, Deserialize)]
+        struct Named {
+            name: String,
+        }
+
+        let preserved: Preserved<Named> = serde_json::from_value(fixture.clone()).unwrap();
+        assert_eq!(preserved.value.name, "products");
+        assert_eq!(preserved.unknown_fields(), vec!["$.extra"]);
+        assert_eq!(serde_json::to_value(&preserved).unwrap(), fixture);
+    }
+
+    #[test]
+    fn test_round_trip_reports_changes() {
+        #[derive(Serialize, Deserialize)]
+        struct Count {
+            count: i64,
+            #[serde(default = "seven")]
+            fixed: i64,
+        }
+        fn seven() -> i64 {
+            7
+        }
+
+        let report = round_trip::<Count>(&json!({"count": 1, "other": true})).unwrap();
+        assert_eq!(report.unknown, vec!["$.other"]);
+        assert_eq!(report.changed, vec!["$.fixed"]);
+        assert!(!report.is_lossless());
+    }
+
+    #[test]
+    fn test_published_fixtures() {
+        assert!(api_versions().contains(&"0.1.80".to_string()));
+        assert_lossless::<ExtractResponse>(&load("0.1.80", "extract").unwrap());
+        assert_lossless::<CrawlJobCreated>(&load("0.1.80", "crawl_job_created").unwrap());
+        assert_lossless::<Job>(&load("0.1.80", "job").unwrap());
+        assert_lossless::<Schema>(&load("0.1.80", "schema").unwrap());
+        assert_lossless::<AnalyzeResponse>(&load("0.1.80", "analyze_depth1").unwrap());
+    }
+
+    #[test]
+    fn test_capture_writes_sanitized_fixture() {
+        let dir = std::env::temp_dir().join(format!("refyne-golden-{}", std::process::id()));
+        let written = capture(&dir, "9.9.9", "key", json!({"key": "rf_live_abc"})).unwrap();
+        assert_eq!(written["key"], REDACTED);
+        assert_eq!(load_from(&dir.join("9.9.9/key.json")).unwrap(), written);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
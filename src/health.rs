@@ -0,0 +1,269 @@
+//! Retry telemetry and retry storm detection.
+//!
+//! The client records every attempt, retry, and rate-limited response per
+//! endpoint over a rolling window. [`Client::health_snapshot`](crate::Client::health_snapshot)
+//! reports the current rates, and an optional callback fires when an endpoint
+//! crosses the configured thresholds.
+
+use crate::runtime::Instant;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Callback invoked when an endpoint enters the degraded state.
+pub type DegradedCallback = Arc<dyn Fn(&EndpointHealth) + Send + Sync>;
+
+/// Thresholds for retry storm detection.
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    /// Rolling window over which rates are computed.
+    pub window: Duration,
+    /// Minimum attempts in the window before an endpoint can be degraded.
+    pub min_requests: u32,
+    /// Fraction of attempts that were retries above which an endpoint is degraded.
+    pub retry_rate_threshold: f64,
+    /// Fraction of attempts that were rate limited above which an endpoint is degraded.
+    pub rate_limit_threshold: f64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(60),
+            min_requests: 10,
+            retry_rate_threshold: 0.5,
+            rate_limit_threshold: 0.2,
+        }
+    }
+}
+
+/// Rolling retry statistics for a single endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointHealth {
+    /// Endpoint key, e.g. `GET /api/v1/jobs/{id}`.
+    pub endpoint: String,
+    /// Attempts made in the window (including retries).
+    pub requests: u32,
+    /// Attempts that were retries.
+    pub retries: u32,
+    /// Responses that were rate limited (HTTP 429).
+    pub rate_limited: u32,
+    /// `retries / requests`.
+    pub retry_rate: f64,
+    /// `rate_limited / requests`.
+    pub rate_limit_rate: f64,
+    /// True if the endpoint exceeds the configured thresholds.
+    pub degraded: bool,
+}
+
+/// Point-in-time view of the client's retry health.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthSnapshot {
+    /// Per-endpoint statistics, sorted by endpoint.
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+impl HealthSnapshot {
+    /// Returns true if any endpoint is degraded.
+    pub fn is_degraded(&self) -> bool {
+        self.endpoints.iter().any(|e| e.degraded)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Event {
+    Request,
+    Retry,
+    RateLimited,
+}
+
+#[derive(Default)]
+struct State {
+    events: HashMap<String, VecDeque<(Instant, Event)>>,
+    degraded: HashSet<String>,
+}
+
+/// Collects retry telemetry for a client.
+pub(crate) struct HealthTracker {
+    config: HealthConfig,
+    on_degraded: Option<DegradedCallback>,
+    state: Mutex<State>,
+}
+
+impl HealthTracker {
+    pub(crate) fn new(config: HealthConfig, on_degraded: Option<DegradedCallback>) -> Self {
+        Self {
+            config,
+            on_degraded,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Record an event for an endpoint and fire the callback on degradation.
+    pub(crate) fn record(&self, endpoint: &str, event: Event) {
+        let now = Instant::now();
+        let newly_degraded = {
+            let mut state = self.state.lock().unwrap();
+            let events = state.events.entry(endpoint.to_string()).or_default();
+            events.push_back((now, event));
+            prune(events, now, self.config.window);
+            let health = summarize(endpoint, events, &self.config);
+
+            if health.degraded {
+                state
+                    .degraded
+                    .insert(endpoint.to_string())
+                    .then_some(health)
+            } else {
+                state.degraded.remove(endpoint);
+                None
+            }
+        };
+
+        if let (Some(health), Some(callback)) = (newly_degraded, &self.on_degraded) {
+            callback(&health);
+        }
+    }
+
+    /// Compute the current snapshot.
+    pub(crate) fn snapshot(&self) -> HealthSnapshot {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let mut endpoints: Vec<EndpointHealth> = state
+            .events
+            .iter_mut()
+            .map(|(endpoint, events)| {
+                prune(events, now, self.config.window);
+                summarize(endpoint, events, &self.config)
+            })
+            .filter(|h| h.requests > 0)
+            .collect();
+        endpoints.sort_by(|a, b| a.endpoint.cmp(&b.endpoint));
+        HealthSnapshot { endpoints }
+    }
+}
+
+fn prune(events: &mut VecDeque<(Instant, Event)>, now: Instant, window: Duration) {
+    while let Some((at, _)) = events.front() {
+        if now.duration_since(*at) > window {
+            events.pop_front();
+        } else {
+            break;
+        }
+    }
+}
+
+fn summarize(
+    endpoint: &str,
+    events: &VecDeque<(Instant, Event)>,
+    config: &HealthConfig,
+) -> EndpointHealth {
+    let count = |kind: Event| events.iter().filter(|(_, e)| *e == kind).count() as u32;
+    let requests = count(Event::Request);
+    let retries = count(Event::Retry);
+    let rate_limited = count(Event::RateLimited);
+
+    let rate = |n: u32| {
+        if requests == 0 {
+            0.0
+        } else {
+            n as f64 / requests as f64
+        }
+    };
+    let retry_rate = rate(retries);
+    let rate_limit_rate = rate(rate_limited);
+    let degraded = requests >= config.min_requests
+        && (retry_rate > config.retry_rate_threshold
+            || rate_limit_rate > config.rate_limit_threshold);
+
+    EndpointHealth {
+        endpoint: endpoint.to_string(),
+        requests,
+        retries,
+        rate_limited,
+        retry_rate,
+        rate_limit_rate,
+        degraded,
+    }
+}
+
+/// Build the endpoint key for a request.
+///
+/// Query strings are dropped and identifier-like path segments (at least 8
+/// characters containing a digit) are replaced with `{id}` so that requests
+/// for different resources share statistics.
+pub(crate) fn endpoint_key(method: &str, path: &str) -> String {
+    let path = path.split('?').next().unwrap_or(path);
+    let normalized: Vec<&str> = path
+        .split('/')
+        .map(|segment| {
+            if segment.len() >= 8 && segment.chars().any(|c| c.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect();
+    format!("{} {}", method.to_uppercase(), normalized.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn config() -> HealthConfig {
+        HealthConfig {
+            min_requests: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_endpoint_key() {
+        assert_eq!(
+            endpoint_key("get", "/api/v1/jobs/01HXYZABCD1234?merge=true"),
+            "GET /api/v1/jobs/{id}"
+        );
+        assert_eq!(
+            endpoint_key("POST", "/api/v1/extract"),
+            "POST /api/v1/extract"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_rates() {
+        let tracker = HealthTracker::new(config(), None);
+        tracker.record("GET /a", Event::Request);
+        tracker.record("GET /a", Event::Request);
+        tracker.record("GET /a", Event::Retry);
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.endpoints.len(), 1);
+        let health = &snapshot.endpoints[0];
+        assert_eq!(health.requests, 2);
+        assert_eq!(health.retries, 1);
+        assert_eq!(health.retry_rate, 0.5);
+        assert!(!health.degraded);
+        assert!(!snapshot.is_degraded());
+    }
+
+    #[test]
+    fn test_callback_fires_once_on_degradation() {
+        let fired = Arc::new(AtomicU32::new(0));
+        let counter = fired.clone();
+        let callback: DegradedCallback = Arc::new(move |health: &EndpointHealth| {
+            assert!(health.degraded);
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let tracker = HealthTracker::new(config(), Some(callback));
+
+        for _ in 0..3 {
+            tracker.record("POST /b", Event::Request);
+            tracker.record("POST /b", Event::RateLimited);
+        }
+
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+        assert!(tracker.snapshot().is_degraded());
+    }
+}
@@ -0,0 +1,173 @@
+//! Per-URL failures of a crawl.
+//!
+//! A crawl can complete with some of its pages failed.
+//! [`JobsClient::get_errors`](crate::JobsClient::get_errors) lists each failed
+//! URL with why it failed and how often it was retried:
+//!
+//! ```rust,no_run
+//! use refyne::UrlErrorCategory;
+//!
+//! # async fn run(client: refyne::Client, job_id: &str) -> Result<(), refyne::Error> {
+//! let errors = client.jobs().get_errors(job_id).await?;
+//! for error in errors.of_category(&UrlErrorCategory::FetchBlocked) {
+//!     println!("blocked after {} retries: {}", error.retries, error.url);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::timestamp::Timestamp;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Why a URL failed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UrlErrorCategory {
+    /// The site refused the fetch.
+    FetchBlocked,
+    /// The fetch or extraction took too long.
+    Timeout,
+    /// The page was fetched but data could not be extracted.
+    ExtractionFailed,
+    /// Data was extracted but did not match the schema.
+    SchemaMismatch,
+    /// A category this SDK version does not know.
+    Other(String),
+}
+
+impl UrlErrorCategory {
+    /// The category as sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            UrlErrorCategory::FetchBlocked => "fetch_blocked",
+            UrlErrorCategory::Timeout => "timeout",
+            UrlErrorCategory::ExtractionFailed => "extraction_failed",
+            UrlErrorCategory::SchemaMismatch => "schema_mismatch",
+            UrlErrorCategory::Other(category) => category,
+        }
+    }
+}
+
+impl From<&str> for UrlErrorCategory {
+    fn from(category: &str) -> Self {
+        match category.to_ascii_lowercase().as_str() {
+            "fetch_blocked" => UrlErrorCategory::FetchBlocked,
+            "timeout" => UrlErrorCategory::Timeout,
+            "extraction_failed" => UrlErrorCategory::ExtractionFailed,
+            "schema_mismatch" => UrlErrorCategory::SchemaMismatch,
+            _ => UrlErrorCategory::Other(category.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for UrlErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for UrlErrorCategory {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for UrlErrorCategory {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::from(String::deserialize(deserializer)?.as_str()))
+    }
+}
+
+/// A URL of a crawl that failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UrlError {
+    /// The failed URL.
+    pub url: String,
+    /// Why it failed.
+    pub category: UrlErrorCategory,
+    /// Error message from the last attempt.
+    #[serde(default)]
+    pub message: String,
+    /// Retries after the first attempt.
+    #[serde(default)]
+    pub retries: u32,
+    /// HTTP status of the target page, if it was fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    /// When the last attempt failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_at: Option<Timestamp>,
+}
+
+/// The failed URLs of a job.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobErrors {
+    /// Job ID.
+    #[serde(default)]
+    pub job_id: String,
+    /// One entry per failed URL.
+    #[serde(default)]
+    pub errors: Vec<UrlError>,
+}
+
+impl JobErrors {
+    /// Failed URLs in `category`.
+    pub fn of_category<'a>(
+        &'a self,
+        category: &'a UrlErrorCategory,
+    ) -> impl Iterator<Item = &'a UrlError> + 'a {
+        self.errors
+            .iter()
+            .filter(move |error| &error.category == category)
+    }
+
+    /// Number of failed URLs per category, most common first.
+    pub fn counts(&self) -> Vec<(&UrlErrorCategory, usize)> {
+        let mut counts: Vec<(&UrlErrorCategory, usize)> = Vec::new();
+        for error in &self.errors {
+            match counts.iter_mut().find(|(c, _)| *c == &error.category) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((&error.category, 1)),
+            }
+        }
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_categories_round_trip() {
+        let errors: JobErrors = serde_json::from_value(json!({
+            "job_id": "job-1",
+            "errors": [
+                {"url": "https://example.com/a", "category": "timeout", "retries": 2},
+                {"url": "https://example.com/b", "category": "FETCH_BLOCKED", "status_code": 403},
+                {"url": "https://example.com/c", "category": "timeout", "retries": 3},
+                {"url": "https://example.com/d", "category": "dns_failure"},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(errors.errors[1].category, UrlErrorCategory::FetchBlocked);
+        assert_eq!(
+            errors.errors[3].category,
+            UrlErrorCategory::Other("dns_failure".into())
+        );
+        assert_eq!(errors.of_category(&UrlErrorCategory::Timeout).count(), 2);
+        assert_eq!(errors.counts()[0], (&UrlErrorCategory::Timeout, 2));
+        assert_eq!(
+            serde_json::to_value(&errors.errors[1]).unwrap(),
+            json!({
+                "url": "https://example.com/b",
+                "category": "fetch_blocked",
+                "message": "",
+                "retries": 0,
+                "status_code": 403,
+            })
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Server-side filtering and sorting of job listings.
+//!
+//! A [`JobFilter`] narrows [`JobsClient::list_filtered`](crate::JobsClient::list_filtered)
+//! to the jobs a service cares about, so it does not have to fetch every job
+//! and filter them itself:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, JobFilter, JobSort, JobStatus};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let filter = JobFilter::new()
+//!     .status(JobStatus::Running)
+//!     .job_type("crawl")
+//!     .created_after("2026-01-12T09:00:00Z")
+//!     .sort(JobSort::NewestFirst);
+//! let running = client.jobs().list_filtered(&filter, Some(20), None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types_ext::JobStatus;
+
+/// Order of a job listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobSort {
+    /// Most recently created first.
+    NewestFirst,
+    /// Least recently created first.
+    OldestFirst,
+}
+
+/// Conditions a listed job must meet. All set conditions apply.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobFilter {
+    statuses: Vec<JobStatus>,
+    job_type: Option<String>,
+    created_after: Option<String>,
+    created_before: Option<String>,
+    url_contains: Option<String>,
+    sort: Option<JobSort>,
+}
+
+impl JobFilter {
+    /// A filter matching every job.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only jobs with this status. Call again to allow several.
+    pub fn status(mut self, status: JobStatus) -> Self {
+        if !self.statuses.contains(&status) {
+            self.statuses.push(status);
+        }
+        self
+    }
+
+    /// Only jobs of this type, such as `crawl` or `extract`.
+    pub fn job_type(mut self, job_type: impl Into<String>) -> Self {
+        self.job_type = Some(job_type.into());
+        self
+    }
+
+    /// Only jobs created at or after this RFC 3339 timestamp.
+    pub fn created_after(mut self, timestamp: impl Into<String>) -> Self {
+        self.created_after = Some(timestamp.into());
+        self
+    }
+
+    /// Only jobs created before this RFC 3339 timestamp.
+    pub fn created_before(mut self, timestamp: impl Into<String>) -> Self {
+        self.created_before = Some(timestamp.into());
+        self
+    }
+
+    /// Only jobs whose URL contains `text`.
+    pub fn url_contains(mut self, text: impl Into<String>) -> Self {
+        self.url_contains = Some(text.into());
+        self
+    }
+
+    /// Order the listing by creation time.
+    pub fn sort(mut self, sort: JobSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Query parameters for the jobs endpoint, percent-encoded.
+    pub(crate) fn query_params(&self) -> Vec<String> {
+        let mut params = Vec::new();
+        if !self.statuses.is_empty() {
+            let statuses: Vec<&str> = self.statuses.iter().map(JobStatus::as_str).collect();
+            params.push(("status", statuses.join(",")));
+        }
+        let values = [
+            ("type", &self.job_type),
+            ("created_after", &self.created_after),
+            ("created_before", &self.created_before),
+            ("url", &self.url_contains),
+        ];
+        params.extend(
+            values
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, value.clone()?))),
+        );
+        match self.sort {
+            Some(JobSort::NewestFirst) => params.push(("sort", "-created_at".into())),
+            Some(JobSort::OldestFirst) => params.push(("sort", "created_at".into())),
+            None => {}
+        }
+        params
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, encode(&value)))
+            .collect()
+    }
+}
+
+/// Percent-encode everything but unreserved characters.
+pub(crate) fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_params() {
+        assert!(JobFilter::new().query_params().is_empty());
+
+        let filter = JobFilter::new()
+            .status(JobStatus::Running)
+            .status(JobStatus::Pending)
+            .status(JobStatus::Running)
+            .job_type("crawl")
+            .created_after("2026-01-12T09:00:00+01:00")
+            .url_contains("example.com/shop?page")
+            .sort(JobSort::NewestFirst);
+        assert_eq!(
+            filter.query_params(),
+            vec![
+                "status=running%2Cpending",
+                "type=crawl",
+                "created_after=2026-01-12T09%3A00%3A00%2B01%3A00",
+                "url=example.com%2Fshop%3Fpage",
+                "sort=-created_at",
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Detailed progress of a running crawl.
+//!
+//! [`JobsClient::progress`](crate::JobsClient::progress) reports where every
+//! URL of a crawl stands, with a few example URLs in each state and an
+//! estimate of the time left, which is enough to drive a progress bar:
+//!
+//! ```rust,no_run
+//! # async fn run(client: refyne::Client, job_id: &str) -> Result<(), refyne::Error> {
+//! let progress = client.jobs().progress(job_id).await?;
+//! if let Some(fraction) = progress.fraction_complete() {
+//!     println!("{:.0}% of {} URLs", fraction * 100.0, progress.discovered);
+//! }
+//! for url in &progress.samples.failed {
+//!     println!("failed: {}", url);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types_ext::JobStatus;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Where the URLs of a crawl stand.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobProgress {
+    /// Job ID.
+    pub job_id: String,
+    /// Job status, as in [`Job::status`](crate::Job::status).
+    pub status: String,
+    /// URLs found so far, in any state.
+    pub discovered: i64,
+    /// URLs waiting to be fetched.
+    pub queued: i64,
+    /// URLs being fetched or extracted.
+    pub in_flight: i64,
+    /// URLs extracted successfully.
+    pub succeeded: i64,
+    /// URLs that failed.
+    pub failed: i64,
+    /// URLs left out, for example by robots.txt, an exclude pattern, or the
+    /// page limit.
+    pub skipped: i64,
+    /// Example URLs in each state.
+    pub samples: ProgressSamples,
+    /// Estimated seconds until the crawl finishes, once the API can tell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
+}
+
+/// A few URLs in each state, most recent first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProgressSamples {
+    /// URLs waiting to be fetched.
+    pub queued: Vec<String>,
+    /// URLs being fetched or extracted.
+    pub in_flight: Vec<String>,
+    /// URLs extracted successfully.
+    pub succeeded: Vec<String>,
+    /// URLs that failed.
+    pub failed: Vec<String>,
+    /// URLs left out.
+    pub skipped: Vec<String>,
+}
+
+impl JobProgress {
+    /// The job's status as a [`JobStatus`].
+    pub fn job_status(&self) -> JobStatus {
+        JobStatus::from(self.status.as_str())
+    }
+
+    /// URLs that are done with, whether they succeeded, failed, or were
+    /// skipped.
+    pub fn finished(&self) -> i64 {
+        self.succeeded + self.failed + self.skipped
+    }
+
+    /// Share of discovered URLs that are finished, from 0.0 to 1.0.
+    ///
+    /// Returns `None` until a URL is discovered. The share can go down as a
+    /// crawl discovers more URLs.
+    pub fn fraction_complete(&self) -> Option<f64> {
+        (self.discovered > 0).then(|| (self.finished() as f64 / self.discovered as f64).min(1.0))
+    }
+
+    /// Estimated time until the crawl finishes.
+    pub fn eta(&self) -> Option<Duration> {
+        self.eta_seconds.map(Duration::from_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_progress_tolerates_missing_fields() {
+        let progress: JobProgress = serde_json::from_value(json!({
+            "job_id": "job-1",
+            "status": "running",
+            "discovered": 40,
+            "succeeded": 8,
+            "failed": 1,
+            "skipped": 1,
+            "samples": {"failed": ["https://example.com/broken"]},
+        }))
+        .unwrap();
+        assert_eq!(progress.job_status(), JobStatus::Running);
+        assert_eq!(progress.finished(), 10);
+        assert_eq!(progress.fraction_complete(), Some(0.25));
+        assert!(progress.samples.queued.is_empty());
+        assert_eq!(progress.eta(), None);
+
+        assert_eq!(JobProgress::default().fraction_complete(), None);
+    }
+}
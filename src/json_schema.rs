@@ -0,0 +1,323 @@
+//! JSON Schema documents as extraction schemas.
+//!
+//! Teams that already maintain JSON Schemas (draft 2020-12) can extract with
+//! them directly. Mark the request with [`SchemaFormat::JsonSchema`] and the
+//! client converts the schema to the Refyne form before sending it:
+//!
+//! ```rust
+//! use refyne::{ExtractRequest, SchemaFormat};
+//! use serde_json::json;
+//!
+//! let request = ExtractRequest {
+//!     url: "https://example.com/product".into(),
+//!     schema: json!({
+//!         "$schema": "https://json-schema.org/draft/2020-12/schema",
+//!         "type": "object",
+//!         "properties": {
+//!             "name": {"type": "string"},
+//!             "price": {"type": ["number", "null"], "description": "Price in GBP"},
+//!             "tags": {"type": "array", "items": {"type": "string"}},
+//!         },
+//!     }),
+//!     schema_format: Some(SchemaFormat::JsonSchema),
+//!     ..Default::default()
+//! };
+//! ```
+//!
+//! [`SchemaDefinition::to_json_schema`] goes the other way, for publishing a
+//! Refyne schema to tools that speak JSON Schema.
+//!
+//! Objects with `properties`, arrays with `items`, the scalar types, local
+//! `$ref`s into `$defs` or `definitions`, and nullable types written as
+//! `["number", "null"]` or with `anyOf` are understood. Constraints such as
+//! `minimum` or `pattern` have no Refyne equivalent and are dropped.
+
+use crate::definition::{FieldDefinition, FieldKind, SchemaDefinition};
+use crate::error::{Error, Result};
+use crate::ExtractRequest;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// The JSON Schema dialect produced by [`SchemaDefinition::to_json_schema`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// `$ref`s followed before giving up on a cycle.
+const MAX_REF_DEPTH: usize = 32;
+
+/// How an extraction request's schema is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaFormat {
+    /// The Refyne form: `{"price": "number", "tags": ["string"]}`, or a
+    /// freeform prompt. Sent as is.
+    #[default]
+    Refyne,
+    /// A JSON Schema document, converted to the Refyne form before sending.
+    JsonSchema,
+}
+
+impl SchemaFormat {
+    /// Guess the format of `schema`: a JSON Schema declares `$schema`, or is
+    /// an object type with `properties`.
+    pub fn detect(schema: &Value) -> Self {
+        let is_json_schema = schema.get("$schema").is_some()
+            || (schema.get("type") == Some(&Value::from("object"))
+                && schema.get("properties").is_some_and(Value::is_object));
+        if is_json_schema {
+            SchemaFormat::JsonSchema
+        } else {
+            SchemaFormat::Refyne
+        }
+    }
+}
+
+impl SchemaDefinition {
+    /// Parse a JSON Schema document describing an object.
+    ///
+    /// Fails with [`Error::Config`] naming the offending property if the
+    /// document is not an object schema or uses a construct with no Refyne
+    /// equivalent, such as a `$ref` to another document.
+    pub fn from_json_schema(schema: &Value) -> Result<Self> {
+        let converter = Converter { root: schema };
+        match converter.kind(schema, "", 0)? {
+            FieldKind::Object(definition) => Ok(definition),
+            _ => Err(Error::Config(
+                "Invalid JSON Schema: the root must be an object with properties".into(),
+            )),
+        }
+    }
+
+    /// The schema as a JSON Schema (draft 2020-12) document.
+    ///
+    /// Descriptions carry over. Fields are not marked required, since
+    /// extraction returns `null` for values a page lacks.
+    pub fn to_json_schema(&self) -> Value {
+        let mut schema = object_schema(self);
+        schema["$schema"] = Value::from(JSON_SCHEMA_DIALECT);
+        schema
+    }
+}
+
+fn object_schema(definition: &SchemaDefinition) -> Value {
+    let properties: Map<String, Value> = definition
+        .fields
+        .iter()
+        .map(|field| {
+            let mut schema = kind_schema(&field.kind);
+            if let Some(description) = &field.description {
+                schema["description"] = Value::from(description.as_str());
+            }
+            (field.name.clone(), schema)
+        })
+        .collect();
+    json!({"type": "object", "properties": properties})
+}
+
+fn kind_schema(kind: &FieldKind) -> Value {
+    match kind {
+        FieldKind::Array(item) => json!({"type": "array", "items": kind_schema(item)}),
+        FieldKind::Object(definition) => object_schema(definition),
+        scalar => json!({"type": scalar.name().unwrap_or_default()}),
+    }
+}
+
+/// Convert a request's JSON Schema to the Refyne form the API expects.
+pub(crate) fn resolve(request: &mut ExtractRequest) -> Result<()> {
+    resolve_schema(&mut request.schema, &mut request.schema_format)
+}
+
+/// Convert `schema` to the Refyne form if `format` says it is a JSON Schema.
+pub(crate) fn resolve_schema(schema: &mut Value, format: &mut Option<SchemaFormat>) -> Result<()> {
+    if *format == Some(SchemaFormat::JsonSchema) {
+        *schema = SchemaDefinition::from_json_schema(schema)?.to_value();
+        *format = Some(SchemaFormat::Refyne);
+    }
+    Ok(())
+}
+
+struct Converter<'a> {
+    root: &'a Value,
+}
+
+impl Converter<'_> {
+    fn kind(&self, schema: &Value, path: &str, depth: usize) -> Result<FieldKind> {
+        let invalid = |reason: String| {
+            let at = if path.is_empty() { "the root" } else { path };
+            Error::Config(format!("Invalid JSON Schema at {}: {}", at, reason))
+        };
+        if depth > MAX_REF_DEPTH {
+            return Err(invalid("$ref cycle".into()));
+        }
+        let Some(map) = schema.as_object() else {
+            return Err(invalid(format!(
+                "expected a schema object, found {}",
+                schema
+            )));
+        };
+
+        if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+                .ok_or_else(|| invalid(format!("cannot resolve $ref {:?}", reference)))?;
+            return self.kind(target, path, depth + 1);
+        }
+        // Nullable unions: the first alternative that is not `null`.
+        for union in ["anyOf", "oneOf"] {
+            if let Some(options) = map.get(union).and_then(Value::as_array) {
+                let option = options
+                    .iter()
+                    .find(|option| option.get("type") != Some(&Value::from("null")))
+                    .ok_or_else(|| invalid(format!("{} has no non-null alternative", union)))?;
+                return self.kind(option, path, depth + 1);
+            }
+        }
+
+        let ty = match map.get("type") {
+            Some(Value::String(ty)) => ty.as_str(),
+            Some(Value::Array(types)) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|ty| *ty != "null")
+                .unwrap_or("string"),
+            None if map.contains_key("properties") => "object",
+            None if map.contains_key("items") => "array",
+            // `enum` and `const` without a type, most often of strings.
+            None => "string",
+            Some(other) => return Err(invalid(format!("unsupported type {}", other))),
+        };
+        Ok(match ty {
+            "object" => {
+                let properties = map
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .ok_or_else(|| invalid("an object needs properties".into()))?;
+                let fields = properties
+                    .iter()
+                    .map(|(name, property)| {
+                        let path = if path.is_empty() {
+                            name.clone()
+                        } else {
+                            format!("{}.{}", path, name)
+                        };
+                        Ok(FieldDefinition {
+                            name: name.clone(),
+                            kind: self.kind(property, &path, depth)?,
+                            description: property
+                                .get("description")
+                                .and_then(Value::as_str)
+                                .map(String::from),
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                FieldKind::Object(SchemaDefinition { fields })
+            }
+            "array" => {
+                let items = map
+                    .get("items")
+                    .ok_or_else(|| invalid("an array needs items".into()))?;
+                FieldKind::Array(Box::new(self.kind(items, &format!("{}[]", path), depth)?))
+            }
+            "string" => FieldKind::String,
+            "number" => FieldKind::Number,
+            "integer" => FieldKind::Integer,
+            "boolean" => FieldKind::Boolean,
+            other => return Err(invalid(format!("unsupported type {:?}", other))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_schema() -> Value {
+        json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "price": {"type": ["number", "null"], "description": "Price in GBP"},
+                "stock": {"anyOf": [{"type": "null"}, {"type": "integer"}]},
+                "condition": {"enum": ["new", "used"]},
+                "reviews": {"type": "array", "items": {"$ref": "#/$defs/review"}},
+            },
+            "required": ["name"],
+            "$defs": {
+                "review": {"type": "object", "properties": {"rating": {"type": "integer"}}},
+            },
+        })
+    }
+
+    #[test]
+    fn test_from_json_schema() {
+        let definition = SchemaDefinition::from_json_schema(&json_schema()).unwrap();
+        assert_eq!(
+            definition.to_value(),
+            json!({
+                "name": "string",
+                "price": {"type": "number", "description": "Price in GBP"},
+                "stock": "integer",
+                "condition": "string",
+                "reviews": [{"rating": "integer"}],
+            })
+        );
+        assert_eq!(
+            SchemaFormat::detect(&json_schema()),
+            SchemaFormat::JsonSchema
+        );
+        assert_eq!(
+            SchemaFormat::detect(&json!({"type": "string", "name": "string"})),
+            SchemaFormat::Refyne
+        );
+    }
+
+    #[test]
+    fn test_from_json_schema_rejects_unsupported() {
+        let message = |schema: Value| match SchemaDefinition::from_json_schema(&schema) {
+            Err(Error::Config(message)) => message,
+            other => panic!("expected a config error, got {:?}", other),
+        };
+        assert_eq!(
+            message(json!({"type": "object", "properties": {"a": {"$ref": "other.json"}}})),
+            "Invalid JSON Schema at a: cannot resolve $ref \"other.json\""
+        );
+        assert_eq!(
+            message(json!({
+                "type": "object",
+                "properties": {"a": {"$ref": "#/$defs/a"}},
+                "$defs": {"a": {"$ref": "#/$defs/a"}},
+            })),
+            "Invalid JSON Schema at a: $ref cycle"
+        );
+        assert_eq!(
+            message(json!({"type": "array", "items": {"type": "string"}})),
+            "Invalid JSON Schema: the root must be an object with properties"
+        );
+    }
+
+    #[test]
+    fn test_json_schema_round_trip() {
+        let definition = SchemaDefinition::from_value(&json!({
+            "name": "string",
+            "price": {"type": "number", "description": "Price in GBP"},
+            "tags": ["string"],
+            "seller": {"name": "string"},
+        }))
+        .unwrap();
+        let schema = definition.to_json_schema();
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(
+            schema["properties"]["price"],
+            json!({"type": "number", "description": "Price in GBP"})
+        );
+        assert_eq!(
+            schema["properties"]["tags"],
+            json!({"type": "array", "items": {"type": "string"}})
+        );
+        assert_eq!(
+            SchemaDefinition::from_json_schema(&schema).unwrap(),
+            definition
+        );
+    }
+}
@@ -27,19 +27,192 @@
 //! }
 //! ```
 
+mod api;
+mod batch;
+mod bootstrap;
+mod budget;
+mod builders;
 mod cache;
+mod cancel;
+mod capabilities;
+mod circuit;
 mod client;
+#[cfg(not(feature = "test-util"))]
+mod clock;
+#[cfg(feature = "test-util")]
+pub mod clock;
+#[cfg(feature = "arrow")]
+mod columnar;
+mod content;
+mod credentials;
+mod dedup;
+mod definition;
+#[cfg(feature = "disk-cache")]
+mod disk_cache;
+mod document;
+#[cfg(not(target_arch = "wasm32"))]
+mod download;
 mod error;
+mod estimate;
+#[cfg(feature = "export")]
+mod export;
+mod fairness;
+mod fetch;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(test, feature = "test-util"))]
+pub mod golden;
+mod health;
+mod job_errors;
+mod job_filter;
+mod job_progress;
+mod json_schema;
+mod llm;
+mod meta;
+mod middleware;
+mod observer;
+mod options;
+mod org;
+mod pagination;
+mod pipeline;
+mod prepared;
+pub mod presets;
+#[cfg(feature = "profiles")]
+mod profile;
+mod progress;
+mod quota;
+#[cfg(feature = "recording")]
+mod recording;
+#[cfg(feature = "redis")]
+mod redis_cache;
+mod request;
+mod rerun;
+mod results;
+mod runtime;
+mod schema;
+pub mod schemas;
+mod screenshot;
+mod secret;
+#[cfg(not(target_arch = "wasm32"))]
+mod seeds;
+#[cfg(feature = "object-store")]
+pub mod sinks;
+mod sitemap;
+mod sites;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+mod stale;
+mod stats;
+#[cfg(all(feature = "test-util", not(target_arch = "wasm32")))]
+pub mod test_support;
+mod timestamp;
+pub mod transforms;
 mod types;
+mod types_ext;
+mod usage;
+mod validation;
 mod version;
+pub mod webhooks;
 
-pub use cache::{Cache, CacheEntry, MemoryCache};
+#[cfg(feature = "test-util")]
+pub use api::MockRefyne;
+pub use api::RefyneApi;
+pub use batch::{
+    BatchItem, BatchResult, BatchUsage, ExtractMany, Failure, FailureKind, FailureQueue,
+    RetryPolicy,
+};
+pub use bootstrap::BootstrappedSite;
+pub use budget::Budget;
+pub use builders::{
+    AnalyzeRequestBuilder, CrawlOptionsBuilder, CrawlRequestBuilder, CreateSchemaRequestBuilder,
+    CreateSiteRequestBuilder, ExtractRequestBuilder, UpsertLlmKeyRequestBuilder,
+};
+pub use cache::{
+    Cache, CacheEntry, CacheObserver, EvictionReason, MemoryCache, REVALIDATION_WINDOW,
+};
+pub use capabilities::Capabilities;
+pub use circuit::CircuitBreakerConfig;
 pub use client::{
-    Client, ClientBuilder, JobsClient, KeysClient, LlmClient, SchemasClient, SitesClient,
+    Client, ClientBuilder, JobsClient, KeysClient, LlmClient, OrgClient, SchemasClient,
+    SitesClient, UsageClient,
+};
+#[cfg(feature = "arrow")]
+pub use columnar::arrow_schema;
+pub use content::ContentFormat;
+pub use credentials::ApiKeyProvider;
+pub use dedup::{crawl_fingerprint, DuplicateCrawlPolicy};
+pub use definition::{FieldDefinition, FieldKind, SchemaDefinition};
+#[cfg(feature = "disk-cache")]
+pub use disk_cache::DiskCache;
+pub use document::{DocumentUpload, ExtractDocumentRequest};
+pub use error::{ApiErrorCode, Error, ErrorCode, ErrorMessages, Result};
+pub use estimate::{Estimate, EstimateRange, EstimateRequest};
+#[cfg(feature = "export")]
+pub use export::CsvOptions;
+pub use fairness::{FairnessConfig, DEFAULT_TENANT};
+pub use fetch::FetchOptions;
+pub use health::{DegradedCallback, EndpointHealth, HealthConfig, HealthSnapshot};
+pub use job_errors::{JobErrors, UrlError, UrlErrorCategory};
+pub use job_filter::{JobFilter, JobSort};
+pub use job_progress::{JobProgress, ProgressSamples};
+pub use json_schema::{SchemaFormat, JSON_SCHEMA_DIALECT};
+pub use llm::{ChainProblem, ChainProblemKind, LlmKeyTest};
+pub use meta::{capture_meta, ResponseMeta, REQUEST_ID_HEADER};
+pub use middleware::{Middleware, MiddlewareFuture, Next};
+pub use observer::{Observer, RequestOutcome};
+pub use options::RequestOptions;
+pub use org::{OrgInvitation, OrgMember, OrgRole, Organization};
+pub use pagination::{Paginator, DEFAULT_PAGE_SIZE};
+pub use pipeline::{ExtractionPipeline, PipelineOutput, PipelinePage};
+pub use prepared::{ApiRequest, PreparedRequest};
+pub use presets::{Preset, SchemaPreset};
+pub use progress::{
+    JobProgressCallback, Polling, ProgressFuture, ProgressSource, ServerSentEvents, WaitOptions,
+    WebhookFeed, WebhookProgress,
 };
-pub use error::{Error, Result};
+pub use quota::{QuotaLimits, QuotaSnapshot};
+#[cfg(feature = "recording")]
+pub use recording::{Cassette, CassetteMode};
+#[cfg(feature = "redis")]
+pub use redis_cache::{RedisCache, DEFAULT_REDIS_PREFIX, DEFAULT_REDIS_TIMEOUT};
+#[cfg(feature = "derive")]
+pub use refyne_derive::RefyneSchema;
+pub use request::RequestBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::redirect::Policy as RedirectPolicy;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::{Certificate, Identity};
+pub use results::{JobResultsExt, ResultItemError, ResultStream};
+pub use runtime::BoxFuture;
+pub use schema::{FieldType, RefyneSchema, SchemaBuilder};
+pub use screenshot::{ScreenshotFormat, ScreenshotOptions, Viewport};
+pub use secret::SecretString;
+pub use sitemap::{SitemapUrl, SitemapUrls};
+pub use sites::{RunOptions, Schedule, ScheduleList};
+#[cfg(feature = "snapshot")]
+pub use snapshot::{
+    inspect_snapshot, Snapshot, SnapshotHeader, SnapshotRecord, SnapshotSection, SnapshotSummary,
+    SNAPSHOT_FORMAT_VERSION,
+};
+pub use stale::Extraction;
+pub use stats::{DailyJobStats, JobStats, StatsRange};
+pub use timestamp::Timestamp;
+pub use transforms::Transform;
 pub use types::*;
+pub use types_ext::{JobStatus, PageTypeSuggestion};
+pub use usage::{UsageBreakdown, UsageGroupBy, UsagePoint, UsageQuery, UsageSeries};
+pub use validation::{SchemaViolation, ViolationKind};
 pub use version::{
-    check_api_version_compatibility, compare_versions, parse_version, MAX_KNOWN_API_VERSION,
-    MIN_API_VERSION, SDK_VERSION,
+    check_api_version_compatibility, compare_versions, parse_version, ApiFeatures, Feature,
+    VersionCheckMode, API_FEATURES_HEADER, MAX_KNOWN_API_VERSION, MIN_API_VERSION, SDK_VERSION,
 };
+
+// Lets derive output in unit tests refer to `::refyne`.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as refyne;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::schema::{describe, flatten};
+    pub use serde_json;
+}
@@ -0,0 +1,233 @@
+//! Checking LLM provider keys and fallback chains.
+//!
+//! [`LlmClient::test_key`](crate::LlmClient::test_key) makes a cheap call to a
+//! provider with the stored key, and
+//! [`test_key_raw`](crate::LlmClient::test_key_raw) with a key that is not
+//! saved yet, so onboarding can reject a bad key before storing it:
+//!
+//! ```rust,no_run
+//! use refyne::UpsertLlmKeyRequest;
+//!
+//! # async fn run(client: refyne::Client) -> Result<(), refyne::Error> {
+//! let request = UpsertLlmKeyRequest::builder("openai").api_key("sk-...").build();
+//! let test = client.llm().test_key_raw(request.clone()).await?;
+//! if test.valid && test.has_model("gpt-4o-mini") {
+//!     client.llm().upsert_key(request).await?;
+//! } else {
+//!     println!("key rejected: {}", test.message.unwrap_or_default());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`LlmClient::validate_chain`](crate::LlmClient::validate_chain) checks a
+//! fallback chain against the configured keys and each provider's models
+//! before it is saved, since a misspelt model otherwise only fails when an
+//! extraction reaches it.
+
+use crate::types::{LlmChainEntry, LlmKey, Model};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Outcome of a test call with an LLM provider key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmKeyTest {
+    /// Provider that was called.
+    pub provider: String,
+    /// Whether the provider accepted the key.
+    pub valid: bool,
+    /// Round trip of the test call in milliseconds, if it was made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Models the key can use.
+    pub models: Vec<String>,
+    /// Why the key was rejected, as reported by the provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl LlmKeyTest {
+    /// Round trip of the test call.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency_ms.map(Duration::from_millis)
+    }
+
+    /// Returns true if the key can use model `id`.
+    pub fn has_model(&self, id: &str) -> bool {
+        self.models.iter().any(|model| model == id)
+    }
+}
+
+/// What is wrong with a fallback chain entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainProblemKind {
+    /// The provider is not one the API offers.
+    UnknownProvider,
+    /// No key is configured for the provider.
+    MissingKey,
+    /// The provider's key is configured but disabled.
+    KeyDisabled,
+    /// The provider does not list the model.
+    UnknownModel,
+    /// The provider has deprecated the model.
+    DeprecatedModel,
+    /// The temperature is outside 0.0 to 1.0.
+    InvalidTemperature,
+    /// The output token limit is not positive, or exceeds the model's
+    /// context window.
+    InvalidMaxTokens,
+}
+
+/// A problem with one entry of a fallback chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainProblem {
+    /// Position of the entry in the chain.
+    pub index: usize,
+    /// Provider of the entry.
+    pub provider: String,
+    /// Model of the entry.
+    pub model: String,
+    /// What is wrong.
+    pub kind: ChainProblemKind,
+}
+
+impl fmt::Display for ChainProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problem = match self.kind {
+            ChainProblemKind::UnknownProvider => "unknown provider",
+            ChainProblemKind::MissingKey => "no key configured for the provider",
+            ChainProblemKind::KeyDisabled => "the provider's key is disabled",
+            ChainProblemKind::UnknownModel => "the provider does not list this model",
+            ChainProblemKind::DeprecatedModel => "the model is deprecated",
+            ChainProblemKind::InvalidTemperature => "temperature must be from 0.0 to 1.0",
+            ChainProblemKind::InvalidMaxTokens => "max_tokens is out of range for the model",
+        };
+        write!(
+            f,
+            "entry {} ({}/{}): {}",
+            self.index, self.provider, self.model, problem
+        )
+    }
+}
+
+/// Check `chain` against the available providers, configured keys, and the
+/// models of each provider.
+pub(crate) fn check_chain(
+    chain: &[LlmChainEntry],
+    providers: &[String],
+    keys: &[LlmKey],
+    models: &HashMap<String, Vec<Model>>,
+) -> Vec<ChainProblem> {
+    let mut problems = Vec::new();
+    for (index, entry) in chain.iter().enumerate() {
+        let mut problem = |kind| {
+            problems.push(ChainProblem {
+                index,
+                provider: entry.provider.clone(),
+                model: entry.model.clone(),
+                kind,
+            })
+        };
+        if !providers.contains(&entry.provider) {
+            problem(ChainProblemKind::UnknownProvider);
+            continue;
+        }
+        match keys.iter().find(|key| key.provider == entry.provider) {
+            None => problem(ChainProblemKind::MissingKey),
+            Some(key) if !key.is_enabled => problem(ChainProblemKind::KeyDisabled),
+            Some(_) => {}
+        }
+        let model = models
+            .get(&entry.provider)
+            .and_then(|models| models.iter().find(|model| model.id == entry.model));
+        match model {
+            None => problem(ChainProblemKind::UnknownModel),
+            Some(model) if model.deprecated => problem(ChainProblemKind::DeprecatedModel),
+            Some(_) => {}
+        }
+        if entry.temperature.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+            problem(ChainProblemKind::InvalidTemperature);
+        }
+        if let Some(max_tokens) = entry.max_tokens {
+            let window = model.and_then(|model| model.context_window);
+            if max_tokens <= 0 || window.is_some_and(|window| max_tokens > window) {
+                problem(ChainProblemKind::InvalidMaxTokens);
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_chain() {
+        let entry = |provider: &str, model: &str| LlmChainEntry {
+            is_enabled: true,
+            provider: provider.into(),
+            model: model.into(),
+            ..Default::default()
+        };
+        let chain = vec![
+            entry("openai", "gpt-4o-mini"),
+            entry("openai", "gpt-4o-mimi"),
+            LlmChainEntry {
+                temperature: Some(1.5),
+                max_tokens: Some(200_000),
+                ..entry("openai", "gpt-4o-mini")
+            },
+            entry("anthropic", "claude-haiku"),
+            entry("mistral", "mistral-small"),
+        ];
+        let providers = ["openai", "anthropic", "ollama"].map(String::from);
+        let keys: Vec<LlmKey> = serde_json::from_value(json!([
+            {"id": "k1", "provider": "openai", "has_key": true, "is_enabled": true,
+             "created_at": "2026-01-12", "updated_at": "2026-01-12"},
+            {"id": "k2", "provider": "anthropic", "has_key": true, "is_enabled": false,
+             "created_at": "2026-01-12", "updated_at": "2026-01-12"},
+        ]))
+        .unwrap();
+        let models = HashMap::from([
+            (
+                "openai".to_string(),
+                vec![Model {
+                    id: "gpt-4o-mini".into(),
+                    context_window: Some(128_000),
+                    ..Default::default()
+                }],
+            ),
+            (
+                "anthropic".to_string(),
+                vec![Model {
+                    id: "claude-haiku".into(),
+                    deprecated: true,
+                    ..Default::default()
+                }],
+            ),
+        ]);
+
+        let problems = check_chain(&chain, &providers, &keys, &models);
+        let kinds: Vec<_> = problems.iter().map(|p| (p.index, p.kind.clone())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (1, ChainProblemKind::UnknownModel),
+                (2, ChainProblemKind::InvalidTemperature),
+                (2, ChainProblemKind::InvalidMaxTokens),
+                (3, ChainProblemKind::KeyDisabled),
+                (3, ChainProblemKind::DeprecatedModel),
+                (4, ChainProblemKind::UnknownProvider),
+            ]
+        );
+        assert_eq!(
+            problems[0].to_string(),
+            "entry 1 (openai/gpt-4o-mimi): the provider does not list this model"
+        );
+    }
+}
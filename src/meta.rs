@@ -0,0 +1,112 @@
+//! Metadata about the API responses behind a call.
+//!
+//! Every API response carries an `X-Request-Id` header identifying the
+//! request in the API's logs. Errors built from a response expose it through
+//! [`Error::request_id`](crate::Error::request_id). For successful calls,
+//! [`capture_meta`] records the [`ResponseMeta`] of the last response
+//! received while a future runs:
+//!
+//! ```rust,no_run
+//! use refyne::{capture_meta, Client};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let (job, meta) = capture_meta(client.jobs().get("job-123")).await;
+//! let job = job?;
+//! if let Some(id) = meta.and_then(|m| m.request_id) {
+//!     println!("job {} fetched by request {}", job.id, id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::header::HeaderMap;
+use std::cell::RefCell;
+use std::future::Future;
+
+/// Header the API uses to identify a request.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Details of an API response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// ID the API assigned to the request, if it sent one.
+    pub request_id: Option<String>,
+    /// HTTP status code.
+    pub status: u16,
+    /// True if the response was served from the client's cache.
+    pub cached: bool,
+}
+
+impl ResponseMeta {
+    pub(crate) fn from_response(response: &reqwest::Response) -> Self {
+        Self {
+            request_id: request_id(response.headers()),
+            status: response.status().as_u16(),
+            cached: false,
+        }
+    }
+
+    pub(crate) fn cached() -> Self {
+        Self {
+            request_id: None,
+            status: 200,
+            cached: true,
+        }
+    }
+}
+
+/// Read the request ID header.
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+tokio::task_local! {
+    static CAPTURED: RefCell<Option<ResponseMeta>>;
+}
+
+/// Run a future and return the metadata of the last response it received.
+///
+/// Calls that make several requests, such as waiting for a job, report the
+/// final one. The metadata is `None` if no request was made.
+pub async fn capture_meta<F: Future>(future: F) -> (F::Output, Option<ResponseMeta>) {
+    CAPTURED
+        .scope(RefCell::new(None), async move {
+            let output = future.await;
+            (output, CAPTURED.with(|meta| meta.take()))
+        })
+        .await
+}
+
+/// Record a response for an enclosing [`capture_meta`], if any.
+pub(crate) fn record(meta: ResponseMeta) {
+    let _ = CAPTURED.try_with(|captured| *captured.borrow_mut() = Some(meta));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_capture_keeps_last_response() {
+        let (output, meta) = capture_meta(async {
+            record(ResponseMeta::cached());
+            record(ResponseMeta {
+                request_id: Some("req-2".into()),
+                status: 201,
+                cached: false,
+            });
+            7
+        })
+        .await;
+        assert_eq!(output, 7);
+        assert_eq!(meta.unwrap().request_id.as_deref(), Some("req-2"));
+
+        // Outside a capture, recording is a no-op.
+        record(ResponseMeta::cached());
+        assert!(capture_meta(async {}).await.1.is_none());
+    }
+}
@@ -0,0 +1,124 @@
+//! Middleware wrapping every HTTP request.
+//!
+//! Middleware added with
+//! [`ClientBuilder::with_middleware`](crate::ClientBuilder::with_middleware)
+//! sees each attempt the client sends, including retries, polling, and
+//! downloads. It can change the request, record it, answer it without
+//! calling the API, or inspect the response. Middleware runs in the order
+//! it was added, so the first added is the outermost.
+//!
+//! ```rust,no_run
+//! use refyne::{Client, Middleware, MiddlewareFuture, Next, PreparedRequest};
+//!
+//! struct Audit;
+//!
+//! impl Middleware for Audit {
+//!     fn handle<'a>(&'a self, request: PreparedRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+//!         Box::pin(async move {
+//!             let (method, url) = (request.method.clone(), request.url.clone());
+//!             let response = next.run(request).await?;
+//!             println!("{} {} -> {}", method, url, response.status());
+//!             Ok(response)
+//!         })
+//!     }
+//! }
+//!
+//! # fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").with_middleware(Audit).build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::prepared::PreparedRequest;
+use crate::runtime::BoxFuture;
+use std::sync::Arc;
+
+/// Future returned by [`Middleware::handle`] and [`Next::run`].
+pub type MiddlewareFuture<'a> = BoxFuture<'a, Result<reqwest::Response>>;
+
+/// A layer around the HTTP requests sent by the client.
+///
+/// Requests do not carry the API key; the client adds the `Authorization`
+/// header after the last middleware unless one is already set.
+pub trait Middleware: Send + Sync {
+    /// Handle a request, usually by passing it on with [`Next::run`].
+    fn handle<'a>(&'a self, request: PreparedRequest, next: Next<'a>) -> MiddlewareFuture<'a>;
+}
+
+/// Sends the request that reaches the end of the chain.
+pub(crate) type SendRequest<'a> =
+    dyn Fn(PreparedRequest) -> MiddlewareFuture<'a> + Send + Sync + 'a;
+
+/// The rest of the middleware chain.
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    send: &'a SendRequest<'a>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(middleware: &'a [Arc<dyn Middleware>], send: &'a SendRequest<'a>) -> Self {
+        Self { middleware, send }
+    }
+
+    /// Pass the request to the next middleware, or send it.
+    pub fn run(self, request: PreparedRequest) -> MiddlewareFuture<'a> {
+        match self.middleware.split_first() {
+            Some((first, rest)) => first.handle(
+                request,
+                Next {
+                    middleware: rest,
+                    send: self.send,
+                },
+            ),
+            None => (self.send)(request),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::sync::Mutex;
+
+    struct Tag(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl Middleware for Tag {
+        fn handle<'a>(
+            &'a self,
+            mut request: PreparedRequest,
+            next: Next<'a>,
+        ) -> MiddlewareFuture<'a> {
+            self.1.lock().unwrap().push(self.0.to_string());
+            let seen = request.header("X-Tags").unwrap_or_default().to_string();
+            request.set_header("X-Tags", format!("{}{}", seen, self.0));
+            next.run(request)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_runs_in_order() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(Tag("a", calls.clone())),
+            Arc::new(Tag("b", calls.clone())),
+        ];
+        let sent = Arc::new(Mutex::new(None));
+        let send = |request: PreparedRequest| -> MiddlewareFuture<'_> {
+            *sent.lock().unwrap() = request.header("X-Tags").map(str::to_string);
+            Box::pin(async { Err(Error::Config("not sent".into())) })
+        };
+        let request = PreparedRequest {
+            method: "GET".into(),
+            url: "https://api.refyne.uk/api/v1/jobs".into(),
+            headers: Vec::new(),
+            body: None,
+        };
+
+        let result = Next::new(&chain, &send).run(request).await;
+        assert!(matches!(result, Err(Error::Config(_))));
+        assert_eq!(*calls.lock().unwrap(), ["a", "b"]);
+        assert_eq!(sent.lock().unwrap().as_deref(), Some("ab"));
+    }
+}
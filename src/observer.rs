@@ -0,0 +1,72 @@
+//! Callbacks for feeding client activity into metrics.
+//!
+//! An [`Observer`] set with
+//! [`ClientBuilder::observer`](crate::ClientBuilder::observer) is told when
+//! each API call starts and ends, when attempts are retried, and when the
+//! cache answers a call. Calls are identified by their endpoint, the method
+//! and path with IDs replaced by `{id}` (e.g. `GET /api/v1/jobs/{id}`), which
+//! keeps metric label cardinality low.
+//!
+//! ```rust,no_run
+//! use refyne::{Client, Observer, RequestOutcome};
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::sync::Arc;
+//!
+//! #[derive(Default)]
+//! struct Counters {
+//!     requests: AtomicU64,
+//!     cache_hits: AtomicU64,
+//! }
+//!
+//! impl Observer for Counters {
+//!     fn on_request_end(&self, endpoint: &str, outcome: &RequestOutcome) {
+//!         self.requests.fetch_add(1, Ordering::Relaxed);
+//!         println!("{} took {:?}", endpoint, outcome.duration);
+//!     }
+//!
+//!     fn on_cache_hit(&self, _endpoint: &str) {
+//!         self.cache_hits.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! # fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key")
+//!     .observer(Arc::new(Counters::default()))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::ErrorCode;
+use std::time::Duration;
+
+/// How an API call ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequestOutcome {
+    /// Status of the last response, or `None` if none was received.
+    pub status: Option<u16>,
+    /// Requests sent, including retries. Zero when served from the cache.
+    pub attempts: u32,
+    /// Time from start to end of the call, including retry delays.
+    pub duration: Duration,
+    /// The error the call failed with, if any.
+    pub error: Option<ErrorCode>,
+}
+
+/// Receives notifications about API calls.
+///
+/// Callbacks run inline on the request path and should return quickly.
+pub trait Observer: Send + Sync {
+    /// An API call started.
+    fn on_request_start(&self, _endpoint: &str) {}
+
+    /// An API call finished, successfully or not.
+    fn on_request_end(&self, _endpoint: &str, _outcome: &RequestOutcome) {}
+
+    /// Attempt `attempt` failed and will be retried after `delay`.
+    fn on_retry(&self, _endpoint: &str, _attempt: u32, _delay: Duration) {}
+
+    /// A call was answered from the cache without contacting the API.
+    fn on_cache_hit(&self, _endpoint: &str) {}
+}
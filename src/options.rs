@@ -0,0 +1,87 @@
+//! Per-call overrides of client settings.
+//!
+//! [`Client::with_options`](crate::Client::with_options) returns a handle that
+//! applies [`RequestOptions`] to every HTTP request it makes, including
+//! retries and polling, and leaves the original client's settings untouched:
+//!
+//! ```rust,no_run
+//! use refyne::{AnalyzeRequest, Client, RequestOptions};
+//! use std::time::Duration;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key")
+//!     .timeout(Duration::from_secs(10))
+//!     .build()?;
+//! let analysis = client
+//!     .with_options(RequestOptions::default().timeout(Duration::from_secs(300)))
+//!     .analyze(AnalyzeRequest {
+//!         url: "https://example.com".into(),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::secret::REDACTED;
+use std::fmt;
+use std::time::Duration;
+
+/// Settings for a single call.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// Timeout for each HTTP request, replacing the client's
+    /// [`timeout`](crate::ClientBuilder::timeout). The connect timeout still
+    /// applies.
+    pub timeout: Option<Duration>,
+    /// API key to send instead of the client's. Cached responses and
+    /// duplicate-crawl tracking are kept separate per key, so one client can
+    /// serve many accounts over a shared connection pool.
+    pub api_key_override: Option<String>,
+    /// Tenant ID for [fair scheduling](crate::ClientBuilder::fair_scheduling),
+    /// as with [`Client::tenant`](crate::Client::tenant).
+    pub tenant: Option<String>,
+    /// Whether extracted data must match its schema, replacing the client's
+    /// [`strict_schema`](crate::ClientBuilder::strict_schema).
+    pub strict_schema: Option<bool>,
+}
+
+impl RequestOptions {
+    /// Set the per-request timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send `api_key` instead of the client's key.
+    pub fn api_key_override(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key_override = Some(api_key.into());
+        self
+    }
+
+    /// Schedule the call as `tenant`.
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Fail extractions whose data does not match the schema, or not.
+    pub fn strict_schema(mut self, enabled: bool) -> Self {
+        self.strict_schema = Some(enabled);
+        self
+    }
+}
+
+impl fmt::Debug for RequestOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestOptions")
+            .field("timeout", &self.timeout)
+            .field(
+                "api_key_override",
+                &self.api_key_override.as_ref().map(|_| REDACTED),
+            )
+            .field("tenant", &self.tenant)
+            .field("strict_schema", &self.strict_schema)
+            .finish()
+    }
+}
@@ -0,0 +1,194 @@
+//! Organization and team membership.
+//!
+//! [`OrgClient`](crate::OrgClient), from
+//! [`Client::org`](crate::Client::org), manages who has access to the
+//! account's organization, so provisioning tools can grant and revoke access
+//! without the dashboard:
+//!
+//! ```rust,no_run
+//! use refyne::OrgRole;
+//!
+//! # async fn run(client: refyne::Client) -> Result<(), refyne::Error> {
+//! let org = client.org();
+//! org.invite("new.hire@example.com", OrgRole::Member).await?;
+//! for member in org.list_members().await? {
+//!     if member.email.ends_with("@contractor.example") {
+//!         org.remove_member(&member.id).await?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::timestamp::Timestamp;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// A member's role in the organization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OrgRole {
+    /// Full control, including billing and deleting the organization.
+    Owner,
+    /// Manages members, keys, and settings.
+    Admin,
+    /// Uses the organization's schemas, sites, and jobs.
+    Member,
+    /// A role this SDK version does not know.
+    Other(String),
+}
+
+impl OrgRole {
+    /// The role as sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            OrgRole::Owner => "owner",
+            OrgRole::Admin => "admin",
+            OrgRole::Member => "member",
+            OrgRole::Other(role) => role,
+        }
+    }
+}
+
+impl From<&str> for OrgRole {
+    fn from(role: &str) -> Self {
+        match role.to_ascii_lowercase().as_str() {
+            "owner" => OrgRole::Owner,
+            "admin" => OrgRole::Admin,
+            "member" => OrgRole::Member,
+            _ => OrgRole::Other(role.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for OrgRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for OrgRole {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OrgRole {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(Self::from(String::deserialize(deserializer)?.as_str()))
+    }
+}
+
+/// The account's organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Organization {
+    /// Organization ID.
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Number of members, including the owner.
+    #[serde(default)]
+    pub member_count: i64,
+    /// When the organization was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<Timestamp>,
+}
+
+/// A member of the organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgMember {
+    /// Member ID, used to remove the member or change their role.
+    pub id: String,
+    /// Email address.
+    pub email: String,
+    /// Display name, if the member set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Role in the organization.
+    pub role: OrgRole,
+    /// When the member joined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub joined_at: Option<Timestamp>,
+}
+
+/// An invitation to join the organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgInvitation {
+    /// Invitation ID.
+    pub id: String,
+    /// Email address the invitation was sent to.
+    pub email: String,
+    /// Role the member will have once they accept.
+    pub role: OrgRole,
+    /// When the invitation stops being valid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Body of the member listing.
+#[derive(Deserialize)]
+pub(crate) struct OrgMemberList {
+    #[serde(default)]
+    pub(crate) members: Vec<OrgMember>,
+}
+
+/// Body of an invitation.
+#[derive(Serialize)]
+pub(crate) struct InviteInput<'a> {
+    email: &'a str,
+    role: &'a OrgRole,
+}
+
+impl<'a> InviteInput<'a> {
+    /// Fail with [`Error::Config`] on something that is not an email address,
+    /// or an invitation as owner, which must be transferred instead.
+    pub(crate) fn new(email: &'a str, role: &'a OrgRole) -> Result<Self> {
+        let valid = email
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.'));
+        if !valid || email.contains(char::is_whitespace) {
+            return Err(Error::Config(format!(
+                "Invalid email address {:?} for an invitation",
+                email
+            )));
+        }
+        if *role == OrgRole::Owner {
+            return Err(Error::Config(
+                "Members cannot be invited as owner. Invite them as admin".into(),
+            ));
+        }
+        Ok(Self { email, role })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_roles_round_trip() {
+        let member: OrgMember = serde_json::from_value(json!({
+            "id": "mem_1", "email": "a@example.com", "role": "Admin",
+        }))
+        .unwrap();
+        assert_eq!(member.role, OrgRole::Admin);
+        assert_eq!(OrgRole::from("billing"), OrgRole::Other("billing".into()));
+        assert_eq!(serde_json::to_value(&member.role).unwrap(), json!("admin"));
+    }
+
+    #[test]
+    fn test_invite_input_checks() {
+        assert!(InviteInput::new("a@example.com", &OrgRole::Member).is_ok());
+        for (email, role) in [
+            ("example.com", OrgRole::Member),
+            ("a b@example.com", OrgRole::Member),
+            ("a@example.com", OrgRole::Owner),
+        ] {
+            assert!(matches!(
+                InviteInput::new(email, &role),
+                Err(Error::Config(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Streams over paginated endpoints.
+//!
+//! A [`Paginator`] fetches pages by limit and offset as it is polled and
+//! yields their items one at a time, stopping at the first empty page. A
+//! short page does not end the stream, since the API may return fewer items
+//! than requested.
+//!
+//! ```rust,no_run
+//! use futures::TryStreamExt;
+//! use refyne::Client;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let mut jobs = client.jobs().list_all();
+//! while let Some(job) = jobs.try_next().await? {
+//!     println!("{} {}", job.id, job.status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::runtime::BoxFuture;
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Items requested per page unless set with [`Paginator::page_size`].
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Future returned by a page fetcher.
+pub(crate) type PageFuture<T> = BoxFuture<'static, Result<Vec<T>>>;
+
+type FetchPage<T> = Box<dyn FnMut(u32, u32) -> PageFuture<T> + Send>;
+
+/// A stream of every item of a paginated endpoint.
+///
+/// Pages are fetched lazily. An error is yielded once and ends the stream.
+#[must_use = "streams do nothing unless polled"]
+pub struct Paginator<T> {
+    fetch: FetchPage<T>,
+    page_size: u32,
+    offset: u32,
+    buffer: VecDeque<T>,
+    pending: Option<PageFuture<T>>,
+    done: bool,
+}
+
+impl<T> Paginator<T> {
+    /// Page through results with `fetch(limit, offset)`.
+    pub(crate) fn new<F>(fetch: F) -> Self
+    where
+        F: FnMut(u32, u32) -> PageFuture<T> + Send + 'static,
+    {
+        Self {
+            fetch: Box::new(fetch),
+            page_size: DEFAULT_PAGE_SIZE,
+            offset: 0,
+            buffer: VecDeque::new(),
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// Request `page_size` items per page.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+}
+
+// No field is structurally pinned; the pending page is boxed.
+impl<T> Unpin for Paginator<T> {}
+
+impl<T> Stream for Paginator<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let page = match &mut this.pending {
+                Some(page) => page,
+                None => this
+                    .pending
+                    .insert((this.fetch)(this.page_size, this.offset)),
+            };
+            let result = match page.as_mut().poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            this.pending = None;
+
+            match result {
+                Ok(items) => {
+                    this.done = items.is_empty();
+                    this.offset += items.len() as u32;
+                    this.buffer.extend(items);
+                }
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use futures::TryStreamExt;
+    use std::sync::{Arc, Mutex};
+
+    fn numbers(total: u32, calls: Arc<Mutex<Vec<(u32, u32)>>>) -> Paginator<u32> {
+        Paginator::new(move |limit, offset| {
+            calls.lock().unwrap().push((limit, offset));
+            let end = (offset + limit).min(total);
+            Box::pin(async move { Ok((offset..end).collect()) })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_pages_until_empty_page() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let items: Vec<u32> = numbers(5, calls.clone())
+            .page_size(2)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+        assert_eq!(*calls.lock().unwrap(), [(2, 0), (2, 2), (2, 4), (2, 5)]);
+
+        calls.lock().unwrap().clear();
+        let items: Vec<u32> = numbers(4, calls.clone())
+            .page_size(2)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 4);
+        assert_eq!(*calls.lock().unwrap(), [(2, 0), (2, 2), (2, 4)]);
+    }
+
+    #[tokio::test]
+    async fn test_server_capped_page_size() {
+        let items: Vec<u32> = Paginator::new(|limit: u32, offset: u32| {
+            // The server returns at most 3 items whatever the limit.
+            let end = (offset + limit.min(3)).min(10);
+            Box::pin(async move { Ok((offset..end).collect()) })
+        })
+        .try_collect()
+        .await
+        .unwrap();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_error_ends_stream() {
+        let mut pages =
+            Paginator::<u32>::new(|_, _| Box::pin(async { Err(Error::Config("boom".into())) }));
+        assert!(pages.try_next().await.is_err());
+        assert!(pages.try_next().await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,442 @@
+//! Analyze, extract or crawl, and collect results in one call.
+//!
+//! An [`ExtractionPipeline`] runs the usual flow for a new URL: analyze it
+//! for a suggested schema unless one is given, then extract the page, or
+//! crawl up to [`max_pages`](ExtractionPipeline::max_pages) pages, wait for
+//! the crawl to finish, and fetch its results.
+//!
+//! ```rust,no_run
+//! use refyne::{Client, ExtractionPipeline};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let output = ExtractionPipeline::new("https://example.com/products")
+//!     .page_type("detail")
+//!     .max_pages(5)
+//!     .run(&client)
+//!     .await?;
+//! for page in &output.pages {
+//!     println!("{}: {}", page.url, page.data);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{ApiErrorCode, Error, Result};
+use crate::progress::WaitOptions;
+use crate::results;
+use crate::types::{
+    AnalyzeRequest, AnalyzeResponse, CrawlOptions, CrawlOptionsFetchMode, CrawlRequest,
+    ExtractInputBodyFetchMode, ExtractRequest, ExtractResponse, Job,
+};
+use crate::types_ext::JobStatus;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A configurable analyze → extract or crawl → results flow.
+#[derive(Clone)]
+#[must_use = "a pipeline does nothing until run"]
+pub struct ExtractionPipeline {
+    url: String,
+    schema: Option<Value>,
+    fallback_schema: Option<Value>,
+    page_type: Option<String>,
+    max_pages: Option<u32>,
+    max_depth: Option<u32>,
+    wait: WaitOptions,
+}
+
+/// Whether `error` means the URL could not be analyzed, rather than that the
+/// request was refused.
+fn analysis_failed(error: &Error) -> bool {
+    error.is_retryable()
+        || matches!(
+            error.api_code(),
+            Some(ApiErrorCode::FetchFailed | ApiErrorCode::ExtractionFailed)
+        )
+}
+
+/// One page of pipeline output.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PipelinePage {
+    /// URL the data was extracted from.
+    pub url: String,
+    /// Extracted data.
+    pub data: Value,
+}
+
+/// What an [`ExtractionPipeline`] produced.
+#[derive(Debug, Clone)]
+pub struct PipelineOutput {
+    /// The analysis, unless a schema was given.
+    pub analysis: Option<AnalyzeResponse>,
+    /// The schema the pages were extracted with.
+    pub schema: Value,
+    /// ID of the extraction or crawl job.
+    pub job_id: String,
+    /// The extraction, when a single page was extracted.
+    pub extraction: Option<ExtractResponse>,
+    /// The finished crawl job, when pages were crawled.
+    pub job: Option<Job>,
+    /// Extracted pages. Empty if the crawl did not complete.
+    pub pages: Vec<PipelinePage>,
+}
+
+impl ExtractionPipeline {
+    /// A pipeline extracting a single page at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            schema: None,
+            fallback_schema: None,
+            page_type: None,
+            max_pages: None,
+            max_depth: None,
+            wait: WaitOptions::default(),
+        }
+    }
+
+    /// Extract with `schema` instead of analyzing the URL.
+    pub fn with_schema(mut self, schema: impl Into<Value>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Extract with `schema` if the analysis fails.
+    ///
+    /// Only failures of the analysis itself fall back: network and server
+    /// errors, and pages that could not be fetched or analyzed. Errors such
+    /// as a rejected API key or exhausted quota are returned.
+    pub fn fallback_schema(mut self, schema: impl Into<Value>) -> Self {
+        self.fallback_schema = Some(schema.into());
+        self
+    }
+
+    /// Use the schema suggested for pages of this type, such as `"detail"`,
+    /// and crawl only URLs matching its pattern.
+    ///
+    /// The URL is analyzed one level deep to find page types.
+    pub fn page_type(mut self, page_type: impl Into<String>) -> Self {
+        self.page_type = Some(page_type.into());
+        self
+    }
+
+    /// Crawl up to `max_pages` pages from the URL. With more than one page
+    /// the pipeline starts a crawl job and waits for it.
+    pub fn max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Follow links at most `max_depth` levels from the URL when crawling.
+    pub fn max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// How to wait for the crawl job.
+    pub fn wait_options(mut self, options: WaitOptions) -> Self {
+        self.wait = options;
+        self
+    }
+
+    /// Run the pipeline.
+    ///
+    /// A crawl that fails or is cancelled is returned with its job and no
+    /// pages; check [`Job::job_status`] on [`PipelineOutput::job`].
+    pub async fn run(self, client: &Client) -> Result<PipelineOutput> {
+        let (analysis, schema, selection) = self.select_schema(client).await?;
+        let fetch_mode = analysis
+            .as_ref()
+            .map(|analysis| analysis.recommended_fetch_mode.as_str());
+
+        if self.max_pages.is_none_or(|pages| pages <= 1) {
+            let extraction = client
+                .extract(ExtractRequest {
+                    url: self.url.clone(),
+                    schema: schema.clone(),
+                    fetch_mode: fetch_mode.and_then(extract_fetch_mode),
+                    ..Default::default()
+                })
+                .await?;
+            return Ok(PipelineOutput {
+                analysis,
+                schema,
+                job_id: extraction.job_id.clone(),
+                pages: vec![PipelinePage {
+                    url: extraction.url.clone(),
+                    data: extraction.data.clone(),
+                }],
+                extraction: Some(extraction),
+                job: None,
+            });
+        }
+
+        let created = client
+            .crawl(CrawlRequest {
+                url: self.url.clone(),
+                schema: schema.clone(),
+                options: Some(CrawlOptions {
+                    fetch_mode: fetch_mode.and_then(crawl_fetch_mode),
+                    follow_pattern: selection.and_then(|s| s.url_pattern),
+                    max_depth: self.max_depth.map(i64::from),
+                    max_pages: self.max_pages.map(i64::from),
+                    ..CrawlOptions::builder().build()
+                }),
+                ..Default::default()
+            })
+            .await?;
+        let job = client.wait_for_job_with(&created.job_id, self.wait).await?;
+        let pages = if job.job_status() == JobStatus::Completed {
+            let results = client.get_job_results(&job.id, false).await?;
+            results::items(&results)
+                .iter()
+                .map(|item| {
+                    PipelinePage::deserialize(item).map_err(|e| Error::deserialize(e, item.clone()))
+                })
+                .collect::<Result<_>>()?
+        } else {
+            Vec::new()
+        };
+        Ok(PipelineOutput {
+            analysis,
+            schema,
+            job_id: created.job_id,
+            extraction: None,
+            job: Some(job),
+            pages,
+        })
+    }
+
+    /// Pick the schema to extract with, analyzing the URL if needed.
+    async fn select_schema(
+        &self,
+        client: &Client,
+    ) -> Result<(Option<AnalyzeResponse>, Value, Option<Selection>)> {
+        if let Some(schema) = &self.schema {
+            return Ok((None, schema.clone(), None));
+        }
+        let request = AnalyzeRequest {
+            url: self.url.clone(),
+            depth: self.page_type.as_ref().map(|_| 1),
+            ..Default::default()
+        };
+        let analysis = match (client.analyze(request).await, &self.fallback_schema) {
+            (Ok(analysis), _) => analysis,
+            (Err(e), Some(fallback)) if analysis_failed(&e) => {
+                return Ok((None, fallback.clone(), None))
+            }
+            (Err(e), _) => return Err(e),
+        };
+
+        let selection = match &self.page_type {
+            Some(page_type) => {
+                let suggestion = analysis
+                    .page_types
+                    .iter()
+                    .find(|suggestion| suggestion.page_type == *page_type)
+                    .ok_or_else(|| {
+                        Error::Config(format!(
+                            "analysis of {} found no {} pages",
+                            self.url, page_type
+                        ))
+                    })?;
+                Selection {
+                    schema: suggestion.suggested_schema.clone(),
+                    url_pattern: suggestion.url_pattern.clone(),
+                }
+            }
+            None => Selection {
+                schema: analysis.suggested_schema.clone(),
+                url_pattern: None,
+            },
+        };
+        Ok((
+            Some(analysis),
+            parse_schema(&selection.schema),
+            Some(selection),
+        ))
+    }
+}
+
+/// The suggestion a schema was taken from.
+struct Selection {
+    schema: String,
+    url_pattern: Option<String>,
+}
+
+/// Suggested schemas are JSON; anything else is sent as text for the API to
+/// interpret.
+fn parse_schema(suggested: &str) -> Value {
+    serde_json::from_str(suggested).unwrap_or_else(|_| Value::String(suggested.to_string()))
+}
+
+fn extract_fetch_mode(mode: &str) -> Option<ExtractInputBodyFetchMode> {
+    match mode {
+        "static" => Some(ExtractInputBodyFetchMode::Static),
+        "dynamic" => Some(ExtractInputBodyFetchMode::Dynamic),
+        _ => None,
+    }
+}
+
+pub(crate) fn crawl_fetch_mode(mode: &str) -> Option<CrawlOptionsFetchMode> {
+    match mode {
+        "static" => Some(CrawlOptionsFetchMode::Static),
+        "dynamic" => Some(CrawlOptionsFetchMode::Dynamic),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::golden::load;
+    use serde_json::json;
+    use std::time::Duration;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn client(server: &MockServer) -> Client {
+        Client::builder("test-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_crawls_with_page_type_suggestion() {
+        let server = MockServer::start().await;
+        let job_id = "01JHB7S0D2F4H6J8K0M2P4R6TV";
+        Mock::given(method("POST"))
+            .and(path("/api/v1/analyze"))
+            .and(body_partial_json(json!({"depth": 1})))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(load("0.1.80", "analyze_depth1").unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/crawl"))
+            .and(body_partial_json(json!({
+                "schema": {"name": "string", "price": "number"},
+                "options": {"max_pages": 5, "follow_pattern": "/products/*", "fetch_mode": "static"},
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(load("0.1.80", "crawl_job_created").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v1/jobs/{}", job_id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(load("0.1.80", "job").unwrap()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v1/jobs/{}/results", job_id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"results": [
+                {"url": "https://example.com/products/pour-over", "data": {"name": "Pour-over"}},
+            ]})))
+            .mount(&server)
+            .await;
+
+        let output = ExtractionPipeline::new("https://example.com/products")
+            .page_type("detail")
+            .max_pages(5)
+            .wait_options(WaitOptions::new().interval(Duration::from_millis(1)))
+            .run(&client(&server).await)
+            .await
+            .unwrap();
+        assert_eq!(output.job_id, job_id);
+        assert!(output.analysis.is_some());
+        assert_eq!(output.job.unwrap().job_status(), JobStatus::Completed);
+        assert_eq!(output.pages[0].data, json!({"name": "Pour-over"}));
+
+        let missing = ExtractionPipeline::new("https://example.com/products")
+            .page_type("recipe")
+            .run(&client(&server).await)
+            .await;
+        assert!(matches!(missing, Err(Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_extracts_single_page_with_given_schema() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(json!({"schema": {"title": "string"}})))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(load("0.1.80", "extract").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let output = ExtractionPipeline::new("https://example.com/products/pour-over")
+            .with_schema(json!({"title": "string"}))
+            .run(&client(&server).await)
+            .await
+            .unwrap();
+        assert!(output.analysis.is_none());
+        assert!(output.job.is_none());
+        assert_eq!(output.pages.len(), 1);
+        assert_eq!(
+            output.pages[0].data,
+            output.extraction.as_ref().unwrap().data
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_only_when_analysis_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/analyze"))
+            .and(body_partial_json(
+                json!({"url": "https://example.com/broken"}),
+            ))
+            .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+                "title": "Unprocessable Entity",
+                "detail": "Page could not be fetched",
+                "code": "fetch_failed",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/analyze"))
+            .and(body_partial_json(
+                json!({"url": "https://example.com/unpaid"}),
+            ))
+            .respond_with(ResponseTemplate::new(402).set_body_json(json!({
+                "title": "Payment Required",
+                "detail": "No credits left",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .and(body_partial_json(json!({"schema": {"title": "string"}})))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(load("0.1.80", "extract").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let output = ExtractionPipeline::new("https://example.com/broken")
+            .fallback_schema(json!({"title": "string"}))
+            .run(&client)
+            .await
+            .unwrap();
+        assert!(output.analysis.is_none());
+
+        let unpaid = ExtractionPipeline::new("https://example.com/unpaid")
+            .fallback_schema(json!({"title": "string"}))
+            .run(&client)
+            .await;
+        assert!(unpaid.unwrap_err().is_quota());
+    }
+}
@@ -0,0 +1,76 @@
+//! Requests prepared for execution elsewhere.
+//!
+//! [`Client::prepare`](crate::Client::prepare) turns a request into its method,
+//! URL, headers, and body without sending it, so it can be queued or passed
+//! through an external signing or egress gateway. The API key is never part of
+//! a prepared request; [`Client::send_prepared`](crate::Client::send_prepared)
+//! adds it when the request is executed.
+
+use crate::types::{
+    AnalyzeRequest, CrawlRequest, CreateSchemaRequest, CreateSiteRequest, ExtractRequest,
+};
+use serde::{Deserialize, Serialize};
+
+/// An HTTP request ready to be sent, without credentials.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreparedRequest {
+    /// HTTP method.
+    pub method: String,
+    /// Absolute request URL.
+    pub url: String,
+    /// Request headers, excluding `Authorization`.
+    pub headers: Vec<(String, String)>,
+    /// Serialized request body.
+    pub body: Option<Vec<u8>>,
+}
+
+impl PreparedRequest {
+    /// Get the first header with the given name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set a header, replacing any existing values with the same name.
+    pub fn set_header(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        self.headers
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+    }
+}
+
+/// A request body that maps to a single API endpoint.
+pub trait ApiRequest: Serialize {
+    /// HTTP method for the endpoint.
+    const METHOD: &'static str;
+    /// Path of the endpoint relative to the base URL.
+    const PATH: &'static str;
+}
+
+impl ApiRequest for ExtractRequest {
+    const METHOD: &'static str = "POST";
+    const PATH: &'static str = "/api/v1/extract";
+}
+
+impl ApiRequest for CrawlRequest {
+    const METHOD: &'static str = "POST";
+    const PATH: &'static str = "/api/v1/crawl";
+}
+
+impl ApiRequest for AnalyzeRequest {
+    const METHOD: &'static str = "POST";
+    const PATH: &'static str = "/api/v1/analyze";
+}
+
+impl ApiRequest for CreateSchemaRequest {
+    const METHOD: &'static str = "POST";
+    const PATH: &'static str = "/api/v1/schemas";
+}
+
+impl ApiRequest for CreateSiteRequest {
+    const METHOD: &'static str = "POST";
+    const PATH: &'static str = "/api/v1/sites";
+}
@@ -0,0 +1,443 @@
+//! Built-in schemas for common page types.
+//!
+//! Each preset pairs an extraction schema with a struct its results
+//! deserialize into, so common pages can be extracted without designing a
+//! schema first.
+//!
+//! ```rust,no_run
+//! use refyne::presets::Product;
+//! use refyne::{Client, SchemaPreset};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let result = client
+//!     .extract(SchemaPreset::product().extract_request("https://example.com/product"))
+//!     .await?;
+//! let product: Product = result.data_as()?;
+//! println!("{:?} costs {:?}", product.name, product.price);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::{CreateSchemaInputBodyVisibility, CreateSchemaRequest, ExtractRequest};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// A ready-made extraction schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaPreset {
+    /// Short name, also used when saving the schema.
+    pub name: &'static str,
+    /// What the schema extracts.
+    pub description: &'static str,
+    /// Schema in the JSON form accepted by extraction requests.
+    pub schema: Value,
+}
+
+impl SchemaPreset {
+    /// Product pages: name, price, availability, and ratings.
+    pub fn product() -> Self {
+        Self {
+            name: "product",
+            description: "Product page details",
+            schema: json!({
+                "name": "string",
+                "description": "string",
+                "brand": "string",
+                "sku": "string",
+                "price": "number",
+                "currency": "string",
+                "availability": "string",
+                "rating": "number",
+                "review_count": "integer",
+                "image_url": "string",
+            }),
+        }
+    }
+
+    /// News and blog articles.
+    pub fn article() -> Self {
+        Self {
+            name: "article",
+            description: "Article headline, byline, and body",
+            schema: json!({
+                "headline": "string",
+                "summary": "string",
+                "author": "string",
+                "published_at": "string",
+                "section": "string",
+                "tags": ["string"],
+                "body": "string",
+            }),
+        }
+    }
+
+    /// Job postings.
+    pub fn job_posting() -> Self {
+        Self {
+            name: "job_posting",
+            description: "Job title, employer, location, and pay",
+            schema: json!({
+                "title": "string",
+                "company": "string",
+                "location": "string",
+                "remote": "boolean",
+                "employment_type": "string",
+                "salary_min": "number",
+                "salary_max": "number",
+                "salary_currency": "string",
+                "posted_at": "string",
+                "description": "string",
+            }),
+        }
+    }
+
+    /// Events such as concerts, meetups, and conferences.
+    pub fn event() -> Self {
+        Self {
+            name: "event",
+            description: "Event name, time, venue, and tickets",
+            schema: json!({
+                "name": "string",
+                "description": "string",
+                "starts_at": "string",
+                "ends_at": "string",
+                "venue": "string",
+                "address": "string",
+                "organizer": "string",
+                "price": "number",
+                "currency": "string",
+                "ticket_url": "string",
+            }),
+        }
+    }
+
+    /// Reviews listed on a page.
+    pub fn review() -> Self {
+        Self {
+            name: "review",
+            description: "Reviews with author, rating, and text",
+            schema: json!({
+                "reviews": [{
+                    "author": "string",
+                    "rating": "number",
+                    "title": "string",
+                    "body": "string",
+                    "published_at": "string",
+                }],
+            }),
+        }
+    }
+
+    /// The schema as YAML, as stored by saved schemas.
+    ///
+    /// Written with `serde_yaml` when the `yaml` feature is enabled.
+    pub fn to_yaml(&self) -> String {
+        to_yaml(&self.schema)
+    }
+
+    /// Build an extraction request for a URL using this schema.
+    pub fn extract_request(&self, url: impl Into<String>) -> ExtractRequest {
+        ExtractRequest {
+            url: url.into(),
+            schema: self.schema.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Build a request that saves this schema to the account.
+    pub fn create_request(&self) -> CreateSchemaRequest {
+        CreateSchemaRequest {
+            category: None,
+            description: Some(self.description.to_string()),
+            name: self.name.to_string(),
+            schema_yaml: self.to_yaml(),
+            tags: None,
+            visibility: CreateSchemaInputBodyVisibility::Private,
+        }
+    }
+}
+
+/// A struct that extraction results for a preset deserialize into.
+pub trait Preset: DeserializeOwned {
+    /// The schema producing this type.
+    fn preset() -> SchemaPreset;
+}
+
+/// Extracted product details.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Product {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub brand: Option<String>,
+    pub sku: Option<String>,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub availability: Option<String>,
+    pub rating: Option<f64>,
+    pub review_count: Option<i64>,
+    pub image_url: Option<String>,
+}
+
+/// Extracted article.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Article {
+    pub headline: Option<String>,
+    pub summary: Option<String>,
+    pub author: Option<String>,
+    pub published_at: Option<String>,
+    pub section: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub body: Option<String>,
+}
+
+/// Extracted job posting.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobPosting {
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub remote: Option<bool>,
+    pub employment_type: Option<String>,
+    pub salary_min: Option<f64>,
+    pub salary_max: Option<f64>,
+    pub salary_currency: Option<String>,
+    pub posted_at: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Extracted event.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Event {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub starts_at: Option<String>,
+    pub ends_at: Option<String>,
+    pub venue: Option<String>,
+    pub address: Option<String>,
+    pub organizer: Option<String>,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub ticket_url: Option<String>,
+}
+
+/// A single extracted review.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Review {
+    pub author: Option<String>,
+    pub rating: Option<f64>,
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub published_at: Option<String>,
+}
+
+/// Extracted reviews.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Reviews {
+    #[serde(default)]
+    pub reviews: Vec<Review>,
+}
+
+impl Preset for Product {
+    fn preset() -> SchemaPreset {
+        SchemaPreset::product()
+    }
+}
+
+impl Preset for Article {
+    fn preset() -> SchemaPreset {
+        SchemaPreset::article()
+    }
+}
+
+impl Preset for JobPosting {
+    fn preset() -> SchemaPreset {
+        SchemaPreset::job_posting()
+    }
+}
+
+impl Preset for Event {
+    fn preset() -> SchemaPreset {
+        SchemaPreset::event()
+    }
+}
+
+impl Preset for Reviews {
+    fn preset() -> SchemaPreset {
+        SchemaPreset::review()
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn to_yaml(value: &Value) -> String {
+    serde_yaml::to_string(value).expect("JSON values are valid YAML")
+}
+
+#[cfg(not(feature = "yaml"))]
+fn to_yaml(value: &Value) -> String {
+    let mut out = String::new();
+    write_yaml(value, 0, &mut out);
+    out
+}
+
+/// Write JSON as block YAML, with empty objects and arrays in flow style.
+#[cfg(not(feature = "yaml"))]
+fn write_yaml(value: &Value, indent: usize, out: &mut String) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, field) in map {
+                if is_block(field) {
+                    out.push_str(&format!("{}{}:\n", pad, scalar_str(key)));
+                    write_yaml(field, indent + 2, out);
+                } else {
+                    out.push_str(&format!("{}{}: {}\n", pad, scalar_str(key), scalar(field)));
+                }
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                if is_block(item) {
+                    let mut nested = String::new();
+                    write_yaml(item, indent + 2, &mut nested);
+                    // Put the first nested line on the dash line.
+                    out.push_str(&format!("{}- {}", pad, nested.trim_start_matches(' ')));
+                } else {
+                    out.push_str(&format!("{}- {}\n", pad, scalar(item)));
+                }
+            }
+        }
+        _ => out.push_str(&format!("{}{}\n", pad, scalar(value))),
+    }
+}
+
+/// Whether a value is written as an indented block rather than inline.
+#[cfg(not(feature = "yaml"))]
+fn is_block(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(not(feature = "yaml"))]
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => scalar_str(s),
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// A string as a plain YAML scalar, or double-quoted if it would otherwise
+/// read as something else.
+#[cfg(not(feature = "yaml"))]
+fn scalar_str(s: &str) -> String {
+    let reserved = matches!(
+        s.to_ascii_lowercase().as_str(),
+        "" | "~" | "null" | "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n"
+    );
+    let needs_quotes = reserved
+        || s.parse::<f64>().is_ok()
+        || s.starts_with(|c: char| c.is_whitespace() || "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        || s.ends_with(|c: char| c.is_whitespace() || c == ':')
+        || s.contains(": ")
+        || s.contains(" #")
+        || s.contains(|c: char| c.is_control());
+    if needs_quotes {
+        // A JSON string is a valid double-quoted YAML scalar.
+        Value::String(s.to_string()).to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_match_structs() {
+        fn keys(value: &Value) -> Vec<String> {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        }
+        fn check<P: Preset + Serialize + Default>() {
+            let preset = P::preset();
+            assert_eq!(
+                keys(&preset.schema),
+                keys(&serde_json::to_value(P::default()).unwrap()),
+                "struct fields differ from the {} schema",
+                preset.name
+            );
+        }
+
+        check::<Product>();
+        check::<Article>();
+        check::<JobPosting>();
+        check::<Event>();
+        check::<Reviews>();
+    }
+
+    #[test]
+    fn test_to_yaml() {
+        assert!(SchemaPreset::product()
+            .to_yaml()
+            .contains("price: number\n"));
+        assert!(SchemaPreset::article()
+            .to_yaml()
+            .lines()
+            .any(|l| l == "tags:"));
+        assert!(SchemaPreset::article().to_yaml().contains("- string\n"));
+        assert!(SchemaPreset::review().to_yaml().starts_with("reviews:\n"));
+        assert!(SchemaPreset::review()
+            .to_yaml()
+            .contains("- author: string\n"));
+    }
+
+    #[cfg(not(feature = "yaml"))]
+    #[test]
+    fn test_to_yaml_quotes_and_empty_values() {
+        let schema = json!({
+            "empty": {},
+            "items": [{}, [], ["a"]],
+            "note": "key: value",
+            "flag": "yes",
+            "count": "42",
+            "blank": "",
+        });
+        assert_eq!(
+            to_yaml(&schema),
+            "blank: \"\"\ncount: \"42\"\nempty: {}\nflag: \"yes\"\nitems:\n  - {}\n  - []\n  - - a\nnote: \"key: value\"\n"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_to_yaml_round_trips() {
+        let schema = json!({
+            "empty": {},
+            "items": [{}, [], ["a"]],
+            "note": "key: value",
+            "flag": "yes",
+        });
+        let yaml = to_yaml(&schema);
+        assert_eq!(serde_yaml::from_str::<Value>(&yaml).unwrap(), schema);
+    }
+
+    #[test]
+    fn test_requests() {
+        let request = SchemaPreset::event().extract_request("https://example.com/e/1");
+        assert_eq!(request.url, "https://example.com/e/1");
+        assert_eq!(request.schema["venue"], "string");
+
+        let create = SchemaPreset::job_posting().create_request();
+        assert_eq!(create.name, "job_posting");
+        assert!(create.schema_yaml.contains("remote: boolean"));
+    }
+}
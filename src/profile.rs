@@ -0,0 +1,188 @@
+//! Named client profiles loaded from a config file.
+//!
+//! [`ClientBuilder::from_profile`](crate::ClientBuilder::from_profile) reads
+//! `~/.config/refyne/config.toml`, or `$XDG_CONFIG_HOME/refyne/config.toml`
+//! when that is set. The `REFYNE_CONFIG_FILE` environment variable points at
+//! another file. Each table is a profile:
+//!
+//! ```toml
+//! [production]
+//! api_key = "rf_live_..."
+//!
+//! [staging]
+//! api_key = "rf_test_..."
+//! base_url = "https://staging.api.refyne.uk"
+//! timeout = 60
+//! max_retries = 5
+//!
+//! [staging.llm_config]
+//! provider = "openrouter"
+//! model = "anthropic/claude-sonnet-4"
+//! ```
+//!
+//! Only `api_key` is required. A profile's `llm_config` becomes the client's
+//! [`default_llm_config`](crate::ClientBuilder::default_llm_config).
+
+use crate::client::ClientBuilder;
+use crate::error::{Error, Result};
+use crate::types::LLMConfigInput;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable overriding the config file location.
+const CONFIG_FILE_ENV: &str = "REFYNE_CONFIG_FILE";
+
+/// Settings of one profile.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    /// Request timeout in seconds.
+    timeout: Option<f64>,
+    max_retries: Option<u32>,
+    llm_config: Option<LLMConfigInput>,
+}
+
+/// Location of the config file, if one can be determined.
+pub(crate) fn default_config_path() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+    if let Some(path) = var(CONFIG_FILE_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    let config_dir = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| var("USERPROFILE").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("refyne").join("config.toml"))
+}
+
+/// Read profile `name` from the file at `path` into a builder.
+pub(crate) fn load(path: &Path, name: &str) -> Result<ClientBuilder> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::Config(format!(
+            "could not read config file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    find(&contents, name)
+        .and_then(|profile| profile.into_builder(name))
+        .map_err(|message| Error::Config(format!("{}: {}", path.display(), message)))
+}
+
+/// Find profile `name` in the contents of a config file.
+fn find(contents: &str, name: &str) -> std::result::Result<Profile, String> {
+    let mut profiles: HashMap<String, Profile> =
+        toml::from_str(contents).map_err(|e| format!("invalid config file: {}", e))?;
+    profiles
+        .remove(name)
+        .ok_or_else(|| format!("profile {:?} not found", name))
+}
+
+impl Profile {
+    fn into_builder(self, name: &str) -> std::result::Result<ClientBuilder, String> {
+        let api_key = self
+            .api_key
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| format!("profile {:?} has no api_key", name))?;
+        let mut builder = ClientBuilder::new(api_key.trim());
+        if let Some(url) = self.base_url {
+            builder = builder.base_url(url);
+        }
+        if let Some(secs) = self.timeout {
+            let timeout = Duration::try_from_secs_f64(secs)
+                .ok()
+                .filter(|timeout| !timeout.is_zero())
+                .ok_or_else(|| {
+                    format!(
+                        "profile {:?}: timeout must be a positive number of seconds",
+                        name
+                    )
+                })?;
+            builder = builder.timeout(timeout);
+        }
+        if let Some(retries) = self.max_retries {
+            builder = builder.max_retries(retries);
+        }
+        if let Some(llm_config) = self.llm_config {
+            builder = builder.default_llm_config(llm_config);
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LLMConfigInputProvider;
+
+    const CONFIG: &str = r#"
+[production]
+api_key = "live-key"
+timeout = 60
+
+[staging]
+api_key = "test-key"
+base_url = "https://staging.example.com/"
+timeout = 1.5
+max_retries = 5
+
+[staging.llm_config]
+provider = "openrouter"
+model = "some/model"
+"#;
+
+    #[test]
+    fn test_find_profile() {
+        let staging = find(CONFIG, "staging").unwrap();
+        assert_eq!(staging.api_key.as_deref(), Some("test-key"));
+        assert_eq!(
+            staging.base_url.as_deref(),
+            Some("https://staging.example.com/")
+        );
+        assert_eq!(staging.timeout, Some(1.5));
+        assert_eq!(staging.max_retries, Some(5));
+        let llm_config = staging.llm_config.as_ref().unwrap();
+        assert_eq!(
+            llm_config.provider,
+            Some(LLMConfigInputProvider::Openrouter)
+        );
+        assert_eq!(llm_config.model.as_deref(), Some("some/model"));
+        assert!(staging.into_builder("staging").unwrap().build().is_ok());
+
+        let production = find(CONFIG, "production").unwrap();
+        assert!(production.base_url.is_none());
+        assert_eq!(production.timeout, Some(60.0));
+        assert!(production.into_builder("production").is_ok());
+    }
+
+    #[test]
+    fn test_profile_errors() {
+        let error = |contents: &str, name: &str| {
+            find(contents, name)
+                .and_then(|profile| profile.into_builder(name))
+                .err()
+                .unwrap()
+        };
+        assert_eq!(error(CONFIG, "dev"), "profile \"dev\" not found");
+        assert_eq!(
+            error("[dev]\nbase_url = \"https://x\"", "dev"),
+            "profile \"dev\" has no api_key"
+        );
+        assert!(error("[dev]\napi_key = \"k\"\ntimeout = -1", "dev").contains("timeout"));
+        assert!(error("[dev]\napi_key = \"k\"\napi_url = \"x\"", "dev").contains("api_url"));
+        assert!(error("not toml", "dev").starts_with("invalid config file"));
+    }
+
+    #[test]
+    fn test_load_reports_path() {
+        let path = Path::new("/nonexistent/refyne/config.toml");
+        match load(path, "default") {
+            Err(Error::Config(message)) => assert!(message.contains("/nonexistent/refyne")),
+            _ => panic!("expected a config error"),
+        }
+    }
+}
@@ -0,0 +1,362 @@
+//! Sources of job progress.
+//!
+//! The job waiters on [`Client`] ([`wait_for_job`](Client::wait_for_job),
+//! [`watch_job`](Client::watch_job), and
+//! [`crawl_and_wait`](Client::crawl_and_wait)) take any [`ProgressSource`], so
+//! the transport can be chosen per deployment:
+//!
+//! - [`Polling`] fetches the job at an interval and works everywhere.
+//! - [`ServerSentEvents`] reads updates from an event stream, or polls when
+//!   the API does not offer [`Feature::Sse`].
+//! - [`WebhookProgress`] is fed by a webhook handler through a
+//!   [`WebhookFeed`].
+//!
+//! For plain polling, [`JobsClient::wait`](crate::JobsClient::wait) takes
+//! [`WaitOptions`] with backoff, a time limit, and a progress callback.
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::runtime::BoxFuture;
+use crate::types::Job;
+use crate::version::Feature;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Future returned by [`ProgressSource::next`].
+pub type ProgressFuture<'a> = BoxFuture<'a, Result<Job>>;
+
+/// A way of learning the current state of a job.
+pub trait ProgressSource: Send {
+    /// Wait for the next known state of a job.
+    ///
+    /// May return the same state more than once; waiters stop once the job is
+    /// no longer pending or running.
+    fn next<'a>(&'a mut self, client: &'a Client, job_id: &'a str) -> ProgressFuture<'a>;
+}
+
+/// Fetch the job at an interval, optionally backing off between polls.
+#[derive(Debug, Clone)]
+pub struct Polling {
+    interval: Duration,
+    backoff: f64,
+    max_interval: Duration,
+    polled: bool,
+    deadline: Option<Duration>,
+}
+
+impl Polling {
+    /// Poll every `interval`. The first poll is immediate.
+    pub fn every(interval: Duration) -> Self {
+        Self {
+            interval,
+            backoff: 1.0,
+            max_interval: interval,
+            polled: false,
+            deadline: None,
+        }
+    }
+
+    /// Multiply the interval by `factor` after each poll, up to
+    /// `max_interval`.
+    pub fn with_backoff(mut self, factor: f64, max_interval: Duration) -> Self {
+        self.backoff = if factor.is_nan() {
+            1.0
+        } else {
+            factor.clamp(1.0, f64::MAX)
+        };
+        self.max_interval = max_interval.max(self.interval);
+        self
+    }
+
+    /// Stop sleeping past `deadline`, a time read from the client's clock.
+    pub(crate) fn until(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Whether the client's clock has reached the deadline.
+    pub(crate) fn is_past_deadline(&self, client: &Client) -> bool {
+        self.deadline
+            .is_some_and(|deadline| client.clock().now() >= deadline)
+    }
+}
+
+impl Default for Polling {
+    fn default() -> Self {
+        Self::every(Duration::from_secs(2))
+    }
+}
+
+impl ProgressSource for Polling {
+    fn next<'a>(&'a mut self, client: &'a Client, job_id: &'a str) -> ProgressFuture<'a> {
+        Box::pin(async move {
+            if self.polled {
+                let mut delay = self.interval;
+                if let Some(deadline) = self.deadline {
+                    delay = delay.min(deadline.saturating_sub(client.clock().now()));
+                }
+                client.clock().sleep(delay).await;
+                self.interval =
+                    Duration::try_from_secs_f64(self.interval.as_secs_f64() * self.backoff)
+                        .map_or(self.max_interval, |next| next.min(self.max_interval));
+            }
+            self.polled = true;
+            client.get_job(job_id).await
+        })
+    }
+}
+
+/// Callback receiving each job state seen while waiting.
+pub type JobProgressCallback = Arc<dyn Fn(&Job) + Send + Sync>;
+
+/// How [`JobsClient::wait`](crate::JobsClient::wait) polls a job.
+///
+/// Defaults to polling every 2 seconds with no backoff and no time limit.
+///
+/// ```rust,no_run
+/// use refyne::{Client, WaitOptions};
+/// use std::time::Duration;
+///
+/// # async fn run() -> Result<(), refyne::Error> {
+/// let client = Client::builder("your-api-key").build()?;
+/// let options = WaitOptions::new()
+///     .interval(Duration::from_secs(1))
+///     .backoff(1.5, Duration::from_secs(30))
+///     .max_wait(Duration::from_secs(600))
+///     .on_progress(|job| println!("{}: {} pages", job.status, job.page_count));
+/// let job = client.jobs().wait("job-id", options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct WaitOptions {
+    polling: Polling,
+    max_wait: Option<Duration>,
+    on_progress: Option<JobProgressCallback>,
+}
+
+impl WaitOptions {
+    /// Options with the defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time between polls, or the first delay when backing off.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        let Polling {
+            backoff,
+            max_interval,
+            ..
+        } = self.polling;
+        self.polling = Polling::every(interval).with_backoff(backoff, max_interval);
+        self
+    }
+
+    /// Multiply the interval by `factor` after each poll, up to
+    /// `max_interval`.
+    pub fn backoff(mut self, factor: f64, max_interval: Duration) -> Self {
+        self.polling = self.polling.with_backoff(factor, max_interval);
+        self
+    }
+
+    /// Give up with [`Error::Timeout`] if the job is still active after
+    /// `max_wait`, as measured by the client's clock.
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Call `callback` with every job state fetched, including the last.
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Job) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    pub(crate) fn into_parts(self) -> (Polling, Option<Duration>, Option<JobProgressCallback>) {
+        (self.polling, self.max_wait, self.on_progress)
+    }
+}
+
+impl std::fmt::Debug for WaitOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitOptions")
+            .field("polling", &self.polling)
+            .field("max_wait", &self.max_wait)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+/// Read job updates from a server-sent event stream.
+///
+/// Each event's `data` is parsed as a [`Job`]; events that do not parse, such
+/// as keep-alives, are skipped. When the stream ends the job is fetched once
+/// and the stream is reopened on the next call.
+///
+/// If the API advertises its features without [`Feature::Sse`], the job is
+/// polled with [`Polling::default`] instead.
+#[derive(Debug)]
+pub struct ServerSentEvents {
+    path: String,
+    stream: Option<reqwest::Response>,
+    buffer: Vec<u8>,
+    data: Vec<String>,
+    features_checked: bool,
+    fallback: Option<Polling>,
+}
+
+impl ServerSentEvents {
+    /// Stream from `path`, relative to the base URL, with `{id}` replaced by
+    /// the job ID.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            stream: None,
+            buffer: Vec::new(),
+            data: Vec::new(),
+            features_checked: false,
+            fallback: None,
+        }
+    }
+
+    /// Take the next complete event's data from the buffer.
+    ///
+    /// Only whole lines are decoded, so characters split between chunks
+    /// arrive intact.
+    fn next_event(&mut self) -> Option<String> {
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let mut line: Vec<u8> = self.buffer.drain(..=end).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            if line.is_empty() {
+                return Some(std::mem::take(&mut self.data).join("\n"));
+            }
+            let line = String::from_utf8_lossy(&line);
+            if let Some(data) = line.strip_prefix("data:") {
+                self.data
+                    .push(data.strip_prefix(' ').unwrap_or(data).to_string());
+            }
+        }
+        None
+    }
+}
+
+impl ProgressSource for ServerSentEvents {
+    fn next<'a>(&'a mut self, client: &'a Client, job_id: &'a str) -> ProgressFuture<'a> {
+        Box::pin(async move {
+            if !self.features_checked {
+                self.features_checked = true;
+                if let Ok(features) = client.api_features().await {
+                    if features.is_advertised() && !features.supports(&Feature::Sse) {
+                        self.fallback = Some(Polling::default());
+                    }
+                }
+            }
+            if let Some(polling) = &mut self.fallback {
+                return polling.next(client, job_id).await;
+            }
+
+            loop {
+                while let Some(data) = self.next_event() {
+                    if let Ok(job) = serde_json::from_str::<Job>(&data) {
+                        return Ok(job);
+                    }
+                }
+
+                let stream = match &mut self.stream {
+                    Some(stream) => stream,
+                    None => {
+                        let path = self.path.replace("{id}", job_id);
+                        self.stream.insert(client.open_event_stream(&path).await?)
+                    }
+                };
+                match stream.chunk().await.map_err(Error::Http)? {
+                    Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                    None => {
+                        self.stream = None;
+                        self.buffer.clear();
+                        self.data.clear();
+                        return client.get_job(job_id).await;
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Sending side of a [`WebhookProgress`], for use in a webhook handler.
+#[derive(Debug, Clone)]
+pub struct WebhookFeed {
+    sender: mpsc::UnboundedSender<Job>,
+}
+
+impl WebhookFeed {
+    /// Pass on a job update received by a webhook.
+    ///
+    /// Returns false if the receiving side has been dropped.
+    pub fn push(&self, job: Job) -> bool {
+        self.sender.send(job).is_ok()
+    }
+}
+
+/// Job updates delivered by webhooks.
+#[derive(Debug)]
+pub struct WebhookProgress {
+    receiver: mpsc::UnboundedReceiver<Job>,
+}
+
+impl WebhookProgress {
+    /// Create a progress source and the feed that supplies it.
+    pub fn channel() -> (WebhookFeed, WebhookProgress) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (WebhookFeed { sender }, WebhookProgress { receiver })
+    }
+}
+
+impl ProgressSource for WebhookProgress {
+    fn next<'a>(&'a mut self, _client: &'a Client, job_id: &'a str) -> ProgressFuture<'a> {
+        Box::pin(async move {
+            while let Some(job) = self.receiver.recv().await {
+                if job.id == job_id {
+                    return Ok(job);
+                }
+            }
+            Err(Error::Config("webhook feed was dropped".into()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_event_parsing() {
+        let mut source = ServerSentEvents::new("/jobs/{id}/events");
+        source.buffer =
+            b": keep-alive\r\n\r\nevent: job\ndata: {\"a\":\ndata: 1}\n\ndata: x".to_vec();
+
+        assert_eq!(source.next_event().as_deref(), Some(""));
+        assert_eq!(source.next_event().as_deref(), Some("{\"a\":\n1}"));
+        assert_eq!(source.next_event(), None);
+        assert_eq!(source.buffer, b"data: x");
+    }
+
+    #[test]
+    fn test_sse_characters_split_between_chunks() {
+        let mut source = ServerSentEvents::new("/jobs/{id}/events");
+        let event = "data: {\"name\": \"Café\"}\n\n".as_bytes();
+        let split = event.iter().position(|&b| b == 0xC3).unwrap() + 1;
+
+        source.buffer.extend_from_slice(&event[..split]);
+        assert_eq!(source.next_event(), None);
+        source.buffer.extend_from_slice(&event[split..]);
+        assert_eq!(source.next_event().as_deref(), Some("{\"name\": \"Café\"}"));
+    }
+}
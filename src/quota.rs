@@ -0,0 +1,209 @@
+//! Remaining quota for scheduling work.
+//!
+//! The client records the rate-limit headers of every API response.
+//! [`Client::remaining_quota`](crate::Client::remaining_quota) combines the
+//! latest of them with the usage endpoint and the account limits configured
+//! with [`ClientBuilder::quota_limits`](crate::ClientBuilder::quota_limits).
+
+use crate::runtime::{Instant, SystemTime, UNIX_EPOCH};
+use reqwest::header::HeaderMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Account limits for the billing period.
+///
+/// The usage endpoint reports consumption but not the plan's limits, so
+/// these are supplied by the caller. Usage is counted in jobs, not pages, so
+/// the allowance is too.
+#[derive(Debug, Clone, Default)]
+pub struct QuotaLimits {
+    /// Jobs allowed per billing period.
+    pub jobs: Option<i64>,
+    /// Spend allowed per billing period, in USD.
+    pub budget_usd: Option<f64>,
+}
+
+/// Snapshot of the quota left for new work.
+#[derive(Debug, Clone)]
+pub struct QuotaSnapshot {
+    /// Requests left in the current rate-limit window.
+    pub requests_remaining: Option<u64>,
+    /// Requests allowed per rate-limit window.
+    pub requests_limit: Option<u64>,
+    /// Time until the rate-limit window resets.
+    pub window_resets_in: Option<Duration>,
+    /// Jobs run this billing period.
+    pub jobs_used: i64,
+    /// Jobs left this billing period, if a limit is configured.
+    pub jobs_remaining: Option<i64>,
+    /// USD charged this billing period.
+    pub spent_usd: f64,
+    /// USD left this billing period, if a budget is configured.
+    pub budget_remaining_usd: Option<f64>,
+}
+
+impl QuotaSnapshot {
+    /// How many more requests can be sent now without exceeding a limit.
+    ///
+    /// Returns `None` when no limit is known.
+    pub fn capacity(&self) -> Option<u64> {
+        let jobs = self.jobs_remaining.map(|remaining| remaining.max(0) as u64);
+        match (self.requests_remaining, jobs) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// True if the budget or job allowance is used up.
+    pub fn is_exhausted(&self) -> bool {
+        self.jobs_remaining.is_some_and(|n| n <= 0)
+            || self.budget_remaining_usd.is_some_and(|usd| usd <= 0.0)
+    }
+}
+
+/// Rate-limit window as reported by the most recent response.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RateLimitWindow {
+    pub(crate) remaining: Option<u64>,
+    pub(crate) limit: Option<u64>,
+    resets_at: Option<Instant>,
+}
+
+impl RateLimitWindow {
+    /// Parse the `X-RateLimit-*` headers of a response.
+    ///
+    /// `X-RateLimit-Reset` may be seconds until the reset or the Unix time of
+    /// the reset; see [`reset_delay`]. A reset too far away to represent is
+    /// treated as unknown.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+        let remaining = number("X-RateLimit-Remaining");
+        let limit = number("X-RateLimit-Limit");
+        let reset = number("X-RateLimit-Reset");
+        if remaining.is_none() && limit.is_none() && reset.is_none() {
+            return None;
+        }
+        Some(Self {
+            remaining,
+            limit,
+            resets_at: reset
+                .and_then(|reset| Instant::now().checked_add(reset_delay(reset, unix_now()))),
+        })
+    }
+
+    pub(crate) fn resets_in(&self) -> Option<Duration> {
+        self.resets_at
+            .map(|at| at.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// Values of `X-RateLimit-Reset` from this one on are Unix times; smaller
+/// ones are seconds until the reset. No rate-limit window lasts ten years.
+const UNIX_RESET_THRESHOLD: u64 = 10 * 365 * 24 * 60 * 60;
+
+/// Time until the reset given by an `X-RateLimit-Reset` value.
+fn reset_delay(reset: u64, now_unix: u64) -> Duration {
+    if reset >= UNIX_RESET_THRESHOLD {
+        Duration::from_secs(reset.saturating_sub(now_unix))
+    } else {
+        Duration::from_secs(reset)
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+/// Latest rate-limit window seen by a client.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimitTracker {
+    latest: Mutex<Option<RateLimitWindow>>,
+}
+
+impl RateLimitTracker {
+    pub(crate) fn observe(&self, headers: &HeaderMap) {
+        if let Some(window) = RateLimitWindow::from_headers(headers) {
+            *self.latest.lock().unwrap() = Some(window);
+        }
+    }
+
+    pub(crate) fn latest(&self) -> Option<RateLimitWindow> {
+        let window = (*self.latest.lock().unwrap())?;
+        // A window that has reset no longer says anything about the next one.
+        match window.resets_in() {
+            Some(left) if left.is_zero() => None,
+            _ => Some(window),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(RateLimitWindow::from_headers(&headers).is_none());
+
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("12"));
+        headers.insert("X-RateLimit-Limit", HeaderValue::from_static("60"));
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("30"));
+        let tracker = RateLimitTracker::default();
+        tracker.observe(&headers);
+
+        let window = tracker.latest().unwrap();
+        assert_eq!(window.remaining, Some(12));
+        assert_eq!(window.limit, Some(60));
+        assert!(window.resets_in().unwrap() <= Duration::from_secs(30));
+
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("0"));
+        tracker.observe(&headers);
+        assert!(tracker.latest().is_none());
+
+        // A reset beyond what an Instant can hold is unknown, not a panic.
+        headers.insert(
+            "X-RateLimit-Reset",
+            HeaderValue::from_static("18446744073709551615"),
+        );
+        tracker.observe(&headers);
+        assert!(tracker.latest().unwrap().resets_in().is_none());
+    }
+
+    #[test]
+    fn test_reset_delay_accepts_seconds_and_unix_times() {
+        let now = 1_767_225_600; // 2026-01-01
+        assert_eq!(reset_delay(30, now), Duration::from_secs(30));
+        assert_eq!(reset_delay(now + 45, now), Duration::from_secs(45));
+        assert_eq!(reset_delay(now - 10, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_snapshot_capacity() {
+        let snapshot = QuotaSnapshot {
+            requests_remaining: Some(20),
+            requests_limit: Some(60),
+            window_resets_in: None,
+            jobs_used: 95,
+            jobs_remaining: Some(5),
+            spent_usd: 4.0,
+            budget_remaining_usd: Some(1.0),
+        };
+        assert_eq!(snapshot.capacity(), Some(5));
+        assert!(!snapshot.is_exhausted());
+
+        let spent = QuotaSnapshot {
+            budget_remaining_usd: Some(0.0),
+            ..snapshot
+        };
+        assert!(spent.is_exhausted());
+    }
+}
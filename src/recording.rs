@@ -0,0 +1,351 @@
+//! Record and replay API traffic for reproducible tests.
+//!
+//! A [`Cassette`] is middleware that either records each response the API
+//! sends to a JSON file, or answers requests from such a file without
+//! touching the network. Requests are matched on method, path and query, and
+//! a SHA-256 hash of the body, so a cassette recorded against one base URL
+//! replays against any other. Request bodies and the API key are never
+//! written to the file.
+//!
+//! ```rust,no_run
+//! use refyne::{Cassette, Client, ExtractRequest};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! // Records on the first run, which needs REFYNE_API_KEY. Later runs replay
+//! // the file and need neither a key nor network access.
+//! let cassette = Cassette::open("tests/cassettes/extract.json")?;
+//! let client = Client::builder(std::env::var("REFYNE_API_KEY").unwrap_or_else(|_| "replay".into()))
+//!     .with_middleware(cassette)
+//!     .build()?;
+//! let result = client
+//!     .extract(ExtractRequest {
+//!         url: "https://demo.refyne.uk/products/1".into(),
+//!         schema: serde_json::json!({"name": "string"}),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Add the cassette after any middleware that changes requests, so it
+//! records them as they are sent.
+
+use crate::error::{Error, Result};
+use crate::middleware::{Middleware, MiddlewareFuture, Next};
+use crate::prepared::PreparedRequest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Whether a [`Cassette`] talks to the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and append each response to the file.
+    Record,
+    /// Answer requests from the file.
+    Replay,
+}
+
+/// Contents of a cassette file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Recording {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RequestKey,
+    response: RecordedResponse,
+}
+
+/// What a request is matched on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RequestKey {
+    method: String,
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// Body, when it is valid UTF-8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    /// Hex-encoded body otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_hex: Option<String>,
+}
+
+/// Middleware recording API responses to, or replaying them from, a file.
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    state: Mutex<State>,
+}
+
+struct State {
+    recording: Recording,
+    /// Whether each interaction has been replayed.
+    used: Vec<bool>,
+}
+
+impl Cassette {
+    /// Record responses to `path`, replacing any existing recording.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::with_recording(path.into(), CassetteMode::Record, Recording::default())
+    }
+
+    /// Replay responses recorded in `path`.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let contents = std::fs::read(&path).map_err(|e| {
+            Error::Config(format!("could not read cassette {}: {}", path.display(), e))
+        })?;
+        let recording = serde_json::from_slice(&contents)
+            .map_err(|e| Error::Config(format!("invalid cassette {}: {}", path.display(), e)))?;
+        Ok(Self::with_recording(path, CassetteMode::Replay, recording))
+    }
+
+    /// Replay `path` if it exists, and record to it otherwise.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            Self::replay(path)
+        } else {
+            Ok(Self::record(path))
+        }
+    }
+
+    fn with_recording(path: PathBuf, mode: CassetteMode, recording: Recording) -> Self {
+        let used = vec![false; recording.interactions.len()];
+        Self {
+            path,
+            mode,
+            state: Mutex::new(State { recording, used }),
+        }
+    }
+
+    /// Whether this cassette records or replays.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Location of the cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Find the response for a request.
+    ///
+    /// Identical requests get their recorded responses in order. Once those
+    /// run out, the last one is repeated.
+    fn find(&self, key: &RequestKey) -> Result<reqwest::Response> {
+        let mut state = self.state.lock().unwrap();
+        let State { recording, used } = &mut *state;
+        let matches: Vec<usize> = recording
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.request == *key)
+            .map(|(index, _)| index)
+            .collect();
+        let index = matches
+            .iter()
+            .copied()
+            .find(|&index| !used[index])
+            .or_else(|| matches.last().copied())
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "no response recorded for {} {} in {}",
+                    key.method,
+                    key.path,
+                    self.path.display()
+                ))
+            })?;
+        used[index] = true;
+        recording.interactions[index].response.to_response()
+    }
+
+    /// Append an interaction and rewrite the file.
+    fn save(&self, interaction: Interaction) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.recording.interactions.push(interaction);
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = serde_json::to_vec_pretty(&state.recording)?;
+        std::fs::write(&self.path, contents)?;
+        Ok(())
+    }
+}
+
+impl Middleware for Cassette {
+    fn handle<'a>(&'a self, request: PreparedRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+        let key = RequestKey::new(&request);
+        Box::pin(async move {
+            if self.mode == CassetteMode::Replay {
+                return self.find(&key);
+            }
+            let response = next.run(request).await?;
+            let recorded = RecordedResponse::read(response).await?;
+            let replayed = recorded.to_response();
+            self.save(Interaction {
+                request: key,
+                response: recorded,
+            })?;
+            replayed
+        })
+    }
+}
+
+impl RequestKey {
+    fn new(request: &PreparedRequest) -> Self {
+        // Drop the scheme and host so recordings are independent of base URL.
+        let path = request
+            .url
+            .split_once("://")
+            .and_then(|(_, rest)| rest.find('/').map(|slash| &rest[slash..]))
+            .unwrap_or("/");
+        Self {
+            method: request.method.to_uppercase(),
+            path: path.to_string(),
+            body_sha256: request
+                .body
+                .as_ref()
+                .map(|body| hex::encode(Sha256::digest(body))),
+        }
+    }
+}
+
+impl RecordedResponse {
+    async fn read(response: reqwest::Response) -> Result<Self> {
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let bytes = response.bytes().await?.to_vec();
+        let (body, body_hex) = match String::from_utf8(bytes) {
+            Ok(text) => (Some(text), None),
+            Err(e) => (None, Some(hex::encode(e.into_bytes()))),
+        };
+        Ok(Self {
+            status,
+            headers,
+            body,
+            body_hex,
+        })
+    }
+
+    fn to_response(&self) -> Result<reqwest::Response> {
+        let body = match (&self.body, &self.body_hex) {
+            (Some(text), _) => text.clone().into_bytes(),
+            (None, Some(encoded)) => hex::decode(encoded)
+                .map_err(|e| Error::Config(format!("invalid cassette body: {}", e)))?,
+            (None, None) => Vec::new(),
+        };
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder
+            .body(body)
+            .map_err(|e| Error::Config(format!("invalid cassette response: {}", e)))?;
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_request_key_ignores_host() {
+        let request = |url: &str, body: Option<&str>| PreparedRequest {
+            method: "post".into(),
+            url: url.into(),
+            headers: Vec::new(),
+            body: body.map(|body| body.as_bytes().to_vec()),
+        };
+        let key = RequestKey::new(&request(
+            "https://api.refyne.uk/api/v1/extract?x=1",
+            Some("{}"),
+        ));
+        assert_eq!(key.method, "POST");
+        assert_eq!(key.path, "/api/v1/extract?x=1");
+        assert_eq!(
+            key,
+            RequestKey::new(&request(
+                "http://127.0.0.1:8080/api/v1/extract?x=1",
+                Some("{}")
+            ))
+        );
+        assert_ne!(
+            key,
+            RequestKey::new(&request(
+                "https://api.refyne.uk/api/v1/extract?x=1",
+                Some("[]")
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/jobs/01HXYZABCD1234"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crate::golden::load("0.1.80", "job").unwrap()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let file = std::env::temp_dir()
+            .join(format!("refyne-cassette-{}", std::process::id()))
+            .join("job.json");
+        let _ = std::fs::remove_file(&file);
+
+        let cassette = Cassette::open(&file).unwrap();
+        assert_eq!(cassette.mode(), CassetteMode::Record);
+        let client = Client::builder("secret-key")
+            .base_url(server.uri())
+            .with_middleware(cassette)
+            .build()
+            .unwrap();
+        let recorded = client.get_job("01HXYZABCD1234").await.unwrap();
+        assert!(!std::fs::read_to_string(&file)
+            .unwrap()
+            .contains("secret-key"));
+
+        // Nothing listens on the discard port, so any request would fail.
+        let cassette = Cassette::open(&file).unwrap();
+        assert_eq!(cassette.mode(), CassetteMode::Replay);
+        let client = Client::builder("other-key")
+            .base_url("http://127.0.0.1:9")
+            .max_retries(0)
+            .with_middleware(cassette)
+            .build()
+            .unwrap();
+        let replayed = client.get_job("01HXYZABCD1234").await.unwrap();
+        assert_eq!(replayed.id, recorded.id);
+        assert_eq!(replayed.status, recorded.status);
+        match client.get_job("01HXYZABCD9999").await {
+            Err(Error::Config(message)) => assert!(message.contains("no response recorded")),
+            other => panic!(
+                "expected a missing recording, got {:?}",
+                other.map(|job| job.id)
+            ),
+        }
+        std::fs::remove_dir_all(file.parent().unwrap()).unwrap();
+    }
+}
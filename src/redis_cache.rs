@@ -0,0 +1,293 @@
+//! Response cache stored in Redis.
+//!
+//! [`RedisCache`] lets several processes share cached responses. Entries are
+//! stored as JSON with a Redis TTL covering their lifetime plus any
+//! stale-while-revalidate or revalidation window, so Redis drops them once
+//! they are of no further use. Entries that never expire, such as last-good
+//! extraction results, are stored without a TTL.
+//!
+//! [`Cache`] is synchronous, so each command blocks the calling thread for at
+//! most the [timeout](RedisCache::with_timeout). On a multi-threaded Tokio
+//! runtime the wait runs in [`block_in_place`](tokio::task::block_in_place),
+//! so other tasks keep running on the remaining workers.
+//!
+//! ```rust,no_run
+//! use refyne::{Client, RedisCache};
+//! use std::sync::Arc;
+//!
+//! # fn run() -> Result<(), refyne::Error> {
+//! let cache = RedisCache::open("redis://127.0.0.1/")?.with_prefix("my-service:refyne:");
+//! let client = Client::builder("your-api-key")
+//!     .cache(Arc::new(cache))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cache::{Cache, CacheEntry};
+use crate::clock::{Clock, SystemClock};
+use crate::error::{Error, Result};
+use redis::Commands;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::warn;
+
+/// Prefix added to cache keys unless set with [`RedisCache::with_prefix`].
+pub const DEFAULT_REDIS_PREFIX: &str = "refyne:";
+
+/// Limit on connecting to Redis and on each command, unless set with
+/// [`RedisCache::with_timeout`].
+pub const DEFAULT_REDIS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Longest TTL given to an entry that does expire. Redis rejects expiry
+/// times that overflow its millisecond clock.
+const MAX_REDIS_TTL: u64 = 365 * 24 * 60 * 60;
+
+/// Cache implementation backed by a Redis server.
+///
+/// Redis errors are logged and treated as cache misses, so an unavailable
+/// server slows requests down but does not fail them. The connection is
+/// re-established on the next call after an error.
+pub struct RedisCache {
+    client: redis::Client,
+    connection: Mutex<Option<redis::Connection>>,
+    prefix: String,
+    timeout: Duration,
+}
+
+impl RedisCache {
+    /// Create a cache for the server at `url`, e.g. `redis://127.0.0.1/`.
+    ///
+    /// No connection is made until the cache is first used.
+    pub fn open(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| Error::Config(format!("invalid Redis URL: {}", e)))?;
+        Ok(Self {
+            client,
+            connection: Mutex::new(None),
+            prefix: DEFAULT_REDIS_PREFIX.to_string(),
+            timeout: DEFAULT_REDIS_TIMEOUT,
+        })
+    }
+
+    /// Prefix cache keys with `prefix` instead of [`DEFAULT_REDIS_PREFIX`].
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Give up connecting, and each command, after `timeout` instead of
+    /// [`DEFAULT_REDIS_TIMEOUT`]. A timed out command counts as a miss.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Open a connection with the configured timeouts.
+    fn connect(&self) -> redis::RedisResult<redis::Connection> {
+        let conn = self.client.get_connection_with_timeout(self.timeout)?;
+        conn.set_read_timeout(Some(self.timeout))?;
+        conn.set_write_timeout(Some(self.timeout))?;
+        Ok(conn)
+    }
+
+    /// Run a command on the shared connection, reconnecting if needed.
+    fn run<T>(
+        &self,
+        key: &str,
+        command: impl FnOnce(&mut redis::Connection, String) -> redis::RedisResult<T>,
+    ) -> Option<T> {
+        blocking(|| self.run_blocking(key, command))
+    }
+
+    fn run_blocking<T>(
+        &self,
+        key: &str,
+        command: impl FnOnce(&mut redis::Connection, String) -> redis::RedisResult<T>,
+    ) -> Option<T> {
+        let mut connection = self.connection.lock().unwrap();
+        let conn = match &mut *connection {
+            Some(conn) => conn,
+            None => match self.connect() {
+                Ok(conn) => connection.insert(conn),
+                Err(e) => {
+                    warn!(error = %e, "Failed to connect to Redis cache");
+                    return None;
+                }
+            },
+        };
+        match command(conn, format!("{}{}", self.prefix, key)) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!(error = %e, "Redis cache command failed");
+                *connection = None;
+                None
+            }
+        }
+    }
+}
+
+/// Run blocking I/O without stalling a multi-threaded Tokio runtime.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current().map(|handle| handle.runtime_flavor()) {
+        Ok(RuntimeFlavor::MultiThread) => tokio::task::block_in_place(f),
+        _ => f(),
+    }
+}
+
+/// How long Redis should keep an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expiry {
+    /// Keep it until it is deleted.
+    Never,
+    /// Keep it for this many seconds.
+    After(u64),
+}
+
+/// How long Redis should keep an entry, or `None` if it is of no further use.
+fn redis_expiry(entry: &CacheEntry, now: u64) -> Option<Expiry> {
+    let retain_until = entry.retain_until();
+    if retain_until == u64::MAX {
+        return Some(Expiry::Never);
+    }
+    let ttl = retain_until.saturating_sub(now);
+    (ttl > 0).then_some(Expiry::After(ttl.min(MAX_REDIS_TTL)))
+}
+
+impl Cache for RedisCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let stored: String = self.run(key, |conn, key| conn.get::<_, Option<String>>(key))??;
+        match serde_json::from_str(&stored) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warn!(error = %e, "Ignoring unreadable Redis cache entry");
+                None
+            }
+        }
+    }
+
+    fn set(&self, key: &str, entry: CacheEntry) {
+        if entry.cache_control.no_store {
+            return;
+        }
+        let Some(expiry) = redis_expiry(&entry, SystemClock.now_unix()) else {
+            return;
+        };
+        let Ok(stored) = serde_json::to_string(&entry) else {
+            return;
+        };
+        self.run(key, |conn, key| match expiry {
+            Expiry::Never => conn.set::<_, _, ()>(key, stored),
+            Expiry::After(ttl) => conn.set_ex::<_, _, ()>(key, stored, ttl),
+        });
+    }
+
+    fn delete(&self, key: &str) {
+        self.run(key, |conn, key| conn.del::<_, ()>(key));
+    }
+
+    fn delete_prefix(&self, prefix: &str) {
+        self.run(prefix, |conn, prefix| {
+            let pattern = format!("{}*", escape_glob(&prefix));
+            let keys: Vec<String> = conn.scan_match(pattern)?.collect();
+            if keys.is_empty() {
+                return Ok(());
+            }
+            conn.del::<_, ()>(keys)
+        });
+    }
+}
+
+/// Escape the characters Redis treats specially in `SCAN MATCH` patterns.
+fn escape_glob(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheControlDirectives;
+    use serde_json::json;
+
+    fn entry(expires_at: u64, stale_while_revalidate: Option<u64>) -> CacheEntry {
+        CacheEntry {
+            value: json!({"id": "job-1"}),
+            expires_at,
+            cache_control: CacheControlDirectives {
+                max_age: Some(60),
+                stale_while_revalidate,
+                ..Default::default()
+            },
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    #[test]
+    fn test_redis_ttl_covers_stale_window() {
+        let after = |ttl| Some(Expiry::After(ttl));
+        assert_eq!(redis_expiry(&entry(1_060, None), 1_000), after(60));
+        assert_eq!(redis_expiry(&entry(1_060, Some(30)), 1_000), after(90));
+        assert_eq!(redis_expiry(&entry(1_060, Some(30)), 1_080), after(10));
+        assert_eq!(redis_expiry(&entry(1_060, None), 1_060), None);
+    }
+
+    #[test]
+    fn test_redis_ttl_of_lasting_entries() {
+        let last_good = crate::stale::last_good_entry(json!({"title": "x"}));
+        assert_eq!(redis_expiry(&last_good, 1_000), Some(Expiry::Never));
+        assert_eq!(
+            redis_expiry(&entry(u64::MAX - 1, None), 1_000),
+            Some(Expiry::After(MAX_REDIS_TTL))
+        );
+    }
+
+    #[test]
+    fn test_blocking_outside_and_inside_runtimes() {
+        assert_eq!(blocking(|| 1), 1);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        assert_eq!(runtime.block_on(async { blocking(|| 2) }), 2);
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .build()
+            .unwrap();
+        assert_eq!(runtime.block_on(async { blocking(|| 3) }), 3);
+    }
+
+    #[test]
+    fn test_entries_round_trip_as_json() {
+        let original = entry(1_060, Some(30));
+        let stored = serde_json::to_string(&original).unwrap();
+        let restored: CacheEntry = serde_json::from_str(&stored).unwrap();
+        assert_eq!(restored.value, original.value);
+        assert_eq!(restored.expires_at, 1_060);
+        assert_eq!(restored.cache_control.stale_while_revalidate, Some(30));
+    }
+
+    #[test]
+    fn test_escape_glob() {
+        assert_eq!(
+            escape_glob("refyne:GET:/a?b=[1]*"),
+            r"refyne:GET:/a\?b=\[1\]\*"
+        );
+    }
+
+    #[test]
+    fn test_open_rejects_invalid_url() {
+        assert!(matches!(
+            RedisCache::open("not a url"),
+            Err(Error::Config(_))
+        ));
+        assert!(RedisCache::open("redis://127.0.0.1/").is_ok());
+    }
+}
@@ -0,0 +1,103 @@
+//! Requests to endpoints without a typed method.
+//!
+//! [`Client::request_builder`](crate::Client::request_builder) starts a
+//! request to any API path. Query parameters, headers, and bodies can be set
+//! freely, while authentication, retries, the API version check, and response
+//! caching behave as for typed methods. This suits beta endpoints whose shape
+//! is not yet covered by the SDK.
+//!
+//! ```rust,no_run
+//! use refyne::Client;
+//! use serde_json::{json, Value};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let preview: Value = client
+//!     .request_builder("POST", "/api/v1/beta/preview")
+//!     .query("mode", "fast")
+//!     .header("X-Beta-Feature", "preview")
+//!     .json(&json!({"url": "https://example.com"}))
+//!     .send()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::prepared::PreparedRequest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A request to an arbitrary API path, sent through the client.
+#[must_use = "requests do nothing until sent"]
+pub struct RequestBuilder<'a> {
+    client: &'a Client,
+    prepared: Result<PreparedRequest>,
+    query: Vec<(String, String)>,
+    skip_cache: bool,
+}
+
+impl<'a> RequestBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, prepared: Result<PreparedRequest>) -> Self {
+        Self {
+            client,
+            prepared,
+            query: Vec::new(),
+            skip_cache: false,
+        }
+    }
+
+    /// Append a query parameter.
+    pub fn query(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.query.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Set a header, replacing any existing value.
+    ///
+    /// An `Authorization` header set here is sent instead of the client's
+    /// API key.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Ok(prepared) = &mut self.prepared {
+            prepared.set_header(name, value);
+        }
+        self
+    }
+
+    /// Send a JSON body.
+    pub fn json<B: Serialize + ?Sized>(mut self, body: &B) -> Self {
+        match serde_json::to_vec(body) {
+            Ok(bytes) => return self.body(bytes, "application/json"),
+            Err(e) => self.prepared = Err(Error::Json(e)),
+        }
+        self
+    }
+
+    /// Send a raw body with the given content type.
+    pub fn body(mut self, body: impl Into<Vec<u8>>, content_type: &str) -> Self {
+        if let Ok(prepared) = &mut self.prepared {
+            prepared.body = Some(body.into());
+            prepared.set_header("Content-Type", content_type);
+        }
+        self
+    }
+
+    /// Bypass the cache for this GET request. The response is still cached.
+    pub fn skip_cache(mut self) -> Self {
+        self.skip_cache = true;
+        self
+    }
+
+    /// Send the request and parse the JSON response.
+    pub async fn send<T: DeserializeOwned>(self) -> Result<T> {
+        let mut prepared = self.prepared?;
+        if !self.query.is_empty() {
+            let mut url = reqwest::Url::parse(&prepared.url)
+                .map_err(|e| Error::Config(format!("invalid request URL: {}", e)))?;
+            url.query_pairs_mut().extend_pairs(&self.query);
+            prepared.url = url.into();
+        }
+        self.client.execute_cached(&prepared, self.skip_cache).await
+    }
+}
@@ -0,0 +1,108 @@
+//! Re-running crawl jobs.
+//!
+//! The API does not return the request a job was started with, so a client
+//! remembers the crawls submitted through it, and
+//! [`JobsClient::retry`](crate::JobsClient::retry) submits the same request
+//! again:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, CrawlRequest, JobStatus, RunOptions, WaitOptions};
+//!
+//! # async fn run(request: CrawlRequest) -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let created = client.crawl(request).await?;
+//! let job = client.jobs().wait(&created.job_id, WaitOptions::new()).await?;
+//! if job.job_status() == JobStatus::Failed {
+//!     let retried = client.jobs().retry(&job.id).await?;
+//!     // Or change part of the original request first.
+//!     let smaller = client
+//!         .jobs()
+//!         .rerun(&job.id, RunOptions::new().max_pages(10))
+//!         .await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Jobs started elsewhere, or by a client that has since been dropped, can
+//! still be re-run from their URL if [`RunOptions`](crate::RunOptions) gives
+//! a schema.
+
+use crate::types::CrawlRequest;
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+/// Crawl requests remembered per client, oldest forgotten first.
+const MAX_REMEMBERED_CRAWLS: usize = 1024;
+
+/// Crawl requests submitted through a client, by job ID.
+#[derive(Debug, Default)]
+pub(crate) struct SubmittedCrawls {
+    inner: RwLock<Submitted>,
+}
+
+#[derive(Debug, Default)]
+struct Submitted {
+    requests: HashMap<String, CrawlRequest>,
+    order: VecDeque<String>,
+}
+
+impl SubmittedCrawls {
+    /// The request that started a job, if it was submitted through the client.
+    pub(crate) fn get(&self, job_id: &str) -> Option<CrawlRequest> {
+        self.inner.read().unwrap().requests.get(job_id).cloned()
+    }
+
+    /// Remember the request that started a job.
+    pub(crate) fn insert(&self, job_id: String, request: CrawlRequest) {
+        let mut inner = self.inner.write().unwrap();
+        if inner.requests.insert(job_id.clone(), request).is_none() {
+            inner.order.push_back(job_id);
+        }
+        while inner.order.len() > MAX_REMEMBERED_CRAWLS {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.requests.remove(&oldest);
+            }
+        }
+    }
+
+    /// Forget a job, such as one that was deleted.
+    pub(crate) fn remove(&self, job_id: &str) {
+        let mut inner = self.inner.write().unwrap();
+        if inner.requests.remove(job_id).is_some() {
+            inner.order.retain(|id| id != job_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str) -> CrawlRequest {
+        CrawlRequest {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_submitted_crawls_forget_oldest() {
+        let submitted = SubmittedCrawls::default();
+        for i in 0..=MAX_REMEMBERED_CRAWLS {
+            submitted.insert(
+                format!("job-{}", i),
+                request(&format!("https://{}.example", i)),
+            );
+        }
+        assert!(submitted.get("job-0").is_none());
+        assert_eq!(submitted.get("job-1").unwrap().url, "https://1.example");
+
+        submitted.remove("job-1");
+        assert!(submitted.get("job-1").is_none());
+        assert_eq!(
+            submitted.inner.read().unwrap().order.len(),
+            MAX_REMEMBERED_CRAWLS - 1
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Typed access to crawl job results.
+//!
+//! [`JobResults`] is the raw JSON the API returns. [`JobResultsExt`] walks
+//! its pages and deserializes each one's data into your own type, reporting
+//! pages that do not match individually instead of failing the whole job:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, JobResultsExt};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Product {
+//!     name: String,
+//!     price: Option<f64>,
+//! }
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let results = client.jobs().get_results("job-id", false).await?;
+//! for item in results.deserialize_each::<Product>() {
+//!     match item {
+//!         Ok(product) => println!("{} {:?}", product.name, product.price),
+//!         Err(e) => eprintln!("skipping {}", e),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! For large crawls, [`JobsClient::stream_results`](crate::JobsClient::stream_results)
+//! reads the results as newline-delimited JSON and yields each page as it
+//! arrives, rather than loading the whole document into memory.
+
+#[cfg(feature = "arrow")]
+use crate::columnar;
+use crate::error::{Error, Result};
+#[cfg(feature = "export")]
+use crate::export::{self, CsvOptions};
+use crate::runtime::BoxFuture;
+use crate::transforms::TransformPipeline;
+use crate::types::JobResults;
+use futures_core::Stream;
+use reqwest::header::CONTENT_TYPE;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::VecDeque;
+#[cfg(any(feature = "export", feature = "parquet"))]
+use std::fs::File;
+#[cfg(feature = "export")]
+use std::io::BufWriter;
+#[cfg(any(feature = "export", feature = "parquet"))]
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A page of job results whose data did not match the requested type.
+#[derive(Debug, thiserror::Error)]
+#[error("result {index}{}: {error}", url.as_deref().map(|url| format!(" ({})", url)).unwrap_or_default())]
+pub struct ResultItemError {
+    /// Position of the page in the results.
+    pub index: usize,
+    /// URL of the page, if the results include it.
+    pub url: Option<String>,
+    /// Why the data did not deserialize, with the data itself.
+    pub error: Box<Error>,
+}
+
+/// Typed iteration over [`JobResults`].
+pub trait JobResultsExt {
+    /// Deserialize the data of each page into `T`, in order.
+    ///
+    /// Results wrapped in `{"results": [...]}`, bare arrays, and merged
+    /// results (a single item) are all accepted. Items of the form
+    /// `{"url": ..., "data": ...}` are unwrapped to their data.
+    fn deserialize_each<T: DeserializeOwned>(&self)
+        -> Vec<std::result::Result<T, ResultItemError>>;
+
+    /// Write each page to `path` as a line of JSON.
+    #[cfg(feature = "export")]
+    fn write_jsonl(&self, path: impl AsRef<Path>) -> Result<()>;
+
+    /// Write the pages to `path` as CSV, one row each, with
+    /// [`CsvOptions::default`].
+    #[cfg(feature = "export")]
+    fn write_csv(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.write_csv_with(path, &CsvOptions::default())
+    }
+
+    /// Write the pages to `path` as CSV, one row each.
+    #[cfg(feature = "export")]
+    fn write_csv_with(&self, path: impl AsRef<Path>, options: &CsvOptions) -> Result<()>;
+
+    /// Convert the pages into an Arrow record batch, one row each, with
+    /// columns inferred from the extraction `schema` by [`arrow_schema`](crate::arrow_schema).
+    #[cfg(feature = "arrow")]
+    fn to_record_batch(&self, schema: &Value) -> Result<arrow_array::RecordBatch>;
+
+    /// Write the pages to `path` as a Parquet file, with columns inferred
+    /// from the extraction `schema`.
+    #[cfg(feature = "parquet")]
+    fn write_parquet(&self, path: impl AsRef<Path>, schema: &Value) -> Result<()> {
+        columnar::write_parquet(&self.to_record_batch(schema)?, File::create(path)?)
+    }
+}
+
+impl JobResultsExt for JobResults {
+    fn deserialize_each<T: DeserializeOwned>(
+        &self,
+    ) -> Vec<std::result::Result<T, ResultItemError>> {
+        items(self)
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let data = item.get("data").unwrap_or(item);
+                T::deserialize(data).map_err(|e| ResultItemError {
+                    index,
+                    url: item.get("url").and_then(Value::as_str).map(String::from),
+                    error: Box::new(Error::deserialize(e, data.clone())),
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "export")]
+    fn write_jsonl(&self, path: impl AsRef<Path>) -> Result<()> {
+        export::write_jsonl(items(self), BufWriter::new(File::create(path)?))
+    }
+
+    #[cfg(feature = "export")]
+    fn write_csv_with(&self, path: impl AsRef<Path>, options: &CsvOptions) -> Result<()> {
+        export::write_csv(items(self), options, BufWriter::new(File::create(path)?))
+    }
+
+    #[cfg(feature = "arrow")]
+    fn to_record_batch(&self, schema: &Value) -> Result<arrow_array::RecordBatch> {
+        let schema = columnar::arrow_schema(schema)?;
+        columnar::to_record_batch(items(self), std::sync::Arc::new(schema))
+    }
+}
+
+/// Pages of results, whether wrapped in `{"results": [...]}`, a bare array,
+/// or merged into a single item.
+pub(crate) fn items(results: &Value) -> &[Value] {
+    match results.get("results").unwrap_or(results) {
+        Value::Array(items) => items.as_slice(),
+        Value::Null => &[],
+        merged => std::slice::from_ref(merged),
+    }
+}
+
+/// Future reading the next chunk of a response, handing the response back.
+type ReadChunk = BoxFuture<'static, (reqwest::Response, Result<Option<Vec<u8>>>)>;
+
+fn read_chunk(mut response: reqwest::Response) -> ReadChunk {
+    Box::pin(async move {
+        let chunk = response.chunk().await.map_err(Error::Http);
+        (
+            response,
+            chunk.map(|chunk| chunk.map(|bytes| bytes.to_vec())),
+        )
+    })
+}
+
+/// A stream of the pages of a job's results.
+///
+/// Each page is parsed as its line arrives, and the next chunk of the
+/// response is only read once the buffered lines have been consumed, so a
+/// slow consumer slows the download rather than growing a buffer. If the
+/// server answers with a plain JSON document instead, it is read in full and
+/// its pages yielded one at a time. An error is yielded once and ends the
+/// stream.
+#[must_use = "streams do nothing unless polled"]
+pub struct ResultStream {
+    open: Option<BoxFuture<'static, Result<reqwest::Response>>>,
+    read: Option<ReadChunk>,
+    transforms: TransformPipeline,
+    /// Unparsed bytes: partial lines, or the whole body of a JSON response.
+    buffer: Vec<u8>,
+    /// Pages parsed from a JSON response and not yet yielded.
+    pending: VecDeque<Value>,
+    json: bool,
+    done: bool,
+}
+
+impl ResultStream {
+    pub(crate) fn new(
+        open: BoxFuture<'static, Result<reqwest::Response>>,
+        transforms: TransformPipeline,
+    ) -> Self {
+        Self {
+            open: Some(open),
+            read: None,
+            transforms,
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+            json: false,
+            done: false,
+        }
+    }
+
+    /// Take the next non-empty line from the buffer. At the end of the body
+    /// whatever remains counts as a line.
+    fn next_line(&mut self, at_end: bool) -> Option<Vec<u8>> {
+        loop {
+            let line = match self.buffer.iter().position(|&b| b == b'\n') {
+                Some(end) => {
+                    let mut line: Vec<u8> = self.buffer.drain(..=end).collect();
+                    line.pop();
+                    line
+                }
+                None if at_end && !self.buffer.is_empty() => std::mem::take(&mut self.buffer),
+                None => return None,
+            };
+            if !line.trim_ascii().is_empty() {
+                return Some(line);
+            }
+        }
+    }
+
+    /// End the stream with `error`.
+    fn fail(&mut self, error: Error) -> Result<Value> {
+        self.read = None;
+        self.buffer.clear();
+        self.pending.clear();
+        self.done = true;
+        Err(error)
+    }
+
+    fn parse(&self, line: &[u8]) -> Result<Value> {
+        let mut item: Value = serde_json::from_slice(line)?;
+        self.transforms.apply_to_item(&mut item);
+        Ok(item)
+    }
+}
+
+impl Stream for ResultStream {
+    type Item = Result<Value>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if !this.json {
+                if let Some(line) = this.next_line(this.done) {
+                    return Poll::Ready(Some(this.parse(&line).or_else(|e| this.fail(e))));
+                }
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            if let Some(open) = &mut this.open {
+                let response = match open.as_mut().poll(cx) {
+                    Poll::Ready(response) => response,
+                    Poll::Pending => return Poll::Pending,
+                };
+                this.open = None;
+                match response {
+                    Ok(response) => {
+                        this.json = !response
+                            .headers()
+                            .get(CONTENT_TYPE)
+                            .and_then(|v| v.to_str().ok())
+                            .is_some_and(|v| v.contains("ndjson") || v.contains("jsonl"));
+                        this.read = Some(read_chunk(response));
+                    }
+                    Err(e) => return Poll::Ready(Some(this.fail(e))),
+                }
+                continue;
+            }
+
+            let Some(read) = &mut this.read else {
+                this.done = true;
+                continue;
+            };
+            let (response, chunk) = match read.as_mut().poll(cx) {
+                Poll::Ready(step) => step,
+                Poll::Pending => return Poll::Pending,
+            };
+            match chunk {
+                Ok(Some(bytes)) => {
+                    this.buffer.extend_from_slice(&bytes);
+                    this.read = Some(read_chunk(response));
+                }
+                Ok(None) => {
+                    this.read = None;
+                    this.done = true;
+                    if this.json {
+                        let body = std::mem::take(&mut this.buffer);
+                        let mut results: Value = match serde_json::from_slice(&body) {
+                            Ok(results) => results,
+                            Err(e) => return Poll::Ready(Some(this.fail(e.into()))),
+                        };
+                        this.transforms.apply_to_results(&mut results, false);
+                        this.pending.extend(items(&results).iter().cloned());
+                    }
+                }
+                Err(e) => return Poll::Ready(Some(this.fail(e))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Product {
+        name: String,
+    }
+
+    #[test]
+    fn test_deserialize_each_reports_bad_items() {
+        let results = json!({"results": [
+            {"url": "https://example.com/a", "data": {"name": "A"}},
+            {"url": "https://example.com/b", "data": {"title": "B"}},
+            {"url": "https://example.com/c", "data": {"name": "C"}},
+        ]});
+        let items = results.deserialize_each::<Product>();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap().name, "A");
+        assert_eq!(items[2].as_ref().unwrap().name, "C");
+
+        let error = items[1].as_ref().unwrap_err();
+        assert_eq!(error.index, 1);
+        assert_eq!(error.url.as_deref(), Some("https://example.com/b"));
+        assert!(
+            matches!(&*error.error, Error::Deserialize { payload, .. } if payload["title"] == "B")
+        );
+        assert!(error
+            .to_string()
+            .starts_with("result 1 (https://example.com/b): "));
+    }
+
+    #[test]
+    fn test_deserialize_each_accepts_all_shapes() {
+        let bare = json!([{"url": "https://example.com/a", "data": {"name": "A"}}]);
+        let merged = json!({"name": "Merged"});
+        assert_eq!(
+            bare.deserialize_each::<Product>()[0].as_ref().unwrap().name,
+            "A"
+        );
+        assert_eq!(
+            merged.deserialize_each::<Product>()[0]
+                .as_ref()
+                .unwrap()
+                .name,
+            "Merged"
+        );
+        assert!(Value::Null.deserialize_each::<Product>().is_empty());
+    }
+}
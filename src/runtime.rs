@@ -0,0 +1,83 @@
+//! Platform support for timers, time, and background tasks.
+//!
+//! Native builds use Tokio and `std::time`. On `wasm32` targets there is no
+//! Tokio timer driver and `std::time` panics, so sleeps go through the
+//! JavaScript `setTimeout` of the browser or worker, time is read with
+//! `web-time`, and background tasks run on the JavaScript event loop. Futures
+//! need not be `Send` there, since everything runs on one thread.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// A boxed future, `Send` except on `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A boxed future, `Send` except on `wasm32`.
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Wait for a duration.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn sleep(duration: Duration) -> BoxFuture<'static, ()> {
+    Box::pin(tokio::time::sleep(duration))
+}
+
+/// Wait for a duration.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn sleep(duration: Duration) -> BoxFuture<'static, ()> {
+    use wasm_bindgen::JsCast;
+
+    let millis = duration.as_millis().min(i32::MAX as u128) as i32;
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let global = js_sys::global();
+        let set_timeout = js_sys::Reflect::get(&global, &"setTimeout".into())
+            .ok()
+            .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+        match set_timeout {
+            Some(set_timeout) => {
+                let _ = set_timeout.call2(&global, &resolve, &millis.into());
+            }
+            None => {
+                let _ = resolve.call0(&global);
+            }
+        }
+    });
+    Box::pin(async move {
+        let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    })
+}
+
+/// Run a future in the background.
+///
+/// Natively this needs a Tokio runtime; without one the future is dropped.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+    if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+        runtime.spawn(future);
+    }
+}
+
+/// Run a future in the background.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn spawn(future: impl Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(future);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sleep() {
+        let started = Instant::now();
+        sleep(Duration::from_millis(10)).await;
+        assert!(started.elapsed() >= Duration::from_millis(10));
+    }
+}
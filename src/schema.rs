@@ -0,0 +1,415 @@
+//! Extraction schemas generated from Rust types.
+//!
+//! [`RefyneSchema`] describes a type in the JSON schema form accepted by
+//! [`ExtractRequest::schema`](crate::ExtractRequest::schema). With the
+//! `derive` feature, `#[derive(RefyneSchema)]` implements it for structs, so
+//! the schema and the type results deserialize into cannot drift apart.
+//!
+#![cfg_attr(feature = "derive", doc = "```rust")]
+#![cfg_attr(not(feature = "derive"), doc = "```rust,ignore")]
+//! use refyne::{ExtractRequest, RefyneSchema};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, RefyneSchema)]
+//! struct Product {
+//!     /// Product name as shown on the page
+//!     name: String,
+//!     price: Option<f64>,
+//!     tags: Vec<String>,
+//! }
+//!
+//! let request = ExtractRequest {
+//!     url: "https://example.com/product".into(),
+//!     schema: Product::schema(),
+//!     ..Default::default()
+//! };
+//! ```
+//!
+//! Fields become `"string"`, `"number"`, `"integer"`, or `"boolean"`; nested
+//! structs become objects; `Vec<T>` becomes a one-item list; and `Option<T>`
+//! has the schema of `T`. A field's doc comment is sent as its description:
+//! `{"type": ..., "description": ...}`. Serde's `rename`, `rename_all`,
+//! `skip`, and `flatten` attributes shape the schema as they shape
+//! deserialization.
+//!
+//! For schemas decided at runtime, [`SchemaBuilder`] composes the same form
+//! field by field and checks it before anything is sent:
+//!
+//! ```rust
+//! use refyne::{FieldType, SchemaBuilder};
+//!
+//! let schema = SchemaBuilder::object()
+//!     .field("name", FieldType::String)
+//!     .field("price", FieldType::Number)
+//!     .description("Price in the page's currency")
+//!     .field("tags", FieldType::array(FieldType::String))
+//!     .field("seller", SchemaBuilder::object().field("name", FieldType::String))
+//!     .build()?;
+//! # Ok::<(), refyne::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+/// A type with an extraction schema.
+pub trait RefyneSchema {
+    /// The schema in the JSON form accepted by extraction requests.
+    fn schema() -> Value;
+}
+
+macro_rules! impl_scalar {
+    ($name:literal: $($ty:ty),+) => {
+        $(impl RefyneSchema for $ty {
+            fn schema() -> Value {
+                Value::from($name)
+            }
+        })+
+    };
+}
+
+impl_scalar!("string": String, str, char);
+impl_scalar!("integer": i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_scalar!("number": f32, f64);
+impl_scalar!("boolean": bool);
+
+impl<T: RefyneSchema + ?Sized> RefyneSchema for &T {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: RefyneSchema + ?Sized> RefyneSchema for Box<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: RefyneSchema> RefyneSchema for Option<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+macro_rules! impl_list {
+    ($($ty:ident),+) => {
+        $(impl<T: RefyneSchema> RefyneSchema for $ty<T> {
+            fn schema() -> Value {
+                Value::Array(vec![T::schema()])
+            }
+        })+
+    };
+}
+
+impl_list!(Vec, VecDeque, HashSet, BTreeSet);
+
+impl<T: RefyneSchema> RefyneSchema for [T] {
+    fn schema() -> Value {
+        Value::Array(vec![T::schema()])
+    }
+}
+
+/// The type of a field in a [`SchemaBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    /// Text.
+    String,
+    /// A number, possibly fractional.
+    Number,
+    /// A whole number.
+    Integer,
+    /// True or false.
+    Boolean,
+    /// A list of items of one type.
+    Array(Box<FieldType>),
+    /// A nested object with its own fields.
+    Object(SchemaBuilder),
+}
+
+impl FieldType {
+    /// A list of `item`s.
+    pub fn array(item: impl Into<FieldType>) -> Self {
+        FieldType::Array(Box::new(item.into()))
+    }
+
+    /// A nested object with the fields of `schema`.
+    pub fn object(schema: SchemaBuilder) -> Self {
+        FieldType::Object(schema)
+    }
+
+    fn to_value(&self, path: &str) -> Result<Value> {
+        Ok(match self {
+            FieldType::String => Value::from("string"),
+            FieldType::Number => Value::from("number"),
+            FieldType::Integer => Value::from("integer"),
+            FieldType::Boolean => Value::from("boolean"),
+            FieldType::Array(item) => Value::Array(vec![item.to_value(path)?]),
+            FieldType::Object(schema) => schema.to_value(path)?,
+        })
+    }
+}
+
+impl From<SchemaBuilder> for FieldType {
+    fn from(schema: SchemaBuilder) -> Self {
+        FieldType::Object(schema)
+    }
+}
+
+/// A field added to a [`SchemaBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+struct SchemaField {
+    name: String,
+    ty: FieldType,
+    description: Option<String>,
+}
+
+/// Builds an extraction schema one field at a time.
+///
+/// Produces the JSON form accepted by
+/// [`ExtractRequest::schema`](crate::ExtractRequest::schema), the same form
+/// [`RefyneSchema`] derives from types. Mistakes such as a field defined
+/// twice are reported by [`build`](Self::build) rather than by the API.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaBuilder {
+    fields: Vec<SchemaField>,
+    /// Set when `description` is called before any field.
+    orphan_description: bool,
+}
+
+impl SchemaBuilder {
+    /// An object with no fields yet.
+    pub fn object() -> Self {
+        Self::default()
+    }
+
+    /// Add a field.
+    pub fn field(mut self, name: impl Into<String>, ty: impl Into<FieldType>) -> Self {
+        self.fields.push(SchemaField {
+            name: name.into(),
+            ty: ty.into(),
+            description: None,
+        });
+        self
+    }
+
+    /// Describe the field added last, to guide extraction.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        match self.fields.last_mut() {
+            Some(field) => field.description = Some(description.into()),
+            None => self.orphan_description = true,
+        }
+        self
+    }
+
+    /// Check the schema and produce its JSON form.
+    ///
+    /// Fails with [`Error::Config`] naming the offending field if an object
+    /// has no fields, a name is empty or repeated, or `description` was
+    /// called before any field was added.
+    pub fn build(&self) -> Result<Value> {
+        self.to_value("")
+    }
+
+    fn to_value(&self, path: &str) -> Result<Value> {
+        let at = if path.is_empty() { "the schema" } else { path };
+        if self.orphan_description {
+            return Err(Error::Config(format!(
+                "description given before any field in {}",
+                at
+            )));
+        }
+        if self.fields.is_empty() {
+            return Err(Error::Config(format!("{} has no fields", at)));
+        }
+
+        let mut map = Map::new();
+        for field in &self.fields {
+            let field_path = if path.is_empty() {
+                field.name.clone()
+            } else {
+                format!("{}.{}", path, field.name)
+            };
+            if field.name.trim().is_empty() {
+                return Err(Error::Config(format!("empty field name in {}", at)));
+            }
+            if map.contains_key(&field.name) {
+                return Err(Error::Config(format!(
+                    "field {:?} is defined twice",
+                    field_path
+                )));
+            }
+            let value = field.ty.to_value(&field_path)?;
+            let value = match &field.description {
+                Some(description) => describe(value, description),
+                None => value,
+            };
+            map.insert(field.name.clone(), value);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+/// Attach a description to a field schema. Used by the derive macro.
+#[doc(hidden)]
+pub fn describe(schema: Value, description: &str) -> Value {
+    json!({"type": schema, "description": description})
+}
+
+/// Merge the fields of a `#[serde(flatten)]` struct into its parent's schema.
+/// Used by the derive macro.
+#[doc(hidden)]
+pub fn flatten(map: &mut Map<String, Value>, schema: Value) {
+    if let Value::Object(fields) = schema {
+        map.extend(fields);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_schemas() {
+        assert_eq!(String::schema(), json!("string"));
+        assert_eq!(<&str>::schema(), json!("string"));
+        assert_eq!(u32::schema(), json!("integer"));
+        assert_eq!(f64::schema(), json!("number"));
+        assert_eq!(bool::schema(), json!("boolean"));
+    }
+
+    #[test]
+    fn test_wrapper_schemas() {
+        assert_eq!(<Option<f64>>::schema(), json!("number"));
+        assert_eq!(<Vec<String>>::schema(), json!(["string"]));
+        assert_eq!(<Option<Vec<i64>>>::schema(), json!(["integer"]));
+    }
+
+    #[test]
+    fn test_schema_builder() {
+        let schema = SchemaBuilder::object()
+            .field("name", FieldType::String)
+            .field("price", FieldType::Number)
+            .description("Price in GBP")
+            .field("tags", FieldType::array(FieldType::String))
+            .field(
+                "reviews",
+                FieldType::array(SchemaBuilder::object().field("rating", FieldType::Integer)),
+            )
+            .field("in_stock", FieldType::Boolean)
+            .build()
+            .unwrap();
+        assert_eq!(
+            schema,
+            json!({
+                "name": "string",
+                "price": {"type": "number", "description": "Price in GBP"},
+                "tags": ["string"],
+                "reviews": [{"rating": "integer"}],
+                "in_stock": "boolean",
+            })
+        );
+    }
+
+    #[test]
+    fn test_schema_builder_rejects_mistakes() {
+        let error = |builder: SchemaBuilder| match builder.build() {
+            Err(Error::Config(message)) => message,
+            other => panic!("expected a config error, got {:?}", other),
+        };
+
+        assert_eq!(error(SchemaBuilder::object()), "the schema has no fields");
+        assert_eq!(
+            error(
+                SchemaBuilder::object()
+                    .field(
+                        "seller",
+                        SchemaBuilder::object().field("name", FieldType::String)
+                    )
+                    .field("seller", FieldType::String)
+            ),
+            "field \"seller\" is defined twice"
+        );
+        assert_eq!(
+            error(SchemaBuilder::object().field("seller", SchemaBuilder::object())),
+            "seller has no fields"
+        );
+        assert_eq!(
+            error(
+                SchemaBuilder::object().field(
+                    "seller",
+                    SchemaBuilder::object()
+                        .description("Who sells it")
+                        .field("name", FieldType::String)
+                )
+            ),
+            "description given before any field in seller"
+        );
+        assert_eq!(
+            error(SchemaBuilder::object().field(" ", FieldType::String)),
+            "empty field name in the schema"
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        #[allow(dead_code)]
+        #[derive(crate::RefyneSchema)]
+        struct Review {
+            author: String,
+            /// Star rating from 1 to 5
+            rating: Option<f64>,
+        }
+
+        #[allow(dead_code)]
+        #[derive(crate::RefyneSchema)]
+        struct Product {
+            name: String,
+            #[serde(rename = "inStock")]
+            in_stock: bool,
+            reviews: Vec<Review>,
+        }
+
+        assert_eq!(
+            Product::schema(),
+            json!({
+                "name": "string",
+                "inStock": "boolean",
+                "reviews": [{
+                    "author": "string",
+                    "rating": {"type": "number", "description": "Star rating from 1 to 5"},
+                }],
+            })
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_follows_serde_attributes() {
+        #[allow(dead_code)]
+        #[derive(crate::RefyneSchema)]
+        struct Seller {
+            seller_name: String,
+        }
+
+        #[allow(dead_code)]
+        #[derive(crate::RefyneSchema)]
+        #[serde(rename_all = "camelCase")]
+        struct Listing {
+            unit_price: f64,
+            #[serde(rename = "SKU")]
+            stock_code: String,
+            #[serde(skip)]
+            cached_at: u64,
+            #[serde(skip_deserializing)]
+            local_note: String,
+            #[serde(flatten)]
+            seller: Seller,
+        }
+
+        assert_eq!(
+            Listing::schema(),
+            json!({"unitPrice": "number", "SKU": "string", "seller_name": "string"})
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Comparing versions of a schema.
+//!
+//! A saved schema shared between teams can break its consumers when a field
+//! is removed or changes type.
+//! [`SchemaDefinition::diff`](crate::SchemaDefinition::diff) lists what
+//! changed between two versions. With the `yaml` feature, `diff` compares
+//! schemas stored as YAML and `SchemasClient::update_with_check` refuses
+//! breaking updates unless forced:
+//!
+//! ```rust
+//! # #[cfg(not(feature = "yaml"))]
+//! # fn main() {}
+//! # #[cfg(feature = "yaml")]
+//! # fn main() -> Result<(), refyne::Error> {
+//! let changes = refyne::schemas::diff(
+//!     "name: string\nprice: integer\n",
+//!     "name: string\nprice: number\nsku: string\n",
+//! )?;
+//! assert!(changes.is_breaking());
+//! assert_eq!(changes.to_string(), "+ sku: string\n~ price: integer -> number\n");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Nested fields are named by path, such as `seller.name` or
+//! `reviews[].rating`. Descriptions are not compared.
+
+use crate::definition::{FieldKind, SchemaDefinition};
+use std::fmt;
+
+/// What changed between two versions of a schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    /// Fields only in the new version, with their types.
+    pub added: Vec<(String, String)>,
+    /// Fields only in the old version, with their types.
+    pub removed: Vec<(String, String)>,
+    /// Fields whose type changed.
+    pub retyped: Vec<Retyped>,
+}
+
+/// A field whose type differs between versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Retyped {
+    /// Path of the field.
+    pub path: String,
+    /// Type in the old version.
+    pub old: String,
+    /// Type in the new version.
+    pub new: String,
+}
+
+impl SchemaDiff {
+    /// True if the versions have the same fields and types.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.retyped.is_empty()
+    }
+
+    /// True if data extracted with the new version may not fit consumers of
+    /// the old one: a field was removed or changed type. Added fields are
+    /// not breaking.
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.retyped.is_empty()
+    }
+}
+
+/// One change per line: `+` added, `-` removed, `~` retyped.
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (path, ty) in &self.added {
+            writeln!(f, "+ {}: {}", path, ty)?;
+        }
+        for (path, ty) in &self.removed {
+            writeln!(f, "- {}: {}", path, ty)?;
+        }
+        for retyped in &self.retyped {
+            writeln!(f, "~ {}: {} -> {}", retyped.path, retyped.old, retyped.new)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare two versions of a schema written as YAML, as stored in
+/// [`Schema::schema_yaml`](crate::Schema).
+///
+/// Fails with [`Error::Yaml`](crate::Error::Yaml) if either is not a schema
+/// of fields.
+#[cfg(feature = "yaml")]
+pub fn diff(old_yaml: &str, new_yaml: &str) -> crate::Result<SchemaDiff> {
+    let old = SchemaDefinition::from_yaml(old_yaml)?;
+    let new = SchemaDefinition::from_yaml(new_yaml)?;
+    Ok(old.diff(&new))
+}
+
+impl SchemaDefinition {
+    /// List what changed from this version of the schema to `new`.
+    pub fn diff(&self, new: &SchemaDefinition) -> SchemaDiff {
+        let mut diff = SchemaDiff::default();
+        diff_objects(self, new, "", &mut diff);
+        diff
+    }
+}
+
+fn diff_objects(old: &SchemaDefinition, new: &SchemaDefinition, path: &str, diff: &mut SchemaDiff) {
+    let child = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+    for field in &new.fields {
+        match old.field(&field.name) {
+            Some(previous) => diff_kinds(&previous.kind, &field.kind, &child(&field.name), diff),
+            None => diff
+                .added
+                .push((child(&field.name), type_name(&field.kind))),
+        }
+    }
+    for field in &old.fields {
+        if new.field(&field.name).is_none() {
+            diff.removed
+                .push((child(&field.name), type_name(&field.kind)));
+        }
+    }
+}
+
+fn diff_kinds(old: &FieldKind, new: &FieldKind, path: &str, diff: &mut SchemaDiff) {
+    match (old, new) {
+        (FieldKind::Object(old), FieldKind::Object(new)) => diff_objects(old, new, path, diff),
+        (FieldKind::Array(old), FieldKind::Array(new)) => {
+            diff_kinds(old, new, &format!("{}[]", path), diff)
+        }
+        (old, new) if old == new => {}
+        (old, new) => diff.retyped.push(Retyped {
+            path: path.to_string(),
+            old: type_name(old),
+            new: type_name(new),
+        }),
+    }
+}
+
+/// `string`, `[string]`, or `object`.
+fn type_name(kind: &FieldKind) -> String {
+    match kind {
+        FieldKind::Array(item) => format!("[{}]", type_name(item)),
+        FieldKind::Object(_) => "object".to_string(),
+        scalar => scalar.name().unwrap_or_default().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition(schema: serde_json::Value) -> SchemaDefinition {
+        SchemaDefinition::from_value(&schema).unwrap()
+    }
+
+    #[test]
+    fn test_diff_lists_changes_by_path() {
+        let old = definition(json!({
+            "name": "string",
+            "price": {"type": "integer", "description": "Price in pence"},
+            "tags": ["string"],
+            "seller": {"name": "string", "rating": "number"},
+            "reviews": [{"rating": "integer"}],
+        }));
+        let new = definition(json!({
+            "name": {"type": "string", "description": "Product name"},
+            "price": "number",
+            "tags": "string",
+            "seller": {"name": "string", "url": "string"},
+            "reviews": [{"rating": "number"}],
+            "sku": "string",
+        }));
+
+        let diff = old.diff(&new);
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.to_string(),
+            "\
++ seller.url: string
++ sku: string
+- seller.rating: number
+~ price: integer -> number
+~ reviews[].rating: integer -> number
+~ tags: [string] -> string
+"
+        );
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_added_fields_are_not_breaking() {
+        let old = definition(json!({"name": "string"}));
+        let new = definition(json!({"name": "string", "seller": {"name": "string"}}));
+        let diff = old.diff(&new);
+        assert!(!diff.is_breaking());
+        assert_eq!(
+            diff.added,
+            vec![("seller".to_string(), "object".to_string())]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Screenshots of extracted pages.
+//!
+//! Set [`capture_screenshot`](crate::ExtractRequestBuilder::capture_screenshot)
+//! to have the API screenshot the page it extracted from. The response names
+//! the screenshot in [`ExtractResponse::screenshot_id`](crate::ExtractResponse),
+//! and [`Client::get_screenshot`](crate::Client::get_screenshot) downloads it,
+//! so extractions can be checked against what the page looked like:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, ExtractRequest, ScreenshotFormat, ScreenshotOptions};
+//! use serde_json::json;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let request = ExtractRequest::builder("https://example.com/products/1")
+//!     .schema(json!({"name": "string", "price": "number"}))
+//!     .capture_screenshot(
+//!         ScreenshotOptions::new()
+//!             .full_page(true)
+//!             .viewport(1280, 800)
+//!             .format(ScreenshotFormat::Png),
+//!     )
+//!     .build();
+//! let response = client.extract(request).await?;
+//! if let Some(id) = &response.screenshot_id {
+//!     let png = client.get_screenshot(id).await?;
+//!     std::fs::write("product-1.png", &png)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Image format of a screenshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotFormat {
+    /// PNG, lossless.
+    Png,
+    /// JPEG, smaller.
+    Jpeg,
+    /// WebP, smaller still.
+    Webp,
+}
+
+/// Size of the browser window, in CSS pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Viewport {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+/// How to screenshot an extracted page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreenshotOptions {
+    /// Capture the whole scrollable page, not only the viewport.
+    #[serde(default)]
+    pub full_page: bool,
+    /// Browser window size. The API's default when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewport: Option<Viewport>,
+    /// Image format. PNG when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ScreenshotFormat>,
+}
+
+impl ScreenshotOptions {
+    /// A PNG of the viewport at the API's default size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the whole scrollable page, not only the viewport.
+    pub fn full_page(mut self, full_page: bool) -> Self {
+        self.full_page = full_page;
+        self
+    }
+
+    /// Render the page in a window of this size.
+    pub fn viewport(mut self, width: u32, height: u32) -> Self {
+        self.viewport = Some(Viewport { width, height });
+        self
+    }
+
+    /// Save the screenshot in this format.
+    pub fn format(mut self, format: ScreenshotFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+}
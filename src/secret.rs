@@ -0,0 +1,85 @@
+//! Credentials that stay out of logs.
+//!
+//! Provider API keys and webhook secrets in request and response types are
+//! held in a [`SecretString`]. Its `Debug` and `Display` output is
+//! `<redacted>`, so logging a request, or recording it as a tracing field,
+//! does not leak the value. It serializes as a plain string.
+//!
+//! ```rust
+//! use refyne::{LLMConfigInput, SecretString};
+//!
+//! let config = LLMConfigInput {
+//!     api_key: Some("sk-or-...".into()),
+//!     ..Default::default()
+//! };
+//! assert!(!format!("{:?}", config).contains("sk-or-"));
+//! assert_eq!(config.api_key.as_ref().map(SecretString::expose_secret), Some("sk-or-..."));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Text shown in place of a secret.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// A string that is not shown by `Debug` or `Display`.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap a secret.
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// Get the secret.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Unwrap the secret.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_is_redacted() {
+        let secret = SecretString::from("sk-live-123");
+        assert_eq!(format!("{:?}", secret), "<redacted>");
+        assert_eq!(secret.to_string(), "<redacted>");
+        assert_eq!(secret.expose_secret(), "sk-live-123");
+        assert_eq!(serde_json::to_string(&secret).unwrap(), "\"sk-live-123\"");
+        let parsed: SecretString = serde_json::from_str("\"sk-live-123\"").unwrap();
+        assert_eq!(parsed, secret);
+    }
+}
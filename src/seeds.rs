@@ -0,0 +1,107 @@
+//! Crawls from lists of URLs.
+//!
+//! A crawl starts from [`CrawlRequest::url`] and any further seeds in
+//! [`CrawlRequest::urls`]. For list-based crawls, such as product URLs from
+//! a sitemap dump, [`CrawlRequest::from_url_file`] reads the seeds from a
+//! file with one URL per line:
+//!
+//! ```rust,no_run
+//! use refyne::CrawlRequest;
+//! use serde_json::json;
+//!
+//! # fn run() -> Result<(), refyne::Error> {
+//! let request = CrawlRequest {
+//!     schema: json!({"name": "string", "price": "number"}),
+//!     ..CrawlRequest::from_url_file("product-urls.txt")?
+//! };
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Blank lines and lines starting with `#` are skipped.
+
+use crate::error::{Error, Result};
+use crate::types::CrawlRequest;
+use reqwest::Url;
+
+impl CrawlRequest {
+    /// A crawl seeded with every URL in a newline-delimited file. Set the
+    /// schema before submitting it.
+    ///
+    /// Fails with [`Error::Config`] naming the line of a URL that is not
+    /// `http` or `https`, or if the file lists no URLs. Not available on
+    /// `wasm32`, which has no file system.
+    pub fn from_url_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let mut urls = parse_url_list(&text)
+            .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?
+            .into_iter();
+        let Some(url) = urls.next() else {
+            return Err(Error::Config(format!("{}: no URLs", path.display())));
+        };
+        Ok(Self {
+            url,
+            urls: urls.collect(),
+            ..Default::default()
+        })
+    }
+}
+
+/// The URLs in `text`, one per line.
+pub(crate) fn parse_url_list(text: &str) -> std::result::Result<Vec<String>, String> {
+    let mut urls = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match Url::parse(line) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => urls.push(line.to_string()),
+            _ => {
+                return Err(format!(
+                    "line {}: not an http(s) URL: {:?}",
+                    number + 1,
+                    line
+                ))
+            }
+        }
+    }
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url_list() {
+        let text = "# products\nhttps://example.com/p/1\n\n  https://example.com/p/2  \r\n";
+        assert_eq!(
+            parse_url_list(text).unwrap(),
+            vec!["https://example.com/p/1", "https://example.com/p/2"]
+        );
+        assert_eq!(
+            parse_url_list("https://example.com\nexample.com/p/3\n").unwrap_err(),
+            "line 2: not an http(s) URL: \"example.com/p/3\""
+        );
+    }
+
+    #[test]
+    fn test_from_url_file() {
+        let dir = std::env::temp_dir().join(format!("refyne-seeds-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("urls.txt");
+        std::fs::write(&path, "https://example.com/a\nhttps://example.com/b\n").unwrap();
+        let request = CrawlRequest::from_url_file(&path).unwrap();
+        assert_eq!(request.url, "https://example.com/a");
+        assert_eq!(request.urls, vec!["https://example.com/b"]);
+
+        std::fs::write(&path, "# nothing yet\n").unwrap();
+        assert!(matches!(
+            CrawlRequest::from_url_file(&path),
+            Err(Error::Config(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
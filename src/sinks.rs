@@ -0,0 +1,217 @@
+//! Destinations for streamed job results.
+//!
+//! With the `object-store` feature, [`ObjectStoreSink`] writes the pages of
+//! [`JobsClient::stream_results`](crate::JobsClient::stream_results) to S3,
+//! GCS, Azure, or any other [`object_store`] backend as JSONL files, using
+//! multipart uploads so crawls too large to hold locally never touch disk:
+//!
+//! ```rust,ignore
+//! use refyne::sinks::ObjectStoreSink;
+//! use refyne::Client;
+//!
+//! let client = Client::builder("your-api-key").build()?;
+//! // Needs the `aws` feature of `object_store`.
+//! let sink = ObjectStoreSink::from_url("s3://bucket/crawls")?;
+//! let written = sink.write_job("job-id", client.jobs().stream_results("job-id")).await?;
+//! // s3://bucket/crawls/job-id/part-00000.jsonl, part-00001.jsonl, ...
+//! println!("{} pages in {} files", written.items, written.parts.len());
+//! ```
+
+use crate::error::{Error, Result};
+use futures_core::Stream;
+use object_store::path::Path;
+use object_store::{ObjectStore, WriteMultipart};
+use serde_json::Value;
+use std::pin::pin;
+use std::sync::Arc;
+
+/// Default size of each part file before a new one is started.
+pub const DEFAULT_PART_SIZE: usize = 128 * 1024 * 1024;
+
+/// Uploads in flight at once for each part file.
+const MAX_CONCURRENT_UPLOADS: usize = 8;
+
+/// Writes job results to an object store as JSONL part files.
+///
+/// Pages are written to `<prefix>/<job-id>/part-NNNNN.jsonl`, one line
+/// each. A new part is started once the current one reaches
+/// [`part_size`](Self::part_size) bytes. The next page is only read from
+/// the stream when there is room among the in-flight uploads, so a slow
+/// store slows the download rather than growing a buffer.
+#[derive(Debug, Clone)]
+pub struct ObjectStoreSink {
+    store: Arc<dyn ObjectStore>,
+    prefix: Path,
+    part_size: usize,
+}
+
+/// What an [`ObjectStoreSink`] wrote.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SinkSummary {
+    /// Paths of the part files, in order.
+    pub parts: Vec<Path>,
+    /// Pages written across all parts.
+    pub items: usize,
+    /// Bytes written across all parts.
+    pub bytes: usize,
+}
+
+impl ObjectStoreSink {
+    /// Write to `store` under `prefix`.
+    pub fn new(store: Arc<dyn ObjectStore>, prefix: impl Into<Path>) -> Self {
+        Self {
+            store,
+            prefix: prefix.into(),
+            part_size: DEFAULT_PART_SIZE,
+        }
+    }
+
+    /// Write to the store and prefix in a URL such as `s3://bucket/prefix`.
+    ///
+    /// The scheme's backend must be enabled on `object_store`, and its
+    /// credentials are read from the environment as `object_store` does.
+    pub fn from_url(url: &str) -> Result<Self> {
+        let url = reqwest::Url::parse(url)
+            .map_err(|e| Error::Config(format!("Invalid object store URL {}: {}", url, e)))?;
+        let (store, prefix) = object_store::parse_url(&url)?;
+        Ok(Self::new(Arc::from(store), prefix))
+    }
+
+    /// Start a new part file once the current one reaches `bytes`.
+    pub fn part_size(mut self, bytes: usize) -> Self {
+        self.part_size = bytes.max(1);
+        self
+    }
+
+    /// Write the pages of `results` for job `job_id`.
+    ///
+    /// An error from the stream or the store aborts the part being written
+    /// and is returned. Parts completed before it are left in place. An
+    /// empty stream writes no parts.
+    pub async fn write_job<S>(&self, job_id: &str, results: S) -> Result<SinkSummary>
+    where
+        S: Stream<Item = Result<Value>>,
+    {
+        let mut results = pin!(results);
+        let mut summary = SinkSummary::default();
+        let mut part: Option<(WriteMultipart, usize)> = None;
+        let mut line = Vec::new();
+
+        while let Some(item) = std::future::poll_fn(|cx| results.as_mut().poll_next(cx)).await {
+            let written = async {
+                line.clear();
+                serde_json::to_writer(&mut line, &item?)?;
+                line.push(b'\n');
+
+                let (upload, size) = match &mut part {
+                    Some(part) => part,
+                    None => {
+                        let path = self.part_path(job_id, summary.parts.len());
+                        let upload = self.store.put_multipart(&path).await?;
+                        summary.parts.push(path);
+                        part.insert((WriteMultipart::new(upload), 0))
+                    }
+                };
+                upload.wait_for_capacity(MAX_CONCURRENT_UPLOADS).await?;
+                upload.write(&line);
+                *size += line.len();
+                Ok::<_, Error>(*size >= self.part_size)
+            }
+            .await;
+
+            match written {
+                Ok(full) => {
+                    summary.items += 1;
+                    summary.bytes += line.len();
+                    if full {
+                        if let Some((upload, _)) = part.take() {
+                            upload.finish().await?;
+                        }
+                    }
+                }
+                Err(e) => {
+                    if let Some((upload, _)) = part.take() {
+                        // The stream or store error is the one worth reporting.
+                        let _ = upload.abort().await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        if let Some((upload, _)) = part {
+            upload.finish().await?;
+        }
+        Ok(summary)
+    }
+
+    fn part_path(&self, job_id: &str, index: usize) -> Path {
+        self.prefix
+            .child(job_id)
+            .child(format!("part-{:05}.jsonl", index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+    use object_store::memory::InMemory;
+    use serde_json::json;
+
+    fn page(name: &str) -> Value {
+        json!({"url": format!("https://example.com/{}", name), "data": {"name": name}})
+    }
+
+    async fn read(store: &InMemory, path: &Path) -> String {
+        let bytes = store.get(path).await.unwrap().bytes().await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_write_job_splits_parts() {
+        let store = Arc::new(InMemory::new());
+        let line_len = serde_json::to_vec(&page("a")).unwrap().len() + 1;
+        let sink = ObjectStoreSink::new(store.clone(), "crawls").part_size(line_len * 2);
+
+        let pages = stream::iter(["a", "b", "c"].map(|name| Ok(page(name))));
+        let summary = sink.write_job("job-1", pages).await.unwrap();
+        assert_eq!(summary.items, 3);
+        assert_eq!(summary.bytes, line_len * 3);
+        assert_eq!(
+            summary.parts,
+            vec![
+                Path::from("crawls/job-1/part-00000.jsonl"),
+                Path::from("crawls/job-1/part-00001.jsonl"),
+            ]
+        );
+
+        let first: Vec<Value> = read(&store, &summary.parts[0])
+            .await
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(first, vec![page("a"), page("b")]);
+        assert_eq!(
+            read(&store, &summary.parts[1]).await,
+            format!("{}\n", page("c"))
+        );
+
+        let empty = sink.write_job("job-2", stream::empty()).await.unwrap();
+        assert_eq!(empty, SinkSummary::default());
+    }
+
+    #[tokio::test]
+    async fn test_write_job_aborts_on_error() {
+        let store = Arc::new(InMemory::new());
+        let sink = ObjectStoreSink::new(store.clone(), "crawls");
+
+        let pages = stream::iter(vec![Ok(page("a")), Err(Error::Timeout), Ok(page("b"))]);
+        let error = sink.write_job("job-1", pages).await.unwrap_err();
+        assert!(matches!(error, Error::Timeout));
+        assert!(store
+            .head(&Path::from("crawls/job-1/part-00000.jsonl"))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,63 @@
+//! Previewing sitemap crawls.
+//!
+//! A crawl with [`use_sitemap`](crate::CrawlRequestBuilder::use_sitemap)
+//! discovers pages from the site's sitemap instead of following links.
+//! [`Client::list_sitemap_urls`](crate::Client::list_sitemap_urls) lists the
+//! URLs such a crawl would cover, so they can be checked, or filtered and
+//! crawled as seeds instead:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, CrawlRequest};
+//! use serde_json::json;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let sitemap = client.list_sitemap_urls("example.com").await?;
+//! println!("{} URLs in {:?}", sitemap.urls.len(), sitemap.sitemaps);
+//!
+//! let products: Vec<_> = sitemap
+//!     .urls
+//!     .iter()
+//!     .map(|entry| entry.url.as_str())
+//!     .filter(|url| url.contains("/products/"))
+//!     .collect();
+//! if let Some((first, rest)) = products.split_first() {
+//!     let request = CrawlRequest::builder(*first, json!({"name": "string"}))
+//!         .urls(rest.iter().copied())
+//!         .max_depth(0)
+//!         .build();
+//!     client.crawl(request).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// URLs listed in a site's sitemaps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SitemapUrls {
+    /// Domain the sitemaps belong to.
+    #[serde(default)]
+    pub domain: String,
+    /// Sitemaps read, including those listed in a sitemap index.
+    #[serde(default)]
+    pub sitemaps: Vec<String>,
+    /// Page URLs, in sitemap order.
+    #[serde(default)]
+    pub urls: Vec<SitemapUrl>,
+    /// True if the site lists more URLs than were returned.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// A page listed in a sitemap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SitemapUrl {
+    /// Page URL.
+    pub url: String,
+    /// When the page last changed, if the sitemap says.
+    #[serde(default)]
+    pub last_modified: Option<Timestamp>,
+}
@@ -0,0 +1,300 @@
+//! Running and scheduling saved sites.
+//!
+//! A saved site holds a URL, a default schema, and crawl options.
+//! [`SitesClient::run`](crate::SitesClient::run) starts a crawl with that
+//! configuration, and [`RunOptions`] overrides parts of it for one run:
+//!
+//! ```rust,no_run
+//! use refyne::{Client, RunOptions, WaitOptions};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let job = client
+//!     .sites()
+//!     .run_and_wait("site-id", RunOptions::new().max_pages(10), WaitOptions::new())
+//!     .await?;
+//! println!("{} pages", job.page_count);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SitesClient::schedule`](crate::SitesClient::schedule) runs a site
+//! repeatedly on a cron schedule instead:
+//!
+//! ```rust,no_run
+//! # async fn run(client: refyne::Client) -> Result<(), refyne::Error> {
+//! // Every day at 06:00 UTC.
+//! let schedule = client.sites().schedule("site-id", "0 6 * * *").await?;
+//! println!("next run at {:?}", schedule.next_run_at);
+//! client.sites().pause_schedule(&schedule.id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::pipeline::crawl_fetch_mode;
+use crate::timestamp::Timestamp;
+use crate::types::{CrawlOptions, CrawlRequest, Site};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Overrides for one run of a saved site, or a re-run of a job.
+///
+/// Anything not set comes from the site or the job's original request.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    url: Option<String>,
+    schema: Option<Value>,
+    schema_id: Option<String>,
+    max_pages: Option<i64>,
+    max_depth: Option<i64>,
+    webhook_id: Option<String>,
+}
+
+impl RunOptions {
+    /// Options using the site's configuration, or the job's request, as is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start crawling from `url` instead.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Extract with an inline schema instead.
+    pub fn schema(mut self, schema: Value) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Extract with a saved schema instead.
+    pub fn schema_id(mut self, id: impl Into<String>) -> Self {
+        self.schema_id = Some(id.into());
+        self
+    }
+
+    /// Stop after this many pages.
+    pub fn max_pages(mut self, max_pages: i64) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Follow links at most this deep.
+    pub fn max_depth(mut self, max_depth: i64) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Notify a saved webhook of the job's events.
+    pub fn webhook_id(mut self, id: impl Into<String>) -> Self {
+        self.webhook_id = Some(id.into());
+        self
+    }
+
+    /// The schema ID to fetch, unless an inline schema was given.
+    pub(crate) fn schema_id_for<'a>(&'a self, site: &'a Site) -> Result<Option<&'a str>> {
+        if self.schema.is_some() {
+            return Ok(None);
+        }
+        self.schema_id
+            .as_deref()
+            .or(site.default_schema_id.as_deref())
+            .map(Some)
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "Site {} has no default schema. Pass one in RunOptions",
+                    site.id
+                ))
+            })
+    }
+
+    /// The saved schema to fetch for a re-run, unless an inline schema was
+    /// given.
+    pub(crate) fn saved_schema_id(&self) -> Option<&str> {
+        self.schema_id.as_deref().filter(|_| self.schema.is_none())
+    }
+
+    /// True if a schema was given, inline or saved.
+    pub(crate) fn has_schema(&self) -> bool {
+        self.schema.is_some() || self.schema_id.is_some()
+    }
+
+    /// Build the crawl request for `site`, extracting with `saved_schema`
+    /// when no inline schema was given.
+    pub(crate) fn crawl_request(self, site: &Site, saved_schema: Option<String>) -> CrawlRequest {
+        let saved = site.crawl_options.as_ref();
+        let site_request = CrawlRequest {
+            url: site.url.clone(),
+            options: Some(CrawlOptions {
+                fetch_mode: crawl_fetch_mode(&site.fetch_mode),
+                follow_pattern: saved.and_then(|o| o.follow_pattern.clone()),
+                follow_selector: saved.and_then(|o| o.follow_selector.clone()),
+                max_depth: saved.and_then(|o| o.max_depth),
+                max_pages: saved.and_then(|o| o.max_pages),
+                ..CrawlOptions::builder().build()
+            }),
+            ..Default::default()
+        };
+        self.apply(site_request, saved_schema)
+    }
+
+    /// Apply the overrides to `request`, extracting with `saved_schema`
+    /// when no inline schema was given.
+    pub(crate) fn apply(
+        self,
+        mut request: CrawlRequest,
+        saved_schema: Option<String>,
+    ) -> CrawlRequest {
+        if let Some(url) = self.url {
+            request.url = url;
+        }
+        // Saved schemas are YAML, which the API accepts as text.
+        if let Some(schema) = self.schema.or(saved_schema.map(Value::String)) {
+            request.schema = schema;
+        }
+        if self.max_pages.is_some() || self.max_depth.is_some() {
+            let options = request
+                .options
+                .get_or_insert_with(|| CrawlOptions::builder().build());
+            options.max_pages = self.max_pages.or(options.max_pages);
+            options.max_depth = self.max_depth.or(options.max_depth);
+        }
+        if self.webhook_id.is_some() {
+            request.webhook_id = self.webhook_id;
+        }
+        request
+    }
+}
+
+/// A recurring run of a saved site.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Schedule ID.
+    pub id: String,
+    /// The site that is run.
+    pub site_id: String,
+    /// Cron expression, evaluated in UTC.
+    pub cron: String,
+    /// True while runs are paused.
+    #[serde(default)]
+    pub paused: bool,
+    /// When the site next runs. `None` while paused.
+    pub next_run_at: Option<Timestamp>,
+    /// When the site last ran, if it has.
+    #[serde(default)]
+    pub last_run_at: Option<Timestamp>,
+    /// ID of the job started by the last run.
+    #[serde(default)]
+    pub last_job_id: Option<String>,
+    /// Creation timestamp.
+    pub created_at: Timestamp,
+    /// Last update timestamp.
+    pub updated_at: Timestamp,
+}
+
+/// Schedule list response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleList {
+    /// All schedules, for every site.
+    #[serde(default)]
+    pub schedules: Vec<Schedule>,
+}
+
+/// Check the shape of a cron expression: five or six fields, or a macro
+/// such as `@daily`. The API validates the fields themselves.
+pub(crate) fn check_cron(expr: &str) -> Result<()> {
+    let fields = expr.split_whitespace().count();
+    if (expr.starts_with('@') && fields == 1) || fields == 5 || fields == 6 {
+        Ok(())
+    } else {
+        Err(Error::Config(format!(
+            "Invalid cron expression {:?}: expected five fields, such as \"0 6 * * *\"",
+            expr
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn site() -> Site {
+        serde_json::from_value(json!({
+            "id": "site-1", "url": "https://example.com", "domain": "example.com",
+            "fetch_mode": "dynamic", "default_schema_id": "schema-1",
+            "crawl_options": {"follow_selector": "a.product", "max_pages": 50},
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z",
+            "user_id": "user-1",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_crawl_request_uses_saved_configuration() {
+        let options = RunOptions::new();
+        assert_eq!(options.schema_id_for(&site()).unwrap(), Some("schema-1"));
+
+        let request = options.crawl_request(&site(), Some("name: string\n".into()));
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "url": "https://example.com",
+                "schema": "name: string\n",
+                "options": {
+                    "concurrency": null, "delay": null, "extract_from_seeds": null,
+                    "fetch_mode": "dynamic", "follow_pattern": null,
+                    "follow_selector": "a.product", "max_depth": null, "max_pages": 50,
+                    "max_urls": null, "next_selector": null, "same_domain_only": null,
+                    "use_sitemap": null,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_cron() {
+        for expr in ["0 6 * * *", "*/15 * * * * *", "@daily"] {
+            assert!(check_cron(expr).is_ok(), "{}", expr);
+        }
+        for expr in ["", "daily", "0 6 * *", "@every 1h"] {
+            assert!(
+                matches!(check_cron(expr), Err(Error::Config(_))),
+                "{}",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn test_crawl_request_applies_overrides() {
+        let options = RunOptions::new()
+            .url("https://example.com/sale")
+            .schema(json!({"name": "string"}))
+            .max_pages(5)
+            .webhook_id("hook-1");
+        assert_eq!(options.schema_id_for(&site()).unwrap(), None);
+
+        let request = options.crawl_request(&site(), None);
+        assert_eq!(request.url, "https://example.com/sale");
+        assert_eq!(request.schema, json!({"name": "string"}));
+        assert_eq!(request.options.unwrap().max_pages, Some(5));
+        assert_eq!(request.webhook_id.as_deref(), Some("hook-1"));
+
+        let mut bare = site();
+        bare.default_schema_id = None;
+        assert!(matches!(
+            RunOptions::new().schema_id_for(&bare),
+            Err(Error::Config(_))
+        ));
+        assert_eq!(
+            RunOptions::new()
+                .schema_id("schema-2")
+                .schema_id_for(&bare)
+                .unwrap(),
+            Some("schema-2")
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Gzip-compressed snapshots of account data.
+//!
+//! [`Client::export_account_snapshot`](crate::Client::export_account_snapshot)
+//! writes schemas, saved sites, job metadata, usage, and account configuration
+//! to a single file for backup or compliance. The file is gzip-compressed JSON
+//! Lines: a [`SnapshotHeader`] followed by one [`SnapshotRecord`] per item.
+//! [`inspect_snapshot`] reads it back.
+//!
+//! ```rust,no_run
+//! use refyne::{inspect_snapshot, Client, SnapshotSection};
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! client.export_account_snapshot("account.jsonl.gz").await?;
+//!
+//! let snapshot = inspect_snapshot("account.jsonl.gz")?;
+//! println!("{} schemas", snapshot.count(SnapshotSection::Schema));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// Compressed bytes collected before they are written to the file.
+const FLUSH_THRESHOLD: usize = 64 * 1024;
+
+/// Version of the snapshot file format written by this SDK.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Kind of item stored in a snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotSection {
+    /// A saved schema.
+    Schema,
+    /// A saved site.
+    Site,
+    /// Metadata of a job (not its results).
+    Job,
+    /// Usage for the current billing period.
+    Usage,
+    /// A webhook.
+    Webhook,
+    /// The LLM fallback chain.
+    LlmChain,
+}
+
+/// First line of a snapshot file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotHeader {
+    /// Snapshot file format version.
+    pub format_version: u32,
+    /// Unix time in seconds when the export started.
+    pub created_at: u64,
+    /// SDK version that wrote the snapshot.
+    pub sdk_version: String,
+    /// API base URL the data was exported from.
+    pub base_url: String,
+}
+
+/// One exported item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRecord {
+    /// What the item is.
+    pub section: SnapshotSection,
+    /// The item as returned by the API.
+    pub data: Value,
+}
+
+/// Outcome of an export.
+#[derive(Debug, Clone)]
+pub struct SnapshotSummary {
+    /// Header written to the file.
+    pub header: SnapshotHeader,
+    /// Number of records written per section.
+    pub counts: BTreeMap<SnapshotSection, usize>,
+}
+
+/// A snapshot read back from disk.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Header of the file.
+    pub header: SnapshotHeader,
+    /// Records in the order they were written.
+    pub records: Vec<SnapshotRecord>,
+}
+
+impl Snapshot {
+    /// Items of one section.
+    pub fn section(&self, section: SnapshotSection) -> impl Iterator<Item = &Value> {
+        self.records
+            .iter()
+            .filter(move |r| r.section == section)
+            .map(|r| &r.data)
+    }
+
+    /// Number of items in one section.
+    pub fn count(&self, section: SnapshotSection) -> usize {
+        self.section(section).count()
+    }
+}
+
+/// Read a snapshot written by
+/// [`Client::export_account_snapshot`](crate::Client::export_account_snapshot).
+///
+/// Fails with [`Error::Config`] if the file was written in a newer format.
+pub fn inspect_snapshot(path: impl AsRef<Path>) -> Result<Snapshot> {
+    let reader = BufReader::new(GzDecoder::new(File::open(path)?));
+    let mut lines = reader.lines();
+
+    let header: SnapshotHeader = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)?,
+        None => return Err(Error::Config("snapshot is empty".into())),
+    };
+    if header.format_version > SNAPSHOT_FORMAT_VERSION {
+        return Err(Error::Config(format!(
+            "snapshot format {} is newer than supported format {}",
+            header.format_version, SNAPSHOT_FORMAT_VERSION
+        )));
+    }
+
+    let mut records = Vec::new();
+    for line in lines {
+        let line = line?;
+        if !line.is_empty() {
+            records.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(Snapshot { header, records })
+}
+
+/// Writes records to a snapshot file as they are fetched.
+///
+/// Records go to a temporary file next to the target, which replaces the
+/// target only once the snapshot is complete. An interrupted export leaves
+/// any earlier snapshot in place.
+pub(crate) struct SnapshotWriter {
+    encoder: GzEncoder<Vec<u8>>,
+    file: tokio::fs::File,
+    path: PathBuf,
+    temp_path: Option<PathBuf>,
+    header: SnapshotHeader,
+    counts: BTreeMap<SnapshotSection, usize>,
+}
+
+impl SnapshotWriter {
+    pub(crate) async fn create(path: &Path, header: SnapshotHeader) -> Result<Self> {
+        let temp_path = temp_path(path);
+        let file = tokio::fs::File::create(&temp_path).await?;
+        let mut writer = Self {
+            encoder: GzEncoder::new(Vec::new(), Compression::default()),
+            file,
+            path: path.to_path_buf(),
+            temp_path: Some(temp_path),
+            header,
+            counts: BTreeMap::new(),
+        };
+        let line = serde_json::to_vec(&writer.header)?;
+        writer.write_line(&line)?;
+        Ok(writer)
+    }
+
+    /// Write one item, or each element if `data` is an array.
+    pub(crate) async fn write(&mut self, section: SnapshotSection, data: Value) -> Result<usize> {
+        let items = match data {
+            Value::Array(items) => items,
+            Value::Null => Vec::new(),
+            other => vec![other],
+        };
+        let written = items.len();
+        for data in items {
+            let line = serde_json::to_vec(&SnapshotRecord { section, data })?;
+            self.write_line(&line)?;
+        }
+        *self.counts.entry(section).or_default() += written;
+        if self.encoder.get_ref().len() >= FLUSH_THRESHOLD {
+            let compressed = std::mem::take(self.encoder.get_mut());
+            self.file.write_all(&compressed).await?;
+        }
+        Ok(written)
+    }
+
+    /// Write the rest of the file and move it into place.
+    pub(crate) async fn finish(mut self) -> Result<SnapshotSummary> {
+        let encoder = std::mem::replace(
+            &mut self.encoder,
+            GzEncoder::new(Vec::new(), Compression::default()),
+        );
+        self.file.write_all(&encoder.finish()?).await?;
+        self.file.sync_all().await?;
+        if let Some(temp_path) = self.temp_path.take() {
+            tokio::fs::rename(&temp_path, &self.path).await?;
+        }
+        Ok(SnapshotSummary {
+            header: self.header.clone(),
+            counts: std::mem::take(&mut self.counts),
+        })
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> Result<()> {
+        self.encoder.write_all(line)?;
+        self.encoder.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl Drop for SnapshotWriter {
+    fn drop(&mut self) {
+        // Not finished: discard the partial snapshot.
+        if let Some(temp_path) = self.temp_path.take() {
+            let _ = std::fs::remove_file(temp_path);
+        }
+    }
+}
+
+/// Hidden file in the same directory as `path`, so renaming it over `path`
+/// does not cross file systems.
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_write_and_inspect() {
+        let path = std::env::temp_dir().join(format!("refyne-snapshot-{}.gz", std::process::id()));
+        let header = SnapshotHeader {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            created_at: 1_700_000_000,
+            sdk_version: "0.1.0".into(),
+            base_url: "https://api.example.com".into(),
+        };
+
+        let mut writer = SnapshotWriter::create(&path, header).await.unwrap();
+        writer
+            .write(SnapshotSection::Schema, json!([{"id": "s1"}, {"id": "s2"}]))
+            .await
+            .unwrap();
+        writer
+            .write(SnapshotSection::Webhook, Value::Null)
+            .await
+            .unwrap();
+        writer
+            .write(SnapshotSection::Usage, json!({"total_jobs": 3}))
+            .await
+            .unwrap();
+        assert!(!path.exists());
+        let summary = writer.finish().await.unwrap();
+        assert_eq!(summary.counts[&SnapshotSection::Schema], 2);
+        assert_eq!(summary.counts[&SnapshotSection::Webhook], 0);
+
+        let snapshot = inspect_snapshot(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(snapshot.header.base_url, "https://api.example.com");
+        assert_eq!(snapshot.count(SnapshotSection::Schema), 2);
+        assert_eq!(
+            snapshot.section(SnapshotSection::Usage).next().unwrap()["total_jobs"],
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_rejects_newer_format() {
+        let path = std::env::temp_dir().join(format!("refyne-future-{}.gz", std::process::id()));
+        let header = SnapshotHeader {
+            format_version: SNAPSHOT_FORMAT_VERSION + 1,
+            created_at: 0,
+            sdk_version: "9.0.0".into(),
+            base_url: String::new(),
+        };
+        SnapshotWriter::create(&path, header)
+            .await
+            .unwrap()
+            .finish()
+            .await
+            .unwrap();
+
+        let result = inspect_snapshot(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unfinished_snapshot_keeps_previous_file() {
+        let path = std::env::temp_dir().join(format!("refyne-keep-{}.gz", std::process::id()));
+        std::fs::write(&path, b"previous").unwrap();
+        let header = SnapshotHeader {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            created_at: 0,
+            sdk_version: "0.1.0".into(),
+            base_url: String::new(),
+        };
+
+        let mut writer = SnapshotWriter::create(&path, header).await.unwrap();
+        writer
+            .write(SnapshotSection::Schema, json!([{"id": "s1"}]))
+            .await
+            .unwrap();
+        drop(writer);
+
+        let kept = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(kept, b"previous");
+        assert!(!temp_path(&path).exists());
+    }
+}
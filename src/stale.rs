@@ -0,0 +1,105 @@
+//! Stale-if-error fallback for extraction.
+//!
+//! Successful results from [`Client::extract_or_stale`](crate::Client::extract_or_stale)
+//! are kept in the client's cache as the last-good result for their URL and
+//! schema. When a later extraction fails, the last-good result is returned
+//! flagged as stale instead of the error.
+
+use crate::cache::{generate_cache_key, hash_string, CacheControlDirectives, CacheEntry};
+use crate::error::Error;
+use crate::types::{ExtractRequest, ExtractResponse};
+use serde_json::Value;
+
+/// Outcome of an extraction that may have fallen back to a stale result.
+#[derive(Debug)]
+pub struct Extraction {
+    /// The extraction response (fresh or last-good).
+    pub response: ExtractResponse,
+    /// True if the response is a previous result served because the call failed.
+    pub stale: bool,
+    /// The error that caused the stale result to be served.
+    pub error: Option<Error>,
+}
+
+impl Extraction {
+    pub(crate) fn fresh(response: ExtractResponse) -> Self {
+        Self {
+            response,
+            stale: false,
+            error: None,
+        }
+    }
+
+    pub(crate) fn stale(response: ExtractResponse, error: Error) -> Self {
+        Self {
+            response,
+            stale: true,
+            error: Some(error),
+        }
+    }
+}
+
+/// Cache key under which the last-good result for a request is stored.
+pub(crate) fn last_good_key(request: &ExtractRequest, auth_hash: &str) -> String {
+    let mut schema = serde_json::to_string(&request.schema).unwrap_or_default();
+    if let Some(id) = &request.schema_id {
+        schema.push_str(&format!("@{}", id));
+    }
+    if let Some(fields) = &request.fields {
+        schema.push_str(&format!("|{}", fields.join(",")));
+    }
+    let target = format!("{}#{}", request.url, hash_string(&schema));
+    generate_cache_key("EXTRACT", &target, Some(auth_hash))
+}
+
+/// Create a non-expiring cache entry holding a last-good result.
+pub(crate) fn last_good_entry(value: Value) -> CacheEntry {
+    CacheEntry {
+        value,
+        expires_at: u64::MAX,
+        cache_control: CacheControlDirectives::default(),
+        etag: None,
+        last_modified: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_last_good_key_includes_schema() {
+        let a = ExtractRequest {
+            url: "https://example.com".into(),
+            schema: json!({"title": "string"}),
+            ..Default::default()
+        };
+        let b = ExtractRequest {
+            schema: json!({"price": "number"}),
+            ..a.clone()
+        };
+        assert_ne!(last_good_key(&a, "auth"), last_good_key(&b, "auth"));
+        assert_eq!(last_good_key(&a, "auth"), last_good_key(&a.clone(), "auth"));
+        assert!(last_good_key(&a, "auth").starts_with("EXTRACT:https://example.com#"));
+    }
+
+    #[test]
+    fn test_last_good_key_includes_field_selection() {
+        let full = ExtractRequest::with_saved_schema("https://example.com", "schema-1");
+        let partial = full.clone().only_fields(["price", "stock"]);
+        let other = ExtractRequest::with_saved_schema("https://example.com", "schema-2");
+        assert_ne!(
+            last_good_key(&full, "auth"),
+            last_good_key(&partial, "auth")
+        );
+        assert_ne!(last_good_key(&full, "auth"), last_good_key(&other, "auth"));
+    }
+
+    #[test]
+    fn test_last_good_entry_does_not_expire() {
+        let entry = last_good_entry(json!({"data": {}}));
+        assert_eq!(entry.expires_at, u64::MAX);
+        assert!(!entry.cache_control.no_store);
+    }
+}
@@ -0,0 +1,170 @@
+//! Account-level job statistics.
+//!
+//! [`JobsClient::stats`](crate::JobsClient::stats) returns aggregates computed
+//! by the API, so dashboards do not need to download and aggregate full job
+//! lists.
+
+use crate::job_filter::encode;
+use serde::{Deserialize, Serialize};
+
+/// Period covered by job statistics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatsRange {
+    /// The last 7 days, including today.
+    Last7Days,
+    /// The last 30 days, including today.
+    Last30Days,
+    /// The last 90 days, including today.
+    Last90Days,
+    /// An inclusive range of dates in `YYYY-MM-DD` form.
+    Between {
+        /// First day of the range.
+        from: String,
+        /// Last day of the range.
+        to: String,
+    },
+}
+
+impl StatsRange {
+    /// Query string for the stats endpoint.
+    pub(crate) fn query(&self) -> String {
+        match self {
+            StatsRange::Last7Days => "range=7d".into(),
+            StatsRange::Last30Days => "range=30d".into(),
+            StatsRange::Last90Days => "range=90d".into(),
+            StatsRange::Between { from, to } => {
+                format!("from={}&to={}", encode(from), encode(to))
+            }
+        }
+    }
+}
+
+/// Aggregates for one day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyJobStats {
+    /// Day in `YYYY-MM-DD` form. Empty for period totals.
+    #[serde(default)]
+    pub date: String,
+    /// Jobs created that day.
+    pub jobs: i64,
+    /// Jobs that completed successfully.
+    pub succeeded: i64,
+    /// Jobs that failed.
+    pub failed: i64,
+    /// Pages processed.
+    pub pages: i64,
+    /// USD charged.
+    pub cost_usd: f64,
+    /// 95th percentile job duration in milliseconds.
+    pub p95_duration_ms: Option<i64>,
+}
+
+/// Job statistics for a period.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobStats {
+    /// First day covered, in `YYYY-MM-DD` form.
+    pub from: String,
+    /// Last day covered, in `YYYY-MM-DD` form.
+    pub to: String,
+    /// Totals over the whole period.
+    pub totals: DailyJobStats,
+    /// One entry per day, oldest first.
+    #[serde(default)]
+    pub daily: Vec<DailyJobStats>,
+}
+
+impl DailyJobStats {
+    /// Share of finished jobs that succeeded, from 0.0 to 1.0.
+    ///
+    /// Returns `None` if no job finished.
+    pub fn success_rate(&self) -> Option<f64> {
+        let finished = self.succeeded + self.failed;
+        (finished > 0).then(|| self.succeeded as f64 / finished as f64)
+    }
+
+    /// Average pages per job.
+    pub fn pages_per_job(&self) -> Option<f64> {
+        (self.jobs > 0).then(|| self.pages as f64 / self.jobs as f64)
+    }
+
+    /// Average USD cost per page.
+    pub fn cost_per_page(&self) -> Option<f64> {
+        (self.pages > 0).then(|| self.cost_usd / self.pages as f64)
+    }
+}
+
+impl JobStats {
+    /// Job counts per day, for charting.
+    pub fn jobs_per_day(&self) -> Vec<(&str, i64)> {
+        self.daily
+            .iter()
+            .map(|d| (d.date.as_str(), d.jobs))
+            .collect()
+    }
+
+    /// Success rate per day, skipping days without finished jobs.
+    pub fn success_rate_per_day(&self) -> Vec<(&str, f64)> {
+        self.daily
+            .iter()
+            .filter_map(|d| Some((d.date.as_str(), d.success_rate()?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_query() {
+        assert_eq!(StatsRange::Last30Days.query(), "range=30d");
+        assert_eq!(
+            StatsRange::Between {
+                from: "2026-01-01".into(),
+                to: "2026-01-31".into(),
+            }
+            .query(),
+            "from=2026-01-01&to=2026-01-31"
+        );
+        assert_eq!(
+            StatsRange::Between {
+                from: "2026-01-01&range=7d".into(),
+                to: "2026-01-31".into(),
+            }
+            .query(),
+            "from=2026-01-01%26range%3D7d&to=2026-01-31"
+        );
+    }
+
+    #[test]
+    fn test_derived_metrics() {
+        let day = DailyJobStats {
+            date: "2026-01-02".into(),
+            jobs: 4,
+            succeeded: 3,
+            failed: 1,
+            pages: 40,
+            cost_usd: 2.0,
+            p95_duration_ms: Some(12_000),
+        };
+        assert_eq!(day.success_rate(), Some(0.75));
+        assert_eq!(day.pages_per_job(), Some(10.0));
+        assert_eq!(day.cost_per_page(), Some(0.05));
+
+        let stats = JobStats {
+            daily: vec![
+                DailyJobStats {
+                    date: "2026-01-01".into(),
+                    ..Default::default()
+                },
+                day,
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            stats.jobs_per_day(),
+            vec![("2026-01-01", 0), ("2026-01-02", 4)]
+        );
+        assert_eq!(stats.success_rate_per_day(), vec![("2026-01-02", 0.75)]);
+    }
+}
@@ -0,0 +1,204 @@
+//! Canned API responses and a preloaded mock server for tests.
+//!
+//! Code built on the SDK can be tested against [`MockApi`], a local
+//! [wiremock](https://docs.rs/wiremock) server that answers the extract,
+//! crawl, analyze, and job endpoints with realistic bodies:
+//!
+//! ```rust,no_run
+//! use refyne::test_support::{self, MockApi};
+//! use refyne::ExtractRequest;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let api = MockApi::start().await;
+//! let client = api.client();
+//! let result = client.extract(ExtractRequest::default()).await?;
+//! assert_eq!(result.data, test_support::extract_response()["data"]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The canned responses have the lowest priority, so mocks mounted on
+//! [`MockApi::server`] take precedence, for example to return an error.
+
+use crate::client::{Client, ClientBuilder};
+use serde_json::Value;
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+/// API version the canned responses were captured from.
+pub const API_VERSION: &str = "0.1.80";
+
+/// ID of the crawl job in [`crawl_job_created`].
+pub const CRAWL_JOB_ID: &str = "01JHB7S0D2F4H6J8K0M2P4R6TV";
+
+/// Priority of the canned mocks; wiremock serves lower numbers first.
+const CANNED_PRIORITY: u8 = u8::MAX;
+
+fn fixture(contents: &str) -> Value {
+    serde_json::from_str(contents).expect("canned response is valid JSON")
+}
+
+/// Body of a successful `POST /api/v1/extract`.
+pub fn extract_response() -> Value {
+    fixture(include_str!("../fixtures/0.1.80/extract.json"))
+}
+
+/// Body of a successful `POST /api/v1/crawl`.
+pub fn crawl_job_created() -> Value {
+    fixture(include_str!("../fixtures/0.1.80/crawl_job_created.json"))
+}
+
+/// Body of a successful `POST /api/v1/analyze`.
+pub fn analyze_response() -> Value {
+    fixture(include_str!("../fixtures/0.1.80/analyze_depth1.json"))
+}
+
+/// Body of `GET /api/v1/jobs/{id}` for a completed crawl.
+pub fn job() -> Value {
+    fixture(include_str!("../fixtures/0.1.80/job.json"))
+}
+
+/// Body of `GET /api/v1/jobs/{id}` for a job with the given ID and status,
+/// such as `"running"` or `"failed"`.
+pub fn job_with_status(id: &str, status: &str) -> Value {
+    let mut job = job();
+    job["id"] = id.into();
+    job["status"] = status.into();
+    if status != "completed" && status != "failed" && status != "cancelled" {
+        job["completed_at"] = Value::Null;
+    }
+    job
+}
+
+/// Body of `GET /api/v1/jobs/{id}/results`.
+pub fn job_results() -> Value {
+    let data = extract_response()["data"].clone();
+    serde_json::json!([
+        {"url": "https://example.com/products/pour-over", "data": data},
+    ])
+}
+
+/// Body the API sends with an error status.
+pub fn error_body(message: &str) -> Value {
+    serde_json::json!({ "error": message })
+}
+
+/// A mock Refyne API on a local port.
+pub struct MockApi {
+    server: MockServer,
+}
+
+impl MockApi {
+    /// Start a server answering the extract, crawl, analyze, and job
+    /// endpoints. Jobs are reported completed whatever their ID.
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let ok = |body: Value| {
+            ResponseTemplate::new(200)
+                .insert_header("X-API-Version", API_VERSION)
+                .set_body_json(body)
+        };
+        let canned = [
+            (
+                Mock::given(method("POST")).and(path("/api/v1/extract")),
+                ok(extract_response()),
+            ),
+            (
+                Mock::given(method("POST")).and(path("/api/v1/crawl")),
+                ok(crawl_job_created()),
+            ),
+            (
+                Mock::given(method("POST")).and(path("/api/v1/analyze")),
+                ok(analyze_response()),
+            ),
+            (
+                Mock::given(method("GET")).and(path("/api/v1/jobs")),
+                ok(serde_json::json!({ "jobs": [job()] })),
+            ),
+            (
+                Mock::given(method("GET")).and(path_regex(r"^/api/v1/jobs/[^/]+/results$")),
+                ok(job_results()),
+            ),
+        ];
+        for (mock, response) in canned {
+            mock.respond_with(response)
+                .with_priority(CANNED_PRIORITY)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/api/v1/jobs/[^/]+$"))
+            .respond_with(move |request: &Request| {
+                let id = request.url.path().rsplit('/').next().unwrap_or_default();
+                ok(job_with_status(id, "completed"))
+            })
+            .with_priority(CANNED_PRIORITY)
+            .mount(&server)
+            .await;
+        Self { server }
+    }
+
+    /// The underlying server, for mounting more mocks or inspecting the
+    /// requests it received.
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// Base URL of the server.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// A builder for a client talking to this server, with retries off.
+    pub fn client_builder(&self) -> ClientBuilder {
+        Client::builder("test-key")
+            .base_url(self.uri())
+            .max_retries(0)
+    }
+
+    /// A client talking to this server.
+    pub fn client(&self) -> Client {
+        self.client_builder()
+            .build()
+            .expect("mock client configuration is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::types::{AnalyzeRequest, CrawlRequest, ExtractRequest};
+
+    #[tokio::test]
+    async fn test_mock_api_serves_canned_responses() {
+        let api = MockApi::start().await;
+        let client = api.client();
+
+        let extracted = client.extract(ExtractRequest::default()).await.unwrap();
+        assert_eq!(extracted.data, extract_response()["data"]);
+        let created = client.crawl(CrawlRequest::default()).await.unwrap();
+        assert_eq!(created.job_id, CRAWL_JOB_ID);
+        client.analyze(AnalyzeRequest::default()).await.unwrap();
+        let job = client.get_job("01HXYZABCD1234").await.unwrap();
+        assert_eq!(
+            (job.id.as_str(), job.status.as_str()),
+            ("01HXYZABCD1234", "completed")
+        );
+        let results = client.get_job_results(CRAWL_JOB_ID, false).await.unwrap();
+        assert_eq!(results, job_results());
+    }
+
+    #[tokio::test]
+    async fn test_mounted_mocks_override_canned_responses() {
+        let api = MockApi::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/extract"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(error_body("page not found")))
+            .mount(api.server())
+            .await;
+
+        let result = api.client().extract(ExtractRequest::default()).await;
+        assert!(matches!(result, Err(Error::NotFound { .. })));
+    }
+}
@@ -0,0 +1,188 @@
+//! Timestamps in API responses.
+//!
+//! Response fields such as `created_at` and `fetched_at` are [`Timestamp`]s.
+//! A timestamp keeps the text the API sent, so it serializes back unchanged
+//! and still reads as a `&str`. With the `chrono` feature (on by default) it
+//! is also parsed once, when the response is deserialized:
+//!
+//! ```rust
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use refyne::Timestamp;
+//!
+//! let created = Timestamp::from("2026-01-12T09:15:10+01:00");
+//! assert_eq!(created.as_str(), "2026-01-12T09:15:10+01:00");
+//! assert_eq!(
+//!     created.datetime().unwrap().to_rfc3339(),
+//!     "2026-01-12T08:15:10+00:00"
+//! );
+//! # }
+//! ```
+//!
+//! Parsing is lenient: besides RFC 3339, times without an offset and bare
+//! dates are read as UTC. Text that is not a time at all, such as an empty
+//! string, is kept with no parsed value rather than failing the response.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+/// A point in time as sent by the API.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Timestamp {
+    raw: String,
+    #[cfg(feature = "chrono")]
+    parsed: Option<DateTime<Utc>>,
+}
+
+impl Timestamp {
+    /// The timestamp as the API sent it.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// The timestamp in UTC, or `None` if the API sent something that is
+    /// not a time.
+    #[cfg(feature = "chrono")]
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        self.parsed
+    }
+}
+
+impl From<String> for Timestamp {
+    fn from(raw: String) -> Self {
+        Self {
+            #[cfg(feature = "chrono")]
+            parsed: parse(&raw),
+            raw,
+        }
+    }
+}
+
+impl From<&str> for Timestamp {
+    fn from(raw: &str) -> Self {
+        Self::from(raw.to_string())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(time: DateTime<Utc>) -> Self {
+        Self {
+            raw: time.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            parsed: Some(time),
+        }
+    }
+}
+
+impl Deref for Timestamp {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl AsRef<str> for Timestamp {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl PartialEq<str> for Timestamp {
+    fn eq(&self, other: &str) -> bool {
+        self.raw == other
+    }
+}
+
+impl PartialEq<&str> for Timestamp {
+    fn eq(&self, other: &&str) -> bool {
+        self.raw == *other
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl fmt::Debug for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.raw, f)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Read RFC 3339, then a time without an offset, then a bare date, as UTC.
+#[cfg(feature = "chrono")]
+fn parse(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(raw) {
+        return Some(time.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|time| time.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_keeps_raw_text() {
+        let timestamp: Timestamp = serde_json::from_str(r#""2026-01-12T09:15:10Z""#).unwrap();
+        assert_eq!(timestamp, "2026-01-12T09:15:10Z");
+        assert_eq!(
+            serde_json::to_string(&timestamp).unwrap(),
+            r#""2026-01-12T09:15:10Z""#
+        );
+        assert!(timestamp.starts_with("2026-01-12"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_timestamp_parsing_is_lenient() {
+        let utc = |raw: &str| Timestamp::from(raw).datetime().map(|t| t.to_rfc3339());
+        assert_eq!(
+            utc("2026-01-12T09:15:10.5+01:00").as_deref(),
+            Some("2026-01-12T08:15:10.500+00:00")
+        );
+        assert_eq!(
+            utc("2026-01-12 09:15:10").as_deref(),
+            Some("2026-01-12T09:15:10+00:00")
+        );
+        assert_eq!(
+            utc("2026-01-12").as_deref(),
+            Some("2026-01-12T00:00:00+00:00")
+        );
+        assert_eq!(utc(""), None);
+        assert_eq!(utc("yesterday"), None);
+
+        let time = DateTime::from_timestamp(1_768_209_310, 0).unwrap();
+        let timestamp = Timestamp::from(time);
+        assert_eq!(timestamp, "2026-01-12T09:15:10Z");
+        assert_eq!(timestamp.datetime(), Some(time));
+    }
+}
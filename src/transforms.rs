@@ -0,0 +1,277 @@
+//! Post-processing of extracted data.
+//!
+//! Transforms registered with
+//! [`ClientBuilder::transform`](crate::ClientBuilder::transform) run in order
+//! on the `data` of every extraction and on each item of job results, so data
+//! conventions are enforced in one place.
+//!
+//! ```rust,no_run
+//! use refyne::transforms::{EmptyToNull, KeyCase, TrimStrings};
+//! use refyne::Client;
+//!
+//! # fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key")
+//!     .transform(TrimStrings)
+//!     .transform(EmptyToNull)
+//!     .transform(KeyCase::Snake)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::warn;
+
+/// A change applied to extracted data.
+///
+/// Implemented for closures taking `&mut Value`.
+pub trait Transform: Send + Sync {
+    /// Modify the value in place.
+    fn apply(&self, value: &mut Value);
+}
+
+impl<F> Transform for F
+where
+    F: Fn(&mut Value) + Send + Sync,
+{
+    fn apply(&self, value: &mut Value) {
+        self(value)
+    }
+}
+
+/// Rename object keys to one casing, recursively.
+///
+/// When two keys of an object convert to the same name, the first in the
+/// object's order keeps it and the other is dropped with a warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCase {
+    /// `snake_case`
+    Snake,
+    /// `camelCase`
+    Camel,
+}
+
+impl KeyCase {
+    fn convert(&self, key: &str) -> String {
+        let words = split_words(key);
+        match self {
+            KeyCase::Snake => words.join("_"),
+            KeyCase::Camel => {
+                let mut out = String::with_capacity(key.len());
+                for (i, word) in words.iter().enumerate() {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) if i > 0 => {
+                            out.extend(first.to_uppercase());
+                            out.push_str(chars.as_str());
+                        }
+                        _ => out.push_str(word),
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+impl Transform for KeyCase {
+    fn apply(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                let mut renamed = Map::with_capacity(map.len());
+                for (key, mut field) in std::mem::take(map) {
+                    let converted = self.convert(&key);
+                    if renamed.contains_key(&converted) {
+                        warn!(key = %key, renamed = %converted, "Dropping field whose renamed key is taken");
+                        continue;
+                    }
+                    self.apply(&mut field);
+                    renamed.insert(converted, field);
+                }
+                *map = renamed;
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.apply(item)),
+            _ => {}
+        }
+    }
+}
+
+/// Replace empty strings with null, recursively.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmptyToNull;
+
+impl Transform for EmptyToNull {
+    fn apply(&self, value: &mut Value) {
+        match value {
+            Value::String(s) if s.is_empty() => *value = Value::Null,
+            Value::Object(map) => map.values_mut().for_each(|v| self.apply(v)),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.apply(v)),
+            _ => {}
+        }
+    }
+}
+
+/// Trim leading and trailing whitespace from strings, recursively.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrimStrings;
+
+impl Transform for TrimStrings {
+    fn apply(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => {
+                let trimmed = s.trim();
+                if trimmed.len() != s.len() {
+                    *s = trimmed.to_string();
+                }
+            }
+            Value::Object(map) => map.values_mut().for_each(|v| self.apply(v)),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.apply(v)),
+            _ => {}
+        }
+    }
+}
+
+/// Ordered transforms registered on a client.
+#[derive(Clone, Default)]
+pub(crate) struct TransformPipeline {
+    transforms: Vec<Arc<dyn Transform>>,
+}
+
+impl TransformPipeline {
+    pub(crate) fn push(&mut self, transform: Arc<dyn Transform>) {
+        self.transforms.push(transform);
+    }
+
+    pub(crate) fn apply(&self, value: &mut Value) {
+        for transform in &self.transforms {
+            transform.apply(value);
+        }
+    }
+
+    /// Apply to each item of job results.
+    ///
+    /// Unmerged results are a list of items, bare or wrapped in
+    /// `{"results": [...]}`. Merged results are a single item, even when the
+    /// extracted data has a `results` field. Items carrying a `data` field are
+    /// transformed inside it.
+    pub(crate) fn apply_to_results(&self, results: &mut Value, merged: bool) {
+        if self.transforms.is_empty() {
+            return;
+        }
+        if merged {
+            self.apply_to_item(results);
+            return;
+        }
+        if let Some(Value::Array(items)) = results
+            .as_object_mut()
+            .and_then(|wrapper| wrapper.get_mut("results"))
+        {
+            items.iter_mut().for_each(|item| self.apply_to_item(item));
+            return;
+        }
+        match results {
+            Value::Array(items) => items.iter_mut().for_each(|item| self.apply_to_item(item)),
+            item => self.apply_to_item(item),
+        }
+    }
+
+    pub(crate) fn apply_to_item(&self, item: &mut Value) {
+        match item.get_mut("data") {
+            Some(data) => self.apply(data),
+            None => self.apply(item),
+        }
+    }
+}
+
+/// Split an identifier into lowercase words at `_`, `-`, spaces, and
+/// lower-to-upper case changes.
+fn split_words(key: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in key.chars() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_key_case() {
+        let mut value = json!({"productName": "x", "price-info": [{"Sale Price": 1}]});
+        KeyCase::Snake.apply(&mut value);
+        assert_eq!(
+            value,
+            json!({"product_name": "x", "price_info": [{"sale_price": 1}]})
+        );
+
+        KeyCase::Camel.apply(&mut value);
+        assert_eq!(
+            value,
+            json!({"productName": "x", "priceInfo": [{"salePrice": 1}]})
+        );
+    }
+
+    #[test]
+    fn test_key_case_keeps_first_of_colliding_keys() {
+        let mut value = json!({"product_name": "first", "productName": "second"});
+        KeyCase::Snake.apply(&mut value);
+        assert_eq!(value.as_object().unwrap().len(), 1);
+        assert!(value["product_name"].is_string());
+    }
+
+    #[test]
+    fn test_pipeline_order() {
+        let mut pipeline = TransformPipeline::default();
+        pipeline.push(Arc::new(TrimStrings));
+        pipeline.push(Arc::new(EmptyToNull));
+        pipeline.push(Arc::new(|value: &mut Value| {
+            value["seen"] = true.into();
+        }));
+
+        let mut value = json!({"name": "  Mug ", "sku": "   "});
+        pipeline.apply(&mut value);
+        assert_eq!(value, json!({"name": "Mug", "sku": null, "seen": true}));
+    }
+
+    #[test]
+    fn test_apply_to_results() {
+        let mut pipeline = TransformPipeline::default();
+        pipeline.push(Arc::new(EmptyToNull));
+
+        let mut listed = json!([{"url": "", "data": {"a": ""}}, {"b": ""}]);
+        pipeline.apply_to_results(&mut listed, false);
+        assert_eq!(
+            listed,
+            json!([{"url": "", "data": {"a": null}}, {"b": null}])
+        );
+
+        let mut wrapped = json!({"results": [{"data": {"a": ""}}]});
+        pipeline.apply_to_results(&mut wrapped, false);
+        assert_eq!(wrapped, json!({"results": [{"data": {"a": null}}]}));
+
+        // A merged item's own `results` field is data, not a wrapper.
+        let mut merged = json!({"title": "", "results": [{"score": ""}]});
+        pipeline.apply_to_results(&mut merged, true);
+        assert_eq!(merged, json!({"title": null, "results": [{"score": null}]}));
+    }
+}
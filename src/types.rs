@@ -7,6 +7,8 @@
 
 #![allow(dead_code)]
 
+use crate::secret::SecretString;
+use crate::timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -238,6 +240,9 @@ pub struct CreateCrawlJobInputBody {
     /// Content cleaner chain (default: [markdown])
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cleaner_chain: Option<serde_json::Value>,
+    /// Headers, cookies, and proxy country used to fetch each page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch_options: Option<crate::FetchOptions>,
     /// Optional LLM configuration override (BYOK)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub llm_config: Option<LLMConfigInput>,
@@ -251,6 +256,9 @@ pub struct CreateCrawlJobInputBody {
     /// Seed URL to start crawling from
     #[serde(rename = "url")]
     pub url: String,
+    /// Further seed URLs, crawled as well as url
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
     /// Inline ephemeral webhook configuration
     #[serde(rename = "webhook")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -419,18 +427,37 @@ pub struct ExtractInputBody {
     /// Enable debug capture to store raw LLM request/response for troubleshooting
     #[serde(skip_serializing_if = "Option::is_none")]
     pub capture_debug: Option<bool>,
+    /// Screenshot the page; the response carries the screenshot's artifact ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_screenshot: Option<crate::ScreenshotOptions>,
     /// Content cleaner chain (default: [markdown])
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cleaner_chain: Option<serde_json::Value>,
     /// Fetch mode: auto, static, or dynamic
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fetch_mode: Option<ExtractInputBodyFetchMode>,
+    /// Headers, cookies, and proxy country used to fetch the page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch_options: Option<crate::FetchOptions>,
+    /// Top-level schema fields to extract (default: all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// Return the fetched page in this form alongside the data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_content: Option<crate::ContentFormat>,
     /// Optional LLM configuration override
     #[serde(skip_serializing_if = "Option::is_none")]
     pub llm_config: Option<LLMConfigInput>,
     /// Extraction instructions - either a structured schema (YAML/JSON with 'name' and 'fields') or freeform natural language prompt. The API auto-detects the format and returns 'input_format' in the response.
     #[serde(rename = "schema")]
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub schema: serde_json::Value,
+    /// How `schema` is written. A JSON Schema is converted to the Refyne form before sending; see [`SchemaFormat`](crate::SchemaFormat).
+    #[serde(skip)]
+    pub schema_format: Option<crate::SchemaFormat>,
+    /// ID of a saved schema to use instead of an inline schema
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<String>,
     /// URL to extract data from
     #[serde(rename = "url")]
     pub url: String,
@@ -491,7 +518,7 @@ pub struct InlineWebhookInput {
     pub headers: Option<serde_json::Value>,
     /// Secret for HMAC-SHA256 signature
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub secret: Option<String>,
+    pub secret: Option<SecretString>,
     /// Webhook URL
     pub url: String,
 }
@@ -500,7 +527,7 @@ pub struct InlineWebhookInput {
 pub struct LLMConfigInput {
     /// API key for the provider
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub api_key: Option<String>,
+    pub api_key: Option<SecretString>,
     /// Custom base URL (for Ollama or self-hosted Helicone)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_url: Option<String>,
@@ -514,7 +541,7 @@ pub struct LLMConfigInput {
     pub provider: Option<LLMConfigInputProvider>,
     /// Underlying provider's API key for Helicone self-hosted mode
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub target_api_key: Option<String>,
+    pub target_api_key: Option<SecretString>,
     /// Underlying provider for Helicone self-hosted mode
     #[serde(skip_serializing_if = "Option::is_none")]
     pub target_provider: Option<String>,
@@ -533,7 +560,7 @@ pub struct ModelValidationRequest {
 pub struct ServiceKeyInput {
     /// API key for the provider (required for new keys, optional for updates)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub api_key: Option<String>,
+    pub api_key: Option<SecretString>,
     /// Whether this provider is enabled
     pub is_enabled: bool,
     /// LLM provider name (see /llm/providers for available options)
@@ -638,7 +665,7 @@ pub struct UserFallbackChainEntryInput {
 pub struct UserServiceKeyInput {
     /// API key for the provider (leave empty to keep existing)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub api_key: Option<String>,
+    pub api_key: Option<SecretString>,
     /// Base URL for the provider (for Ollama or custom endpoints)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_url: Option<String>,
@@ -690,7 +717,7 @@ pub struct WebhookInput {
     /// Secret for HMAC-SHA256 signature (leave empty to disable signing)
     #[serde(rename = "secret")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub secret: Option<String>,
+    pub secret: Option<SecretString>,
     /// Webhook URL to send events to
     #[serde(rename = "url")]
     pub url: String,
@@ -702,12 +729,12 @@ pub struct WebhookInput {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct APIKeyResponse {
-    pub created_at: String,
-    pub expires_at: Option<String>,
+    pub created_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
     #[serde(rename = "id")]
     pub id: String,
     pub key_prefix: String,
-    pub last_used_at: Option<String>,
+    pub last_used_at: Option<Timestamp>,
     #[serde(rename = "name")]
     pub name: String,
     #[serde(rename = "scopes")]
@@ -719,7 +746,7 @@ pub struct AdminJobResultsOutputBody {
     /// Presigned URL to download results (valid for 1 hour)
     pub download_url: String,
     /// URL expiration time
-    pub expires_at: String,
+    pub expires_at: Timestamp,
     /// Job ID
     pub job_id: String,
 }
@@ -744,9 +771,9 @@ pub struct AnalysisResultOutput {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyticsJobResponse {
-    pub completed_at: Option<String>,
+    pub completed_at: Option<Timestamp>,
     pub cost_usd: f64,
-    pub created_at: String,
+    pub created_at: Timestamp,
     pub discovery_method: Option<String>,
     pub error_category: Option<String>,
     pub error_message: Option<String>,
@@ -779,6 +806,9 @@ pub struct AnalyzeResponseBody {
     pub job_id: String,
     /// Detected page type: listing, detail, article, product, recipe, unknown
     pub page_type: String,
+    /// Suggestions per detected page type (depth > 0)
+    #[serde(default)]
+    pub page_types: Vec<crate::PageTypeSuggestion>,
     /// Recommended fetch mode: static or dynamic
     pub recommended_fetch_mode: String,
     /// Optional preview extraction result
@@ -862,13 +892,13 @@ pub struct CrawlOptionsOutput {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateKeyOutputBody {
-    pub created_at: String,
-    pub expires_at: Option<String>,
+    pub created_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
     #[serde(rename = "id")]
     pub id: String,
     /// Full API key - only shown once!
     #[serde(rename = "key")]
-    pub key: String,
+    pub key: SecretString,
     pub key_prefix: String,
     #[serde(rename = "name")]
     pub name: String,
@@ -953,7 +983,7 @@ pub struct DownloadJobDebugCaptureOutputBody {
     /// Signed URL for downloading the debug capture file
     pub download_url: String,
     /// When the download URL expires
-    pub expires_at: String,
+    pub expires_at: Timestamp,
     /// Suggested filename for the download
     #[serde(rename = "filename")]
     pub filename: String,
@@ -974,11 +1004,14 @@ pub struct ErrorCategoryResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractOutputBody {
+    /// The fetched page, in the form requested with include_content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
     /// Extracted data matching the schema
     #[serde(rename = "data")]
     pub data: serde_json::Value,
     /// Timestamp when the page was fetched
-    pub fetched_at: String,
+    pub fetched_at: Timestamp,
     /// How the input was interpreted: 'schema' (structured YAML/JSON) or 'prompt' (freeform text)
     pub input_format: String,
     /// Job ID for this extraction (for history/tracking)
@@ -986,6 +1019,9 @@ pub struct ExtractOutputBody {
     /// Extraction metadata
     #[serde(rename = "metadata")]
     pub metadata: MetadataResponse,
+    /// Artifact ID of the page screenshot, when one was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screenshot_id: Option<String>,
     /// URL that was extracted
     #[serde(rename = "url")]
     pub url: String,
@@ -1003,7 +1039,7 @@ pub struct FailingURLResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FallbackChainEntryResponse {
-    pub created_at: String,
+    pub created_at: Timestamp,
     #[serde(rename = "id")]
     pub id: String,
     pub is_enabled: bool,
@@ -1018,7 +1054,7 @@ pub struct FallbackChainEntryResponse {
     pub temperature: Option<f64>,
     #[serde(rename = "tier")]
     pub tier: Option<String>,
-    pub updated_at: String,
+    pub updated_at: Timestamp,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1118,7 +1154,7 @@ pub struct GetJobResultsDownloadOutputBody {
     /// Presigned URL to download results (valid for 1 hour)
     pub download_url: String,
     /// URL expiration time
-    pub expires_at: String,
+    pub expires_at: Timestamp,
     /// Job ID
     pub job_id: String,
 }
@@ -1164,16 +1200,20 @@ pub struct HealthCheckOutputBody {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobResponse {
     pub capture_debug: bool,
-    pub completed_at: Option<String>,
+    pub completed_at: Option<Timestamp>,
     pub cost_usd: f64,
-    pub created_at: String,
+    pub created_at: Timestamp,
     pub error_category: Option<String>,
     pub error_message: Option<String>,
     #[serde(rename = "id")]
     pub id: String,
     pub page_count: i64,
     pub queue_position: i64,
-    pub started_at: Option<String>,
+    /// Checkpoint of a stopped crawl, if it can be resumed; see
+    /// [`Client::resume_job`](crate::Client::resume_job).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_token: Option<String>,
+    pub started_at: Option<Timestamp>,
     #[serde(rename = "status")]
     pub status: String,
     pub token_usage_input: i64,
@@ -1190,9 +1230,9 @@ pub struct JobWebhookDeliveryResponse {
     /// Current attempt number
     pub attempt_number: i64,
     /// Creation timestamp
-    pub created_at: String,
+    pub created_at: Timestamp,
     /// Successful delivery timestamp
-    pub delivered_at: Option<String>,
+    pub delivered_at: Option<Timestamp>,
     /// Error message if failed
     pub error_message: Option<String>,
     /// Event type that triggered this delivery
@@ -1324,12 +1364,16 @@ pub struct MetadataResponse {
     pub extract_duration_ms: i64,
     /// Time to fetch the page in milliseconds
     pub fetch_duration_ms: i64,
+    /// URL of the page after following redirects
+    pub final_url: Option<String>,
     /// Model used for extraction
     #[serde(rename = "model")]
     pub model: String,
     /// LLM provider used
     #[serde(rename = "provider")]
     pub provider: String,
+    /// URLs redirected through, in order, starting with the submitted URL
+    pub redirect_chain: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1405,7 +1449,7 @@ pub struct SavedSiteOutput {
     /// Saved crawl options
     pub crawl_options: Option<CrawlOptionsOutput>,
     /// Creation timestamp
-    pub created_at: String,
+    pub created_at: Timestamp,
     /// Default schema to use
     pub default_schema_id: Option<String>,
     /// Extracted domain
@@ -1422,7 +1466,7 @@ pub struct SavedSiteOutput {
     /// Organization ID for sharing
     pub organization_id: Option<String>,
     /// Last update timestamp
-    pub updated_at: String,
+    pub updated_at: Timestamp,
     /// Site URL
     #[serde(rename = "url")]
     pub url: String,
@@ -1436,7 +1480,7 @@ pub struct SchemaOutput {
     #[serde(rename = "category")]
     pub category: Option<String>,
     /// Creation timestamp
-    pub created_at: String,
+    pub created_at: Timestamp,
     /// Schema description
     #[serde(rename = "description")]
     pub description: Option<String>,
@@ -1456,7 +1500,7 @@ pub struct SchemaOutput {
     #[serde(rename = "tags")]
     pub tags: Option<serde_json::Value>,
     /// Last update timestamp
-    pub updated_at: String,
+    pub updated_at: Timestamp,
     /// Number of times schema has been used
     pub usage_count: i64,
     /// Creator user ID
@@ -1468,12 +1512,12 @@ pub struct SchemaOutput {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceKeyResponse {
-    pub created_at: String,
+    pub created_at: Timestamp,
     pub has_key: bool,
     pub is_enabled: bool,
     #[serde(rename = "provider")]
     pub provider: String,
-    pub updated_at: String,
+    pub updated_at: Timestamp,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1566,7 +1610,7 @@ pub struct UsageResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserFallbackChainEntryResponse {
-    pub created_at: String,
+    pub created_at: Timestamp,
     #[serde(rename = "id")]
     pub id: String,
     pub is_enabled: bool,
@@ -1579,7 +1623,7 @@ pub struct UserFallbackChainEntryResponse {
     pub provider: String,
     #[serde(rename = "temperature")]
     pub temperature: Option<f64>,
-    pub updated_at: String,
+    pub updated_at: Timestamp,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1603,14 +1647,14 @@ pub struct UserModelResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserServiceKeyResponse {
     pub base_url: Option<String>,
-    pub created_at: String,
+    pub created_at: Timestamp,
     pub has_key: bool,
     #[serde(rename = "id")]
     pub id: String,
     pub is_enabled: bool,
     #[serde(rename = "provider")]
     pub provider: String,
-    pub updated_at: String,
+    pub updated_at: Timestamp,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1641,9 +1685,9 @@ pub struct WebhookDeliveryResponse {
     /// Current attempt number
     pub attempt_number: i64,
     /// Creation timestamp
-    pub created_at: String,
+    pub created_at: Timestamp,
     /// Successful delivery timestamp
-    pub delivered_at: Option<String>,
+    pub delivered_at: Option<Timestamp>,
     /// Error message if failed
     pub error_message: Option<String>,
     /// Event type that triggered this delivery
@@ -1656,7 +1700,7 @@ pub struct WebhookDeliveryResponse {
     /// Maximum retry attempts
     pub max_attempts: i64,
     /// Next retry time if retrying
-    pub next_retry_at: Option<String>,
+    pub next_retry_at: Option<Timestamp>,
     /// Response time in milliseconds
     pub response_time_ms: Option<i64>,
     /// Delivery status (pending, success, failed, retrying)
@@ -1674,7 +1718,7 @@ pub struct WebhookDeliveryResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookResponse {
     /// Creation timestamp
-    pub created_at: String,
+    pub created_at: Timestamp,
     /// Subscribed event types
     #[serde(rename = "events")]
     pub events: serde_json::Value,
@@ -1692,7 +1736,7 @@ pub struct WebhookResponse {
     #[serde(rename = "name")]
     pub name: String,
     /// Last update timestamp
-    pub updated_at: String,
+    pub updated_at: Timestamp,
     /// Webhook URL
     #[serde(rename = "url")]
     pub url: String,
@@ -1705,12 +1749,12 @@ pub struct WebhookResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrawlMapEntry {
     /// When processing completed
-    pub completed_at: Option<String>,
+    pub completed_at: Option<Timestamp>,
     /// Crawl depth (0 for seed URL)
     #[serde(rename = "depth")]
     pub depth: i64,
     /// When URL was discovered
-    pub discovered_at: Option<String>,
+    pub discovered_at: Option<Timestamp>,
     /// Error classification: rate_limit, quota_exceeded, provider_error, invalid_key, context_length, invalid_response, network_error, unknown
     pub error_category: Option<String>,
     /// Full error details (BYOK users only)
@@ -1747,9 +1791,15 @@ pub struct CrawlOptions {
     /// Concurrent extraction requests
     #[serde(rename = "concurrency")]
     pub concurrency: Option<i64>,
+    /// Wait between requests for the Crawl-delay in robots.txt, when it is longer than delay
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crawl_delay_from_robots: Option<bool>,
     /// Delay between requests (e.g., 500ms, 1s, 2s)
     #[serde(rename = "delay")]
     pub delay: Option<String>,
+    /// Regex pattern for URLs never to crawl, even if they match follow_pattern
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_pattern: Option<String>,
     /// Extract data from the seed URL (not just discovered pages)
     pub extract_from_seeds: Option<bool>,
     /// Page fetching mode: auto (detect and retry with browser if needed), static (fast, Colly-based), dynamic (browser rendering for JS-heavy sites, requires content_dynamic feature)
@@ -1766,8 +1816,14 @@ pub struct CrawlOptions {
     pub max_urls: Option<i64>,
     /// CSS selector for pagination 'next' link
     pub next_selector: Option<String>,
+    /// Skip URLs disallowed for the crawler by the site's robots.txt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub respect_robots_txt: Option<bool>,
     /// Only follow links on the same domain as seed URL
     pub same_domain_only: Option<bool>,
+    /// Sitemap to read with use_sitemap, instead of the site's default /sitemap.xml
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sitemap_url: Option<String>,
     /// Discover URLs from sitemap.xml instead of CSS selectors
     pub use_sitemap: Option<bool>,
 }
@@ -1794,7 +1850,7 @@ pub struct DebugCaptureEntry {
     pub sequence: Option<i64>,
     /// When the request was made
     #[serde(rename = "timestamp")]
-    pub timestamp: String,
+    pub timestamp: Timestamp,
     /// Page URL being processed
     #[serde(rename = "url")]
     pub url: String,
@@ -1969,12 +2025,34 @@ pub struct ProvidersResponse {
 }
 
 /// Available LLM model.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Model {
     /// Model identifier.
     pub id: String,
     /// Display name.
     pub name: String,
+    /// Provider serving the model.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub provider: String,
+    /// Maximum input and output tokens per call.
+    #[serde(alias = "context_size", skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<i64>,
+    /// USD per input token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_price_per_token: Option<f64>,
+    /// USD per output token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_price_per_token: Option<f64>,
+    /// Whether the model accepts images.
+    pub supports_vision: bool,
+    /// Whether the model can be constrained to return JSON.
+    pub supports_json_mode: bool,
+    /// Whether the provider has deprecated the model.
+    pub deprecated: bool,
+    /// When a deprecated model stops being served.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunset_at: Option<Timestamp>,
 }
 
 // ==========================================================================
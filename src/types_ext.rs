@@ -0,0 +1,250 @@
+//! Hand-written additions to the generated API types.
+//!
+//! `types.rs` is regenerated from the OpenAPI specification, so helpers on
+//! the generated types, and types the specification does not describe, live
+//! here.
+
+use crate::types::{
+    AnalysisResultInput, AnalyzeResponseBody, CreateSiteRequest, ExtractInputBody,
+    ExtractOutputBody, FollowPatternInput, JobResponse, Model, SchemaOutput,
+    UserFallbackChainEntryInput, UserListModelsOutputBody,
+};
+use serde::{Deserialize, Serialize};
+
+/// Schema suggested for one page type found during a multi-page analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageTypeSuggestion {
+    /// Detected page type, e.g. listing or detail.
+    pub page_type: String,
+    /// URL pattern matching pages of this type.
+    pub url_pattern: Option<String>,
+    /// Schema suggestion for this page type (JSON format).
+    pub suggested_schema: String,
+}
+
+impl PageTypeSuggestion {
+    /// Build a request that saves a site extracting pages of this type.
+    ///
+    /// The URL pattern, if any, becomes the site's follow pattern.
+    pub fn to_site_request(
+        &self,
+        url: impl Into<String>,
+        name: Option<String>,
+    ) -> CreateSiteRequest {
+        let follow_patterns = self.url_pattern.as_ref().map(|pattern| {
+            serde_json::json!([FollowPatternInput {
+                description: Some(format!("{} pages", self.page_type)),
+                pattern: Some(pattern.clone()),
+                sample_urls: None,
+            }])
+        });
+
+        CreateSiteRequest {
+            analysis_result: Some(AnalysisResultInput {
+                follow_patterns,
+                page_type: Some(self.page_type.clone()),
+                suggested_schema: Some(self.suggested_schema.clone()),
+                ..Default::default()
+            }),
+            name,
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl AnalyzeResponseBody {
+    /// Suggestions for every detected page type.
+    ///
+    /// Single-page analyses return one suggestion built from the top-level
+    /// page type and schema.
+    pub fn suggestions(&self) -> Vec<PageTypeSuggestion> {
+        if !self.page_types.is_empty() {
+            return self.page_types.clone();
+        }
+        vec![PageTypeSuggestion {
+            page_type: self.page_type.clone(),
+            url_pattern: None,
+            suggested_schema: self.suggested_schema.clone(),
+        }]
+    }
+}
+
+impl Model {
+    /// USD charged for a call with these token counts, if the model's prices
+    /// are known.
+    pub fn cost_usd(&self, input_tokens: i64, output_tokens: i64) -> Option<f64> {
+        Some(
+            self.input_price_per_token? * input_tokens as f64
+                + self.output_price_per_token? * output_tokens as f64,
+        )
+    }
+
+    /// An enabled fallback chain entry for this model.
+    pub fn chain_entry(&self) -> UserFallbackChainEntryInput {
+        UserFallbackChainEntryInput {
+            is_enabled: true,
+            model: self.id.clone(),
+            provider: self.provider.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl UserListModelsOutputBody {
+    /// The listed models.
+    ///
+    /// Fails with [`Error::Deserialize`](crate::Error::Deserialize) if the
+    /// listing is not a list of models.
+    pub fn to_models(&self) -> crate::Result<Vec<Model>> {
+        match &self.models {
+            serde_json::Value::Null => Ok(Vec::new()),
+            models => serde_json::from_value(models.clone())
+                .map_err(|e| crate::Error::deserialize(e, models.clone())),
+        }
+    }
+}
+
+impl ExtractInputBody {
+    /// Build a request that extracts a URL with a saved schema.
+    pub fn with_saved_schema(url: impl Into<String>, schema_id: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            schema_id: Some(schema_id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Extract only the given top-level fields of the schema.
+    ///
+    /// Refresh loops that only need a few cheap fields of a large saved
+    /// schema, such as price and stock, use fewer tokens this way.
+    pub fn only_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl ExtractOutputBody {
+    /// URL the data was extracted from, after redirects.
+    ///
+    /// Falls back to the submitted URL when the API did not report a final URL.
+    pub fn canonical_url(&self) -> &str {
+        self.metadata.final_url.as_deref().unwrap_or(&self.url)
+    }
+
+    /// Deserialize the extracted data into a typed value.
+    ///
+    /// Fails with [`Error::Deserialize`](crate::Error::Deserialize) carrying
+    /// the data if it does not match `T`.
+    pub fn data_as<T: serde::de::DeserializeOwned>(&self) -> crate::Result<T> {
+        T::deserialize(&self.data).map_err(|e| crate::Error::deserialize(e, self.data.clone()))
+    }
+
+    /// Check the extracted data against the `schema` it was extracted with.
+    ///
+    /// Returns every place the data does not match, or nothing if it does;
+    /// see [`SchemaViolation`](crate::SchemaViolation).
+    pub fn validate(&self, schema: &serde_json::Value) -> Vec<crate::SchemaViolation> {
+        crate::validation::validate(schema, &self.data)
+    }
+
+    /// True if fetching the page followed at least one redirect.
+    pub fn was_redirected(&self) -> bool {
+        self.metadata
+            .redirect_chain
+            .as_ref()
+            .is_some_and(|chain| chain.len() > 1)
+            || self
+                .metadata
+                .final_url
+                .as_ref()
+                .is_some_and(|url| url != &self.url)
+    }
+}
+
+/// State of a job, parsed from [`JobResponse::status`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum JobStatus {
+    /// Queued and not yet started.
+    Pending,
+    /// In progress.
+    Running,
+    /// Finished successfully.
+    Completed,
+    /// Stopped by an error.
+    Failed,
+    /// Stopped by a cancel request.
+    Cancelled,
+    /// A status this SDK version does not know.
+    Other(String),
+}
+
+impl JobStatus {
+    /// The status as sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+            JobStatus::Other(status) => status,
+        }
+    }
+
+    /// Returns true while the job is pending or running.
+    pub fn is_active(&self) -> bool {
+        matches!(self, JobStatus::Pending | JobStatus::Running)
+    }
+}
+
+impl From<&str> for JobStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "pending" => JobStatus::Pending,
+            "running" => JobStatus::Running,
+            "completed" => JobStatus::Completed,
+            "failed" => JobStatus::Failed,
+            "cancelled" | "canceled" => JobStatus::Cancelled,
+            other => JobStatus::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl SchemaOutput {
+    /// Parse [`schema_yaml`](Self::schema_yaml) into a typed definition.
+    #[cfg(feature = "yaml")]
+    pub fn definition(&self) -> crate::Result<crate::SchemaDefinition> {
+        crate::SchemaDefinition::from_yaml(&self.schema_yaml)
+    }
+
+    /// The schema in the JSON form accepted by
+    /// [`ExtractRequest::schema`](ExtractInputBody::schema).
+    #[cfg(feature = "yaml")]
+    pub fn as_extract_schema(&self) -> crate::Result<serde_json::Value> {
+        Ok(self.definition()?.to_value())
+    }
+}
+
+impl JobResponse {
+    /// The job's status as a [`JobStatus`].
+    pub fn job_status(&self) -> JobStatus {
+        JobStatus::from(self.status.as_str())
+    }
+
+    /// Returns true if the job stopped with a checkpoint it can resume from.
+    pub fn can_resume(&self) -> bool {
+        self.resume_token.is_some() && !self.job_status().is_active()
+    }
+}
@@ -0,0 +1,156 @@
+//! Usage broken down over time.
+//!
+//! [`UsageClient::breakdown`](crate::UsageClient::breakdown) returns daily
+//! jobs, tokens, and cost for a period, optionally split into one series per
+//! API key, model, or job type:
+//!
+//! ```rust,no_run
+//! use refyne::{UsageGroupBy, UsageQuery};
+//!
+//! # async fn run(client: refyne::Client) -> Result<(), refyne::Error> {
+//! let query = UsageQuery::new("2026-01-01", "2026-01-31").group_by(UsageGroupBy::Model);
+//! let usage = client.usage().breakdown(&query).await?;
+//! for series in &usage.series {
+//!     println!("{}: ${:.2}", series.key, series.totals.cost_usd);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::job_filter::encode;
+use serde::{Deserialize, Serialize};
+
+/// What to split usage by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageGroupBy {
+    /// One series per API key.
+    ApiKey,
+    /// One series per LLM model.
+    Model,
+    /// One series per job type, such as `extract` or `crawl`.
+    JobType,
+}
+
+impl UsageGroupBy {
+    /// The grouping as sent to the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageGroupBy::ApiKey => "api_key",
+            UsageGroupBy::Model => "model",
+            UsageGroupBy::JobType => "job_type",
+        }
+    }
+}
+
+/// Period and grouping of a usage breakdown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageQuery {
+    /// Split usage into one series per group. A single series when unset.
+    pub group_by: Option<UsageGroupBy>,
+    /// First day covered, in `YYYY-MM-DD` form.
+    pub from: String,
+    /// Last day covered, in `YYYY-MM-DD` form.
+    pub to: String,
+}
+
+impl UsageQuery {
+    /// Usage from `from` to `to`, inclusive, in `YYYY-MM-DD` form.
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            group_by: None,
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    /// Split usage into one series per group.
+    pub fn group_by(mut self, group_by: UsageGroupBy) -> Self {
+        self.group_by = Some(group_by);
+        self
+    }
+
+    /// Query string for the breakdown endpoint.
+    pub(crate) fn query(&self) -> String {
+        let mut query = format!("from={}&to={}", encode(&self.from), encode(&self.to));
+        if let Some(group_by) = self.group_by {
+            query.push_str("&group_by=");
+            query.push_str(group_by.as_str());
+        }
+        query
+    }
+}
+
+/// Usage in one day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsagePoint {
+    /// Day in `YYYY-MM-DD` form. Empty for period totals.
+    pub date: String,
+    /// Jobs created.
+    pub jobs: i64,
+    /// LLM input tokens.
+    pub input_tokens: i64,
+    /// LLM output tokens.
+    pub output_tokens: i64,
+    /// USD charged.
+    pub cost_usd: f64,
+}
+
+/// Usage of one group over the period.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageSeries {
+    /// The group: an API key ID, a model, or a job type. Empty when usage is
+    /// not grouped.
+    pub key: String,
+    /// Readable name of the group, such as an API key's name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Totals over the whole period.
+    pub totals: UsagePoint,
+    /// One entry per day, oldest first.
+    pub points: Vec<UsagePoint>,
+}
+
+/// Usage for a period, in one series per group.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageBreakdown {
+    /// First day covered, in `YYYY-MM-DD` form.
+    pub from: String,
+    /// Last day covered, in `YYYY-MM-DD` form.
+    pub to: String,
+    /// The series, largest cost first.
+    pub series: Vec<UsageSeries>,
+}
+
+impl UsageBreakdown {
+    /// USD charged over the period, across all series.
+    pub fn total_cost_usd(&self) -> f64 {
+        self.series.iter().map(|s| s.totals.cost_usd).sum()
+    }
+
+    /// The series for group `key`.
+    pub fn get(&self, key: &str) -> Option<&UsageSeries> {
+        self.series.iter().find(|s| s.key == key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        assert_eq!(
+            UsageQuery::new("2026-01-01", "2026-01-31").query(),
+            "from=2026-01-01&to=2026-01-31"
+        );
+        assert_eq!(
+            UsageQuery::new("2026-01-01", "2026-01-31")
+                .group_by(UsageGroupBy::ApiKey)
+                .query(),
+            "from=2026-01-01&to=2026-01-31&group_by=api_key"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Checking extracted data against its schema.
+//!
+//! LLM extraction occasionally returns data of the wrong shape: a price as
+//! text, a list where an object was asked for, or a field left out.
+//! [`ExtractResponse::validate`](crate::ExtractResponse::validate) reports
+//! each mismatch with its path, and
+//! [`ClientBuilder::strict_schema`](crate::ClientBuilder::strict_schema)
+//! turns them into [`Error::SchemaMismatch`](crate::Error::SchemaMismatch):
+//!
+//! ```rust,no_run
+//! use refyne::{Client, ExtractRequest};
+//! use serde_json::json;
+//!
+//! # async fn run() -> Result<(), refyne::Error> {
+//! let client = Client::builder("your-api-key").build()?;
+//! let schema = json!({"name": "string", "price": "number"});
+//! let result = client
+//!     .extract(ExtractRequest {
+//!         url: "https://example.com/product".into(),
+//!         schema: schema.clone(),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! for violation in result.validate(&schema) {
+//!     eprintln!("{}", violation);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every field of the schema must be present, though `null` is accepted for
+//! any of them, since pages often lack a value. Fields the schema does not
+//! mention are ignored, as are freeform prompt schemas, which have no shape
+//! to check. JSON Schemas are recognised and checked the same way.
+
+use crate::definition::{FieldKind, SchemaDefinition};
+use crate::json_schema::SchemaFormat;
+use serde_json::Value;
+use std::fmt;
+
+/// A place where extracted data does not match its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Where in the data, such as `price` or `reviews[2].rating`.
+    pub path: String,
+    /// What is wrong there.
+    pub kind: ViolationKind,
+}
+
+/// What is wrong at a [`SchemaViolation`]'s path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// A field of the schema is absent.
+    Missing,
+    /// The value has the wrong type.
+    WrongType {
+        /// The type the schema asks for.
+        expected: String,
+        /// The type of the value found.
+        found: String,
+    },
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ViolationKind::Missing => write!(f, "{}: missing", self.path),
+            ViolationKind::WrongType { expected, found } => {
+                write!(f, "{}: expected {}, found {}", self.path, expected, found)
+            }
+        }
+    }
+}
+
+/// Check `data` against the extraction `schema`.
+pub(crate) fn validate(schema: &Value, data: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    let definition = match SchemaFormat::detect(schema) {
+        SchemaFormat::JsonSchema => SchemaDefinition::from_json_schema(schema),
+        SchemaFormat::Refyne => SchemaDefinition::from_value(schema),
+    };
+    if let Ok(definition) = definition {
+        check_object(&definition, data, "", &mut violations);
+    }
+    violations
+}
+
+fn check_object(
+    definition: &SchemaDefinition,
+    data: &Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let Some(map) = data.as_object() else {
+        violations.push(wrong_type(path, "object", data));
+        return;
+    };
+    for field in &definition.fields {
+        let path = if path.is_empty() {
+            field.name.clone()
+        } else {
+            format!("{}.{}", path, field.name)
+        };
+        match map.get(&field.name) {
+            Some(value) => check(&field.kind, value, &path, violations),
+            None => violations.push(SchemaViolation {
+                path,
+                kind: ViolationKind::Missing,
+            }),
+        }
+    }
+}
+
+fn check(kind: &FieldKind, value: &Value, path: &str, violations: &mut Vec<SchemaViolation>) {
+    let matches = match (kind, value) {
+        (_, Value::Null) | (FieldKind::Other(_), _) => true,
+        (FieldKind::String, Value::String(_)) | (FieldKind::Boolean, Value::Bool(_)) => true,
+        (FieldKind::Number, Value::Number(_)) => true,
+        (FieldKind::Integer, Value::Number(n)) => {
+            n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        (FieldKind::Array(item), Value::Array(items)) => {
+            for (index, value) in items.iter().enumerate() {
+                check(item, value, &format!("{}[{}]", path, index), violations);
+            }
+            true
+        }
+        (FieldKind::Object(definition), Value::Object(_)) => {
+            check_object(definition, value, path, violations);
+            true
+        }
+        _ => false,
+    };
+    if !matches {
+        violations.push(wrong_type(path, expected(kind), value));
+    }
+}
+
+fn expected(kind: &FieldKind) -> &str {
+    match kind {
+        FieldKind::Array(_) => "array",
+        FieldKind::Object(_) => "object",
+        scalar => scalar.name().unwrap_or_default(),
+    }
+}
+
+fn wrong_type(path: &str, expected: &str, value: &Value) -> SchemaViolation {
+    let found = match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+    SchemaViolation {
+        path: if path.is_empty() { "$" } else { path }.to_string(),
+        kind: ViolationKind::WrongType {
+            expected: expected.to_string(),
+            found: found.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "name": "string",
+            "price": {"type": "number", "description": "Price in GBP"},
+            "stock": "integer",
+            "tags": ["string"],
+            "reviews": [{"rating": "integer", "author": "string"}],
+            "seller": {"name": "string"},
+        })
+    }
+
+    #[test]
+    fn test_matching_data_passes() {
+        let data = json!({
+            "name": "Kettle",
+            "price": 49.5,
+            "stock": 3.0,
+            "tags": [],
+            "reviews": [{"rating": 5, "author": null, "extra": true}],
+            "seller": null,
+        });
+        assert_eq!(validate(&schema(), &data), vec![]);
+        assert_eq!(validate(&json!("name and price"), &json!(42)), vec![]);
+    }
+
+    #[test]
+    fn test_violations_are_reported_with_paths() {
+        let data = json!({
+            "name": 7,
+            "price": "£49.50",
+            "stock": 2.5,
+            "tags": ["brew", 1],
+            "reviews": [{"rating": 5, "author": "A"}, {"author": "B"}],
+            "seller": ["Acme"],
+        });
+        let violations: Vec<String> = validate(&schema(), &data)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "name: expected string, found integer",
+                "price: expected number, found string",
+                "reviews[1].rating: missing",
+                "seller: expected object, found array",
+                "stock: expected integer, found number",
+                "tags[1]: expected string, found integer",
+            ]
+        );
+
+        let violations = validate(&schema(), &json!([]));
+        assert_eq!(violations[0].path, "$");
+    }
+
+    #[test]
+    fn test_json_schema_is_recognised() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}, "price": {"type": "number"}},
+        });
+        let violations: Vec<String> = validate(&schema, &json!({"name": "Kettle", "price": "£5"}))
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(violations, vec!["price: expected number, found string"]);
+    }
+}
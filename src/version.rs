@@ -1,6 +1,24 @@
 //! SDK version information and API compatibility checking.
+//!
+//! Besides its version, the API advertises optional features in an
+//! `X-API-Features` header, or at `/api/v1/features` when a response lacks
+//! it. [`Client::supports`](crate::Client::supports) checks the set so
+//! helpers can fall back instead of failing against deployments without a
+//! feature:
+//!
+//! ```rust,no_run
+//! use refyne::Feature;
+//!
+//! # async fn run(client: refyne::Client) {
+//! if !client.supports(Feature::Sse).await {
+//!     println!("event streams are off, polling instead");
+//! }
+//! # }
+//! ```
 
 use crate::Error;
+use serde::Deserialize;
+use std::fmt;
 use tracing::warn;
 
 /// Current SDK version.
@@ -12,6 +30,141 @@ pub const MIN_API_VERSION: &str = "0.0.0";
 /// Maximum API version this SDK was built against.
 pub const MAX_KNOWN_API_VERSION: &str = "0.0.0";
 
+/// How the client reacts to the API's version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersionCheckMode {
+    /// Fail with [`Error::UnsupportedApiVersion`] when the API is older than
+    /// this SDK supports (default), on that request and every later one.
+    /// [`ClientBuilder::connect`](crate::ClientBuilder::connect) checks
+    /// before returning the client, and fails if the API does not report its
+    /// version. Otherwise a response without `X-API-Version` leaves the
+    /// version to be checked on the next one.
+    #[default]
+    Strict,
+    /// Log a warning and carry on.
+    WarnOnly,
+    /// Do not check the version.
+    Disabled,
+}
+
+/// What a client has learned about the API's version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ApiVersionStatus {
+    /// No response has reported a version yet.
+    Unchecked,
+    /// The version was checked, or the check was waived.
+    Accepted,
+    /// The API reported this version, which is older than supported.
+    Unsupported(String),
+}
+
+/// Header listing the API's optional features, separated by commas.
+pub const API_FEATURES_HEADER: &str = "X-API-Features";
+
+/// An optional API feature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Job updates as server-sent events.
+    Sse,
+    /// MessagePack response bodies.
+    Msgpack,
+    /// Page screenshots with extraction results.
+    Screenshots,
+    /// Extraction from uploaded documents.
+    DocumentExtraction,
+    /// Sitemap discovery.
+    Sitemaps,
+    /// Cost estimates.
+    Estimates,
+    /// A feature this SDK version does not know.
+    Other(String),
+}
+
+impl Feature {
+    /// The feature as named by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Feature::Sse => "sse",
+            Feature::Msgpack => "msgpack",
+            Feature::Screenshots => "screenshots",
+            Feature::DocumentExtraction => "document_extraction",
+            Feature::Sitemaps => "sitemaps",
+            Feature::Estimates => "estimates",
+            Feature::Other(feature) => feature,
+        }
+    }
+}
+
+impl From<&str> for Feature {
+    fn from(feature: &str) -> Self {
+        match feature.to_ascii_lowercase().as_str() {
+            "sse" => Feature::Sse,
+            "msgpack" => Feature::Msgpack,
+            "screenshots" => Feature::Screenshots,
+            "document_extraction" => Feature::DocumentExtraction,
+            "sitemaps" => Feature::Sitemaps,
+            "estimates" => Feature::Estimates,
+            _ => Feature::Other(feature.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Optional features offered by the API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ApiFeatures {
+    #[serde(skip, default = "advertised")]
+    advertised: bool,
+    #[serde(rename = "features")]
+    names: Vec<String>,
+}
+
+fn advertised() -> bool {
+    true
+}
+
+impl ApiFeatures {
+    /// Parse the value of the [`API_FEATURES_HEADER`].
+    pub fn from_header(value: &str) -> Self {
+        Self {
+            advertised: true,
+            names: value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// Features of an API that predates feature negotiation.
+    pub(crate) fn unadvertised() -> Self {
+        Self::default()
+    }
+
+    /// Returns false if the API does not advertise features, in which case
+    /// [`supports`](Self::supports) is false for every feature.
+    pub fn is_advertised(&self) -> bool {
+        self.advertised
+    }
+
+    /// Returns true if the API offers `feature`.
+    pub fn supports(&self, feature: &Feature) -> bool {
+        self.iter().any(|f| f == *feature)
+    }
+
+    /// The features offered.
+    pub fn iter(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.names.iter().map(|name| Feature::from(name.as_str()))
+    }
+}
+
 /// Parse a semver version string into components.
 ///
 /// Returns (major, minor, patch, prerelease).
@@ -57,11 +210,16 @@ pub fn compare_versions(a: &str, b: &str) -> i8 {
 /// Returns an error if the API version is too old.
 /// Logs a warning if the API version is newer than expected.
 pub fn check_api_version_compatibility(api_version: &str) -> Result<(), Error> {
+    check_compatibility(api_version, MIN_API_VERSION)
+}
+
+/// [`check_api_version_compatibility`] against a given minimum version.
+pub(crate) fn check_compatibility(api_version: &str, min_version: &str) -> Result<(), Error> {
     // If API version is lower than minimum supported, return error
-    if compare_versions(api_version, MIN_API_VERSION) < 0 {
+    if compare_versions(api_version, min_version) < 0 {
         return Err(Error::UnsupportedApiVersion {
             api_version: api_version.to_string(),
-            min_version: MIN_API_VERSION.to_string(),
+            min_version: min_version.to_string(),
             max_known_version: MAX_KNOWN_API_VERSION.to_string(),
         });
     }
@@ -128,6 +286,22 @@ mod tests {
         assert!(compare_versions(MIN_API_VERSION, MAX_KNOWN_API_VERSION) <= 0);
     }
 
+    #[test]
+    fn test_api_features() {
+        let features = ApiFeatures::from_header("sse, Screenshots,,vector_search");
+        assert!(features.is_advertised());
+        assert!(features.supports(&Feature::Sse));
+        assert!(features.supports(&Feature::Screenshots));
+        assert!(features.supports(&Feature::Other("vector_search".into())));
+        assert!(!features.supports(&Feature::Msgpack));
+
+        let listed: ApiFeatures =
+            serde_json::from_value(serde_json::json!({"features": ["estimates"]})).unwrap();
+        assert!(listed.is_advertised());
+        assert!(listed.supports(&Feature::Estimates));
+        assert!(!ApiFeatures::unadvertised().supports(&Feature::Sse));
+    }
+
     #[test]
     fn test_build_user_agent() {
         let ua = build_user_agent(None);
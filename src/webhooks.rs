@@ -0,0 +1,406 @@
+//! Verifying and parsing webhook deliveries.
+//!
+//! When a webhook has a secret, each delivery carries two headers:
+//!
+//! - `X-Refyne-Timestamp`: Unix time the delivery was signed, in seconds.
+//! - `X-Refyne-Signature`: `sha256=` followed by the hex HMAC-SHA256 of
+//!   `"{timestamp}.{body}"`, keyed with the secret.
+//!
+//! [`verify_signature`] checks both, rejecting deliveries signed more than
+//! [`DEFAULT_TOLERANCE`] ago so a captured request cannot be replayed later.
+//! Verify the raw body before parsing it, since re-serializing JSON changes
+//! the bytes:
+//!
+//! ```rust,no_run
+//! use refyne::webhooks::{self, WebhookEvent};
+//! use reqwest::header::HeaderMap;
+//!
+//! fn handle(headers: &HeaderMap, body: &[u8]) -> Result<(), refyne::Error> {
+//!     let event = webhooks::verify_event("whsec_...", headers, body)?;
+//!     match event {
+//!         WebhookEvent::JobCompleted(job) => println!("{} finished", job.job_id),
+//!         WebhookEvent::JobFailed(job) => println!("{} failed: {:?}", job.job_id, job.error_message),
+//!         WebhookEvent::PageExtracted(page) => println!("{}: {}", page.url, page.data),
+//!         _ => {}
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+use crate::error::{Error, Result};
+use crate::runtime::{SystemTime, UNIX_EPOCH};
+use crate::secret::SecretString;
+use crate::types::{WebhookHeaderInput, WebhookInput};
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+/// Header carrying the delivery's signature.
+pub const SIGNATURE_HEADER: &str = "X-Refyne-Signature";
+
+/// Header carrying the time the delivery was signed.
+pub const TIMESTAMP_HEADER: &str = "X-Refyne-Timestamp";
+
+/// How far a delivery's timestamp may be from the current time.
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+/// Prefix of the signature header value.
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// An event a webhook can subscribe to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WebhookEventType {
+    /// Every event.
+    All,
+    /// `job.completed`
+    JobCompleted,
+    /// `job.failed`
+    JobFailed,
+    /// `page.extracted`
+    PageExtracted,
+    /// Another event type, by name.
+    Other(String),
+}
+
+impl WebhookEventType {
+    /// Name of the event type as the API spells it.
+    pub fn as_str(&self) -> &str {
+        match self {
+            WebhookEventType::All => "*",
+            WebhookEventType::JobCompleted => "job.completed",
+            WebhookEventType::JobFailed => "job.failed",
+            WebhookEventType::PageExtracted => "page.extracted",
+            WebhookEventType::Other(name) => name,
+        }
+    }
+}
+
+impl WebhookInput {
+    /// Settings for an active webhook delivering every event to `url`.
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            is_active: true,
+            ..Default::default()
+        }
+    }
+
+    /// Only deliver the given events.
+    pub fn events(mut self, events: impl IntoIterator<Item = WebhookEventType>) -> Self {
+        let names: Vec<_> = events
+            .into_iter()
+            .map(|event| serde_json::Value::from(event.as_str()))
+            .collect();
+        self.events = Some(names.into());
+        self
+    }
+
+    /// Sign deliveries with `secret`. See [`verify_signature`].
+    pub fn secret(mut self, secret: impl Into<SecretString>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Send a custom header with each delivery.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let header = WebhookHeaderInput {
+            name: name.into(),
+            value: value.into(),
+        };
+        let mut headers = match self.headers.take() {
+            Some(serde_json::Value::Array(headers)) => headers,
+            _ => Vec::new(),
+        };
+        headers.push(serde_json::to_value(header).expect("header serializes"));
+        self.headers = Some(headers.into());
+        self
+    }
+
+    /// Pause or resume deliveries.
+    pub fn active(mut self, active: bool) -> Self {
+        self.is_active = active;
+        self
+    }
+}
+
+/// A parsed webhook payload.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WebhookEvent {
+    /// `job.completed`: a job finished successfully.
+    JobCompleted(JobEvent),
+    /// `job.failed`: a job stopped with an error.
+    JobFailed(JobEvent),
+    /// `page.extracted`: a crawl extracted data from a page.
+    PageExtracted(PageExtractedEvent),
+    /// An event type this SDK does not know.
+    Other {
+        /// Event type, e.g. `job.started`.
+        event: String,
+        /// Event data.
+        data: serde_json::Value,
+    },
+}
+
+/// Data of `job.completed` and `job.failed` events.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[non_exhaustive]
+pub struct JobEvent {
+    /// ID of the job.
+    pub job_id: String,
+    /// Job status, e.g. `completed` or `failed`.
+    pub status: String,
+    /// URL the job started from.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Number of pages processed.
+    #[serde(default)]
+    pub page_count: Option<i64>,
+    /// Total cost in USD.
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+    /// Error message, for failed jobs.
+    #[serde(default)]
+    pub error_message: Option<String>,
+    /// Error category, for failed jobs.
+    #[serde(default)]
+    pub error_category: Option<String>,
+    /// When the job finished.
+    #[serde(default)]
+    pub completed_at: Option<String>,
+}
+
+/// Data of `page.extracted` events.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[non_exhaustive]
+pub struct PageExtractedEvent {
+    /// ID of the crawl job.
+    pub job_id: String,
+    /// URL of the page.
+    pub url: String,
+    /// Data extracted from the page.
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// The envelope every delivery shares.
+#[derive(Deserialize)]
+struct Envelope {
+    event: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// Check a delivery's signature, allowing [`DEFAULT_TOLERANCE`] of clock
+/// difference.
+pub fn verify_signature(secret: &str, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+    verify_signature_with_tolerance(secret, headers, body, DEFAULT_TOLERANCE)
+}
+
+/// Check a delivery's signature, allowing `tolerance` of clock difference.
+pub fn verify_signature_with_tolerance(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance: Duration,
+) -> Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    verify_at(secret, headers, body, tolerance, now)
+}
+
+/// Check a delivery's signature and parse its payload.
+pub fn verify_event(secret: &str, headers: &HeaderMap, body: &[u8]) -> Result<WebhookEvent> {
+    verify_signature(secret, headers, body)?;
+    parse_event(body)
+}
+
+/// Parse a payload without checking its signature.
+pub fn parse_event(body: &[u8]) -> Result<WebhookEvent> {
+    let Envelope { event, data } = serde_json::from_slice(body)?;
+    Ok(match event.as_str() {
+        "job.completed" => WebhookEvent::JobCompleted(typed(data)?),
+        "job.failed" => WebhookEvent::JobFailed(typed(data)?),
+        "page.extracted" => WebhookEvent::PageExtracted(typed(data)?),
+        _ => WebhookEvent::Other { event, data },
+    })
+}
+
+fn typed<T: serde::de::DeserializeOwned>(data: serde_json::Value) -> Result<T> {
+    serde_json::from_value(data.clone()).map_err(|e| Error::deserialize(e, data))
+}
+
+/// Compute the signature header value for a delivery.
+///
+/// Useful for sending signed test deliveries to your own handler.
+pub fn sign(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let tag = mac(secret, timestamp, body).finalize().into_bytes();
+    format!("{}{}", SIGNATURE_PREFIX, hex::encode(tag))
+}
+
+fn mac(secret: &str, timestamp: u64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+fn verify_at(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance: Duration,
+    now: u64,
+) -> Result<()> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| Error::InvalidSignature(format!("missing {} header", name)))
+    };
+    let timestamp: u64 = header(TIMESTAMP_HEADER)?
+        .trim()
+        .parse()
+        .map_err(|_| Error::InvalidSignature(format!("malformed {} header", TIMESTAMP_HEADER)))?;
+    if now.abs_diff(timestamp) > tolerance.as_secs() {
+        return Err(Error::InvalidSignature(
+            "timestamp is outside the allowed tolerance".into(),
+        ));
+    }
+    // Several signatures may be sent while a secret is being rotated.
+    let valid = header(SIGNATURE_HEADER)?.split(',').any(|signature| {
+        signature
+            .trim()
+            .strip_prefix(SIGNATURE_PREFIX)
+            .and_then(|digest| hex::decode(digest).ok())
+            .is_some_and(|digest| mac(secret, timestamp, body).verify_slice(&digest).is_ok())
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature("signature does not match".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    const SECRET: &str = "whsec_test";
+    const NOW: u64 = 1_768_000_000;
+
+    fn signed(timestamp: u64, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        headers
+    }
+
+    fn rejection(headers: &HeaderMap, body: &[u8]) -> String {
+        match verify_at(SECRET, headers, body, DEFAULT_TOLERANCE, NOW) {
+            Err(Error::InvalidSignature(reason)) => reason,
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let body = br#"{"event":"job.completed","data":{}}"#;
+        let headers = signed(NOW - 10, &sign(SECRET, NOW - 10, body));
+        verify_at(SECRET, &headers, body, DEFAULT_TOLERANCE, NOW).unwrap();
+
+        assert_eq!(rejection(&headers, b"{}"), "signature does not match");
+        let other = sign("whsec_old", NOW, body);
+        let rotated = signed(NOW, &format!("{}, {}", other, sign(SECRET, NOW, body)));
+        verify_at(SECRET, &rotated, body, DEFAULT_TOLERANCE, NOW).unwrap();
+        assert_eq!(
+            rejection(&signed(NOW, &other), body),
+            "signature does not match"
+        );
+        assert_eq!(
+            rejection(&signed(NOW, "sha256=zz"), body),
+            "signature does not match"
+        );
+        assert_eq!(
+            rejection(&HeaderMap::new(), body),
+            "missing X-Refyne-Timestamp header"
+        );
+    }
+
+    #[test]
+    fn test_rejects_replayed_deliveries() {
+        let body = b"{}";
+        let stale = NOW - DEFAULT_TOLERANCE.as_secs() - 1;
+        let headers = signed(stale, &sign(SECRET, stale, body));
+        assert_eq!(
+            rejection(&headers, body),
+            "timestamp is outside the allowed tolerance"
+        );
+        verify_at(SECRET, &headers, body, Duration::from_secs(3600), NOW).unwrap();
+
+        let error = verify_signature(SECRET, &headers, body).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidSignature);
+    }
+
+    #[test]
+    fn test_webhook_input_builder() {
+        let input = WebhookInput::new("jobs", "https://example.com/hooks")
+            .events([WebhookEventType::JobCompleted, WebhookEventType::JobFailed])
+            .secret("whsec_abc")
+            .header("X-Team", "data")
+            .header("X-Env", "prod");
+        assert_eq!(
+            serde_json::to_value(&input).unwrap(),
+            serde_json::json!({
+                "name": "jobs",
+                "url": "https://example.com/hooks",
+                "is_active": true,
+                "events": ["job.completed", "job.failed"],
+                "secret": "whsec_abc",
+                "headers": [
+                    {"name": "X-Team", "value": "data"},
+                    {"name": "X-Env", "value": "prod"},
+                ],
+            })
+        );
+        assert!(!format!("{:?}", input).contains("whsec_abc"));
+        assert!(!input.active(false).is_active);
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let completed = parse_event(
+            br#"{"event":"job.completed","data":{"job_id":"job-1","status":"completed","page_count":3}}"#,
+        )
+        .unwrap();
+        match completed {
+            WebhookEvent::JobCompleted(job) => {
+                assert_eq!(job.job_id, "job-1");
+                assert_eq!(job.page_count, Some(3));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let page = parse_event(
+            br#"{"event":"page.extracted","data":{"job_id":"job-1","url":"https://example.com","data":{"name":"x"}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(page, WebhookEvent::PageExtracted(ref p) if p.data["name"] == "x"));
+
+        let other = parse_event(br#"{"event":"job.started","data":{"job_id":"job-1"}}"#).unwrap();
+        assert!(matches!(other, WebhookEvent::Other { ref event, .. } if event == "job.started"));
+
+        assert!(parse_event(br#"{"event":"job.failed","data":{}}"#).is_err());
+    }
+}
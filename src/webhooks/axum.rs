@@ -0,0 +1,119 @@
+//! Axum extractor for verified webhook deliveries.
+//!
+//! [`RefyneEvent`] reads the body, checks its signature with the
+//! [`WebhookSecret`] in the router state, and parses the event. Deliveries
+//! that fail are rejected with `401 Unauthorized` or `400 Bad Request`
+//! before the handler runs:
+//!
+//! ```rust,ignore
+//! use axum::{routing::post, Router};
+//! use refyne::webhooks::axum::{RefyneEvent, WebhookSecret};
+//! use refyne::webhooks::WebhookEvent;
+//!
+//! async fn on_event(RefyneEvent(event): RefyneEvent) {
+//!     if let WebhookEvent::JobCompleted(job) = event {
+//!         println!("{} finished", job.job_id);
+//!     }
+//! }
+//!
+//! let app = Router::new()
+//!     .route("/webhooks/refyne", post(on_event))
+//!     .with_state(WebhookSecret::new(std::env::var("REFYNE_WEBHOOK_SECRET")?));
+//! ```
+//!
+//! Routers with their own state implement `FromRef` to hand out the secret.
+
+use super::{verify_event, WebhookEvent};
+use crate::error::{Error, ErrorCode};
+use crate::secret::SecretString;
+use axum_core::extract::{FromRef, FromRequest, Request};
+use axum_core::response::{IntoResponse, Response};
+use bytes::Bytes;
+use reqwest::StatusCode;
+
+/// The secret webhook deliveries are signed with.
+#[derive(Debug, Clone)]
+pub struct WebhookSecret(SecretString);
+
+impl WebhookSecret {
+    /// Wrap a webhook secret.
+    pub fn new(secret: impl Into<SecretString>) -> Self {
+        Self(secret.into())
+    }
+}
+
+/// A webhook event whose signature has been verified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefyneEvent(pub WebhookEvent);
+
+impl<S> FromRequest<S> for RefyneEvent
+where
+    WebhookSecret: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let secret = WebhookSecret::from_ref(state);
+        let headers = request.headers().clone();
+        let body = Bytes::from_request(request, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        verify_event(secret.0.expose_secret(), &headers, &body)
+            .map(RefyneEvent)
+            .map_err(rejection)
+    }
+}
+
+/// Respond to a delivery that failed verification or parsing.
+fn rejection(error: Error) -> Response {
+    let status = match error.code() {
+        ErrorCode::InvalidSignature => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    (status, error.to_string()).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{SystemTime, UNIX_EPOCH};
+    use crate::webhooks::{sign, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+    use axum_core::body::Body;
+
+    const SECRET: &str = "whsec_test";
+
+    fn delivery(body: &'static str, signature: Option<String>) -> Request {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut request = Request::new(Body::from(body));
+        let headers = request.headers_mut();
+        headers.insert(TIMESTAMP_HEADER, now.to_string().parse().unwrap());
+        let signature = signature.unwrap_or_else(|| sign(SECRET, now, body.as_bytes()));
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_extracts_verified_events() {
+        let state = WebhookSecret::new(SECRET);
+        let body = r#"{"event":"job.completed","data":{"job_id":"job-1","status":"completed"}}"#;
+
+        let RefyneEvent(event) = RefyneEvent::from_request(delivery(body, None), &state)
+            .await
+            .unwrap();
+        assert!(matches!(event, WebhookEvent::JobCompleted(job) if job.job_id == "job-1"));
+
+        let forged = delivery(body, Some("sha256=00".into()));
+        let rejected = RefyneEvent::from_request(forged, &state).await.unwrap_err();
+        assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+
+        let malformed = delivery("not json", None);
+        let rejected = RefyneEvent::from_request(malformed, &state)
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+    }
+}